@@ -8,6 +8,16 @@ use std::time::{Duration, Instant};
 use serde::{Deserialize, Serialize};
 use clap::{Parser, Subcommand};
 use tracing::{info, warn, error};
+use qagml::{CpuBackend, GpuBackend};
+use symmetrix_core::galois::{GaloisEngine, GaloisElement, MERSENNE_61};
+use symmetrix_core::tensor::{CacheConfig, TensorFolder};
+
+/// Largest matrix actually multiplied (GF(p) multiply is O(n³))
+const MAX_MATRIX_DIM: usize = 256;
+/// Largest tensor side actually allocated (n³ cache-padded elements)
+const MAX_TENSOR_DIM: usize = 64;
+/// FFT passes per Symmetrix FFT measurement
+const FFT_REPEATS: usize = 100;
 
 #[derive(Parser)]
 #[command(name = "symmetrix-gpu-benchmark")]
@@ -129,7 +139,19 @@ impl GPUBenchmark {
     fn detect_gpu() -> (bool, Option<GPUInfo>) {
         info!("🔍 Detecting GPU hardware...");
         
-        // Simulate GPU detection (in real implementation, use CUDA/OpenCL APIs)
+        #[cfg(feature = "nvml")]
+        match Self::probe_nvml() {
+            Ok(mut devices) if !devices.is_empty() => {
+                info!("✅ NVML found {} CUDA device(s)", devices.len());
+                let gpu_info = devices.remove(0);
+                info!("✅ GPU detected: {}", gpu_info.name);
+                return (true, Some(gpu_info));
+            }
+            Ok(_) => warn!("NVML reports no CUDA devices; falling back to simulation"),
+            Err(e) => warn!("NVML unavailable ({}); falling back to simulation", e),
+        }
+        
+        // Simulated device, only reported when SYMMETRIX_SIMULATE_GPU is set
         let gpu_info = GPUInfo {
             name: "NVIDIA RTX 4090".to_string(),
             memory_gb: 24.0,
@@ -154,6 +176,36 @@ impl GPUBenchmark {
         }
     }
     
+    /// Enumerate CUDA devices through NVML
+    #[cfg(feature = "nvml")]
+    fn probe_nvml() -> Result<Vec<GPUInfo>, nvml_wrapper::error::NvmlError> {
+        use nvml_wrapper::enum_wrappers::device::{Clock, ClockId};
+        
+        let nvml = nvml_wrapper::Nvml::init()?;
+        let mut devices = Vec::new();
+        for index in 0..nvml.device_count()? {
+            let device = nvml.device_by_index(index)?;
+            let memory = device.memory_info()?;
+            let compute_capability = device.cuda_compute_capability()
+                .map(|cc| format!("{}.{}", cc.major, cc.minor))
+                .unwrap_or_else(|_| "unknown".to_string());
+            devices.push(GPUInfo {
+                name: device.name()?,
+                memory_gb: memory.total as f64 / (1024.0 * 1024.0 * 1024.0),
+                compute_capability,
+                cuda_cores: device.num_cores().ok(),
+                // Not exposed by NVML
+                tensor_cores: None,
+                base_clock_mhz: device.clock(Clock::Graphics, ClockId::AppClockDefault).ok(),
+                boost_clock_mhz: device.max_clock_info(Clock::Graphics).ok(),
+                memory_bandwidth_gbps: None,
+                // NVML reports milliwatts
+                power_limit_watts: device.power_management_limit().ok().map(|mw| mw as f64 / 1000.0),
+            });
+        }
+        Ok(devices)
+    }
+    
     fn benchmark_matrix_multiply_gpu(&self, size: usize, iterations: usize) -> BenchmarkResult {
         info!("🎯 Running GPU matrix multiplication benchmark ({}x{}, {} iterations)", size, size, iterations);
         
@@ -170,7 +222,7 @@ impl GPUBenchmark {
         let ops_per_second = (iterations as f64 * size as f64 * size as f64 * size as f64) / duration.as_secs_f64();
         
         BenchmarkResult {
-            name: format!("GPU Matrix Multiply {}x{}", size, size),
+            name: format!("GPU Matrix Multiply {}x{} (simulated)", size, size),
             duration_ms,
             operations_per_second: ops_per_second,
             memory_usage_mb: (size * size * 8 * 3) as f64 / (1024.0 * 1024.0), // 3 matrices, 8 bytes per element
@@ -183,28 +235,38 @@ impl GPUBenchmark {
     fn benchmark_matrix_multiply_symmetrix(&self, size: usize, iterations: usize) -> BenchmarkResult {
         info!("🧮 Running Symmetrix matrix multiplication benchmark ({}x{}, {} iterations)", size, size, iterations);
         
-        let start = Instant::now();
+        let n = size.clamp(1, MAX_MATRIX_DIM);
+        if n < size {
+            warn!("Matrix size capped at {}x{} for the GF(2^61-1) multiply", n, n);
+        }
+        
+        let engine = GaloisEngine::new(MERSENNE_61);
+        let a: Vec<Vec<GaloisElement>> = (0..n)
+            .map(|i| (0..n).map(|j| engine.element((i * n + j) as u64 + 1)).collect())
+            .collect();
+        let b: Vec<Vec<GaloisElement>> = (0..n)
+            .map(|i| (0..n).map(|j| engine.element((j * n + i) as u64 + 7)).collect())
+            .collect();
         
-        // Use actual Symmetrix mathematical acceleration
+        let iterations = iterations.max(1);
+        let start = Instant::now();
         for _ in 0..iterations {
-            // Simulate Symmetrix mathematical operations with acceleration
-            let acceleration_factor = 2.5; // Our demonstrated acceleration
-            let _base_time = Duration::from_micros(100);
-            let accelerated_time = Duration::from_micros((100.0 / acceleration_factor) as u64);
-            std::thread::sleep(accelerated_time);
+            let product = engine.matrix_multiply_polynomial(&a, &b)
+                .expect("square matrices of equal size");
+            std::hint::black_box(product);
         }
-        
         let duration = start.elapsed();
+        
         let duration_ms = duration.as_secs_f64() * 1000.0;
-        let ops_per_second = (iterations as f64 * size as f64 * size as f64 * size as f64) / duration.as_secs_f64();
+        let ops_per_second = (iterations as f64 * n as f64 * n as f64 * n as f64) / duration.as_secs_f64().max(f64::EPSILON);
         
         BenchmarkResult {
-            name: format!("Symmetrix Matrix Multiply {}x{}", size, size),
+            name: format!("Symmetrix Matrix Multiply {}x{}", n, n),
             duration_ms,
             operations_per_second: ops_per_second,
-            memory_usage_mb: (size * size * 8 * 3) as f64 / (1024.0 * 1024.0),
-            power_consumption_watts: Some(65.0), // CPU power consumption
-            acceleration_factor: 2.5, // Our mathematical acceleration
+            memory_usage_mb: (n * n * std::mem::size_of::<GaloisElement>() * 3) as f64 / (1024.0 * 1024.0),
+            power_consumption_watts: Some(65.0), // Estimated CPU package power
+            acceleration_factor: 1.0, // Measured; relative advantage is computed in the summary
             efficiency_score: ops_per_second / 65.0, // Operations per watt
         }
     }
@@ -220,7 +282,7 @@ impl GPUBenchmark {
         let ops_per_second = (size as f64 * (size as f64).log2()) / duration.as_secs_f64();
         
         BenchmarkResult {
-            name: format!("GPU FFT (size: {})", size),
+            name: format!("GPU FFT (size: {}) (simulated)", size),
             duration_ms,
             operations_per_second: ops_per_second,
             memory_usage_mb: (size * 16) as f64 / (1024.0 * 1024.0), // Complex numbers
@@ -233,24 +295,27 @@ impl GPUBenchmark {
     fn benchmark_fft_symmetrix(&self, size: usize) -> BenchmarkResult {
         info!("🧮 Running Symmetrix FFT benchmark (size: {})", size);
         
+        // The same FFT QAGML runs for dimensional folding
+        let size = size.max(2);
+        let backend = CpuBackend::new();
+        let signal: Vec<f32> = (0..size).map(|i| (i as f32 * 0.01).sin()).collect();
+        
         let start = Instant::now();
-        // Symmetrix uses Galois field arithmetic for FFT acceleration
-        let acceleration_factor = 3.2; // Our Galois field acceleration
-        let _base_time = Duration::from_millis(50);
-        let accelerated_time = Duration::from_millis((50.0 / acceleration_factor) as u64);
-        std::thread::sleep(accelerated_time);
+        for _ in 0..FFT_REPEATS {
+            std::hint::black_box(backend.fold(&signal));
+        }
         let duration = start.elapsed();
         
         let duration_ms = duration.as_secs_f64() * 1000.0;
-        let ops_per_second = (size as f64 * (size as f64).log2()) / duration.as_secs_f64();
+        let ops_per_second = (FFT_REPEATS as f64 * size as f64 * (size as f64).log2()) / duration.as_secs_f64().max(f64::EPSILON);
         
         BenchmarkResult {
             name: format!("Symmetrix FFT (size: {})", size),
             duration_ms,
             operations_per_second: ops_per_second,
-            memory_usage_mb: (size * 16) as f64 / (1024.0 * 1024.0),
-            power_consumption_watts: Some(45.0), // Lower CPU power
-            acceleration_factor: 3.2,
+            memory_usage_mb: (size * 8) as f64 / (1024.0 * 1024.0), // Complex<f32>
+            power_consumption_watts: Some(45.0), // Estimated CPU package power
+            acceleration_factor: 1.0,
             efficiency_score: ops_per_second / 45.0,
         }
     }
@@ -299,22 +364,36 @@ impl GPUBenchmark {
     fn benchmark_tensor_operations_symmetrix(&self, size: usize) -> BenchmarkResult {
         info!("📦 Running Symmetrix tensor operations benchmark");
         
+        let n = size.clamp(1, MAX_TENSOR_DIM);
+        if n < size {
+            warn!("Tensor side capped at {} ({}³ elements)", n, n);
+        }
+        
+        let folder = TensorFolder::<f64>::new(CacheConfig::default());
         let start = Instant::now();
-        // Symmetrix tensor folding with Morton encoding
-        let acceleration_factor = 1.8; // Our tensor folding acceleration
-        std::thread::sleep(Duration::from_millis((100.0 / acceleration_factor) as u64));
+        let tensor = folder.allocate_tensor(vec![n, n, n])
+            .expect("non-empty tensor dimensions");
+        let mut sum = 0.0;
+        for i in 0..n {
+            for j in 0..n {
+                for k in 0..n {
+                    sum += *tensor.get(&[i, j, k]).expect("index within bounds");
+                }
+            }
+        }
+        std::hint::black_box(sum);
         let duration = start.elapsed();
         
         let duration_ms = duration.as_secs_f64() * 1000.0;
-        let ops_per_second = (size * size * size) as f64 / duration.as_secs_f64();
+        let ops_per_second = (n * n * n) as f64 / duration.as_secs_f64().max(f64::EPSILON);
         
         BenchmarkResult {
-            name: format!("Symmetrix Tensor Ops ({}³)", size),
+            name: format!("Symmetrix Tensor Ops ({}³)", n),
             duration_ms,
             operations_per_second: ops_per_second,
-            memory_usage_mb: (size * size * size * 4) as f64 / (1024.0 * 1024.0),
-            power_consumption_watts: Some(55.0),
-            acceleration_factor: 1.8,
+            memory_usage_mb: folder.get_memory_stats().total_allocated as f64 / (1024.0 * 1024.0),
+            power_consumption_watts: Some(55.0), // Estimated CPU package power
+            acceleration_factor: 1.0,
             efficiency_score: ops_per_second / 55.0,
         }
     }
@@ -330,7 +409,7 @@ impl GPUBenchmark {
         let ops_per_second = (size * size * size) as f64 / duration.as_secs_f64();
         
         BenchmarkResult {
-            name: format!("GPU Tensor Ops ({}³)", size),
+            name: format!("GPU Tensor Ops ({}³) (simulated)", size),
             duration_ms,
             operations_per_second: ops_per_second,
             memory_usage_mb: (size * size * size * 4) as f64 / (1024.0 * 1024.0),
@@ -383,6 +462,7 @@ impl GPUBenchmark {
             println!("🖥️  GPU: {}", gpu_info.name);
             println!("💾 GPU Memory: {:.1} GB", gpu_info.memory_gb);
             println!("⚡ GPU Power: {} W", gpu_info.power_limit_watts.unwrap_or(0.0));
+            println!("⚠️  GPU timings are simulated (no CUDA kernels are run); Symmetrix timings are measured");
         } else {
             println!("❌ No GPU detected");
         }
@@ -437,7 +517,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 println!("   Power Limit: {} W", gpu_info.power_limit_watts.unwrap_or(0.0));
             } else {
                 println!("❌ No GPU detected");
-                println!("💡 Set SYMMETRIX_SIMULATE_GPU=1 to simulate GPU for testing");
+                println!("💡 Build with --features nvml to probe NVIDIA devices, or set SYMMETRIX_SIMULATE_GPU=1 to simulate one");
             }
         },
         
@@ -611,3 +691,26 @@ fn generate_benchmark_cards(symmetrix: &[BenchmarkResult], gpu: &[BenchmarkResul
         )
     }).collect::<Vec<_>>().join("")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matrix_benchmark_measures_real_work() {
+        let benchmark = GPUBenchmark { gpu_available: false, gpu_info: None };
+        let result = benchmark.benchmark_matrix_multiply_symmetrix(16, 2);
+        assert_eq!(result.name, "Symmetrix Matrix Multiply 16x16");
+        assert!(result.duration_ms > 0.0);
+        assert!(result.operations_per_second > 0.0);
+    }
+
+    #[cfg(not(feature = "nvml"))]
+    #[test]
+    fn test_detect_gpu_without_nvml_or_simulation() {
+        std::env::remove_var("SYMMETRIX_SIMULATE_GPU");
+        let (available, info) = GPUBenchmark::detect_gpu();
+        assert!(!available);
+        assert!(info.is_none());
+    }
+}
@@ -13,7 +13,7 @@
 //! - **Matrix Representation**: Matrices as polynomials in finite fields
 //! - **Convolution Optimization**: O(n log n) matrix multiplication
 
-use num_bigint::BigUint;
+use num_bigint::{BigInt, BigUint};
 use num_traits::{Zero, One};
 use serde::{Deserialize, Serialize};
 use std::ops::{Add, Sub, Mul, Div, Neg};
@@ -43,6 +43,18 @@ pub type GaloisResult<T> = Result<T, GaloisError>;
 /// Mersenne prime 2^61 - 1 used as the primary Galois field modulus
 pub const MERSENNE_61: u64 = (1u64 << 61) - 1;
 
+/// Reduce modulo 2^61 - 1 with shifts and adds instead of a 128-bit division
+#[inline]
+pub fn mersenne_reduce(x: u128) -> u64 {
+    let m = MERSENNE_61 as u128;
+    let folded = (x & m) + (x >> 61);
+    let mut r = (folded & m) + (folded >> 61);
+    if r >= m {
+        r -= m;
+    }
+    r as u64
+}
+
 /// Additional primes for CRT decomposition
 pub const CRT_PRIMES: &[u64] = &[
     2147483647,  // 2^31 - 1 (Mersenne prime)
@@ -55,6 +67,24 @@ pub const CRT_PRIMES: &[u64] = &[
     2147483497,  // Large prime
 ];
 
+/// Deterministic primality test by trial division (fine for 31-bit CRT primes)
+fn is_prime(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    if n.is_multiple_of(2) {
+        return n == 2;
+    }
+    let mut d = 3;
+    while d * d <= n {
+        if n.is_multiple_of(d) {
+            return false;
+        }
+        d += 2;
+    }
+    true
+}
+
 /// A Galois field element in GF(p)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct GaloisElement {
@@ -197,8 +227,13 @@ impl Mul for GaloisElement {
     
     fn mul(self, other: Self) -> Self {
         debug_assert_eq!(self.modulus, other.modulus);
-        let product = ((self.value as u128) * (other.value as u128)) % (self.modulus as u128);
-        Self::new(product as u64, self.modulus)
+        let wide = (self.value as u128) * (other.value as u128);
+        let product = if self.modulus == MERSENNE_61 {
+            mersenne_reduce(wide)
+        } else {
+            (wide % (self.modulus as u128)) as u64
+        };
+        Self::new(product, self.modulus)
     }
 }
 
@@ -239,10 +274,18 @@ pub struct CRTDecomposition {
     pub primes: Vec<u64>,
     /// Product of all primes
     pub modulus_product: BigUint,
+    /// Bit length of the decomposed value, checked on reconstruction
+    /// (`None` for results of residue arithmetic)
+    pub value_bits: Option<u64>,
 }
 
 impl CRTDecomposition {
     /// Decompose a large integer using CRT
+    ///
+    /// The value is only recoverable if it is below the product of
+    /// `primes`; larger values wrap silently here and are caught by the
+    /// bit-length check in `reconstruct`. Use `decompose_checked` to reject
+    /// them up front.
     pub fn decompose(value: &BigUint, primes: &[u64]) -> Self {
         let mut residues = Vec::with_capacity(primes.len());
         let mut modulus_product = BigUint::one();
@@ -258,9 +301,56 @@ impl CRTDecomposition {
             residues,
             primes: primes.to_vec(),
             modulus_product,
+            value_bits: Some(value.bits()),
         }
     }
     
+    /// Distinct primes whose product exceeds `2^bits`
+    ///
+    /// Starts from [`CRT_PRIMES`] and, once those run out, continues with the
+    /// next primes below the smallest of them, so every prime stays below 2^31.
+    /// Always returns at least one prime.
+    pub fn primes_for_bits(bits: usize) -> Vec<u64> {
+        let mut primes = Vec::new();
+        let mut product = BigUint::one();
+        let limit = BigUint::one() << bits;
+        let mut candidate = CRT_PRIMES.last().copied().unwrap_or(1 << 31);
+        let mut table = CRT_PRIMES.iter().copied();
+
+        while primes.is_empty() || product <= limit {
+            let prime = match table.next() {
+                Some(prime) => prime,
+                None => {
+                    candidate -= 2;
+                    while !is_prime(candidate) {
+                        candidate -= 2;
+                    }
+                    candidate
+                }
+            };
+            product *= prime;
+            primes.push(prime);
+        }
+        primes
+    }
+
+    /// Decompose a value of at most `bits` bits over [`Self::primes_for_bits`]
+    pub fn decompose_for_bits(value: &BigUint, bits: usize) -> GaloisResult<Self> {
+        Self::decompose_checked(value, &Self::primes_for_bits(bits))
+    }
+
+    /// Decompose, failing if `value` is not below the product of `primes`
+    pub fn decompose_checked(value: &BigUint, primes: &[u64]) -> GaloisResult<Self> {
+        let product: BigUint = primes.iter().map(|&prime| BigUint::from(prime)).product();
+        if value >= &product {
+            return Err(GaloisError::CRTError(format!(
+                "Value of {} bits does not fit below the {}-bit prime product",
+                value.bits(), product.bits()
+            )));
+        }
+        Ok(Self::decompose(value, primes))
+    }
+    
     /// Reconstruct the original value using CRT
     pub fn reconstruct(&self) -> GaloisResult<BigUint> {
         if self.residues.is_empty() {
@@ -281,12 +371,32 @@ impl CRTDecomposition {
             result = (result + term) % &self.modulus_product;
         }
         
+        if let Some(bits) = self.value_bits {
+            if result.bits() != bits {
+                return Err(GaloisError::CRTError(format!(
+                    "Reconstructed {} bits but decomposed {}; value exceeded the prime product",
+                    result.bits(), bits
+                )));
+            }
+        }
+        
         Ok(result)
     }
     
+    /// Reconstruct the balanced representative in `[-P/2, P/2)`, where `P`
+    /// is the product of the primes, so small negative results come back
+    /// negative instead of wrapping to just below `P`
+    pub fn reconstruct_balanced(&self) -> GaloisResult<BigInt> {
+        let value = self.reconstruct()?;
+        if &value * 2u32 >= self.modulus_product {
+            Ok(BigInt::from(value) - BigInt::from(self.modulus_product.clone()))
+        } else {
+            Ok(BigInt::from(value))
+        }
+    }
+    
     /// Compute modular inverse using extended Euclidean algorithm
     fn mod_inverse(&self, a: &BigUint, m: &BigUint) -> GaloisResult<BigUint> {
-        use num_bigint::BigInt;
         use num_traits::Signed;
 
         if a.is_zero() {
@@ -343,6 +453,27 @@ impl CRTDecomposition {
             residues: result_residues,
             primes: self.primes.clone(),
             modulus_product: self.modulus_product.clone(),
+            value_bits: None,
+        })
+    }
+    
+    /// Perform parallel subtraction using CRT residues
+    pub fn sub(&self, other: &Self) -> GaloisResult<Self> {
+        if self.primes != other.primes {
+            return Err(GaloisError::CRTError("Incompatible CRT decompositions".to_string()));
+        }
+        
+        let mut result_residues = Vec::with_capacity(self.residues.len());
+        
+        for (a, b) in self.residues.iter().zip(other.residues.iter()) {
+            result_residues.push(*a - *b);
+        }
+        
+        Ok(Self {
+            residues: result_residues,
+            primes: self.primes.clone(),
+            modulus_product: self.modulus_product.clone(),
+            value_bits: None,
         })
     }
     
@@ -362,6 +493,7 @@ impl CRTDecomposition {
             residues: result_residues,
             primes: self.primes.clone(),
             modulus_product: self.modulus_product.clone(),
+            value_bits: None,
         })
     }
 }
@@ -474,6 +606,98 @@ impl GaloisEngine {
         
         Ok(result)
     }
+    
+    /// Multiply matrix `a` (rows of equal length) by column vector `x`
+    pub fn matrix_vector_multiply(&self, a: &[Vec<GaloisElement>], x: &[GaloisElement])
+                                  -> GaloisResult<Vec<GaloisElement>> {
+        a.iter()
+            .map(|row| {
+                if row.len() != x.len() {
+                    return Err(GaloisError::DimensionMismatch {
+                        expected: x.len(),
+                        actual: row.len(),
+                    });
+                }
+                Ok(row.iter()
+                    .zip(x)
+                    .fold(self.element(0), |sum, (&a_ij, &x_j)| sum + a_ij * x_j))
+            })
+            .collect()
+    }
+    
+    /// Determinant of a square matrix by Gaussian elimination
+    pub fn determinant(&self, a: &[Vec<GaloisElement>]) -> GaloisResult<GaloisElement> {
+        if let Some(row) = a.iter().find(|row| row.len() != a.len()) {
+            return Err(GaloisError::DimensionMismatch {
+                expected: a.len(),
+                actual: row.len(),
+            });
+        }
+        
+        let (_, determinant) = self.eliminate(a)?;
+        Ok(determinant)
+    }
+    
+    /// Rank of a (possibly rectangular) matrix by Gaussian elimination
+    pub fn rank(&self, a: &[Vec<GaloisElement>]) -> GaloisResult<usize> {
+        let (rank, _) = self.eliminate(a)?;
+        Ok(rank)
+    }
+    
+    /// Reduce to row echelon form, returning the rank and, for square
+    /// input, the determinant (signed product of the pivots)
+    fn eliminate(&self, a: &[Vec<GaloisElement>]) -> GaloisResult<(usize, GaloisElement)> {
+        let rows = a.len();
+        let cols = a.first().map_or(0, Vec::len);
+        if let Some(row) = a.iter().find(|row| row.len() != cols) {
+            return Err(GaloisError::DimensionMismatch {
+                expected: cols,
+                actual: row.len(),
+            });
+        }
+        
+        let mut m = a.to_vec();
+        let mut rank = 0;
+        let mut determinant = self.element(1);
+        
+        for col in 0..cols {
+            if rank == rows {
+                break;
+            }
+            
+            let pivot_row = match (rank..rows).find(|&r| !m[r][col].is_zero()) {
+                Some(r) => r,
+                None => {
+                    // No pivot in this column: the matrix is singular
+                    determinant = self.element(0);
+                    continue;
+                }
+            };
+            if pivot_row != rank {
+                m.swap(pivot_row, rank);
+                determinant = -determinant;
+            }
+            
+            let pivot = m[rank][col];
+            determinant = determinant * pivot;
+            let pivot_inv = pivot.inverse()?;
+            
+            let pivot_values = m[rank].clone();
+            for row in m.iter_mut().skip(rank + 1) {
+                let factor = row[col] * pivot_inv;
+                if factor.is_zero() {
+                    continue;
+                }
+                for (cell, &p) in row[col..].iter_mut().zip(&pivot_values[col..]) {
+                    *cell = *cell - factor * p;
+                }
+            }
+            
+            rank += 1;
+        }
+        
+        Ok((rank, determinant))
+    }
 }
 
 #[cfg(test)]
@@ -507,6 +731,59 @@ mod tests {
         assert_eq!(product.value, 1);
     }
 
+    #[test]
+    fn test_mersenne_reduce() {
+        let m = MERSENNE_61 as u128;
+        for x in [0, 1, m - 1, m, m + 1, (m - 1) * (m - 1), u128::MAX] {
+            assert_eq!(mersenne_reduce(x) as u128, x % m, "x = {}", x);
+        }
+    }
+
+    #[test]
+    fn test_matrix_vector_multiply() {
+        let engine = GaloisEngine::new(MERSENNE_61);
+        let e = |v: u64| engine.element(v);
+        let a = vec![
+            vec![e(1), e(2), e(3)],
+            vec![e(4), e(5), e(6)],
+            vec![e(1 << 60), e(0), e(1)],
+        ];
+        let x = vec![e(MERSENNE_61 - 1), e(2), e(3)];
+
+        // Row 0: -1 + 4 + 9; row 1: -4 + 10 + 18; row 2: -2^60 + 3 = 2^60 + 2 (mod 2^61 - 1)
+        let y = engine.matrix_vector_multiply(&a, &x).unwrap();
+        assert_eq!(y, vec![e(12), e(24), e((1 << 60) + 2)]);
+
+        assert!(matches!(
+            engine.matrix_vector_multiply(&a, &x[..2]),
+            Err(GaloisError::DimensionMismatch { expected: 2, actual: 3 })
+        ));
+    }
+
+    #[test]
+    fn test_determinant_and_rank() {
+        let engine = GaloisEngine::new(MERSENNE_61);
+        let e = |v: u64| engine.element(v);
+
+        let singular = vec![vec![e(2), e(4)], vec![e(3), e(6)]];
+        assert!(engine.determinant(&singular).unwrap().is_zero());
+        assert_eq!(engine.rank(&singular).unwrap(), 1);
+
+        let identity: Vec<Vec<GaloisElement>> = (0..4)
+            .map(|i| (0..4).map(|j| e((i == j) as u64)).collect())
+            .collect();
+        assert!(engine.determinant(&identity).unwrap().is_one());
+        assert_eq!(engine.rank(&identity).unwrap(), 4);
+
+        // det [[0, 1], [1, 0]] = -1; the row swap must flip the sign
+        let swap = vec![vec![e(0), e(1)], vec![e(1), e(0)]];
+        assert_eq!(engine.determinant(&swap).unwrap(), e(MERSENNE_61 - 1));
+
+        let wide = vec![vec![e(1), e(2), e(3)], vec![e(2), e(4), e(7)]];
+        assert_eq!(engine.rank(&wide).unwrap(), 2);
+        assert!(engine.determinant(&wide).is_err());
+    }
+
     #[test]
     fn test_crt_decomposition() {
         // Use larger primes so product > test value
@@ -528,4 +805,68 @@ mod tests {
 
         assert_eq!(small_value, small_reconstructed);
     }
+
+    #[test]
+    fn test_crt_product_boundary() {
+        let primes = &[101u64, 103u64, 107u64];
+        let product = BigUint::from(101u64 * 103 * 107);
+
+        let below = &product - 1u32;
+        let decomp = CRTDecomposition::decompose_checked(&below, primes).unwrap();
+        assert_eq!(decomp.reconstruct().unwrap(), below);
+
+        assert!(CRTDecomposition::decompose_checked(&product, primes).is_err());
+        let past = &product + 1u32;
+        assert!(CRTDecomposition::decompose_checked(&past, primes).is_err());
+
+        // The unchecked path wraps past = P + 1 to 1, which reconstruct rejects
+        let wrapped = CRTDecomposition::decompose(&past, primes);
+        assert!(matches!(wrapped.reconstruct(), Err(GaloisError::CRTError(_))));
+    }
+
+    #[test]
+    fn test_crt_primes_for_bits() {
+        assert_eq!(CRTDecomposition::primes_for_bits(0).len(), 1);
+        assert_eq!(CRTDecomposition::primes_for_bits(200), CRT_PRIMES[..7].to_vec());
+
+        let primes = CRTDecomposition::primes_for_bits(400);
+        assert!(primes.len() > CRT_PRIMES.len());
+        assert!(primes.iter().all(|&p| is_prime(p) && p < 1 << 31));
+        let mut distinct = primes.clone();
+        distinct.sort_unstable();
+        distinct.dedup();
+        assert_eq!(distinct.len(), primes.len());
+        let product: BigUint = primes.iter().map(|&p| BigUint::from(p)).product();
+        assert!(product.bits() > 400);
+
+        // 2^400 - 1 and a dense 400-bit pattern both round-trip exactly
+        let max = (BigUint::one() << 400u32) - 1u32;
+        let pattern = BigUint::from_bytes_be(&[0xA5; 50]);
+        for value in [max, pattern] {
+            assert_eq!(value.bits(), 400);
+            let decomp = CRTDecomposition::decompose_for_bits(&value, 400).unwrap();
+            assert_eq!(decomp.reconstruct().unwrap(), value);
+        }
+
+        let too_wide = BigUint::one() << 450u32;
+        assert!(CRTDecomposition::decompose_for_bits(&too_wide, 400).is_err());
+    }
+
+    #[test]
+    fn test_crt_reconstruct_balanced() {
+        let primes = &[101u64, 103u64, 107u64];
+        let small = CRTDecomposition::decompose(&BigUint::from(250u64), primes);
+        let large = CRTDecomposition::decompose(&BigUint::from(1000u64), primes);
+
+        let difference = small.sub(&large).unwrap();
+        assert_eq!(difference.reconstruct_balanced().unwrap(), BigInt::from(-750));
+        // The unsigned form wraps to P - 750
+        assert_eq!(
+            difference.reconstruct().unwrap(),
+            BigUint::from(101u64 * 103 * 107 - 750)
+        );
+
+        let positive = large.sub(&small).unwrap();
+        assert_eq!(positive.reconstruct_balanced().unwrap(), BigInt::from(750));
+    }
 }
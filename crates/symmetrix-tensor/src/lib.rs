@@ -15,7 +15,10 @@
 
 use num_traits::Zero;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::alloc::{self, Layout as AllocLayout};
+use std::collections::{HashMap, VecDeque};
+use std::ops::{Deref, DerefMut};
+use std::ptr::NonNull;
 use std::sync::{Arc, RwLock};
 use crossbeam_utils::CachePadded;
 
@@ -70,12 +73,29 @@ impl CacheLevel {
     }
 }
 
+/// Largest coordinate that survives a 3D Morton round trip (21 bits per axis)
+pub const MORTON_3D_MAX_COORD: u32 = (1 << 21) - 1;
+
 /// Morton encoding utilities for Z-order curve mapping
 pub struct MortonEncoding;
 
 impl MortonEncoding {
     /// Encode 2D coordinates to Morton order (Z-order curve)
+    ///
+    /// Two full 32-bit axes fill the 64-bit result exactly, so every `u32`
+    /// pair encodes losslessly and no checked variant is needed.
     pub fn encode_2d(x: u32, y: u32) -> u64 {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if bmi2::available() {
+                // SAFETY: BMI2 support was checked above
+                return unsafe { bmi2::encode_2d(x, y) };
+            }
+        }
+        Self::encode_2d_scalar(x, y)
+    }
+
+    fn encode_2d_scalar(x: u32, y: u32) -> u64 {
         let mut result = 0u64;
         
         for i in 0..32 {
@@ -90,7 +110,22 @@ impl MortonEncoding {
     }
     
     /// Encode 3D coordinates to Morton order
+    ///
+    /// Only the low 21 bits of each axis are kept; bits above
+    /// `MORTON_3D_MAX_COORD` are silently dropped. Use `try_encode_3d`
+    /// when the inputs are not known to be in range.
     pub fn encode_3d(x: u32, y: u32, z: u32) -> u64 {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if bmi2::available() {
+                // SAFETY: BMI2 support was checked above
+                return unsafe { bmi2::encode_3d(x, y, z) };
+            }
+        }
+        Self::encode_3d_scalar(x, y, z)
+    }
+
+    fn encode_3d_scalar(x: u32, y: u32, z: u32) -> u64 {
         let mut result = 0u64;
         
         for i in 0..21 { // 21 bits per dimension for 64-bit result
@@ -106,8 +141,32 @@ impl MortonEncoding {
         result
     }
     
+    /// Encode 3D coordinates, rejecting any axis above `MORTON_3D_MAX_COORD`
+    pub fn try_encode_3d(x: u32, y: u32, z: u32) -> TensorResult<u64> {
+        for (axis, value) in [("x", x), ("y", y), ("z", z)] {
+            if value > MORTON_3D_MAX_COORD {
+                return Err(TensorError::MortonError(format!(
+                    "{} coordinate {} exceeds 3D maximum {}",
+                    axis, value, MORTON_3D_MAX_COORD
+                )));
+            }
+        }
+        Ok(Self::encode_3d(x, y, z))
+    }
+    
     /// Decode Morton order to 2D coordinates
     pub fn decode_2d(morton: u64) -> (u32, u32) {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if bmi2::available() {
+                // SAFETY: BMI2 support was checked above
+                return unsafe { bmi2::decode_2d(morton) };
+            }
+        }
+        Self::decode_2d_scalar(morton)
+    }
+
+    fn decode_2d_scalar(morton: u64) -> (u32, u32) {
         let mut x = 0u32;
         let mut y = 0u32;
         
@@ -124,6 +183,17 @@ impl MortonEncoding {
     
     /// Decode Morton order to 3D coordinates
     pub fn decode_3d(morton: u64) -> (u32, u32, u32) {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if bmi2::available() {
+                // SAFETY: BMI2 support was checked above
+                return unsafe { bmi2::decode_3d(morton) };
+            }
+        }
+        Self::decode_3d_scalar(morton)
+    }
+
+    fn decode_3d_scalar(morton: u64) -> (u32, u32, u32) {
         let mut x = 0u32;
         let mut y = 0u32;
         let mut z = 0u32;
@@ -142,6 +212,179 @@ impl MortonEncoding {
     }
 }
 
+/// Single-instruction bit interleaving with BMI2 `pdep`/`pext`
+#[cfg(target_arch = "x86_64")]
+mod bmi2 {
+    use std::arch::x86_64::{_pdep_u64, _pext_u64};
+
+    const MASK_2D_X: u64 = 0x5555_5555_5555_5555;
+    const MASK_2D_Y: u64 = MASK_2D_X << 1;
+    // 21 bits per axis; pdep only consumes as many source bits as the mask has
+    const MASK_3D_X: u64 = 0x1249_2492_4924_9249;
+    const MASK_3D_Y: u64 = MASK_3D_X << 1;
+    const MASK_3D_Z: u64 = MASK_3D_X << 2;
+
+    /// Compile-time BMI2 skips the runtime probe (which is cached by std anyway)
+    #[inline]
+    pub(super) fn available() -> bool {
+        cfg!(target_feature = "bmi2") || is_x86_feature_detected!("bmi2")
+    }
+
+    /// # Safety
+    /// The CPU must support BMI2.
+    #[target_feature(enable = "bmi2")]
+    pub(super) unsafe fn encode_2d(x: u32, y: u32) -> u64 {
+        _pdep_u64(x as u64, MASK_2D_X) | _pdep_u64(y as u64, MASK_2D_Y)
+    }
+
+    /// # Safety
+    /// The CPU must support BMI2.
+    #[target_feature(enable = "bmi2")]
+    pub(super) unsafe fn encode_3d(x: u32, y: u32, z: u32) -> u64 {
+        _pdep_u64(x as u64, MASK_3D_X)
+            | _pdep_u64(y as u64, MASK_3D_Y)
+            | _pdep_u64(z as u64, MASK_3D_Z)
+    }
+
+    /// # Safety
+    /// The CPU must support BMI2.
+    #[target_feature(enable = "bmi2")]
+    pub(super) unsafe fn decode_2d(morton: u64) -> (u32, u32) {
+        (_pext_u64(morton, MASK_2D_X) as u32, _pext_u64(morton, MASK_2D_Y) as u32)
+    }
+
+    /// # Safety
+    /// The CPU must support BMI2.
+    #[target_feature(enable = "bmi2")]
+    pub(super) unsafe fn decode_3d(morton: u64) -> (u32, u32, u32) {
+        (
+            _pext_u64(morton, MASK_3D_X) as u32,
+            _pext_u64(morton, MASK_3D_Y) as u32,
+            _pext_u64(morton, MASK_3D_Z) as u32,
+        )
+    }
+}
+
+/// Element ordering of a tensor block's backing storage
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Layout {
+    /// Last index varies fastest (C order)
+    #[default]
+    RowMajor,
+    /// First index varies fastest (Fortran/BLAS order)
+    ColMajor,
+}
+
+/// Contiguous heap buffer whose base pointer is aligned to a chosen boundary
+///
+/// `Vec` only aligns its buffer to `align_of::<T>()`; SIMD loads such as
+/// AVX-512 need the base on a 64-byte boundary. Every element is always
+/// initialised.
+pub struct AlignedBuffer<T> {
+    ptr: NonNull<T>,
+    len: usize,
+    layout: AllocLayout,
+}
+
+// SAFETY: the buffer owns its elements exclusively, like `Vec<T>`
+unsafe impl<T: Send> Send for AlignedBuffer<T> {}
+// SAFETY: shared access only hands out `&[T]`
+unsafe impl<T: Sync> Sync for AlignedBuffer<T> {}
+
+impl<T> AlignedBuffer<T> {
+    /// Allocate uninitialised room for `len` elements at `alignment`
+    /// (raised to `align_of::<T>()` if smaller)
+    fn allocate(len: usize, alignment: usize) -> TensorResult<(NonNull<T>, AllocLayout)> {
+        let alignment = alignment.max(std::mem::align_of::<T>());
+        let layout = std::mem::size_of::<T>()
+            .checked_mul(len)
+            .and_then(|size| AllocLayout::from_size_align(size, alignment).ok())
+            .ok_or_else(|| TensorError::AlignmentError(format!(
+                "Cannot lay out {} elements at {}-byte alignment", len, alignment
+            )))?;
+        if layout.size() == 0 {
+            // Nothing to allocate; any non-null, aligned address will do
+            let dangling = NonNull::new(layout.align() as *mut T).expect("alignment is non-zero");
+            return Ok((dangling, layout));
+        }
+        // SAFETY: the layout has a non-zero size
+        let ptr = unsafe { alloc::alloc(layout) } as *mut T;
+        let ptr = NonNull::new(ptr).ok_or_else(|| TensorError::CacheAllocationError(format!(
+            "Failed to allocate {} bytes at {}-byte alignment", layout.size(), alignment
+        )))?;
+        Ok((ptr, layout))
+    }
+
+    /// Alignment of the base pointer in bytes
+    pub fn alignment(&self) -> usize {
+        self.layout.align()
+    }
+}
+
+impl<T: Clone + Zero> AlignedBuffer<T> {
+    /// Zero-filled buffer of `len` elements aligned to `alignment` bytes
+    ///
+    /// `alignment` must be a power of two.
+    pub fn new(len: usize, alignment: usize) -> TensorResult<Self> {
+        let (ptr, layout) = Self::allocate(len, alignment)?;
+        for i in 0..len {
+            // SAFETY: `i < len` lies inside the allocation
+            unsafe { ptr.as_ptr().add(i).write(T::zero()) };
+        }
+        Ok(Self { ptr, len, layout })
+    }
+}
+
+impl<T> Deref for AlignedBuffer<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        // SAFETY: `ptr` is valid and aligned for `len` initialised elements
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T> DerefMut for AlignedBuffer<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        // SAFETY: as for `deref`, and `&mut self` guarantees exclusive access
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T> Drop for AlignedBuffer<T> {
+    fn drop(&mut self) {
+        // SAFETY: every element is initialised, and a non-empty layout was
+        // allocated by `alloc::alloc` with this exact layout
+        unsafe {
+            std::ptr::drop_in_place(std::ptr::slice_from_raw_parts_mut(self.ptr.as_ptr(), self.len));
+            if self.layout.size() != 0 {
+                alloc::dealloc(self.ptr.as_ptr() as *mut u8, self.layout);
+            }
+        }
+    }
+}
+
+impl<T: Clone> Clone for AlignedBuffer<T> {
+    fn clone(&self) -> Self {
+        let (ptr, layout) = Self::allocate(self.len, self.layout.align())
+            .unwrap_or_else(|_| alloc::handle_alloc_error(self.layout));
+        for (i, value) in self.iter().enumerate() {
+            // SAFETY: `i < len` lies inside the allocation
+            unsafe { ptr.as_ptr().add(i).write(value.clone()) };
+        }
+        Self { ptr, len: self.len, layout }
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for AlignedBuffer<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AlignedBuffer")
+            .field("alignment", &self.alignment())
+            .field("data", &&**self)
+            .finish()
+    }
+}
+
 /// Cache-aware tensor block for optimal memory layout
 #[derive(Debug, Clone)]
 pub struct TensorBlock<T> {
@@ -155,11 +398,15 @@ pub struct TensorBlock<T> {
     pub cache_level: CacheLevel,
     /// Memory alignment (in bytes)
     pub alignment: usize,
+    /// Ordering of `data`
+    pub layout: Layout,
+    /// Contiguous copy of `data` at `alignment`, filled by `as_aligned_slice`
+    aligned: Option<AlignedBuffer<T>>,
 }
 
 impl<T: Clone + Zero> TensorBlock<T> {
     /// Create a new tensor block with specified dimensions
-    pub fn new(dimensions: Vec<usize>, cache_level: CacheLevel) -> TensorResult<Self> {
+    pub fn new(dimensions: Vec<usize>, cache_level: CacheLevel, layout: Layout) -> TensorResult<Self> {
         let total_elements: usize = dimensions.iter().product();
         
         if total_elements == 0 {
@@ -183,8 +430,22 @@ impl<T: Clone + Zero> TensorBlock<T> {
         // Initialize data with cache-padded elements
         let data = vec![CachePadded::new(T::zero()); total_elements];
         
-        // Compute Morton index based on dimensions
-        let morton_index = match dimensions.len() {
+        let morton_index = Self::dimensions_morton_index(&dimensions);
+        
+        Ok(Self {
+            data,
+            dimensions,
+            morton_index,
+            cache_level,
+            alignment: cache_level.line_size(),
+            layout,
+            aligned: None,
+        })
+    }
+    
+    /// Compute Morton index based on dimensions
+    fn dimensions_morton_index(dimensions: &[usize]) -> u64 {
+        match dimensions.len() {
             2 => MortonEncoding::encode_2d(dimensions[0] as u32, dimensions[1] as u32),
             3 => MortonEncoding::encode_3d(
                 dimensions[0] as u32, 
@@ -192,17 +453,91 @@ impl<T: Clone + Zero> TensorBlock<T> {
                 dimensions[2] as u32
             ),
             _ => 0, // Fallback for higher dimensions
-        };
+        }
+    }
+    
+    /// Copy of this block viewed with different dimensions
+    ///
+    /// Elements keep their storage order, so a row-major block is reshaped
+    /// in row-major order.
+    pub fn reshape(&self, new_dims: Vec<usize>) -> TensorResult<TensorBlock<T>> {
+        let total_elements: usize = new_dims.iter().product();
+        if total_elements != self.data.len() {
+            return Err(TensorError::InvalidDimensions(format!(
+                "Cannot reshape {:?} ({} elements) to {:?} ({} elements)",
+                self.dimensions, self.data.len(), new_dims, total_elements
+            )));
+        }
         
-        Ok(Self {
-            data,
-            dimensions,
-            morton_index,
-            cache_level,
-            alignment: cache_level.line_size(),
+        Ok(TensorBlock {
+            data: self.data.clone(),
+            morton_index: Self::dimensions_morton_index(&new_dims),
+            dimensions: new_dims,
+            cache_level: self.cache_level,
+            alignment: self.alignment,
+            layout: self.layout,
+            aligned: None,
         })
     }
     
+    /// Copy with axes permuted: axis `k` of the result is axis `axes[k]` here
+    pub fn transpose(&self, axes: &[usize]) -> TensorResult<TensorBlock<T>> {
+        let rank = self.dimensions.len();
+        let mut seen = vec![false; rank];
+        let is_permutation = axes.len() == rank
+            && axes.iter().all(|&axis| axis < rank && !std::mem::replace(&mut seen[axis], true));
+        if !is_permutation {
+            return Err(TensorError::InvalidDimensions(format!(
+                "{:?} is not a permutation of {} axes", axes, rank
+            )));
+        }
+        
+        let new_dims: Vec<usize> = axes.iter().map(|&axis| self.dimensions[axis]).collect();
+        let mut transposed = TensorBlock {
+            data: self.data.clone(),
+            morton_index: Self::dimensions_morton_index(&new_dims),
+            dimensions: new_dims,
+            cache_level: self.cache_level,
+            alignment: self.alignment,
+            layout: self.layout,
+            aligned: None,
+        };
+        
+        let mut source = vec![0; rank];
+        for linear in 0..transposed.data.len() {
+            let target = transposed.unravel_index(linear);
+            for (k, &axis) in axes.iter().enumerate() {
+                source[axis] = target[k];
+            }
+            transposed.data[linear] = self.data[self.compute_linear_index(&source)?].clone();
+        }
+        
+        Ok(transposed)
+    }
+    
+    /// The elements packed contiguously, in storage order, with the base
+    /// pointer aligned to `alignment` bytes
+    ///
+    /// `data` pads every element to its own cache line, so it cannot be
+    /// fed to vector loads directly. The packed copy is refreshed from
+    /// `data` on each call; its allocation is reused while the element
+    /// count and alignment stay the same.
+    pub fn as_aligned_slice(&mut self) -> TensorResult<&[T]> {
+        let alignment = self.alignment.max(std::mem::align_of::<T>());
+        let reusable = matches!(
+            &self.aligned,
+            Some(buffer) if buffer.len() == self.data.len() && buffer.alignment() == alignment
+        );
+        if !reusable {
+            self.aligned = Some(AlignedBuffer::new(self.data.len(), self.alignment)?);
+        }
+        let buffer = self.aligned.as_mut().expect("aligned buffer was just allocated");
+        for (slot, value) in buffer.iter_mut().zip(&self.data) {
+            *slot = (**value).clone();
+        }
+        Ok(&**buffer)
+    }
+    
     /// Get element at multi-dimensional index
     pub fn get(&self, indices: &[usize]) -> TensorResult<&T> {
         let linear_index = self.compute_linear_index(indices)?;
@@ -216,6 +551,49 @@ impl<T: Clone + Zero> TensorBlock<T> {
         Ok(())
     }
     
+    /// Iterate elements in increasing Morton (Z-order) index
+    ///
+    /// The last axis is the fastest-varying one within each Morton cell,
+    /// regardless of `layout`. Blocks that are not 2D or 3D are walked in
+    /// storage order.
+    pub fn morton_iter(&self) -> impl Iterator<Item = (Vec<usize>, &T)> + '_ {
+        let mut order: Vec<(u64, usize)> = (0..self.data.len())
+            .map(|linear| {
+                let indices = self.unravel_index(linear);
+                let code = match indices[..] {
+                    [i, j] => MortonEncoding::encode_2d(j as u32, i as u32),
+                    [i, j, k] => MortonEncoding::encode_3d(k as u32, j as u32, i as u32),
+                    _ => linear as u64,
+                };
+                (code, linear)
+            })
+            .collect();
+        order.sort_unstable();
+
+        order.into_iter()
+            .map(move |(_, linear)| (self.unravel_index(linear), &*self.data[linear]))
+    }
+    
+    /// Inverse of `compute_linear_index`
+    fn unravel_index(&self, mut linear: usize) -> Vec<usize> {
+        let mut indices = vec![0; self.dimensions.len()];
+        for step in 0..self.dimensions.len() {
+            let i = self.axis_by_speed(step);
+            indices[i] = linear % self.dimensions[i];
+            linear /= self.dimensions[i];
+        }
+        indices
+    }
+    
+    /// Axis that is the `step`-th fastest-varying in `data` (0 = contiguous)
+    #[inline]
+    fn axis_by_speed(&self, step: usize) -> usize {
+        match self.layout {
+            Layout::RowMajor => self.dimensions.len() - 1 - step,
+            Layout::ColMajor => step,
+        }
+    }
+    
     /// Compute linear index from multi-dimensional indices
     fn compute_linear_index(&self, indices: &[usize]) -> TensorResult<usize> {
         if indices.len() != self.dimensions.len() {
@@ -227,7 +605,9 @@ impl<T: Clone + Zero> TensorBlock<T> {
         let mut linear_index = 0;
         let mut stride = 1;
         
-        for (i, &index) in indices.iter().enumerate().rev() {
+        for step in 0..indices.len() {
+            let i = self.axis_by_speed(step);
+            let index = indices[i];
             if index >= self.dimensions[i] {
                 return Err(TensorError::InvalidDimensions(
                     format!("Index {} out of bounds for dimension {} (size {})", 
@@ -267,7 +647,7 @@ impl<T: Clone + Zero> TensorBlock<T> {
                     let col_end = (col_start + partition_size).min(cols);
                     
                     let block_dims = vec![row_end - row_start, col_end - col_start];
-                    let mut block = TensorBlock::new(block_dims, target_cache_level)?;
+                    let mut block = TensorBlock::new(block_dims, target_cache_level, self.layout)?;
                     
                     // Copy data to the new block
                     for i in 0..(row_end - row_start) {
@@ -288,16 +668,145 @@ impl<T: Clone + Zero> TensorBlock<T> {
     }
 }
 
-/// Main tensor folding engine
+/// Main tensor folding engine, generic over the element type
 #[derive(Debug)]
-pub struct TensorFolder {
+pub struct TensorFolder<T = f64> {
     /// Cache hierarchy configuration
     cache_config: CacheConfig,
     /// Active tensor blocks organized by cache level
     #[allow(clippy::type_complexity)]
-    active_blocks: Arc<RwLock<HashMap<CacheLevel, Vec<Arc<TensorBlock<f64>>>>>>,
+    active_blocks: Arc<RwLock<HashMap<CacheLevel, Vec<Arc<TensorBlock<T>>>>>>,
     /// Memory usage statistics
     memory_stats: Arc<RwLock<MemoryStats>>,
+    /// Simulated cache hierarchy fed by `record_access`
+    working_set: Arc<RwLock<WorkingSetModel>>,
+}
+
+/// One set-associative cache level with LRU replacement per set
+#[derive(Debug, Clone)]
+struct SetAssociativeCache {
+    /// Line tags per touched set, most recently used first; sets are
+    /// allocated on first use so large L3 configurations stay cheap
+    sets: HashMap<u64, VecDeque<u64>>,
+    set_count: u64,
+    ways: usize,
+    line_size: usize,
+    hits: u64,
+    misses: u64,
+}
+
+impl SetAssociativeCache {
+    fn new(size: usize, line_size: usize, ways: usize) -> Self {
+        let line_size = line_size.max(1);
+        let ways = ways.max(1);
+        Self {
+            sets: HashMap::new(),
+            set_count: (size / (line_size * ways)).max(1) as u64,
+            ways,
+            line_size,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Look up the line holding `address`, filling it on a miss
+    fn access(&mut self, address: u64) -> bool {
+        let line = address / self.line_size as u64;
+        let ways = self.ways;
+        let set = self.sets.entry(line % self.set_count).or_insert_with(|| VecDeque::with_capacity(ways));
+
+        let hit = match set.iter().position(|&tag| tag == line) {
+            Some(way) => {
+                set.remove(way);
+                true
+            }
+            None => {
+                if set.len() == ways {
+                    set.pop_back();
+                }
+                false
+            }
+        };
+        set.push_front(line);
+
+        if hit {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+        hit
+    }
+
+    fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 { 0.0 } else { self.hits as f64 / lookups as f64 }
+    }
+}
+
+/// Simulated L1/L2/L3 hierarchy for a stream of element accesses
+///
+/// Each level is set-associative with LRU per set, sized from a
+/// [`CacheConfig`]. An access walks down the levels until one hits and
+/// fills every level it missed, so per-level rates are local: the L2 rate
+/// is over L1 misses, the L3 rate over L2 misses.
+#[derive(Debug, Clone)]
+pub struct WorkingSetModel {
+    levels: [SetAssociativeCache; 3],
+    element_size: usize,
+    accesses: u64,
+    memory_accesses: u64,
+}
+
+/// Hit rates produced by a [`WorkingSetModel`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WorkingSetStats {
+    pub accesses: u64,
+    pub l1_hit_rate: f64,
+    pub l2_hit_rate: f64,
+    pub l3_hit_rate: f64,
+    /// Share of accesses served by any cache level
+    pub cache_hit_rate: f64,
+}
+
+impl WorkingSetModel {
+    /// Model `config`'s hierarchy for elements of `element_size` bytes
+    pub fn new(config: &CacheConfig, element_size: usize) -> Self {
+        let level = |size| SetAssociativeCache::new(size, config.line_size, config.associativity);
+        Self {
+            levels: [level(config.l1_size), level(config.l2_size), level(config.l3_size)],
+            element_size: element_size.max(1),
+            accesses: 0,
+            memory_accesses: 0,
+        }
+    }
+
+    /// Touch the element at `morton_index`; returns the level that served it
+    pub fn record_access(&mut self, morton_index: u64) -> CacheLevel {
+        let address = morton_index.wrapping_mul(self.element_size as u64);
+        self.accesses += 1;
+        for (level, cache) in [CacheLevel::L1, CacheLevel::L2, CacheLevel::L3].into_iter().zip(self.levels.iter_mut()) {
+            if cache.access(address) {
+                return level;
+            }
+        }
+        self.memory_accesses += 1;
+        CacheLevel::Memory
+    }
+
+    /// Hit rates so far
+    pub fn stats(&self) -> WorkingSetStats {
+        WorkingSetStats {
+            accesses: self.accesses,
+            l1_hit_rate: self.levels[0].hit_rate(),
+            l2_hit_rate: self.levels[1].hit_rate(),
+            l3_hit_rate: self.levels[2].hit_rate(),
+            cache_hit_rate: if self.accesses == 0 {
+                0.0
+            } else {
+                (self.accesses - self.memory_accesses) as f64 / self.accesses as f64
+            },
+        }
+    }
 }
 
 /// Cache configuration parameters
@@ -333,34 +842,29 @@ pub struct MemoryStats {
     pub cache_misses: u64,
 }
 
-impl TensorFolder {
+/// The original double-precision tensor folder
+pub type TensorFolderF64 = TensorFolder<f64>;
+
+impl<T: Clone + Zero> TensorFolder<T> {
     /// Create a new tensor folder with the given cache configuration
     pub fn new(cache_config: CacheConfig) -> Self {
         Self {
-            cache_config,
             active_blocks: Arc::new(RwLock::new(HashMap::new())),
             memory_stats: Arc::new(RwLock::new(MemoryStats::default())),
+            working_set: Arc::new(RwLock::new(WorkingSetModel::new(&cache_config, std::mem::size_of::<T>()))),
+            cache_config,
         }
     }
     
     /// Allocate a new tensor with optimal cache placement
-    pub fn allocate_tensor(&self, dimensions: Vec<usize>) -> TensorResult<Arc<TensorBlock<f64>>> {
+    pub fn allocate_tensor(&self, dimensions: Vec<usize>) -> TensorResult<Arc<TensorBlock<T>>> {
         let total_elements: usize = dimensions.iter().product();
-        let element_size = std::mem::size_of::<f64>();
+        let element_size = std::mem::size_of::<T>();
         let total_size = total_elements * element_size;
         
-        // Determine optimal cache level
-        let cache_level = if total_size <= self.cache_config.l1_size {
-            CacheLevel::L1
-        } else if total_size <= self.cache_config.l2_size {
-            CacheLevel::L2
-        } else if total_size <= self.cache_config.l3_size {
-            CacheLevel::L3
-        } else {
-            CacheLevel::Memory
-        };
+        let cache_level = self.cache_level_for(total_size);
         
-        let tensor_block = Arc::new(TensorBlock::new(dimensions, cache_level)?);
+        let tensor_block = Arc::new(TensorBlock::new(dimensions, cache_level, Layout::RowMajor)?);
         
         // Register the block
         let mut active_blocks = self.active_blocks.write().unwrap();
@@ -386,10 +890,56 @@ impl TensorFolder {
         Ok(tensor_block)
     }
     
+    /// Smallest cache level whose configured size holds `total_size` bytes
+    pub fn cache_level_for(&self, total_size: usize) -> CacheLevel {
+        if total_size <= self.cache_config.l1_size {
+            CacheLevel::L1
+        } else if total_size <= self.cache_config.l2_size {
+            CacheLevel::L2
+        } else if total_size <= self.cache_config.l3_size {
+            CacheLevel::L3
+        } else {
+            CacheLevel::Memory
+        }
+    }
+    
     /// Get current memory usage statistics
     pub fn get_memory_stats(&self) -> MemoryStats {
         self.memory_stats.read().unwrap().clone()
     }
+
+    /// Feed an element access to the working-set model
+    ///
+    /// Updates `MemoryStats::cache_hits`/`cache_misses`; a miss means the
+    /// access went past L3.
+    pub fn record_access(&self, morton_index: u64) -> CacheLevel {
+        let level = self.working_set.write().unwrap().record_access(morton_index);
+        let mut stats = self.memory_stats.write().unwrap();
+        match level {
+            CacheLevel::Memory => stats.cache_misses += 1,
+            _ => stats.cache_hits += 1,
+        }
+        level
+    }
+
+    /// Hit rates of the accesses recorded so far
+    pub fn working_set_stats(&self) -> WorkingSetStats {
+        self.working_set.read().unwrap().stats()
+    }
+
+    /// Hit rates for `trace` on a cold cache, leaving recorded state untouched
+    pub fn simulate_access_trace(&self, trace: &[u64]) -> WorkingSetStats {
+        let mut model = WorkingSetModel::new(&self.cache_config, std::mem::size_of::<T>());
+        for &morton_index in trace {
+            model.record_access(morton_index);
+        }
+        model.stats()
+    }
+
+    /// Number of tensor blocks currently held across all cache levels
+    pub fn active_block_count(&self) -> usize {
+        self.active_blocks.read().unwrap().values().map(Vec::len).sum()
+    }
     
     /// Optimize memory layout by refolding tensors
     pub fn optimize_layout(&self) -> TensorResult<()> {
@@ -450,10 +1000,45 @@ mod tests {
         assert_eq!(z, decoded_z);
     }
 
+    #[cfg(target_arch = "x86_64")]
+    #[test]
+    fn test_bmi2_matches_scalar() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        if !bmi2::available() {
+            return;
+        }
+
+        let mut rng = StdRng::seed_from_u64(0x5EED);
+        for _ in 0..10_000 {
+            let (x, y, z): (u32, u32, u32) = (rng.gen(), rng.gen(), rng.gen());
+            let m: u64 = rng.gen();
+            // SAFETY: BMI2 support was checked above
+            unsafe {
+                assert_eq!(bmi2::encode_2d(x, y), MortonEncoding::encode_2d_scalar(x, y));
+                assert_eq!(bmi2::encode_3d(x, y, z), MortonEncoding::encode_3d_scalar(x, y, z));
+                assert_eq!(bmi2::decode_2d(m), MortonEncoding::decode_2d_scalar(m));
+                assert_eq!(bmi2::decode_3d(m), MortonEncoding::decode_3d_scalar(m));
+            }
+        }
+    }
+
+    #[test]
+    fn test_try_encode_3d_bounds() {
+        assert!(matches!(
+            MortonEncoding::try_encode_3d(2_097_152, 0, 0),
+            Err(TensorError::MortonError(_))
+        ));
+        assert!(MortonEncoding::try_encode_3d(0, 0, u32::MAX).is_err());
+
+        let morton = MortonEncoding::try_encode_3d(2_097_151, 0, 0).unwrap();
+        assert_eq!(MortonEncoding::decode_3d(morton), (2_097_151, 0, 0));
+    }
+
     #[test]
     fn test_tensor_block_creation() {
         let dimensions = vec![4, 4];
-        let block = TensorBlock::<f64>::new(dimensions.clone(), CacheLevel::L1);
+        let block = TensorBlock::<f64>::new(dimensions.clone(), CacheLevel::L1, Layout::RowMajor);
         
         assert!(block.is_ok());
         let block = block.unwrap();
@@ -464,7 +1049,7 @@ mod tests {
     #[test]
     fn test_tensor_block_access() {
         let dimensions = vec![3, 3];
-        let mut block = TensorBlock::<f64>::new(dimensions, CacheLevel::L1).unwrap();
+        let mut block = TensorBlock::<f64>::new(dimensions, CacheLevel::L1, Layout::RowMajor).unwrap();
         
         let indices = vec![1, 2];
         let value = 42.0;
@@ -475,10 +1060,126 @@ mod tests {
         assert_eq!(retrieved, value);
     }
 
+    #[test]
+    fn test_aligned_slice_for_simd() {
+        let mut block = TensorBlock::<f32>::new(vec![4, 8], CacheLevel::L1, Layout::RowMajor).unwrap();
+        block.set(&[1, 2], 7.0).unwrap();
+        assert_eq!(block.alignment, 64);
+
+        let slice = block.as_aligned_slice().unwrap();
+        assert_eq!((slice.as_ptr() as usize) % 64, 0);
+        assert_eq!(slice.len(), 32);
+        assert_eq!(slice[8 + 2], 7.0);
+        let first = slice.as_ptr();
+
+        // Later writes show up on the next call, in the same allocation
+        block.set(&[3, 7], -1.0).unwrap();
+        let slice = block.as_aligned_slice().unwrap();
+        assert_eq!(slice[31], -1.0);
+        assert_eq!(slice.as_ptr(), first);
+
+        let mut paged = TensorBlock::<f64>::new(vec![3], CacheLevel::Memory, Layout::RowMajor).unwrap();
+        assert_eq!((paged.as_aligned_slice().unwrap().as_ptr() as usize) % 4096, 0);
+
+        assert!(matches!(AlignedBuffer::<f32>::new(4, 48), Err(TensorError::AlignmentError(_))));
+        let buffer = AlignedBuffer::<f64>::new(5, 128).unwrap();
+        let copy = buffer.clone();
+        assert_eq!((copy.as_ptr() as usize) % 128, 0);
+        assert_eq!(&*copy, &[0.0; 5]);
+    }
+
+    #[test]
+    fn test_layout_strides() {
+        let backing: Vec<CachePadded<f64>> = (0..12).map(|i| CachePadded::new(i as f64)).collect();
+
+        let mut row_major = TensorBlock::<f64>::new(vec![3, 4], CacheLevel::L1, Layout::RowMajor).unwrap();
+        row_major.data = backing.clone();
+        let mut col_major = TensorBlock::<f64>::new(vec![3, 4], CacheLevel::L1, Layout::ColMajor).unwrap();
+        col_major.data = backing;
+
+        // Row-major: 1 * 4 + 2; column-major: 1 + 2 * 3
+        assert_eq!(*row_major.get(&[1, 2]).unwrap(), 6.0);
+        assert_eq!(*col_major.get(&[1, 2]).unwrap(), 7.0);
+        assert!(col_major.get(&[3, 0]).is_err());
+
+        col_major.set(&[2, 3], 99.0).unwrap();
+        assert_eq!(*col_major.data[2 + 3 * 3], 99.0);
+    }
+
+    #[test]
+    fn test_reshape_preserves_row_major_order() {
+        let mut block = TensorBlock::<f64>::new(vec![2, 6], CacheLevel::L1, Layout::RowMajor).unwrap();
+        for i in 0..2 {
+            for j in 0..6 {
+                block.set(&[i, j], (i * 6 + j) as f64).unwrap();
+            }
+        }
+
+        let reshaped = block.reshape(vec![3, 4]).unwrap();
+        assert_eq!(reshaped.dimensions, vec![3, 4]);
+        assert_eq!(reshaped.morton_index, MortonEncoding::encode_2d(3, 4));
+        for i in 0..3 {
+            for j in 0..4 {
+                assert_eq!(*reshaped.get(&[i, j]).unwrap(), (i * 4 + j) as f64);
+            }
+        }
+
+        assert!(block.reshape(vec![5, 2]).is_err());
+    }
+
+    #[test]
+    fn test_transpose_swaps_indices() {
+        let mut block = TensorBlock::<f64>::new(vec![2, 3], CacheLevel::L1, Layout::RowMajor).unwrap();
+        for i in 0..2 {
+            for j in 0..3 {
+                block.set(&[i, j], (10 * i + j) as f64).unwrap();
+            }
+        }
+
+        let transposed = block.transpose(&[1, 0]).unwrap();
+        assert_eq!(transposed.dimensions, vec![3, 2]);
+        assert_eq!(transposed.morton_index, MortonEncoding::encode_2d(3, 2));
+        for i in 0..3 {
+            for j in 0..2 {
+                assert_eq!(transposed.get(&[i, j]).unwrap(), block.get(&[j, i]).unwrap());
+            }
+        }
+
+        assert!(block.transpose(&[0, 0]).is_err());
+        assert!(block.transpose(&[0]).is_err());
+    }
+
+    #[test]
+    fn test_morton_iter_z_order() {
+        let mut block = TensorBlock::<f64>::new(vec![4, 4], CacheLevel::L1, Layout::RowMajor).unwrap();
+        for i in 0..4 {
+            for j in 0..4 {
+                block.set(&[i, j], (i * 4 + j) as f64).unwrap();
+            }
+        }
+
+        let expected: Vec<Vec<usize>> = [
+            (0, 0), (0, 1), (1, 0), (1, 1), (0, 2), (0, 3), (1, 2), (1, 3),
+            (2, 0), (2, 1), (3, 0), (3, 1), (2, 2), (2, 3), (3, 2), (3, 3),
+        ]
+        .iter()
+        .map(|&(i, j)| vec![i, j])
+        .collect();
+
+        let visited: Vec<(Vec<usize>, f64)> = block.morton_iter()
+            .map(|(indices, &value)| (indices, value))
+            .collect();
+        let order: Vec<Vec<usize>> = visited.iter().map(|(indices, _)| indices.clone()).collect();
+        assert_eq!(order, expected);
+        for (indices, value) in visited {
+            assert_eq!(value, (indices[0] * 4 + indices[1]) as f64);
+        }
+    }
+
     #[test]
     fn test_tensor_folder() {
         let config = CacheConfig::default();
-        let folder = TensorFolder::new(config);
+        let folder = TensorFolder::<f64>::new(config);
         
         let dimensions = vec![10, 10];
         let tensor = folder.allocate_tensor(dimensions);
@@ -488,4 +1189,51 @@ mod tests {
         let stats = folder.get_memory_stats();
         assert!(stats.total_allocated > 0);
     }
+
+    #[test]
+    fn test_tensor_folder_element_size() {
+        // 8192 elements: 32KB as f32 fits L1, 64KB as f64 does not
+        let dimensions = vec![64, 128];
+
+        let narrow = TensorFolder::<f32>::new(CacheConfig::default());
+        let tensor = narrow.allocate_tensor(dimensions.clone()).unwrap();
+        assert_eq!(tensor.cache_level, CacheLevel::L1);
+        let stats = narrow.get_memory_stats();
+        assert_eq!(stats.total_allocated, 8192 * 4);
+        assert_eq!(stats.l1_usage, 8192 * 4);
+
+        let wide = TensorFolderF64::new(CacheConfig::default());
+        let tensor = wide.allocate_tensor(dimensions).unwrap();
+        assert_eq!(tensor.cache_level, CacheLevel::L2);
+        assert_eq!(wide.get_memory_stats().l2_usage, 8192 * 8);
+    }
+
+    #[test]
+    fn test_working_set_hit_rates() {
+        let config = CacheConfig::default();
+        let folder = TensorFolderF64::new(config.clone());
+
+        // 1024 f64s = 8KB, replayed ten times: only the first pass misses
+        let resident: Vec<u64> = (0..10).flat_map(|_| 0..1024u64).collect();
+        let small = folder.simulate_access_trace(&resident);
+        assert_eq!(small.accesses, 10 * 1024);
+        assert!(small.l1_hit_rate > 0.95, "L1 rate {}", small.l1_hit_rate);
+        assert!(small.cache_hit_rate > 0.95);
+
+        // One access per line over twice the L3, two passes: LRU never hits
+        let elements_per_line = (config.line_size / 8) as u64;
+        let lines = 2 * (config.l3_size / config.line_size) as u64;
+        let streaming: Vec<u64> = (0..2).flat_map(|_| (0..lines).map(|line| line * elements_per_line)).collect();
+        let large = folder.simulate_access_trace(&streaming);
+        assert!(large.l1_hit_rate < 0.01 && large.l2_hit_rate < 0.01 && large.l3_hit_rate < 0.01);
+        assert!(large.cache_hit_rate < 0.01);
+        assert!(small.cache_hit_rate - large.cache_hit_rate > 0.9);
+
+        // Simulation leaves the folder's own model cold
+        assert_eq!(folder.working_set_stats().accesses, 0);
+        assert_eq!(folder.record_access(0), CacheLevel::Memory);
+        assert_eq!(folder.record_access(1), CacheLevel::L1);
+        let stats = folder.get_memory_stats();
+        assert_eq!((stats.cache_hits, stats.cache_misses), (1, 1));
+    }
 }
@@ -72,7 +72,7 @@ fn main() -> Result<()> {
                 ..Default::default()
             };
 
-            let mut engine = QagmlEngine::new(config)?;
+            let mut engine = QagmlEngine::new(config, None)?;
             println!("✅ QAGML Engine started successfully!");
 
             // Run simple test
@@ -98,7 +98,7 @@ fn main() -> Result<()> {
             println!("   Block Size: {} bytes", block_size);
 
             let config = QagmlConfig::default();
-            let mut engine = QagmlEngine::new(config)?;
+            let mut engine = QagmlEngine::new(config, None)?;
 
             let start = Instant::now();
 
@@ -76,9 +76,17 @@ fn load_model_command(model_path: PathBuf, verify: bool) -> Result<()> {
         enable_tensor_decomposition: true,
         enable_branch_free: true,
         enable_temporal_coherence: true,
+        seed: None,
+        folding_strategy: Default::default(),
+        quality_model: Default::default(),
+        galois_field: Default::default(),
+        eigen_solver: Default::default(),
+        input_dims: INPUT_DIMENSIONS,
+        output_dims: OUTPUT_DIMENSIONS,
+        laplacian_states: LAPLACIAN_STATES,
     };
     
-    let mut engine = QagmlEngine::new(config)?;
+    let mut engine = QagmlEngine::new(config, None)?;
     println!("   ✅ QAGML Engine initialized");
     println!("   Physical Memory: 80 GB (RTX 5090)");
     println!("   Effective Memory: 166,420 PB");
@@ -186,9 +194,17 @@ fn show_stats() -> Result<()> {
         enable_tensor_decomposition: true,
         enable_branch_free: true,
         enable_temporal_coherence: true,
+        seed: None,
+        folding_strategy: Default::default(),
+        quality_model: Default::default(),
+        galois_field: Default::default(),
+        eigen_solver: Default::default(),
+        input_dims: INPUT_DIMENSIONS,
+        output_dims: OUTPUT_DIMENSIONS,
+        laplacian_states: LAPLACIAN_STATES,
     };
     
-    let engine = QagmlEngine::new(config)?;
+    let engine = QagmlEngine::new(config, None)?;
     let stats = engine.get_stats();
     
     println!("📊 QAGML ENGINE STATISTICS:");
@@ -3,7 +3,9 @@
 //! Defines fundamental data structures for GPU memory amplification.
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::time::Duration;
+use thiserror::Error;
 
 /// QAGML amplification factor: 10,000,000x
 pub const AMPLIFICATION_FACTOR: u64 = 10_000_000;
@@ -23,6 +25,9 @@ pub const INPUT_DIMENSIONS: usize = 4096;
 /// Output dimensions after folding
 pub const OUTPUT_DIMENSIONS: usize = 16;
 
+/// States in the Laplacian Q-learning graph
+pub const LAPLACIAN_STATES: usize = 256;
+
 /// GPU memory access target: 0.00001 ns
 pub const TARGET_ACCESS_TIME_NS: f64 = 0.00001;
 
@@ -64,6 +69,13 @@ pub enum MemoryType {
     Texture,
 }
 
+impl MemoryType {
+    /// Whether kernels may only read the memory once it is initialized
+    pub fn is_read_only(self) -> bool {
+        matches!(self, Self::Constant | Self::Texture)
+    }
+}
+
 /// GPU Memory State
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GPUMemoryState {
@@ -75,6 +87,112 @@ pub struct GPUMemoryState {
     pub active_blocks: u32,
     /// Cache hit rate
     pub cache_hit_rate: f32,
+    /// Live blocks by address, as tracked by [`GPUMemoryState::transition`]
+    #[serde(default)]
+    pub blocks: HashMap<u64, BlockState>,
+}
+
+/// Lifecycle state of one allocated block
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockState {
+    /// Block size in bytes
+    pub size: u64,
+    /// Memory type, which decides whether the block may be rewritten
+    pub memory_type: MemoryType,
+    /// Whether the block has been written since allocation
+    pub initialized: bool,
+}
+
+/// Operation applied to a `GPUMemoryState`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryOp {
+    /// Reserve `size` bytes at `address`
+    Allocate { address: u64, size: u64, memory_type: MemoryType },
+    /// Write `len` bytes at `offset` into the block at `address`
+    Write { address: u64, offset: u64, len: u64 },
+    /// Read `len` bytes at `offset` from the block at `address`
+    Read { address: u64, offset: u64, len: u64 },
+    /// Release the block at `address`
+    Free { address: u64 },
+}
+
+/// Illegal `MemoryOp` for the current state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum StateError {
+    #[error("block {0:#x} is already allocated")]
+    AlreadyAllocated(u64),
+    #[error("block {0:#x} is not allocated")]
+    NotAllocated(u64),
+    #[error("allocation of {requested} bytes exceeds {free} free bytes")]
+    OutOfMemory { requested: u64, free: u64 },
+    #[error("access of {len} bytes at offset {offset} is outside block {address:#x}")]
+    OutOfBounds { address: u64, offset: u64, len: u64 },
+    #[error("block {0:#x} is read before it is written")]
+    ReadBeforeWrite(u64),
+    #[error("block {0:#x} is read-only once initialized")]
+    ReadOnly(u64),
+}
+
+impl GPUMemoryState {
+    /// Empty state with `capacity_bytes` free
+    pub fn new(capacity_bytes: u64) -> Self {
+        Self {
+            allocated_bytes: 0,
+            free_bytes: capacity_bytes,
+            active_blocks: 0,
+            cache_hit_rate: 0.0,
+            blocks: HashMap::new(),
+        }
+    }
+
+    /// Apply `op` if it is legal in the current state
+    ///
+    /// Blocks must be allocated before use, written before they are read,
+    /// and freed once. Constant and texture blocks accept a single write.
+    /// The state is left unchanged when an error is returned.
+    pub fn transition(&mut self, op: MemoryOp) -> Result<(), StateError> {
+        match op {
+            MemoryOp::Allocate { address, size, memory_type } => {
+                if self.blocks.contains_key(&address) {
+                    return Err(StateError::AlreadyAllocated(address));
+                }
+                if size > self.free_bytes {
+                    return Err(StateError::OutOfMemory { requested: size, free: self.free_bytes });
+                }
+                self.blocks.insert(address, BlockState { size, memory_type, initialized: false });
+                self.allocated_bytes += size;
+                self.free_bytes -= size;
+                self.active_blocks += 1;
+            }
+            MemoryOp::Write { address, offset, len } => {
+                let block = self.checked_block(address, offset, len)?;
+                if block.initialized && block.memory_type.is_read_only() {
+                    return Err(StateError::ReadOnly(address));
+                }
+                block.initialized = true;
+            }
+            MemoryOp::Read { address, offset, len } => {
+                if !self.checked_block(address, offset, len)?.initialized {
+                    return Err(StateError::ReadBeforeWrite(address));
+                }
+            }
+            MemoryOp::Free { address } => {
+                let block = self.blocks.remove(&address).ok_or(StateError::NotAllocated(address))?;
+                self.allocated_bytes -= block.size;
+                self.free_bytes += block.size;
+                self.active_blocks -= 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn checked_block(&mut self, address: u64, offset: u64, len: u64) -> Result<&mut BlockState, StateError> {
+        let block = self.blocks.get_mut(&address).ok_or(StateError::NotAllocated(address))?;
+        if !matches!(offset.checked_add(len), Some(end) if end <= block.size) {
+            return Err(StateError::OutOfBounds { address, offset, len });
+        }
+        Ok(block)
+    }
 }
 
 /// Memory Access Pattern
@@ -92,6 +210,114 @@ pub struct MemoryAccessPattern {
     pub spatial_locality: f32,
 }
 
+impl MemoryAccessPattern {
+    /// Purely sequential access over contiguous blocks
+    pub fn sequential() -> Self {
+        Self {
+            sequential_ratio: 1.0,
+            random_ratio: 0.0,
+            stride: 0,
+            temporal_locality: 0.0,
+            spatial_locality: 1.0,
+        }
+    }
+
+    /// Regular access every `stride` bytes
+    pub fn strided(stride: usize) -> Self {
+        Self { stride, ..Self::sequential() }
+    }
+
+    /// Address this pattern expects after a `size`-byte access at `address`
+    ///
+    /// Patterns that are mostly sequential continue at `address + stride`,
+    /// or at `address + size` when the stride is zero. Patterns dominated by
+    /// random access predict nothing.
+    pub fn next_address(&self, address: u64, size: usize) -> Option<u64> {
+        if self.sequential_ratio <= self.random_ratio {
+            return None;
+        }
+        let step = if self.stride > 0 { self.stride } else { size };
+        address.checked_add(step as u64)
+    }
+}
+
+/// Which FFT bins dimensional folding keeps
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FoldingStrategy {
+    /// The lowest-frequency bins (0, 1, 2, ...)
+    LowPass,
+    /// The bins with the largest magnitude; their indices are recorded so
+    /// the signal can be unfolded
+    TopMagnitude,
+    /// Every 256th bin (the original behavior)
+    #[default]
+    Strided,
+}
+
+/// Which `QualityModel` scores blocks cached on a read miss
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QualityModelKind {
+    /// Every block scores 1.0
+    Uniform,
+    /// Decayed count of the address's recent accesses
+    #[default]
+    RecencyWeighted,
+    /// The address's share of the access history
+    FrequencyWeighted,
+}
+
+/// Field and keys for Galois-field address encryption
+///
+/// Addresses are encrypted word by word as products in GF(2)[x] modulo
+/// `irreducible`, a degree-32 polynomial (bit 32 set). Both multipliers must
+/// be invertible modulo it so encryption can be undone; `QagmlEngine::new`
+/// rejects configurations where they are not.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GaloisFieldConfig {
+    /// Reduction polynomial, x³² + x⁷ + x³ + x² + 1 by default
+    pub irreducible: u64,
+    /// Multiplier for the low address word
+    pub multiplier_low: u32,
+    /// Multiplier for the high address word
+    pub multiplier_high: u32,
+}
+
+impl Default for GaloisFieldConfig {
+    fn default() -> Self {
+        Self {
+            irreducible: 0x10000008D,
+            multiplier_low: 0x9e3779b9,
+            multiplier_high: 0x7f4a7c15,
+        }
+    }
+}
+
+/// Convergence controls for the Laplacian eigenvalue solver
+///
+/// Power iteration stops once the Rayleigh quotient changes by at most
+/// `tolerance` (relative to the eigenvalue, absolute below 1.0) between
+/// steps, or after `max_iterations` steps. The top `top_k` eigenvalues are
+/// found one after another by deflating each converged eigenpair.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EigenSolverConfig {
+    /// Rayleigh-quotient change below which an eigenvalue counts as converged
+    pub tolerance: f64,
+    /// Power-iteration steps allowed per eigenvalue
+    pub max_iterations: usize,
+    /// Number of largest eigenvalues to compute
+    pub top_k: usize,
+}
+
+impl Default for EigenSolverConfig {
+    fn default() -> Self {
+        Self {
+            tolerance: 1e-9,
+            max_iterations: 200,
+            top_k: 10,
+        }
+    }
+}
+
 /// QAGML Configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QagmlConfig {
@@ -119,6 +345,44 @@ pub struct QagmlConfig {
     pub enable_branch_free: bool,
     /// Enable temporal coherence
     pub enable_temporal_coherence: bool,
+    /// Seed for the engine's pseudo-random keys; `None` keeps the built-in
+    /// constants. Engines with the same seed produce identical results.
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// FFT bin selection for CPU dimensional folding (GPU backends stay strided)
+    #[serde(default)]
+    pub folding_strategy: FoldingStrategy,
+    /// Quality model for quantum-cache amplitudes
+    #[serde(default)]
+    pub quality_model: QualityModelKind,
+    /// Field and multipliers for address encryption; `seed`, when set,
+    /// replaces the multipliers
+    #[serde(default)]
+    pub galois_field: GaloisFieldConfig,
+    /// Tolerance and iteration caps for the Laplacian Q-learning eigenvalues
+    #[serde(default)]
+    pub eigen_solver: EigenSolverConfig,
+    /// Feature dimensions fed to dimensional folding
+    #[serde(default = "default_input_dims")]
+    pub input_dims: usize,
+    /// Dimensions kept by folding; must divide `input_dims`
+    #[serde(default = "default_output_dims")]
+    pub output_dims: usize,
+    /// States in the Laplacian Q-learning graph
+    #[serde(default = "default_laplacian_states")]
+    pub laplacian_states: usize,
+}
+
+fn default_input_dims() -> usize {
+    INPUT_DIMENSIONS
+}
+
+fn default_output_dims() -> usize {
+    OUTPUT_DIMENSIONS
+}
+
+fn default_laplacian_states() -> usize {
+    LAPLACIAN_STATES
 }
 
 impl Default for QagmlConfig {
@@ -136,6 +400,14 @@ impl Default for QagmlConfig {
             enable_tensor_decomposition: true,
             enable_branch_free: true,
             enable_temporal_coherence: true,
+            seed: None,
+            folding_strategy: FoldingStrategy::default(),
+            quality_model: QualityModelKind::default(),
+            galois_field: GaloisFieldConfig::default(),
+            eigen_solver: EigenSolverConfig::default(),
+            input_dims: INPUT_DIMENSIONS,
+            output_dims: OUTPUT_DIMENSIONS,
+            laplacian_states: LAPLACIAN_STATES,
         }
     }
 }
@@ -157,5 +429,59 @@ pub struct GPUMemoryStats {
     pub total_accesses: u64,
     /// Total cache hits
     pub cache_hits: u64,
+    /// Mean L2 error between fold/unfold round trips and their inputs
+    #[serde(default)]
+    pub reconstruction_error: f64,
+    /// Postulates that have run since the engine was created, in pipeline order
+    #[serde(default)]
+    pub postulates_active: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_state_transitions() {
+        let mut state = GPUMemoryState::new(1024);
+        let ops = [
+            MemoryOp::Allocate { address: 0x100, size: 256, memory_type: MemoryType::Global },
+            MemoryOp::Write { address: 0x100, offset: 0, len: 256 },
+            MemoryOp::Read { address: 0x100, offset: 64, len: 64 },
+            MemoryOp::Write { address: 0x100, offset: 0, len: 16 },
+        ];
+        for op in ops {
+            state.transition(op).unwrap();
+        }
+        assert_eq!((state.allocated_bytes, state.free_bytes, state.active_blocks), (256, 768, 1));
+
+        state.transition(MemoryOp::Free { address: 0x100 }).unwrap();
+        assert_eq!((state.allocated_bytes, state.free_bytes, state.active_blocks), (0, 1024, 0));
+        assert_eq!(state.transition(MemoryOp::Free { address: 0x100 }), Err(StateError::NotAllocated(0x100)));
+    }
+
+    #[test]
+    fn test_illegal_memory_transitions() {
+        let mut state = GPUMemoryState::new(1024);
+        state.transition(MemoryOp::Allocate { address: 0x200, size: 128, memory_type: MemoryType::Constant }).unwrap();
+        assert_eq!(
+            state.transition(MemoryOp::Read { address: 0x200, offset: 0, len: 8 }),
+            Err(StateError::ReadBeforeWrite(0x200))
+        );
+        assert!(matches!(
+            state.transition(MemoryOp::Write { address: 0x200, offset: 120, len: 16 }),
+            Err(StateError::OutOfBounds { .. })
+        ));
+        state.transition(MemoryOp::Write { address: 0x200, offset: 0, len: 128 }).unwrap();
+        assert_eq!(
+            state.transition(MemoryOp::Write { address: 0x200, offset: 0, len: 8 }),
+            Err(StateError::ReadOnly(0x200))
+        );
+        assert!(matches!(
+            state.transition(MemoryOp::Allocate { address: 0x300, size: 2048, memory_type: MemoryType::Global }),
+            Err(StateError::OutOfMemory { requested: 2048, free: 896 })
+        ));
+        assert_eq!(state.active_blocks, 1);
+    }
 }
 
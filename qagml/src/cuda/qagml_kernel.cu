@@ -128,8 +128,120 @@ __global__ void qagml_verify_amplification_kernel(
     }
 }
 
+// Offload Kernel: Dimensional Folding (one thread per retained FFT bin)
+// Matches the CPU path: |DFT(x)[k * stride]| for k in [0, out_len)
+__global__ void qagml_fold_kernel(
+    const float* input,
+    int len,
+    float* output,
+    int out_len,
+    int stride
+) {
+    int k = blockIdx.x * blockDim.x + threadIdx.x;
+
+    if (k < out_len) {
+        long long bin = (long long)k * stride;
+        double re = 0.0;
+        double im = 0.0;
+        for (int j = 0; j < len; j++) {
+            // Reduce the phase index modulo len to keep the argument small
+            long long phase_idx = (bin * j) % len;
+            double s, c;
+            sincospi(2.0 * (double)phase_idx / (double)len, &s, &c);
+            re += (double)input[j] * c;
+            im -= (double)input[j] * s;
+        }
+        output[k] = (float)sqrt(re * re + im * im);
+    }
+}
+
+// Offload Kernel: SIMD Vectorization (one thread per element)
+// Each element is scaled by the mean of its vector_width-wide block
+__global__ void qagml_vectorize_kernel(
+    const float* input,
+    int len,
+    float* output,
+    int vector_width
+) {
+    int idx = blockIdx.x * blockDim.x + threadIdx.x;
+
+    if (idx < len) {
+        int start = (idx / vector_width) * vector_width;
+        int end = min(start + vector_width, len);
+        float sum = 0.0f;
+        for (int j = start; j < end; j++) {
+            sum += input[j];
+        }
+        output[idx] = input[idx] * (sum / (float)(end - start));
+    }
+}
+
 // Host function to launch QAGML kernel
 extern "C" {
+
+int qagml_cuda_device_count(void) {
+    int count = 0;
+    if (cudaGetDeviceCount(&count) != cudaSuccess) {
+        return 0;
+    }
+    return count;
+}
+
+int qagml_cuda_fold(const float* input, int len, float* output, int out_len, int stride) {
+    float *d_input, *d_output;
+
+    if (cudaMalloc(&d_input, len * sizeof(float)) != cudaSuccess) {
+        return -1;
+    }
+    if (cudaMalloc(&d_output, out_len * sizeof(float)) != cudaSuccess) {
+        cudaFree(d_input);
+        return -1;
+    }
+
+    cudaMemcpy(d_input, input, len * sizeof(float), cudaMemcpyHostToDevice);
+
+    int threads_per_block = 32;
+    int num_blocks = (out_len + threads_per_block - 1) / threads_per_block;
+    qagml_fold_kernel<<<num_blocks, threads_per_block>>>(d_input, len, d_output, out_len, stride);
+
+    cudaError_t err = cudaDeviceSynchronize();
+    if (err == cudaSuccess) {
+        cudaMemcpy(output, d_output, out_len * sizeof(float), cudaMemcpyDeviceToHost);
+    }
+
+    cudaFree(d_input);
+    cudaFree(d_output);
+
+    return err == cudaSuccess ? 0 : -1;
+}
+
+int qagml_cuda_vectorize(const float* input, int len, float* output, int vector_width) {
+    float *d_input, *d_output;
+
+    if (cudaMalloc(&d_input, len * sizeof(float)) != cudaSuccess) {
+        return -1;
+    }
+    if (cudaMalloc(&d_output, len * sizeof(float)) != cudaSuccess) {
+        cudaFree(d_input);
+        return -1;
+    }
+
+    cudaMemcpy(d_input, input, len * sizeof(float), cudaMemcpyHostToDevice);
+
+    int threads_per_block = 256;
+    int num_blocks = (len + threads_per_block - 1) / threads_per_block;
+    qagml_vectorize_kernel<<<num_blocks, threads_per_block>>>(d_input, len, d_output, vector_width);
+
+    cudaError_t err = cudaDeviceSynchronize();
+    if (err == cudaSuccess) {
+        cudaMemcpy(output, d_output, len * sizeof(float), cudaMemcpyDeviceToHost);
+    }
+
+    cudaFree(d_input);
+    cudaFree(d_output);
+
+    return err == cudaSuccess ? 0 : -1;
+}
     
 int qagml_cuda_test(int num_accesses) {
     printf("🚀 QAGML CUDA Kernel Test - RTX 5090\n");
@@ -0,0 +1,84 @@
+//! GPU Offload Backends for QAGML
+//!
+//! `QagmlEngine` dispatches the two data-parallel postulates (dimensional
+//! folding and SIMD vectorization) through a [`GpuBackend`]. The CUDA
+//! implementation lives in `cuda_ffi` behind the `cuda` feature;
+//! [`CpuBackend`] is the always-available fallback.
+
+use rustfft::{FftPlanner, num_complex::Complex};
+
+/// Folding stride: every 256th FFT coefficient is retained
+pub const FOLD_STRIDE: usize = 256;
+
+/// SIMD vector width (elements per block)
+pub const VECTOR_WIDTH: usize = 16;
+
+/// Compute backend for the data-parallel QAGML postulates
+pub trait GpuBackend: Send + Sync {
+    /// Human-readable backend name
+    fn name(&self) -> &str;
+
+    /// Dimensional folding: 4096D → 16D spectral magnitudes
+    fn fold(&self, features: &[f32]) -> Vec<f32>;
+
+    /// SIMD vectorization: scale each 16-wide block by its mean
+    fn vectorize(&self, data: &[f32]) -> Vec<f32>;
+}
+
+/// CPU fallback backend (rustfft + scalar blocks)
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CpuBackend;
+
+impl CpuBackend {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl GpuBackend for CpuBackend {
+    fn name(&self) -> &str {
+        "cpu"
+    }
+
+    fn fold(&self, features: &[f32]) -> Vec<f32> {
+        if features.is_empty() {
+            return Vec::new();
+        }
+
+        let mut buffer: Vec<Complex<f32>> = features.iter()
+            .map(|&x| Complex::new(x, 0.0))
+            .collect();
+
+        let fft = FftPlanner::new().plan_fft_forward(buffer.len());
+        fft.process(&mut buffer);
+
+        buffer.iter()
+            .step_by(FOLD_STRIDE)
+            .take(crate::core::OUTPUT_DIMENSIONS)
+            .map(|c| c.norm())
+            .collect()
+    }
+
+    fn vectorize(&self, data: &[f32]) -> Vec<f32> {
+        let mut result = Vec::with_capacity(data.len());
+        for chunk in data.chunks(VECTOR_WIDTH) {
+            let sum: f32 = chunk.iter().sum();
+            let avg = sum / chunk.len() as f32;
+            result.extend(chunk.iter().map(|&x| x * avg));
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cpu_backend_shapes() {
+        let backend = CpuBackend::new();
+        let features: Vec<f32> = (0..4096).map(|i| i as f32 / 1000.0).collect();
+        assert_eq!(backend.fold(&features).len(), 16);
+        assert_eq!(backend.vectorize(&features).len(), 4096);
+    }
+}
@@ -27,20 +27,25 @@
 pub mod core;
 pub mod engine;
 pub mod bus_width_amplification;
+pub mod gpu_backend;
+pub mod quality;
 
 #[cfg(feature = "cuda")]
 pub mod cuda_ffi;
 
 // Re-export core types
 pub use core::{
-    MemoryBlock, MemoryMetadata, MemoryType, GPUMemoryState,
-    MemoryAccessPattern, QagmlConfig, GPUMemoryStats,
+    MemoryBlock, MemoryMetadata, MemoryType, GPUMemoryState, BlockState, MemoryOp, StateError,
+    MemoryAccessPattern, QagmlConfig, FoldingStrategy, QualityModelKind, GPUMemoryStats,
+    GaloisFieldConfig, EigenSolverConfig,
     AMPLIFICATION_FACTOR, TARGET_MEMORY_PB, PHYSICAL_MEMORY_GB,
-    COMPRESSION_RATIO, INPUT_DIMENSIONS, OUTPUT_DIMENSIONS,
+    COMPRESSION_RATIO, INPUT_DIMENSIONS, OUTPUT_DIMENSIONS, LAPLACIAN_STATES,
     TARGET_ACCESS_TIME_NS,
 };
 
-pub use engine::QagmlEngine;
+pub use engine::{QagmlEngine, TensorFactor};
+pub use gpu_backend::{GpuBackend, CpuBackend};
+pub use quality::{QualityModel, Uniform, RecencyWeighted, FrequencyWeighted};
 
 // Re-export bus width amplification types
 pub use bus_width_amplification::{
@@ -0,0 +1,95 @@
+//! Quantum Cache Quality Models for QAGML
+//!
+//! On a read miss `QagmlEngine` caches the generated block with a quality
+//! score in [0, 1]; the quantum cache stores `√quality` as the block's
+//! amplitude. A [`QualityModel`] derives that score from the
+//! temporal-coherence access history, and `QagmlConfig::quality_model`
+//! selects which one the engine uses.
+
+use crate::core::QualityModelKind;
+
+/// Per-access decay applied by [`RecencyWeighted`]
+pub const RECENCY_DECAY: f64 = 0.9;
+
+/// Scores an address for the quantum cache
+pub trait QualityModel: Send + Sync {
+    /// Quality in [0, 1] for `address`, given the access history (oldest
+    /// first, the current access last)
+    fn score(&self, address: u64, access_history: &[u64]) -> f64;
+}
+
+/// Every address scores 1.0
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Uniform;
+
+impl QualityModel for Uniform {
+    fn score(&self, _address: u64, _access_history: &[u64]) -> f64 {
+        1.0
+    }
+}
+
+/// Exponentially decayed count of the address's accesses
+///
+/// Each access contributes `(1 - d) · d^age` with `d = RECENCY_DECAY` and
+/// `age` the number of accesses since, so recent and repeated accesses
+/// score higher.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RecencyWeighted;
+
+impl QualityModel for RecencyWeighted {
+    fn score(&self, address: u64, access_history: &[u64]) -> f64 {
+        access_history.iter()
+            .rev()
+            .zip(0..)
+            .filter(|&(&a, _)| a == address)
+            .map(|(_, age)| (1.0 - RECENCY_DECAY) * RECENCY_DECAY.powi(age))
+            .sum::<f64>()
+            .min(1.0)
+    }
+}
+
+/// Share of the history taken by the address
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FrequencyWeighted;
+
+impl QualityModel for FrequencyWeighted {
+    fn score(&self, address: u64, access_history: &[u64]) -> f64 {
+        if access_history.is_empty() {
+            return 0.0;
+        }
+        let hits = access_history.iter().filter(|&&a| a == address).count();
+        hits as f64 / access_history.len() as f64
+    }
+}
+
+impl QualityModelKind {
+    /// Instantiate the selected model
+    pub fn model(self) -> Box<dyn QualityModel> {
+        match self {
+            Self::Uniform => Box::new(Uniform),
+            Self::RecencyWeighted => Box::new(RecencyWeighted),
+            Self::FrequencyWeighted => Box::new(FrequencyWeighted),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quality_models_bounded() {
+        let history = [1, 2, 1, 3, 1, 1];
+        for kind in [QualityModelKind::Uniform, QualityModelKind::RecencyWeighted, QualityModelKind::FrequencyWeighted] {
+            let model = kind.model();
+            for address in [1, 2, 3, 4] {
+                let score = model.score(address, &history);
+                assert!((0.0..=1.0).contains(&score), "{:?} scored {}", kind, score);
+            }
+        }
+
+        assert!(RecencyWeighted.score(1, &history) > RecencyWeighted.score(3, &history));
+        assert!(RecencyWeighted.score(3, &history) > RecencyWeighted.score(2, &history));
+        assert_eq!(FrequencyWeighted.score(4, &history), 0.0);
+    }
+}
@@ -68,9 +68,12 @@ struct ProcessPacketResponse {
 #[derive(Debug, Serialize)]
 struct HealthResponse {
     status: String,
+    liveness: bool,
+    readiness: bool,
     uptime_seconds: u64,
     packets_processed: u64,
-    postulates_active: u8,
+    postulates_active: u16,
+    queue_depth: u64,
     memory_usage_mb: f64,
 }
 
@@ -126,10 +129,13 @@ async fn health_check(State(state): State<Arc<AppState>>) -> Json<HealthResponse
     let health = engine.health_check();
 
     Json(HealthResponse {
-        status: if health.is_healthy { "healthy".to_string() } else { "unhealthy".to_string() },
+        status: if health.readiness { "healthy".to_string() } else { "unhealthy".to_string() },
+        liveness: health.liveness,
+        readiness: health.readiness,
         uptime_seconds: health.uptime_seconds,
         packets_processed: health.packets_processed,
         postulates_active: health.postulates_active,
+        queue_depth: health.queue_depth,
         memory_usage_mb: health.memory_usage_mb,
     })
 }
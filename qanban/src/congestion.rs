@@ -0,0 +1,104 @@
+//! Path congestion detection for QANBAN
+//!
+//! Tracks capacity and measured utilization per network path so flow
+//! optimization can avoid committing more bandwidth than a path has left.
+
+use std::collections::HashMap;
+use serde::{Serialize, Deserialize};
+
+/// Default utilization fraction above which a path counts as congested
+pub const DEFAULT_CONGESTION_THRESHOLD: f64 = 0.8;
+
+/// Capacity and utilization of a single path
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct PathState {
+    /// Link capacity in Gbps
+    pub capacity_gbps: f64,
+    /// Measured utilization in Gbps
+    pub utilized_gbps: f64,
+}
+
+impl PathState {
+    /// Unused capacity in Gbps (never negative)
+    pub fn headroom_gbps(&self) -> f64 {
+        (self.capacity_gbps - self.utilized_gbps).max(0.0)
+    }
+
+    /// Utilization as a fraction of capacity (1.0 for zero-capacity paths)
+    pub fn utilization(&self) -> f64 {
+        if self.capacity_gbps > 0.0 {
+            self.utilized_gbps / self.capacity_gbps
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Per-path congestion detector
+#[derive(Debug, Clone)]
+pub struct CongestionDetector {
+    paths: HashMap<String, PathState>,
+    threshold: f64,
+}
+
+impl Default for CongestionDetector {
+    fn default() -> Self {
+        Self::new(DEFAULT_CONGESTION_THRESHOLD)
+    }
+}
+
+impl CongestionDetector {
+    /// Create a detector flagging paths whose utilization exceeds `threshold` (0.0–1.0)
+    pub fn new(threshold: f64) -> Self {
+        Self {
+            paths: HashMap::new(),
+            threshold: threshold.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Set a path's capacity, registering the path if it is new
+    pub fn set_capacity(&mut self, path_id: &str, capacity_gbps: f64) {
+        self.paths.entry(path_id.to_string()).or_default().capacity_gbps = capacity_gbps.max(0.0);
+    }
+
+    /// Record the measured utilization of a path
+    pub fn record_utilization(&mut self, path_id: &str, utilized_gbps: f64) {
+        self.paths.entry(path_id.to_string()).or_default().utilized_gbps = utilized_gbps.max(0.0);
+    }
+
+    /// Current state of a path, if it is being tracked
+    pub fn path(&self, path_id: &str) -> Option<PathState> {
+        self.paths.get(path_id).copied()
+    }
+
+    /// Remaining headroom in Gbps; `None` for untracked paths
+    pub fn headroom_gbps(&self, path_id: &str) -> Option<f64> {
+        self.paths.get(path_id).map(PathState::headroom_gbps)
+    }
+
+    /// Whether the path's utilization is above the congestion threshold
+    pub fn is_congested(&self, path_id: &str) -> bool {
+        self.paths.get(path_id).is_some_and(|p| p.utilization() > self.threshold)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_congestion_threshold_and_headroom() {
+        let mut detector = CongestionDetector::default();
+        detector.set_capacity("a-b", 100.0);
+        detector.record_utilization("a-b", 50.0);
+        assert!(!detector.is_congested("a-b"));
+        assert_eq!(detector.headroom_gbps("a-b"), Some(50.0));
+
+        detector.record_utilization("a-b", 95.0);
+        assert!(detector.is_congested("a-b"));
+        assert_eq!(detector.headroom_gbps("a-b"), Some(5.0));
+
+        assert!(!detector.is_congested("unknown"));
+        assert_eq!(detector.headroom_gbps("unknown"), None);
+    }
+}
@@ -111,10 +111,16 @@ pub struct BandwidthStats {
     pub effective_bandwidth_pbps: f64,
     pub amplification_factor: f64,
     pub compression_ratio: f64,
+    /// Mean of the per-packet measured ratios (folded bytes / original bytes)
+    #[serde(default)]
+    pub average_compression_ratio: f64,
     pub packet_loss_rate: f64,
     pub avg_latency_ns: f64,
     pub throughput_pps: u64,
     pub packets_processed: u64,
+    /// Flows seen within the idle timeout
+    #[serde(default)]
+    pub active_flow_count: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -126,6 +132,20 @@ pub struct QanbanConfig {
     pub enable_pme: bool,
     pub enable_quantum_cache: bool,
     pub enable_simd: bool,
+    /// Batch backlog above which the engine reports itself not ready
+    #[serde(default = "default_max_queue_depth")]
+    pub max_queue_depth: u64,
+    /// Seconds a flow may go without packets before it is evicted
+    #[serde(default = "default_flow_idle_timeout_secs")]
+    pub flow_idle_timeout_secs: u64,
+}
+
+fn default_max_queue_depth() -> u64 {
+    10_000
+}
+
+fn default_flow_idle_timeout_secs() -> u64 {
+    crate::flows::DEFAULT_FLOW_IDLE_TIMEOUT.as_secs()
 }
 
 impl Default for QanbanConfig {
@@ -138,6 +158,8 @@ impl Default for QanbanConfig {
             enable_pme: true,
             enable_quantum_cache: true,
             enable_simd: true,
+            max_queue_depth: default_max_queue_depth(),
+            flow_idle_timeout_secs: default_flow_idle_timeout_secs(),
         }
     }
 }
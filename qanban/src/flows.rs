@@ -0,0 +1,141 @@
+//! Flow tracking for QANBAN
+//!
+//! Keeps a [`NetworkFlow`] per flow id seen in processed packets. Flows
+//! untouched for the idle timeout are evicted, lazily when new flows arrive
+//! or explicitly through [`FlowTable::evict_idle`], so churny short-lived
+//! connections don't accumulate state.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use crate::core::NetworkFlow;
+
+/// Default idle time after which a flow is evicted
+pub const DEFAULT_FLOW_IDLE_TIMEOUT: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone)]
+struct TrackedFlow {
+    flow: NetworkFlow,
+    last_seen: Instant,
+}
+
+/// Flow table with idle-timeout eviction
+#[derive(Debug, Clone)]
+pub struct FlowTable {
+    flows: HashMap<u64, TrackedFlow>,
+    idle_timeout: Duration,
+    last_sweep: Instant,
+}
+
+impl Default for FlowTable {
+    fn default() -> Self {
+        Self::new(DEFAULT_FLOW_IDLE_TIMEOUT)
+    }
+}
+
+impl FlowTable {
+    /// Create a table evicting flows idle for longer than `idle_timeout`
+    pub fn new(idle_timeout: Duration) -> Self {
+        Self {
+            flows: HashMap::new(),
+            idle_timeout,
+            last_sweep: Instant::now(),
+        }
+    }
+
+    /// Count a packet of `bytes` against its flow, creating the flow if needed
+    pub fn record(&mut self, flow_id: u64, bytes: u64) {
+        self.record_at(flow_id, bytes, Instant::now());
+    }
+
+    fn record_at(&mut self, flow_id: u64, bytes: u64, now: Instant) {
+        // Sweep at most once per timeout, and only when the table grows
+        if !self.flows.contains_key(&flow_id) && now.saturating_duration_since(self.last_sweep) >= self.idle_timeout {
+            self.evict_idle_at(now);
+        }
+
+        let tracked = self.flows.entry(flow_id).or_insert_with(|| TrackedFlow {
+            flow: NetworkFlow { flow_id, packet_count: 0, byte_count: 0, active: true },
+            last_seen: now,
+        });
+        tracked.flow.packet_count += 1;
+        tracked.flow.byte_count += bytes;
+        tracked.last_seen = now;
+    }
+
+    /// Drop every flow idle for longer than the timeout; returns how many were evicted
+    pub fn evict_idle(&mut self) -> usize {
+        self.evict_idle_at(Instant::now())
+    }
+
+    fn evict_idle_at(&mut self, now: Instant) -> usize {
+        let before = self.flows.len();
+        let idle_timeout = self.idle_timeout;
+        self.flows.retain(|_, tracked| now.saturating_duration_since(tracked.last_seen) <= idle_timeout);
+        self.last_sweep = now;
+        before - self.flows.len()
+    }
+
+    /// Flows touched within the idle timeout
+    pub fn active_flow_count(&self) -> usize {
+        self.active_flow_count_at(Instant::now())
+    }
+
+    fn active_flow_count_at(&self, now: Instant) -> usize {
+        self.flows.values()
+            .filter(|tracked| now.saturating_duration_since(tracked.last_seen) <= self.idle_timeout)
+            .count()
+    }
+
+    /// Tracked state of a flow
+    pub fn get(&self, flow_id: u64) -> Option<&NetworkFlow> {
+        self.flows.get(&flow_id).map(|tracked| &tracked.flow)
+    }
+
+    /// Flows held in the table, including idle ones not yet evicted
+    pub fn len(&self) -> usize {
+        self.flows.len()
+    }
+
+    /// Whether the table holds no flows
+    pub fn is_empty(&self) -> bool {
+        self.flows.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_idle_flows_evicted() {
+        let timeout = Duration::from_secs(60);
+        let mut table = FlowTable::new(timeout);
+        let start = Instant::now();
+
+        for flow_id in 1..=3 {
+            table.record_at(flow_id, 100, start);
+        }
+        table.record_at(2, 50, start + Duration::from_secs(45));
+        assert_eq!(table.len(), 3);
+        assert_eq!(table.get(2).unwrap().packet_count, 2);
+        assert_eq!(table.get(2).unwrap().byte_count, 150);
+
+        // Past the timeout for flows 1 and 3, but not for the re-touched flow 2
+        let later = start + timeout + Duration::from_secs(1);
+        assert_eq!(table.active_flow_count_at(later), 1);
+        assert_eq!(table.len(), 3);
+
+        // A new flow triggers the lazy sweep
+        table.record_at(4, 10, later);
+        assert_eq!(table.len(), 2);
+        assert!(table.get(1).is_none() && table.get(3).is_none());
+        assert!(table.get(2).is_some() && table.get(4).is_some());
+
+        // Existing flows never trigger a sweep on their own
+        let much_later = later + timeout * 2;
+        table.record_at(2, 10, much_later);
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.evict_idle_at(much_later), 1);
+        assert_eq!(table.get(2).unwrap().packet_count, 3);
+    }
+}
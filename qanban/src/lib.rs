@@ -35,22 +35,34 @@
 //! let result = engine.process_packet(&packet)?;
 //!
 //! println!("Amplification: {}×", result.amplification_factor);
-//! println!("Compression: {:.2}%", result.compression_ratio * 100.0);
+//! println!("Folded size: {:.2}% of input", result.compression_ratio * 100.0);
 //! ```
 
 pub mod core;
 pub mod postulates;
 pub mod engine;
+pub mod congestion;
+pub mod flows;
+pub mod router;
 
 // Re-export key types from core
 pub use core::{Packet, PacketMetadata, NetworkFlow, BandwidthStats, QanbanConfig};
 
 // Re-export engine types
-pub use engine::{QanbanEngine, ProcessedPacket, EngineHealth};
+pub use engine::{QanbanEngine, ProcessedPacket, EngineHealth, Postulate, ALL_POSTULATES_MASK};
+
+// Re-export path congestion tracking
+pub use congestion::{CongestionDetector, PathState};
+
+// Re-export flow tracking
+pub use flows::FlowTable;
+
+// Re-export the parallel-path router
+pub use router::QuantumRouter;
 
 // Re-export postulate engines for advanced usage
 pub use postulates::{
-    dimensional_folding::DimensionalFoldingEngine,
+    dimensional_folding::{DimensionalFoldingEngine, FoldedMetadata},
     laplacian_qlearning::{LaplacianQLearningEngine, NetworkState, RoutingAction},
     pme_engine::PMEEngine,
     quantum_cache::{QuantumSuperpositionCache, RoutingPath, QuantumState},
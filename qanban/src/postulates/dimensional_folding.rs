@@ -3,21 +3,35 @@
 //! POSTULATE 1: Dimensional Folding (1024D → 10D)
 //! 
 //! Compress packet metadata from 1024 dimensions to 10 dimensions
-//! using Babai reduction, De Bruijn sequences, and Fourier transforms.
+//! using Babai reduction over the Fourier basis.
 //!
 //! **Mathematical Foundation**:
 //! - Babai's Nearest Plane Algorithm for lattice reduction
-//! - De Bruijn sequences for optimal encoding
 //! - Fast Fourier Transform for frequency domain compression
 //!
+//! The Fourier basis is orthogonal, so the nearest-plane step reduces to
+//! projecting onto each basis vector; folding keeps the basis vectors
+//! carrying the most energy and records their indices in
+//! [`FoldedMetadata::basis`] so the metadata can be unfolded again.
+//!
 //! **Performance**:
 //! - Compression ratio: 98.97% (1024D → 10D)
-//! - Reconstruction error: < 0.01%
+//! - Reconstruction error: exact for inputs spanned by 5 basis vectors
 //! - Compression time: < 1 µs per packet
 
-use ndarray::{Array1, Array2};
 use rustfft::{FftPlanner, num_complex::Complex};
 use anyhow::Result;
+use serde::{Serialize, Deserialize};
+
+/// Folded packet metadata plus the basis indices needed to unfold it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FoldedMetadata {
+    /// Real and imaginary coefficient of each retained basis vector in turn,
+    /// scaled by 1/n and zero-padded to the output dimension
+    pub values: Vec<f32>,
+    /// Fourier basis indices (0..=n/2) of the retained vectors, ascending
+    pub basis: Vec<usize>,
+}
 
 /// Dimensional Folding Engine
 pub struct DimensionalFoldingEngine {
@@ -25,147 +39,84 @@ pub struct DimensionalFoldingEngine {
     input_dims: usize,
     /// Output dimensions
     output_dims: usize,
-    /// Projection matrix (Babai reduction)
-    projection_matrix: Array2<f32>,
     /// FFT planner
     fft_planner: FftPlanner<f32>,
-    /// De Bruijn sequence for encoding
-    debruijn_sequence: Vec<u8>,
 }
 
 impl DimensionalFoldingEngine {
     /// Create new dimensional folding engine
     pub fn new(input_dims: usize, output_dims: usize) -> Self {
-        // Initialize projection matrix using Gram-Schmidt orthogonalization
-        let projection_matrix = Self::initialize_projection_matrix(input_dims, output_dims);
-        
-        // Generate De Bruijn sequence for optimal encoding
-        let debruijn_sequence = Self::generate_debruijn_sequence(output_dims);
-        
         Self {
             input_dims,
             output_dims,
-            projection_matrix,
             fft_planner: FftPlanner::new(),
-            debruijn_sequence,
         }
     }
 
     /// Fold high-dimensional packet metadata to low-dimensional representation
+    ///
+    /// Keeps the `output_dims / 2` basis vectors with the most energy, two
+    /// values (real, imaginary) each.
     #[inline(always)]
-    pub fn fold(&mut self, features: &[f32]) -> Result<Vec<f32>> {
+    pub fn fold(&mut self, features: &[f32]) -> Result<FoldedMetadata> {
         assert_eq!(features.len(), self.input_dims, "Input dimension mismatch");
+        let n = self.input_dims;
 
-        // Step 1: FFT preprocessing (frequency domain compression)
-        let fft_features = self.fft_preprocess(features)?;
-
-        // Step 2: Babai reduction (lattice-based projection)
-        let projected = self.babai_project(&fft_features)?;
-
-        // Step 3: De Bruijn encoding (optimal bit packing)
-        let encoded = self.debruijn_encode(&projected)?;
-
-        Ok(encoded)
-    }
-
-    /// Unfold low-dimensional representation back to high-dimensional space
-    #[inline(always)]
-    pub fn unfold(&mut self, folded: &[f32]) -> Result<Vec<f32>> {
-        assert_eq!(folded.len(), self.output_dims, "Output dimension mismatch");
-
-        // Step 1: De Bruijn decoding
-        let decoded = self.debruijn_decode(folded)?;
-
-        // Step 2: Inverse Babai projection
-        let unprojected = self.inverse_babai_project(&decoded)?;
-
-        // Step 3: Inverse FFT
-        let reconstructed = self.ifft_postprocess(&unprojected)?;
-
-        Ok(reconstructed)
-    }
-
-    /// FFT preprocessing for frequency domain compression
-    fn fft_preprocess(&mut self, features: &[f32]) -> Result<Vec<f32>> {
-        // Convert to complex numbers
         let mut buffer: Vec<Complex<f32>> = features
             .iter()
             .map(|&x| Complex::new(x, 0.0))
             .collect();
-
-        // Perform FFT
-        let fft = self.fft_planner.plan_fft_forward(buffer.len());
+        let fft = self.fft_planner.plan_fft_forward(n);
         fft.process(&mut buffer);
 
-        // Extract magnitude (discard phase for compression)
-        let magnitudes: Vec<f32> = buffer.iter().map(|c| c.norm()).collect();
-
-        Ok(magnitudes)
-    }
-
-    /// Inverse FFT postprocessing
-    fn ifft_postprocess(&mut self, features: &[f32]) -> Result<Vec<f32>> {
-        // Convert to complex numbers (zero phase)
-        let mut buffer: Vec<Complex<f32>> = features
-            .iter()
-            .map(|&x| Complex::new(x, 0.0))
+        // A real signal has n/2 + 1 independent basis coefficients; the
+        // others are their conjugates and count towards their energy
+        let energy = |k: usize| {
+            let weight = if k == 0 || 2 * k == n { 1.0 } else { 2.0 };
+            buffer[k].norm_sqr() * weight
+        };
+        let mut order: Vec<usize> = (0..=n / 2).collect();
+        order.sort_by(|&a, &b| energy(b).total_cmp(&energy(a)).then(a.cmp(&b)));
+        let mut basis: Vec<usize> = order.into_iter().take(self.output_dims / 2).collect();
+        basis.sort_unstable();
+
+        let scale = 1.0 / n as f32;
+        let mut values: Vec<f32> = basis.iter()
+            .flat_map(|&k| [buffer[k].re * scale, buffer[k].im * scale])
             .collect();
+        values.resize(self.output_dims, 0.0);
 
-        // Perform inverse FFT
-        let ifft = self.fft_planner.plan_fft_inverse(buffer.len());
-        ifft.process(&mut buffer);
-
-        // Extract real part
-        let reconstructed: Vec<f32> = buffer.iter().map(|c| c.re).collect();
-
-        Ok(reconstructed)
-    }
-
-    /// Babai projection using nearest plane algorithm
-    fn babai_project(&self, features: &[f32]) -> Result<Vec<f32>> {
-        let input = Array1::from_vec(features.to_vec());
-        let projected = self.projection_matrix.dot(&input);
-        Ok(projected.to_vec())
+        Ok(FoldedMetadata { values, basis })
     }
 
-    /// Inverse Babai projection
-    fn inverse_babai_project(&self, projected: &[f32]) -> Result<Vec<f32>> {
-        // Pseudo-inverse reconstruction
-        let proj_vec = Array1::from_vec(projected.to_vec());
-        
-        // Simplified reconstruction (in production, use proper pseudo-inverse)
-        let mut reconstructed = vec![0.0; self.input_dims];
-        for i in 0..self.output_dims.min(self.input_dims) {
-            reconstructed[i] = proj_vec[i];
+    /// Unfold low-dimensional representation back to high-dimensional space
+    ///
+    /// Exact up to f32 rounding when the input was spanned by at most
+    /// `output_dims / 2` basis vectors; otherwise the squared error is the
+    /// energy of the discarded ones.
+    #[inline(always)]
+    pub fn unfold(&mut self, folded: &FoldedMetadata) -> Result<Vec<f32>> {
+        let n = self.input_dims;
+        if folded.values.len() < 2 * folded.basis.len() {
+            anyhow::bail!("{} values cannot hold {} basis coefficients", folded.values.len(), folded.basis.len());
         }
-        
-        Ok(reconstructed)
-    }
-
-    /// De Bruijn encoding for optimal bit packing
-    fn debruijn_encode(&self, features: &[f32]) -> Result<Vec<f32>> {
-        // Simplified encoding (in production, use proper De Bruijn sequences)
-        Ok(features.to_vec())
-    }
 
-    /// De Bruijn decoding
-    fn debruijn_decode(&self, encoded: &[f32]) -> Result<Vec<f32>> {
-        // Simplified decoding
-        Ok(encoded.to_vec())
-    }
+        let mut buffer = vec![Complex::new(0.0f32, 0.0); n];
+        for (i, &k) in folded.basis.iter().enumerate() {
+            if k > n / 2 {
+                anyhow::bail!("basis index {} out of range for {} dimensions", k, n);
+            }
+            let coefficient = Complex::new(folded.values[2 * i], folded.values[2 * i + 1]);
+            buffer[k] = coefficient;
+            if k != 0 && 2 * k != n {
+                buffer[n - k] = coefficient.conj();
+            }
+        }
 
-    /// Initialize projection matrix using Gram-Schmidt
-    fn initialize_projection_matrix(input_dims: usize, output_dims: usize) -> Array2<f32> {
-        // Random initialization (in production, use proper Gram-Schmidt)
-        Array2::from_shape_fn((output_dims, input_dims), |(i, j)| {
-            ((i + j) as f32 * 0.01).sin()
-        })
-    }
+        let ifft = self.fft_planner.plan_fft_inverse(n);
+        ifft.process(&mut buffer);
 
-    /// Generate De Bruijn sequence
-    fn generate_debruijn_sequence(k: usize) -> Vec<u8> {
-        // Simplified De Bruijn sequence generation
-        (0..k).map(|i| (i % 256) as u8).collect()
+        Ok(buffer.iter().map(|c| c.re).collect())
     }
 }
 
@@ -173,16 +124,59 @@ impl DimensionalFoldingEngine {
 mod tests {
     use super::*;
 
+    fn relative_error(original: &[f32], restored: &[f32]) -> f32 {
+        let residual: f32 = original.iter().zip(restored).map(|(a, b)| (a - b).powi(2)).sum();
+        let norm: f32 = original.iter().map(|a| a * a).sum();
+        (residual / norm).sqrt()
+    }
+
     #[test]
     fn test_dimensional_folding() {
         let mut engine = DimensionalFoldingEngine::new(1024, 10);
         let features = vec![1.0; 1024];
 
         let folded = engine.fold(&features).unwrap();
-        assert_eq!(folded.len(), 10);
+        assert_eq!(folded.values.len(), 10);
+        assert_eq!(folded.basis.len(), 5);
 
         let unfolded = engine.unfold(&folded).unwrap();
         assert_eq!(unfolded.len(), 1024);
     }
-}
 
+    #[test]
+    fn test_unfold_structured_and_noise() {
+        let n = 1024;
+        let mut engine = DimensionalFoldingEngine::new(n, 10);
+
+        // Four basis vectors: within the five a 10D fold retains
+        let structured: Vec<f32> = (0..n)
+            .map(|t| {
+                let phase = 2.0 * std::f32::consts::PI * t as f32 / n as f32;
+                0.5 + (3.0 * phase).cos() + 0.5 * (17.0 * phase).sin() + 0.25 * (100.0 * phase).cos()
+            })
+            .collect();
+        let folded = engine.fold(&structured).unwrap();
+        for k in [0, 3, 17, 100] {
+            assert!(folded.basis.contains(&k), "basis {:?} misses {}", folded.basis, k);
+        }
+        let structured_error = relative_error(&structured, &engine.unfold(&folded).unwrap());
+        assert!(structured_error < 1e-3, "structured error {}", structured_error);
+
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let noise: Vec<f32> = (0..n)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                (state >> 40) as f32 / (1u64 << 24) as f32 * 2.0 - 1.0
+            })
+            .collect();
+        let folded = engine.fold(&noise).unwrap();
+        let noise_error = relative_error(&noise, &engine.unfold(&folded).unwrap());
+        assert!(noise_error > 0.5, "noise error {}", noise_error);
+        assert!(noise_error > structured_error);
+
+        let bad = FoldedMetadata { values: vec![0.0; 10], basis: vec![n] };
+        assert!(engine.unfold(&bad).is_err());
+    }
+}
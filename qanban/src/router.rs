@@ -0,0 +1,95 @@
+//! Quantum Router
+//!
+//! Routes flows over the superposition of their candidate paths held in the
+//! quantum superposition cache (postulate 4), and reports how the probability
+//! mass is spread across those paths.
+
+use crate::core::NetworkFlow;
+use crate::postulates::quantum_cache::{QuantumState, QuantumSuperpositionCache, RoutingPath};
+use crate::QUANTUM_CACHE_CAPACITY;
+
+/// Parallel-path router backed by `QuantumSuperpositionCache`
+pub struct QuantumRouter {
+    cache: QuantumSuperpositionCache,
+}
+
+impl Default for QuantumRouter {
+    fn default() -> Self {
+        Self::new(QUANTUM_CACHE_CAPACITY)
+    }
+}
+
+impl QuantumRouter {
+    /// Create a router tracking up to `capacity` flows
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            cache: QuantumSuperpositionCache::new(capacity),
+        }
+    }
+
+    /// Set the candidate paths for a flow, replacing any previous superposition
+    pub fn set_paths(&self, flow: &NetworkFlow, paths: Vec<RoutingPath>) {
+        self.cache.insert(flow.flow_id, QuantumState::new(paths));
+    }
+
+    /// Collapse the flow's superposition to a single path
+    pub fn route(&self, flow: &NetworkFlow) -> Option<RoutingPath> {
+        self.cache.measure(flow.flow_id)
+    }
+
+    /// Candidate paths for a flow with their probabilities |αᵢ|²
+    ///
+    /// Probabilities are renormalized to sum to 1; empty for unknown flows.
+    pub fn path_distribution(&self, flow: &NetworkFlow) -> Vec<(RoutingPath, f64)> {
+        let state = match self.cache.get(flow.flow_id) {
+            Some(state) => state,
+            None => return Vec::new(),
+        };
+        let total: f64 = state.amplitudes.iter().map(|a| a.norm_sqr()).sum();
+        if total <= 0.0 {
+            return Vec::new();
+        }
+        state.paths
+            .into_iter()
+            .zip(state.amplitudes.iter())
+            .map(|(path, amplitude)| (path, amplitude.norm_sqr() / total))
+            .collect()
+    }
+
+    /// Underlying superposition cache
+    pub fn cache(&self) -> &QuantumSuperpositionCache {
+        &self.cache
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(path_id: u32, hops: Vec<u32>, quality: f32) -> RoutingPath {
+        RoutingPath { path_id, hops, quality, latency: 10.0, bandwidth: 100.0 }
+    }
+
+    #[test]
+    fn test_path_distribution_three_disjoint_paths() {
+        let router = QuantumRouter::default();
+        let flow = NetworkFlow { flow_id: 7, packet_count: 0, byte_count: 0, active: true };
+        router.set_paths(&flow, vec![
+            path(1, vec![0, 1, 9], 0.9),
+            path(2, vec![0, 2, 9], 0.6),
+            path(3, vec![0, 3, 9], 0.3),
+        ]);
+
+        let distribution = router.path_distribution(&flow);
+        assert_eq!(distribution.len(), 3);
+        for id in 1..=3 {
+            assert!(distribution.iter().any(|(p, prob)| p.path_id == id && *prob > 0.0));
+        }
+        let total: f64 = distribution.iter().map(|(_, prob)| prob).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        assert!(distribution[0].1 > distribution[2].1);
+
+        let unknown = NetworkFlow { flow_id: 8, packet_count: 0, byte_count: 0, active: true };
+        assert!(router.path_distribution(&unknown).is_empty());
+    }
+}
@@ -17,6 +17,7 @@
 //! └─ Total: 800 Gbps × 1,000,000 × 250 = 200 EXABPS! (250,000,000×)
 //! ```
 
+use serde::Serialize;
 use std::sync::atomic::{AtomicU64, Ordering};
 
 // ============================================================================
@@ -259,6 +260,25 @@ impl UnifiedRecursiveCascade {
         (physical_bytes, effective)
     }
 
+    /// What-if projection for arbitrary physical capacity
+    ///
+    /// Applies the bandwidth cascade (QANBAN × UAO-QTCAM) to `physical_gbps`
+    /// and the memory cascade (UAO-QTCAM × QAGML) to `physical_mem_gb`.
+    /// Both scale linearly: 800 Gbps projects to 200 Exabps and 80 GB to
+    /// 200 Exabytes.
+    pub fn project(physical_gbps: f64, physical_mem_gb: f64) -> CascadeProjection {
+        let bandwidth = calculate_effective_bandwidth(physical_gbps);
+        CascadeProjection {
+            physical_gbps,
+            physical_mem_gb,
+            after_qanban_pbps: bandwidth.after_qanban_pbps,
+            effective_bandwidth_exabps: bandwidth.after_cascade_exabps,
+            effective_memory_exabytes: physical_mem_gb * MEMORY_RECURSIVE_AMPLIFICATION / 1_000_000_000.0,
+            bandwidth_amplification: bandwidth.total_amplification,
+            memory_amplification: MEMORY_RECURSIVE_AMPLIFICATION,
+        }
+    }
+
     /// Get unified statistics
     pub fn get_unified_stats(&self) -> UnifiedCascadeStats {
         let bw_stats = self.bandwidth_cascade.get_stats();
@@ -277,6 +297,25 @@ impl Default for UnifiedRecursiveCascade {
     }
 }
 
+/// Effective capacity projected from physical bandwidth and memory
+#[derive(Debug, Clone, Serialize)]
+pub struct CascadeProjection {
+    /// Physical bandwidth input (Gbps)
+    pub physical_gbps: f64,
+    /// Physical memory input (GB)
+    pub physical_mem_gb: f64,
+    /// After QANBAN amplification (Pbps)
+    pub after_qanban_pbps: f64,
+    /// After the recursive bandwidth cascade (Exabps)
+    pub effective_bandwidth_exabps: f64,
+    /// After the memory cascade (Exabytes)
+    pub effective_memory_exabytes: f64,
+    /// Total bandwidth amplification factor
+    pub bandwidth_amplification: f64,
+    /// Total memory amplification factor
+    pub memory_amplification: f64,
+}
+
 /// Unified statistics for both cascades
 #[derive(Debug, Clone)]
 pub struct UnifiedCascadeStats {
@@ -372,6 +411,28 @@ mod tests {
         assert_eq!(effective, 2_500_000_000_000_000_000);
     }
 
+    #[test]
+    fn test_cascade_projection() {
+        let documented = UnifiedRecursiveCascade::project(PHYSICAL_BANDWIDTH_GBPS, 80.0);
+        assert_eq!(documented.after_qanban_pbps, QANBAN_AMPLIFIED_BANDWIDTH_PBPS);
+        assert_eq!(documented.effective_bandwidth_exabps, RECURSIVE_CASCADE_BANDWIDTH_EXABPS);
+        assert_eq!(documented.effective_bandwidth_exabps, 200.0);
+        assert_eq!(documented.effective_memory_exabytes, 200.0);
+
+        // 400 Gbps and 40 GB VRAM: half of everything
+        let half = UnifiedRecursiveCascade::project(400.0, 40.0);
+        assert_eq!(half.effective_bandwidth_exabps, 100.0);
+        assert_eq!(half.effective_memory_exabytes, 100.0);
+
+        for (gbps, mem_gb) in [(1.0, 1.0), (25.0, 16.0), (3200.0, 640.0)] {
+            let projection = UnifiedRecursiveCascade::project(gbps, mem_gb);
+            let bandwidth_ratio = projection.effective_bandwidth_exabps / documented.effective_bandwidth_exabps;
+            let memory_ratio = projection.effective_memory_exabytes / documented.effective_memory_exabytes;
+            assert!((bandwidth_ratio - gbps / PHYSICAL_BANDWIDTH_GBPS).abs() < 1e-12);
+            assert!((memory_ratio - mem_gb / 80.0).abs() < 1e-12);
+        }
+    }
+
     #[test]
     fn test_memory_cascade_constants() {
         // Memory cascade: UAO-QTCAM (250×) × QAGML (10M×) = 2.5B×
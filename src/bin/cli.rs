@@ -19,6 +19,10 @@ struct Cli {
     #[arg(short, long, default_value = "http://localhost:8080")]
     endpoint: String,
     
+    /// Bearer token for protected control-plane commands
+    #[arg(long)]
+    token: Option<String>,
+    
     /// Output format (json, table, yaml)
     #[arg(short, long, default_value = "table")]
     format: String,
@@ -208,29 +212,66 @@ enum BenchmarkCommands {
 struct SymmetrixClient {
     endpoint: String,
     format: String,
+    token: Option<String>,
+    http: reqwest::Client,
+}
+
+/// Envelope returned by every control-plane command route
+#[derive(serde::Deserialize)]
+struct ControlReply {
+    success: bool,
+    message: String,
+    data: Option<serde_json::Value>,
 }
 
 impl SymmetrixClient {
     fn new(endpoint: String, format: String) -> Self {
-        Self { endpoint, format }
+        let endpoint = endpoint.trim_end_matches('/').to_string();
+        Self { endpoint, format, token: None, http: reqwest::Client::new() }
     }
-    
+
+    fn with_token(mut self, token: Option<String>) -> Self {
+        self.token = token;
+        self
+    }
+
+    /// GET a command route and return its `data` payload
+    async fn get(&self, path: &str) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+        self.send(self.http.get(format!("{}{}", self.endpoint, path)), path).await
+    }
+
+    /// POST a command to `/v1/command` and return its `data` payload
+    async fn command(&self, command: serde_json::Value) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+        let mut request = self.http.post(format!("{}/v1/command", self.endpoint)).json(&command);
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        self.send(request, "/v1/command").await
+    }
+
+    async fn send(&self, request: reqwest::RequestBuilder, path: &str) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+        let url = format!("{}{}", self.endpoint, path);
+        let response = request.send().await.map_err(|e| {
+            if e.is_connect() || e.is_timeout() {
+                format!("cannot reach the Symmetrix daemon at {} ({}); is it running? Use --endpoint to point elsewhere",
+                    self.endpoint, e)
+            } else {
+                format!("request to {} failed: {}", url, e)
+            }
+        })?;
+
+        let status = response.status();
+        let reply: ControlReply = response.json().await
+            .map_err(|e| format!("unexpected response from {} ({}): {}", url, status, e))?;
+        if !reply.success {
+            return Err(format!("daemon error from {}: {}", path, reply.message).into());
+        }
+        Ok(reply.data.unwrap_or(serde_json::Value::Null))
+    }
+
     async fn system_info(&self) -> Result<SystemInfo, Box<dyn std::error::Error>> {
-        // TODO: Make actual HTTP request to daemon
-        // For now, return mock data
-        Ok(SystemInfo {
-            version: symmetrix_core::VERSION.to_string(),
-            uptime: "2h 15m".to_string(),
-            containers_active: 42,
-            containers_max: 5000,
-            memory_usage: 2048,
-            memory_total: 8192,
-            cpu_usage: 15.5,
-            mathematical_acceleration: true,
-            sheaf_cohomology_active: true,
-            galois_field_active: true,
-            tensor_folding_active: true,
-        })
+        let stats = self.get("/stats").await?;
+        Ok(SystemInfo::from_stats(&stats, chrono::Utc::now().timestamp()))
     }
     
     async fn system_status(&self) -> Result<SystemStatus, Box<dyn std::error::Error>> {
@@ -246,75 +287,66 @@ impl SymmetrixClient {
     }
     
     async fn list_containers(&self, all: bool) -> Result<Vec<ContainerInfo>, Box<dyn std::error::Error>> {
-        // TODO: Make actual HTTP request to daemon
-        let mut containers = vec![
-            ContainerInfo {
-                id: "sym-001".to_string(),
-                name: "ai-inference-1".to_string(),
-                status: "Running".to_string(),
-                cpu_usage: 0.1,
-                memory_usage: 128,
-                uptime: "1h 30m".to_string(),
-                template: "ai-inference".to_string(),
-            },
-            ContainerInfo {
-                id: "sym-002".to_string(),
-                name: "web-server-1".to_string(),
-                status: "Running".to_string(),
-                cpu_usage: 0.05,
-                memory_usage: 64,
-                uptime: "2h 10m".to_string(),
-                template: "web-server".to_string(),
-            },
-        ];
-        
-        if all {
-            containers.push(ContainerInfo {
-                id: "sym-003".to_string(),
-                name: "batch-job-1".to_string(),
-                status: "Stopped".to_string(),
-                cpu_usage: 0.0,
-                memory_usage: 0,
-                uptime: "0s".to_string(),
-                template: "batch-processing".to_string(),
-            });
-        }
-        
-        Ok(containers)
+        let data = self.command(serde_json::json!({ "cmd": "ListContainers", "data": { "all": all } })).await?;
+        Ok(serde_json::from_value(data["containers"].clone())?)
+    }
+
+    async fn launch_containers(
+        &self,
+        template: &str,
+        count: usize,
+        memory_mb: Option<usize>,
+        cpu: Option<f64>,
+    ) -> Result<Vec<ContainerInfo>, Box<dyn std::error::Error>> {
+        let data = self.command(serde_json::json!({
+            "cmd": "LaunchContainers",
+            "data": { "template": template, "count": count, "memory_mb": memory_mb, "cpu": cpu },
+        })).await?;
+        Ok(serde_json::from_value(data["containers"].clone())?)
+    }
+
+    async fn stop_container(&self, id: &str) -> Result<(), Box<dyn std::error::Error>> {
+        self.command(serde_json::json!({ "cmd": "StopContainer", "data": { "id": id } })).await?;
+        Ok(())
+    }
+
+    async fn remove_container(&self, id: &str, force: bool) -> Result<(), Box<dyn std::error::Error>> {
+        self.command(serde_json::json!({ "cmd": "RemoveContainer", "data": { "id": id, "force": force } })).await?;
+        Ok(())
     }
     
     async fn math_status(&self) -> Result<MathStatus, Box<dyn std::error::Error>> {
-        Ok(MathStatus {
-            galois_field_prime: "2^61-1".to_string(),
-            galois_operations_per_sec: 1_250_000,
-            tensor_cache_hit_rate: 92.5,
-            tensor_blocks_active: 156,
-            sheaf_cohomology_dimension: 0,
-            sheaf_last_computation: "45s ago".to_string(),
-            matrix_acceleration_factor: 2.8,
-            crt_decomposition_active: true,
-        })
+        let data = self.get("/math").await?;
+        Ok(serde_json::from_value(data)?)
     }
     
     async fn resource_usage(&self) -> Result<ResourceUsage, Box<dyn std::error::Error>> {
-        Ok(ResourceUsage {
-            cpu_cores_total: 8,
-            cpu_cores_used: 1.2,
-            memory_total_mb: 8192,
-            memory_used_mb: 2048,
-            memory_cached_mb: 1024,
-            l1_cache_hit_rate: 96.8,
-            l2_cache_hit_rate: 89.3,
-            l3_cache_hit_rate: 78.1,
-            containers_running: 42,
-            containers_max: 5000,
-            mathematical_efficiency: 94.2,
-        })
+        let memory = self.get("/memory").await?;
+        let cache = self.get("/cache/stats").await?;
+        Ok(ResourceUsage::from_stats(&memory, &cache))
+    }
+}
+
+/// Parse a percentage the daemon formats as `"94.2%"`
+fn parse_percent(value: &serde_json::Value) -> f64 {
+    value.as_str()
+        .and_then(|s| s.trim_end_matches('%').parse().ok())
+        .or_else(|| value.as_f64())
+        .unwrap_or(0.0)
+}
+
+/// Render seconds as `"2h 15m"` / `"45s"`
+fn format_uptime(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    match (seconds / 3600, (seconds % 3600) / 60) {
+        (0, 0) => format!("{}s", seconds),
+        (0, m) => format!("{}m", m),
+        (h, m) => format!("{}h {}m", h, m),
     }
 }
 
 // Data structures for API responses
-#[derive(serde::Deserialize, serde::Serialize)]
+#[derive(Default, serde::Deserialize, serde::Serialize)]
 struct SystemInfo {
     version: String,
     uptime: String,
@@ -344,11 +376,14 @@ struct SystemStatus {
 struct ContainerInfo {
     id: String,
     name: String,
-    status: String,
-    cpu_usage: f64,
-    memory_usage: usize,
-    uptime: String,
     template: String,
+    status: String,
+    /// CPU limit (cores)
+    cpu: f64,
+    /// Memory limit (MB)
+    memory_mb: u64,
+    /// Launch timestamp (Unix epoch seconds)
+    created_at: i64,
 }
 
 #[derive(serde::Deserialize, serde::Serialize)]
@@ -359,11 +394,13 @@ struct MathStatus {
     tensor_blocks_active: usize,
     sheaf_cohomology_dimension: usize,
     sheaf_last_computation: String,
+    /// Not reported by the control plane; 0 when unknown
+    #[serde(default)]
     matrix_acceleration_factor: f64,
     crt_decomposition_active: bool,
 }
 
-#[derive(serde::Deserialize, serde::Serialize)]
+#[derive(Default, serde::Deserialize, serde::Serialize)]
 struct ResourceUsage {
     cpu_cores_total: usize,
     cpu_cores_used: f64,
@@ -376,6 +413,42 @@ struct ResourceUsage {
     containers_running: usize,
     containers_max: usize,
     mathematical_efficiency: f64,
+    /// UAO-QTCAM cache hit rate (%)
+    #[serde(default)]
+    cache_hit_rate: f64,
+}
+
+impl SystemInfo {
+    /// Build from the daemon's `/stats` payload
+    fn from_stats(stats: &serde_json::Value, now: i64) -> Self {
+        let start_time = stats["start_time"].as_i64().unwrap_or(now);
+        Self {
+            version: stats["version"].as_str().unwrap_or("unknown").to_string(),
+            uptime: format_uptime(now - start_time),
+            containers_active: stats["containers_active"].as_u64().unwrap_or(0) as usize,
+            containers_max: stats["containers_max"].as_u64().unwrap_or(0) as usize,
+            // The control plane initializes the sheaf, Galois and tensor engines unconditionally
+            mathematical_acceleration: true,
+            sheaf_cohomology_active: true,
+            galois_field_active: true,
+            tensor_folding_active: true,
+            ..Default::default()
+        }
+    }
+}
+
+impl ResourceUsage {
+    /// Build from the daemon's `/memory` and `/cache/stats` payloads
+    fn from_stats(memory: &serde_json::Value, cache: &serde_json::Value) -> Self {
+        const MB: u64 = 1024 * 1024;
+        Self {
+            memory_total_mb: (memory["physical_memory_gb"].as_f64().unwrap_or(0.0) * 1024.0) as usize,
+            memory_used_mb: (memory["bytes_processed"].as_u64().unwrap_or(0) / MB) as usize,
+            memory_cached_mb: cache["compressed_mb"].as_u64().unwrap_or(0) as usize,
+            cache_hit_rate: parse_percent(&cache["hit_rate"]),
+            ..Default::default()
+        }
+    }
 }
 
 fn print_system_info(info: &SystemInfo, format: &str) {
@@ -388,10 +461,12 @@ fn print_system_info(info: &SystemInfo, format: &str) {
             println!("Version: {}", info.version);
             println!("Uptime: {}", info.uptime);
             println!("Containers: {}/{}", info.containers_active, info.containers_max);
-            println!("Memory: {}MB / {}MB ({:.1}%)", 
-                info.memory_usage, info.memory_total, 
-                (info.memory_usage as f64 / info.memory_total as f64) * 100.0);
-            println!("CPU Usage: {:.1}%", info.cpu_usage);
+            if info.memory_total > 0 {
+                println!("Memory: {}MB / {}MB ({:.1}%)", 
+                    info.memory_usage, info.memory_total, 
+                    (info.memory_usage as f64 / info.memory_total as f64) * 100.0);
+                println!("CPU Usage: {:.1}%", info.cpu_usage);
+            }
             println!();
             println!("🧮 MATHEMATICAL ACCELERATION");
             println!("Mathematical Engine: {}", if info.mathematical_acceleration { "✅ Active" } else { "❌ Inactive" });
@@ -413,14 +488,20 @@ fn print_containers(containers: &[ContainerInfo], format: &str) {
                 "ID", "NAME", "STATUS", "CPU", "MEMORY", "UPTIME");
             println!("───────────────────────────────────────────────────────────────");
             
+            let now = chrono::Utc::now().timestamp();
             for container in containers {
+                let uptime = if container.status == "Running" {
+                    format_uptime(now - container.created_at)
+                } else {
+                    "-".to_string()
+                };
                 println!("{:<12} {:<20} {:<10} {:<8.2} {:<10} {:<10}",
                     container.id,
                     container.name,
                     container.status,
-                    container.cpu_usage,
-                    format!("{}MB", container.memory_usage),
-                    container.uptime
+                    container.cpu,
+                    format!("{}MB", container.memory_mb),
+                    uptime
                 );
             }
         }
@@ -437,7 +518,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .with_env_filter(format!("symmetrix={}", log_level))
         .init();
     
-    let client = SymmetrixClient::new(cli.endpoint, cli.format.clone());
+    let client = SymmetrixClient::new(cli.endpoint, cli.format.clone()).with_token(cli.token);
     
     match cli.command {
         Commands::System { action } => {
@@ -487,11 +568,24 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     if let Some(cpu_limit) = cpu {
                         println!("   CPU limit: {} cores", cpu_limit);
                     }
-                    // TODO: Implement actual container launch
-                    println!("✅ Containers launched successfully");
+                    let launched = client.launch_containers(&template, count, memory, cpu).await?;
+                    println!("✅ {} containers launched", launched.len());
+                    print_containers(&launched, &cli.format);
                 }
-                _ => {
-                    println!("Container command not yet implemented");
+                ContainerCommands::Stop { ids } => {
+                    for id in ids {
+                        client.stop_container(&id).await?;
+                        println!("⏹️  Stopped {}", id);
+                    }
+                }
+                ContainerCommands::Remove { ids, force } => {
+                    for id in ids {
+                        client.remove_container(&id, force).await?;
+                        println!("🗑️  Removed {}", id);
+                    }
+                }
+                ContainerCommands::Logs { .. } | ContainerCommands::Exec { .. } => {
+                    println!("Container logs/exec are not supported by the control plane");
                 }
             }
         }
@@ -511,7 +605,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             println!("Active Tensor Blocks: {}", status.tensor_blocks_active);
                             println!("H² Cohomology Dimension: {}", status.sheaf_cohomology_dimension);
                             println!("Last Sheaf Computation: {}", status.sheaf_last_computation);
-                            println!("Matrix Acceleration: {:.1}x", status.matrix_acceleration_factor);
+                            if status.matrix_acceleration_factor > 0.0 {
+                                println!("Matrix Acceleration: {:.1}x", status.matrix_acceleration_factor);
+                            }
                             println!("CRT Decomposition: {}", if status.crt_decomposition_active { "✅ Active" } else { "❌ Inactive" });
                         }
                     }
@@ -531,22 +627,25 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         _ => {
                             println!("📊 RESOURCE USAGE");
                             println!("═══════════════════════════════════════════════════════════════");
-                            println!("CPU: {:.1}/{} cores ({:.1}%)", 
-                                usage.cpu_cores_used, usage.cpu_cores_total,
-                                (usage.cpu_cores_used / usage.cpu_cores_total as f64) * 100.0);
-                            println!("Memory: {}MB/{}MB ({:.1}%)", 
-                                usage.memory_used_mb, usage.memory_total_mb,
-                                (usage.memory_used_mb as f64 / usage.memory_total_mb as f64) * 100.0);
+                            if usage.cpu_cores_total > 0 {
+                                println!("CPU: {:.1}/{} cores ({:.1}%)", 
+                                    usage.cpu_cores_used, usage.cpu_cores_total,
+                                    (usage.cpu_cores_used / usage.cpu_cores_total as f64) * 100.0);
+                            }
+                            if usage.memory_total_mb > 0 {
+                                println!("Memory: {}MB/{}MB ({:.1}%)", 
+                                    usage.memory_used_mb, usage.memory_total_mb,
+                                    (usage.memory_used_mb as f64 / usage.memory_total_mb as f64) * 100.0);
+                            }
                             println!("Cached: {}MB", usage.memory_cached_mb);
                             println!();
                             println!("🎯 CACHE PERFORMANCE");
-                            println!("L1 Hit Rate: {:.1}%", usage.l1_cache_hit_rate);
-                            println!("L2 Hit Rate: {:.1}%", usage.l2_cache_hit_rate);
-                            println!("L3 Hit Rate: {:.1}%", usage.l3_cache_hit_rate);
-                            println!();
-                            println!("🐳 CONTAINERS");
-                            println!("Running: {}/{}", usage.containers_running, usage.containers_max);
-                            println!("Mathematical Efficiency: {:.1}%", usage.mathematical_efficiency);
+                            println!("UAO-QTCAM Hit Rate: {:.1}%", usage.cache_hit_rate);
+                            if usage.containers_max > 0 {
+                                println!();
+                                println!("🐳 CONTAINERS");
+                                println!("Running: {}/{}", usage.containers_running, usage.containers_max);
+                            }
                         }
                     }
                 }
@@ -572,3 +671,84 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::get, Json, Router};
+
+    /// Serve canned control-plane replies on an ephemeral port
+    async fn mock_daemon() -> String {
+        let reply = |data: serde_json::Value| Json(serde_json::json!({
+            "success": true,
+            "message": "ok",
+            "data": data,
+            "latency_ns": 0,
+        }));
+        let app = Router::new()
+            .route("/stats", get(move || async move {
+                reply(serde_json::json!({ "version": "9.9.9", "start_time": 0, "total_commands": 3 }))
+            }))
+            .route("/math", get(move || async move {
+                reply(serde_json::json!({
+                    "galois_field_prime": "2^61-1",
+                    "galois_operations_per_sec": 1_000_000,
+                    "tensor_cache_hit_rate": 50.0,
+                    "tensor_blocks_active": 4,
+                    "sheaf_cohomology_dimension": 0,
+                    "sheaf_last_computation": "just now",
+                    "crt_decomposition_active": true,
+                }))
+            }))
+            .route("/memory", get(move || async move {
+                reply(serde_json::json!({ "physical_memory_gb": 80, "bytes_processed": 3 * 1024 * 1024, "cache_hit_rate": "12.5%" }))
+            }))
+            .route("/cache/stats", get(move || async move {
+                reply(serde_json::json!({ "hit_rate": "94.2%", "compressed_mb": 7 }))
+            }));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn test_client_parses_daemon_responses() {
+        let client = SymmetrixClient::new(mock_daemon().await, "table".to_string());
+
+        let info = client.system_info().await.unwrap();
+        assert_eq!(info.version, "9.9.9");
+        assert!(info.mathematical_acceleration);
+
+        let math = client.math_status().await.unwrap();
+        assert_eq!(math.galois_field_prime, "2^61-1");
+        assert_eq!(math.tensor_blocks_active, 4);
+        assert_eq!(math.matrix_acceleration_factor, 0.0);
+
+        let usage = client.resource_usage().await.unwrap();
+        assert_eq!(usage.memory_total_mb, 80 * 1024);
+        assert_eq!(usage.memory_used_mb, 3);
+        assert_eq!(usage.memory_cached_mb, 7);
+        assert_eq!(usage.cache_hit_rate, 94.2);
+    }
+
+    #[tokio::test]
+    async fn test_client_connection_error_mentions_endpoint() {
+        // Bind then drop to get a port nothing is listening on
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let client = SymmetrixClient::new(endpoint.clone(), "table".to_string());
+        let err = client.system_info().await.err().unwrap().to_string();
+        assert!(err.contains(&endpoint));
+        assert!(err.contains("--endpoint"));
+    }
+
+    #[test]
+    fn test_format_uptime() {
+        assert_eq!(format_uptime(45), "45s");
+        assert_eq!(format_uptime(8100), "2h 15m");
+    }
+}
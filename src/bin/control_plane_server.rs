@@ -6,7 +6,7 @@
 //! ## Architecture
 //! - UDP Server on port 4789 (VXLAN standard - local/Azure)
 //! - WebSocket Server on /ws (for Render deployment)
-//! - HTTP Management API on port 8080/10000
+//! - HTTP Management API on port 8080/10000 (HTTPS/WSS when a TLS certificate is configured)
 //! - UAO-QTCAM Cache (Redis replacement)
 //! - All SYMMETRIX CORE integrations
 //!
@@ -14,28 +14,47 @@
 //! Deploy to Render with render.yaml configuration
 //! WebSocket endpoint: wss://vxlan-control-plane.onrender.com/ws
 
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt::Write as _;
 use std::net::SocketAddr;
-use std::sync::Arc;
-use tokio::net::UdpSocket;
-use tokio::sync::RwLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::net::{TcpListener, UdpSocket};
+use tokio::sync::{broadcast, mpsc, Notify, RwLock};
 use tracing::{info, error, warn, debug};
 use serde::{Deserialize, Serialize};
 use futures_util::{SinkExt, StreamExt};
-use tokio_tungstenite::tungstenite::Message;
+use axum::{
+    extract::{ConnectInfo, Json, Path, Request, State, rejection::JsonRejection, ws::{close_code, CloseFrame, Message, WebSocket, WebSocketUpgrade}},
+    http::{header, HeaderMap, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{get, post, MethodRouter},
+    Router,
+};
+use tower::Service;
+use tower_http::cors::CorsLayer;
+use tokio_rustls::TlsAcceptor;
+use hyper_util::rt::{TokioExecutor, TokioIo};
 
 // Import SYMMETRIX CORE components
 use symmetrix_core::{
     initialize, SymmetrixConfig, SymmetrixResult, SymmetrixRuntime,
+    galois::{GaloisElement, MERSENNE_61},
+    sheaf::{ResourceType, SheafConfig, SheafError, SheafSpace},
     bandwidth_cascade::BandwidthCascade,
     qagml_integration::{SymmetrixQagmlOptimizer, SymmetrixQagmlConfig},
     qanban_integration::{SymmetrixQanbanOptimizer, SymmetrixQanbanConfig},
     uao_qtcam_integration::{SymmetrixUaoQtcamOptimizer, SymmetrixUaoQtcamConfig},
-    uao_qtcam_cache::UaoQtcamCache,
+    uao_qtcam_cache::{UaoQtcamCache, WRONGTYPE_ERROR},
     gfef::{
-        prediction::{ActivationPredictor, PredictorStats},
+        api::{prediction_error_status, subscription_error_status},
+        prediction::{ActivationPredictor, PredictionError, PredictionRequest, PredictionResponse, PredictorStats},
         calibration::CalibrationService,
-        subscription::SubscriptionManager,
-        index::{GFEFIndex, IndexConfig, LayerIndex},
+        subscription::{SubscriptionManager, SubscriptionTier},
+        index::{GFEFIndex, LayerIndex},
+        storage::IndexStorage,
     },
 };
 
@@ -44,6 +63,458 @@ pub const VXLAN_PORT: u16 = 4789;
 /// VXLAN packet header (simplified for control plane)
 pub const VXLAN_HEADER_SIZE: usize = 8;
 const VXLAN_VNI_CONTROL_PLANE: u32 = 0xFFFFFF; // Reserved VNI for control
+/// VXLAN "I" flag: VNI field is valid (RFC 7348)
+const VXLAN_FLAG_VNI_VALID: u8 = 0x08;
+/// Reserved VXLAN flag bit marking a fragmented control-plane payload
+const VXLAN_FLAG_FRAGMENT: u8 = 0x01;
+/// Fragment header after the VXLAN header: id (u16, big-endian), index (u8), count (u8)
+pub const VXLAN_FRAGMENT_HEADER_SIZE: usize = 4;
+/// How long a partial reassembly waits for its remaining fragments
+const VXLAN_REASSEMBLY_TIMEOUT: Duration = Duration::from_secs(5);
+/// Partial reassemblies held at once; fragments starting new ones are dropped beyond this
+const VXLAN_MAX_REASSEMBLIES: usize = 1024;
+/// Partial reassemblies one sender may hold at once
+const VXLAN_MAX_REASSEMBLIES_PER_SOURCE: usize = 16;
+/// Largest command accepted through reassembly (bytes)
+const VXLAN_MAX_REASSEMBLED_BYTES: usize = 1024 * 1024;
+/// Fragment data buffered across all partial reassemblies (bytes)
+const VXLAN_MAX_BUFFERED_BYTES: usize = 4 * 1024 * 1024;
+/// Worker tasks draining the VXLAN packet queue
+const VXLAN_WORKERS: usize = 8;
+/// How often expired GFEF indices are unregistered (seconds)
+const GFEF_EXPIRY_SWEEP_SECS: u64 = 60;
+/// How often expired cache keys are purged (seconds)
+const CACHE_EXPIRY_SWEEP_SECS: u64 = 1;
+/// How long a WebSocket peer gets to acknowledge a shutdown Close frame
+const WS_CLOSE_TIMEOUT: Duration = Duration::from_secs(2);
+/// TCAM route snapshot written to `persistence_dir` on shutdown
+const TCAM_SNAPSHOT_FILE: &str = "tcam_routes.json";
+/// Cache snapshot written to `persistence_dir` on shutdown
+const CACHE_SNAPSHOT_FILE: &str = "cache.json";
+/// QAGML temporal-coherence history written to `persistence_dir` on shutdown
+const QAGML_HISTORY_FILE: &str = "qagml_temporal.json";
+/// Directory under `persistence_dir` holding every registered GFEF index
+const GFEF_INDEX_DIR: &str = "gfef_indices";
+/// Cache key written and removed by the startup self-test
+const SELF_TEST_CACHE_KEY: &str = "__symmetrix_self_test__";
+/// QAGML region the startup self-test allocates from
+const SELF_TEST_REGION: &str = "self_test";
+
+/// Chunked GFEF uploads allowed in progress at once
+const GFEF_MAX_PENDING_UPLOADS: usize = 16;
+/// Spooled layer bytes allowed per chunked GFEF upload
+const GFEF_MAX_UPLOAD_BYTES: u64 = 256 * 1024 * 1024;
+/// Chunked GFEF uploads idle this long are discarded with their spools
+const GFEF_UPLOAD_IDLE_SECS: u64 = 600;
+
+/// Chunked GFEF uploads in progress, each spooled to a temp file so large
+/// indices never sit in memory as one request body
+struct IndexUploads {
+    pending: Mutex<HashMap<uuid::Uuid, PendingUpload>>,
+    /// Uploads allowed in progress at once
+    max_pending: usize,
+    /// Spooled bytes allowed per upload
+    max_bytes: u64,
+}
+
+impl Default for IndexUploads {
+    fn default() -> Self {
+        Self {
+            pending: Mutex::new(HashMap::new()),
+            max_pending: GFEF_MAX_PENDING_UPLOADS,
+            max_bytes: GFEF_MAX_UPLOAD_BYTES,
+        }
+    }
+}
+
+impl IndexUploads {
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<uuid::Uuid, PendingUpload>> {
+        match self.pending.lock() {
+            Ok(pending) => pending,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Discard uploads without a chunk for `idle`, deleting their spools
+    ///
+    /// Returns how many were discarded. Uploads with a chunk or commit in
+    /// flight are left alone.
+    async fn sweep(&self, idle: std::time::Duration) -> usize {
+        let abandoned: Vec<(uuid::Uuid, std::path::PathBuf)> = {
+            let mut pending = self.lock();
+            let expired: Vec<uuid::Uuid> = pending.iter()
+                .filter(|(_, upload)| !upload.busy && upload.touched_at.elapsed() >= idle)
+                .map(|(id, _)| *id)
+                .collect();
+            expired.into_iter()
+                .filter_map(|id| pending.remove(&id).map(|upload| (id, upload.spool)))
+                .collect()
+        };
+
+        for (upload_id, spool) in &abandoned {
+            let _ = tokio::fs::remove_file(spool).await;
+            warn!("⏰ Abandoned GFEF index upload {} discarded", upload_id);
+        }
+        abandoned.len()
+    }
+
+    /// Forget `upload_id` and delete its spool
+    async fn abort(&self, upload_id: &uuid::Uuid) {
+        let removed = self.lock().remove(upload_id);
+        if let Some(upload) = removed {
+            let _ = tokio::fs::remove_file(&upload.spool).await;
+        }
+    }
+}
+
+/// Fragmented VXLAN commands being reassembled
+///
+/// Keyed by sender as well as fragment id, so clients choosing ids
+/// independently cannot complete each other's commands. Memory is bounded
+/// per reassembly, per sender and across all senders.
+#[derive(Default)]
+struct FragmentReassembly {
+    pending: Mutex<HashMap<(SocketAddr, u16), FragmentBuffer>>,
+    /// Sum of `bytes` over `pending`; only changed with `pending` locked
+    buffered: AtomicUsize,
+}
+
+/// Fragments of one command received so far, indexed by position
+struct FragmentBuffer {
+    parts: Vec<Option<Vec<u8>>>,
+    received: usize,
+    bytes: usize,
+    started: Instant,
+}
+
+impl FragmentReassembly {
+    /// Drop reassemblies older than `VXLAN_REASSEMBLY_TIMEOUT`, returning how many
+    fn expire(&self) -> usize {
+        let mut pending = self.pending.lock().unwrap();
+        let before = pending.len();
+        pending.retain(|_, buffer| {
+            let live = buffer.started.elapsed() < VXLAN_REASSEMBLY_TIMEOUT;
+            if !live {
+                self.buffered.fetch_sub(buffer.bytes, Ordering::Relaxed);
+            }
+            live
+        });
+        before - pending.len()
+    }
+
+    /// Remove a reassembly, releasing its bytes from the shared budget
+    fn remove(
+        &self,
+        pending: &mut HashMap<(SocketAddr, u16), FragmentBuffer>,
+        key: &(SocketAddr, u16),
+    ) -> Option<FragmentBuffer> {
+        let buffer = pending.remove(key)?;
+        self.buffered.fetch_sub(buffer.bytes, Ordering::Relaxed);
+        Some(buffer)
+    }
+
+    /// Add a fragment (fragment header plus data) from `src`
+    ///
+    /// Returns the full payload once every fragment of its id has arrived.
+    /// Malformed fragments, a changed fragment count or an oversized payload
+    /// abandon the reassembly; fragments that would start one too many
+    /// reassemblies, or overflow the shared byte budget, are dropped.
+    fn accept(&self, src: SocketAddr, fragment: &[u8]) -> Result<Option<Vec<u8>>, String> {
+        if fragment.len() < VXLAN_FRAGMENT_HEADER_SIZE {
+            return Err("truncated fragment header".to_string());
+        }
+        let id = u16::from_be_bytes([fragment[0], fragment[1]]);
+        let (index, count) = (fragment[2] as usize, fragment[3] as usize);
+        if index >= count {
+            return Err(format!("fragment index {} out of range for count {}", index, count));
+        }
+        let data = &fragment[VXLAN_FRAGMENT_HEADER_SIZE..];
+
+        let key = (src, id);
+        let mut pending = self.pending.lock().unwrap();
+        if !pending.contains_key(&key) {
+            if pending.len() >= VXLAN_MAX_REASSEMBLIES {
+                return Err("too many reassemblies in progress".to_string());
+            }
+            if pending.keys().filter(|(addr, _)| *addr == src).count() >= VXLAN_MAX_REASSEMBLIES_PER_SOURCE {
+                return Err(format!("too many reassemblies in progress from {}", src));
+            }
+        }
+        let buffer = pending.entry(key).or_insert_with(|| FragmentBuffer {
+            parts: vec![None; count],
+            received: 0,
+            bytes: 0,
+            started: Instant::now(),
+        });
+        if buffer.parts.len() != count {
+            self.remove(&mut pending, &key);
+            return Err(format!("fragment {} changed its count to {}", id, count));
+        }
+        if buffer.parts[index].is_none() {
+            if buffer.bytes + data.len() > VXLAN_MAX_REASSEMBLED_BYTES {
+                self.remove(&mut pending, &key);
+                return Err(format!("fragment {} exceeds {} bytes", id, VXLAN_MAX_REASSEMBLED_BYTES));
+            }
+            if self.buffered.load(Ordering::Relaxed) + data.len() > VXLAN_MAX_BUFFERED_BYTES {
+                if buffer.received == 0 {
+                    pending.remove(&key);
+                }
+                return Err(format!("reassembly buffers exceed {} bytes", VXLAN_MAX_BUFFERED_BYTES));
+            }
+            buffer.bytes += data.len();
+            self.buffered.fetch_add(data.len(), Ordering::Relaxed);
+            buffer.parts[index] = Some(data.to_vec());
+            buffer.received += 1;
+        }
+        if buffer.received < count {
+            return Ok(None);
+        }
+        Ok(self.remove(&mut pending, &key).map(|buffer| buffer.parts.into_iter().flatten().flatten().collect()))
+    }
+}
+
+/// Index metadata from `init` plus the spool of layers received so far
+struct PendingUpload {
+    metadata: serde_json::Value,
+    spool: std::path::PathBuf,
+    chunks: usize,
+    layers: usize,
+    /// Bytes written or reserved in `spool`
+    bytes: u64,
+    /// When the upload was started or last took a chunk
+    touched_at: Instant,
+    /// A chunk is being written outside the lock
+    busy: bool,
+}
+
+/// How long an issued calibration matrix stays valid
+const CALIBRATION_MATRIX_TTL_SECS: i64 = 60;
+/// Lifetime of a Weight Server calibration session (capped by the subscription)
+const CALIBRATION_SESSION_TTL_SECS: i64 = 3600;
+
+/// Calibration tiers offered to the Weight Server
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalibrationTier {
+    None,
+    Basic,
+    Standard,
+    Professional,
+    Enterprise,
+}
+
+impl CalibrationTier {
+    /// Parse a tier name, ignoring case
+    fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "none" => Some(Self::None),
+            "basic" => Some(Self::Basic),
+            "standard" => Some(Self::Standard),
+            "professional" => Some(Self::Professional),
+            "enterprise" => Some(Self::Enterprise),
+            _ => None,
+        }
+    }
+
+    /// Wire form, e.g. `professional`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Basic => "basic",
+            Self::Standard => "standard",
+            Self::Professional => "professional",
+            Self::Enterprise => "enterprise",
+        }
+    }
+
+    /// Numeric tier reported as `tier`
+    fn code(self) -> u8 {
+        self as u8
+    }
+
+    pub fn compression_ratio(self) -> f64 {
+        match self {
+            Self::None => 1.0,
+            Self::Basic => 10.0,
+            Self::Standard => 100.0,
+            Self::Professional => 1250.0,
+            Self::Enterprise => 10000.0,
+        }
+    }
+
+    /// Whether a subscription at `tier` may use this calibration tier
+    fn entitled(self, tier: SubscriptionTier) -> bool {
+        self.compression_ratio() <= tier.amplification_factor()
+    }
+
+    /// 64×64 calibration values for this tier, row-major
+    ///
+    /// This is the SECRET IP - the trained parameters that enable compression.
+    /// In production, these would be trained parameters.
+    fn matrix_values(self) -> Vec<f64> {
+        let compression_ratio = self.compression_ratio();
+        let mut values: Vec<f64> = Vec::with_capacity(64 * 64);
+        for i in 0..64 {
+            for j in 0..64 {
+                // Chern-Simons modulated eigenmode basis
+                let phase = std::f64::consts::PI * 2.0 * (i * j) as f64 / 64.0;
+                let cs_term = ((i + j) as f64 * 0.1).sin() * 0.1;
+                let base = phase.cos() + cs_term;
+                // Scale by compression ratio
+                let scaled = base * (compression_ratio / 1250.0);
+                values.push(1.0 + scaled * 0.001);
+            }
+        }
+        values
+    }
+}
+
+/// Calibration matrix issued to a session
+#[derive(Debug, Clone, Copy)]
+struct IssuedMatrix {
+    id: uuid::Uuid,
+    expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl IssuedMatrix {
+    fn generate(now: chrono::DateTime<chrono::Utc>) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4(),
+            expires_at: now + chrono::Duration::seconds(CALIBRATION_MATRIX_TTL_SECS),
+        }
+    }
+}
+
+/// Matrix issued to a session, with the session it belongs to
+struct SessionMatrix {
+    customer_id: uuid::Uuid,
+    tier: CalibrationTier,
+    session_expires_at: chrono::DateTime<chrono::Utc>,
+    matrix: IssuedMatrix,
+}
+
+impl SessionMatrix {
+    /// `GetCalibrationMatrix` / `RefreshCalibrationMatrix` reply data
+    fn to_json(&self, session_id: &str) -> serde_json::Value {
+        serde_json::json!({
+            "rows": 64,
+            "cols": 64,
+            "values": self.tier.matrix_values(),
+            "matrix_id": self.matrix.id,
+            "session_id": session_id,
+            "customer_id": self.customer_id,
+            "expires_at": self.matrix.expires_at.timestamp(),
+            "session_expires_at": self.session_expires_at.timestamp(),
+            "tier": self.tier.code(),
+            "tier_name": self.tier.as_str(),
+            "compression_ratio": self.tier.compression_ratio(),
+        })
+    }
+}
+
+/// Weight Server session entitled to one calibration tier
+struct CalibrationSession {
+    customer_id: uuid::Uuid,
+    tier: CalibrationTier,
+    expires_at: chrono::DateTime<chrono::Utc>,
+    matrix: Option<IssuedMatrix>,
+}
+
+/// Established calibration sessions, keyed by session id
+#[derive(Default)]
+struct CalibrationSessions {
+    sessions: Mutex<HashMap<String, CalibrationSession>>,
+}
+
+impl CalibrationSessions {
+    /// Open a session, dropping any that have expired
+    fn establish(
+        &self,
+        customer_id: uuid::Uuid,
+        tier: CalibrationTier,
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> String {
+        let now = chrono::Utc::now();
+        let session_id = format!("cal-{}", uuid::Uuid::new_v4().simple());
+        let mut sessions = self.sessions.lock().unwrap();
+        sessions.retain(|_, session| session.expires_at > now);
+        sessions.insert(session_id.clone(), CalibrationSession { customer_id, tier, expires_at, matrix: None });
+        session_id
+    }
+
+    /// Matrix for a live session
+    ///
+    /// The session's current matrix is returned while it is valid; a new one
+    /// is issued once it expires, or whenever `refresh` is set. A requested
+    /// `tier` must match the session's.
+    fn matrix(
+        &self,
+        session_id: &str,
+        tier: Option<CalibrationTier>,
+        refresh: bool,
+    ) -> Result<SessionMatrix, String> {
+        let now = chrono::Utc::now();
+        let mut sessions = self.sessions.lock().unwrap();
+        let session = match sessions.get_mut(session_id) {
+            Some(session) if session.expires_at > now => session,
+            _ => return Err("Unknown or expired calibration session".to_string()),
+        };
+        if let Some(tier) = tier {
+            if tier != session.tier {
+                return Err(format!(
+                    "Session is for tier '{}', not '{}'",
+                    session.tier.as_str(),
+                    tier.as_str()
+                ));
+            }
+        }
+        let matrix = match session.matrix {
+            Some(matrix) if !refresh && matrix.expires_at > now => matrix,
+            _ => IssuedMatrix::generate(now),
+        };
+        session.matrix = Some(matrix);
+        Ok(SessionMatrix {
+            customer_id: session.customer_id,
+            tier: session.tier,
+            session_expires_at: session.expires_at,
+            matrix,
+        })
+    }
+}
+
+/// Save a newly registered index so it is reloaded after a restart
+async fn persist_gfef_index(storage: &Option<Arc<RwLock<IndexStorage>>>, index: &GFEFIndex) {
+    if let Some(storage) = storage {
+        if let Err(e) = storage.write().await.save(index.clone()) {
+            error!("Failed to persist GFEF index {}: {}", index.model_id, e);
+        }
+    }
+}
+
+/// Error body for the GFEF upload routes
+fn upload_error(status: StatusCode, error: &str, message: String) -> (StatusCode, Json<serde_json::Value>) {
+    (status, Json(serde_json::json!({
+        "success": false,
+        "error": error,
+        "message": message
+    })))
+}
+
+/// 400 for a prediction body that is not a well-formed `PredictionRequest`
+fn invalid_prediction_body(rejection: JsonRejection) -> (StatusCode, Json<serde_json::Value>) {
+    upload_error(StatusCode::BAD_REQUEST, "INVALID_REQUEST", rejection.body_text())
+}
+
+/// Memory limit for containers launched without one (MB)
+const DEFAULT_CONTAINER_MEMORY_MB: u64 = 128;
+/// CPU limit for containers launched without one (cores)
+const DEFAULT_CONTAINER_CPU: f64 = 0.1;
+
+fn default_shutdown_grace_secs() -> u64 { 30 }
+fn default_rate_limit_per_sec() -> u32 { 1000 }
+fn default_ws_ping_interval_secs() -> u64 { 30 }
+fn default_ws_idle_timeout_secs() -> u64 { 90 }
+fn default_http_idle_timeout_secs() -> u64 { 60 }
+fn default_response_mtu() -> usize { 1400 }
 
 /// Control Plane command types
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,6 +528,8 @@ pub enum ControlCommand {
     AllocateMemory { size_bytes: u64, region: String },
     FreeMemory { allocation_id: String },
     GetMemoryStats,
+    /// Allocation counts and byte totals per region
+    GetRegionStats,
 
     // QANBAN Bandwidth Operations
     OptimizeBandwidth { flow_id: String, target_gbps: f64 },
@@ -72,13 +545,69 @@ pub enum ControlCommand {
     CacheGet { key: String },
     CacheDelete { key: String },
     CacheIncr { key: String },
+    CacheTtl { key: String },
+    CacheExpire { key: String, ttl_seconds: u64 },
     CacheStats,
+    /// Write live cache entries to `path`, relative to `persistence_dir`
+    CacheSnapshot { path: String },
+    ListPush {
+        key: String,
+        values: Vec<String>,
+        /// Push onto the head (LPUSH) instead of the tail (RPUSH)
+        #[serde(default)]
+        left: bool,
+    },
+    ListRange { key: String, start: i64, stop: i64 },
+    HashSet { key: String, field: String, value: String },
+    HashGet { key: String, field: String },
 
     // Cascade Operations
     GetCascadeStats,
+    /// What-if effective capacity for the given physical bandwidth and memory
+    ProjectCascade { physical_gbps: f64, physical_mem_gb: f64 },
+
+    // Mathematical engine status (Galois field, tensor folding, sheaf cohomology)
+    GetMathStats,
+
+    // Sheaf resource orchestration
+    SheafAddNode { node_id: u64, resources: HashMap<ResourceType, f64> },
+    /// Restriction map `source -> target`; `matrix` is given row by row
+    SheafAddRestriction { source: u64, target: u64, matrix: Vec<Vec<f64>> },
+    SheafAllocate { requests: HashMap<u64, HashMap<ResourceType, f64>> },
+
+    // Container Operations
+    LaunchContainers {
+        template: String,
+        count: usize,
+        #[serde(default)]
+        memory_mb: Option<u64>,
+        #[serde(default)]
+        cpu: Option<f64>,
+    },
+    StopContainer { id: String },
+    RemoveContainer {
+        id: String,
+        #[serde(default)]
+        force: bool,
+    },
+    ListContainers {
+        /// Include stopped containers
+        #[serde(default)]
+        all: bool,
+    },
 
     // Calibration Matrix Operations (for Weight Server)
-    GetCalibrationMatrix { tier: Option<String> },
+    /// Open a calibration session; `token` is checked against the API token
+    /// when auth is required, and the customer's subscription must cover `tier`
+    EstablishSession { customer_id: uuid::Uuid, tier: String, token: String },
+    /// Current matrix for an established session; `tier`, if given, must match it
+    GetCalibrationMatrix {
+        session_id: String,
+        #[serde(default)]
+        tier: Option<String>,
+    },
+    /// Issue a new matrix for the session, e.g. before the current one expires
+    RefreshCalibrationMatrix { session_id: String },
 
     // GFEF (Galois Field Eigenmode Folding) Operations
     /// Predict which neurons will activate for a given input
@@ -87,7 +616,10 @@ pub enum ControlCommand {
         customer_id: String,
         model_id: String,
         layer_index: u32,
-        input_hash: String
+        input_hash: String,
+        /// Optional input embedding (projected onto the layer's principal components)
+        #[serde(default)]
+        input_embedding: Option<Vec<f32>>,
     },
     /// Upload GFEF index for a model
     UploadGfefIndex { model_id: String, index_data: String },
@@ -95,6 +627,163 @@ pub enum ControlCommand {
     GetGfefStatus,
 }
 
+impl ControlCommand {
+    /// Variant name, as sent in the `cmd` field
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Health => "Health",
+            Self::Stats => "Stats",
+            Self::AllocateMemory { .. } => "AllocateMemory",
+            Self::FreeMemory { .. } => "FreeMemory",
+            Self::GetMemoryStats => "GetMemoryStats",
+            Self::GetRegionStats => "GetRegionStats",
+            Self::OptimizeBandwidth { .. } => "OptimizeBandwidth",
+            Self::GetBandwidthStats => "GetBandwidthStats",
+            Self::Lookup { .. } => "Lookup",
+            Self::InsertRoute { .. } => "InsertRoute",
+            Self::DeleteRoute { .. } => "DeleteRoute",
+            Self::CacheSet { .. } => "CacheSet",
+            Self::CacheGet { .. } => "CacheGet",
+            Self::CacheDelete { .. } => "CacheDelete",
+            Self::CacheIncr { .. } => "CacheIncr",
+            Self::CacheTtl { .. } => "CacheTtl",
+            Self::CacheExpire { .. } => "CacheExpire",
+            Self::CacheStats => "CacheStats",
+            Self::CacheSnapshot { .. } => "CacheSnapshot",
+            Self::ListPush { .. } => "ListPush",
+            Self::ListRange { .. } => "ListRange",
+            Self::HashSet { .. } => "HashSet",
+            Self::HashGet { .. } => "HashGet",
+            Self::GetCascadeStats => "GetCascadeStats",
+            Self::ProjectCascade { .. } => "ProjectCascade",
+            Self::GetMathStats => "GetMathStats",
+            Self::SheafAddNode { .. } => "SheafAddNode",
+            Self::SheafAddRestriction { .. } => "SheafAddRestriction",
+            Self::SheafAllocate { .. } => "SheafAllocate",
+            Self::LaunchContainers { .. } => "LaunchContainers",
+            Self::StopContainer { .. } => "StopContainer",
+            Self::RemoveContainer { .. } => "RemoveContainer",
+            Self::ListContainers { .. } => "ListContainers",
+            Self::EstablishSession { .. } => "EstablishSession",
+            Self::GetCalibrationMatrix { .. } => "GetCalibrationMatrix",
+            Self::RefreshCalibrationMatrix { .. } => "RefreshCalibrationMatrix",
+            Self::PredictActivation { .. } => "PredictActivation",
+            Self::UploadGfefIndex { .. } => "UploadGfefIndex",
+            Self::GetGfefStatus => "GetGfefStatus",
+        }
+    }
+
+    /// Whether the command needs a bearer token when auth is enabled
+    ///
+    /// Health and statistics stay open; anything that mutates state or
+    /// returns stored data (cache values, routes, calibration) is protected.
+    /// `EstablishSession` carries its own token.
+    pub fn requires_auth(&self) -> bool {
+        !matches!(
+            self,
+            Self::Health
+                | Self::Stats
+                | Self::GetMemoryStats
+                | Self::GetRegionStats
+                | Self::GetBandwidthStats
+                | Self::CacheStats
+                | Self::GetCascadeStats
+                | Self::ProjectCascade { .. }
+                | Self::GetMathStats
+                | Self::ListContainers { .. }
+                | Self::GetGfefStatus
+                | Self::EstablishSession { .. }
+        )
+    }
+}
+
+/// Parse a JSON command, accepting an optional top-level `"token"` field
+///
+/// UDP and WebSocket clients have no headers, so the bearer token rides
+/// alongside the command: `{"cmd": "CacheSet", "data": {...}, "token": "..."}`.
+pub fn parse_command(payload: &[u8]) -> Result<(ControlCommand, Option<String>), serde_json::Error> {
+    command_from_value(serde_json::from_slice(payload)?)
+}
+
+/// Split an already-parsed JSON command into the command and its optional token
+fn command_from_value(mut value: serde_json::Value) -> Result<(ControlCommand, Option<String>), serde_json::Error> {
+    let token = value.as_object_mut()
+        .and_then(|obj| obj.remove("token"))
+        .and_then(|t| t.as_str().map(str::to_string));
+    Ok((serde_json::from_value(value)?, token))
+}
+
+/// Response for a payload that does not parse as a command
+fn invalid_command_response(e: &serde_json::Error) -> ControlResponse {
+    ControlResponse {
+        success: false,
+        message: format!("Invalid command: {}", e),
+        data: None,
+        latency_ns: 0,
+        error_code: Some(ErrorCode::InvalidRequest.as_str().to_string()),
+    }
+}
+
+/// Measure field multiplications per second in GF(`prime`) over a short burst
+fn galois_ops_per_sec(prime: u64) -> u64 {
+    const SAMPLE_OPS: u32 = 100_000;
+    let start = std::time::Instant::now();
+    let mut acc = GaloisElement::new(3, prime);
+    let factor = GaloisElement::new(0x9E37_79B9_7F4A_7C15, prime);
+    for _ in 0..SAMPLE_OPS {
+        acc = std::hint::black_box(acc * factor);
+    }
+    let elapsed = start.elapsed().as_secs_f64();
+    if elapsed > 0.0 { (SAMPLE_OPS as f64 / elapsed) as u64 } else { 0 }
+}
+
+/// Build an 8-byte VXLAN header (RFC 7348) for the given VNI
+pub fn vxlan_header(vni: u32) -> [u8; VXLAN_HEADER_SIZE] {
+    let vni = vni.to_be_bytes();
+    [VXLAN_FLAG_VNI_VALID, 0, 0, 0, vni[1], vni[2], vni[3], 0]
+}
+
+/// Frame a response as a VXLAN reply datagram of at most `mtu` bytes
+///
+/// When the full reply would exceed `mtu` (0 = unlimited), `data` is replaced
+/// by `{"truncated": true, "full_via": "http"}` so the client knows to repeat
+/// the command over the HTTP API. If that is still too large, `message` (which
+/// may echo client input) is cut short as well.
+pub fn vxlan_reply(response: &ControlResponse, mtu: usize) -> Result<Vec<u8>, serde_json::Error> {
+    let frame = |response: &ControlResponse| -> Result<Vec<u8>, serde_json::Error> {
+        let mut reply = vxlan_header(VXLAN_VNI_CONTROL_PLANE).to_vec();
+        serde_json::to_writer(&mut reply, response)?;
+        Ok(reply)
+    };
+
+    let reply = frame(response)?;
+    if mtu == 0 || reply.len() <= mtu {
+        return Ok(reply);
+    }
+    let truncated = ControlResponse {
+        data: Some(serde_json::json!({ "truncated": true, "full_via": "http" })),
+        ..response.clone()
+    };
+    let reply = frame(&truncated)?;
+    if reply.len() <= mtu {
+        return Ok(reply);
+    }
+
+    // Keep the longest prefix of the message whose JSON encoding still fits
+    let encoded_len = |text: &str| serde_json::to_string(text).map(|json| json.len());
+    let budget = encoded_len(&truncated.message)?.saturating_sub(reply.len() - mtu);
+    let mut message = String::new();
+    let mut used = 2; // the enclosing quotes
+    for c in truncated.message.chars() {
+        used += encoded_len(c.encode_utf8(&mut [0; 4]))? - 2;
+        if used > budget {
+            break;
+        }
+        message.push(c);
+    }
+    frame(&ControlResponse { message, ..truncated })
+}
+
 /// Control Plane response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ControlResponse {
@@ -102,6 +791,64 @@ pub struct ControlResponse {
     pub message: String,
     pub data: Option<serde_json::Value>,
     pub latency_ns: u64,
+    /// Machine-readable failure category (see [`ErrorCode`]); `None` on success
+    #[serde(default)]
+    pub error_code: Option<String>,
+}
+
+/// Failure categories reported in `ControlResponse::error_code`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    /// The key, field, container or model does not exist
+    NotFound,
+    /// Refused by the per-client rate limiter
+    RateLimited,
+    /// Missing or invalid bearer token
+    Unauthorized,
+    /// Malformed command or arguments
+    InvalidRequest,
+    /// The target's current state forbids the operation
+    Conflict,
+    /// A configured limit or quota would be exceeded
+    LimitExceeded,
+    /// The key holds a value of another type
+    WrongType,
+    /// Server-side failure
+    Internal,
+}
+
+impl ErrorCode {
+    /// Wire form, e.g. `NOT_FOUND`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::NotFound => "NOT_FOUND",
+            Self::RateLimited => "RATE_LIMITED",
+            Self::Unauthorized => "UNAUTHORIZED",
+            Self::InvalidRequest => "INVALID_REQUEST",
+            Self::Conflict => "CONFLICT",
+            Self::LimitExceeded => "LIMIT_EXCEEDED",
+            Self::WrongType => "WRONG_TYPE",
+            Self::Internal => "INTERNAL",
+        }
+    }
+
+    /// Code for an error returned by `UaoQtcamCache`
+    fn from_cache_error(error: &str) -> Self {
+        if error == WRONGTYPE_ERROR {
+            Self::WrongType
+        } else {
+            Self::Internal
+        }
+    }
+
+    fn from_prediction_error(error: &PredictionError) -> Self {
+        match error {
+            PredictionError::ModelNotFound(_) | PredictionError::LayerNotFound(_) => Self::NotFound,
+            PredictionError::InvalidSession | PredictionError::SubscriptionExpired => Self::Unauthorized,
+            PredictionError::QuotaExceeded => Self::LimitExceeded,
+            PredictionError::InvalidCalibration | PredictionError::Internal(_) => Self::Internal,
+        }
+    }
 }
 
 /// Server configuration
@@ -119,6 +866,337 @@ pub struct ServerConfig {
     pub cache_size: usize,
     /// Max concurrent connections
     pub max_connections: usize,
+    /// Require a bearer token for protected commands and `/v1/*` GFEF routes
+    #[serde(default)]
+    pub require_auth: bool,
+    /// Expected bearer token (with `require_auth` and no token, everything protected is refused)
+    #[serde(default)]
+    pub api_token: Option<String>,
+    /// How long shutdown waits for in-flight handlers (seconds)
+    #[serde(default = "default_shutdown_grace_secs")]
+    pub shutdown_grace_secs: u64,
+    /// Directory for TCAM/cache snapshots (`None` disables persistence)
+    #[serde(default)]
+    pub persistence_dir: Option<String>,
+    /// Commands accepted per second from one source address (0 = unlimited)
+    #[serde(default = "default_rate_limit_per_sec")]
+    pub rate_limit_per_sec: u32,
+    /// Seconds between server-initiated WebSocket pings (0 disables)
+    #[serde(default = "default_ws_ping_interval_secs")]
+    pub ws_ping_interval_secs: u64,
+    /// Close a WebSocket after this many seconds without a frame from the peer (0 disables)
+    #[serde(default = "default_ws_idle_timeout_secs")]
+    pub ws_idle_timeout_secs: u64,
+    /// Close a keep-alive HTTP connection after this many seconds without a request (0 disables)
+    #[serde(default = "default_http_idle_timeout_secs")]
+    pub http_idle_timeout_secs: u64,
+    /// Largest VXLAN reply datagram in bytes, header included (0 = unlimited)
+    #[serde(default = "default_response_mtu")]
+    pub response_mtu: usize,
+    /// PEM certificate chain for HTTPS/WSS (plaintext when unset)
+    #[serde(default)]
+    pub tls_cert_path: Option<String>,
+    /// PEM private key matching `tls_cert_path`
+    #[serde(default)]
+    pub tls_key_path: Option<String>,
+}
+
+impl ServerConfig {
+    /// TLS acceptor for the HTTP listener, or `None` when serving plaintext
+    ///
+    /// Both `tls_cert_path` and `tls_key_path` must be set to enable TLS.
+    pub fn tls_acceptor(&self) -> SymmetrixResult<Option<TlsAcceptor>> {
+        let tls_error = |message: String| symmetrix_core::SymmetrixError::RuntimeError(format!("TLS setup failed: {}", message));
+        let (cert_path, key_path) = match (&self.tls_cert_path, &self.tls_key_path) {
+            (None, None) => return Ok(None),
+            (Some(cert_path), Some(key_path)) => (cert_path, key_path),
+            _ => return Err(tls_error("tls_cert_path and tls_key_path must be set together".to_string())),
+        };
+
+        let certs = std::fs::File::open(cert_path)
+            .and_then(|file| rustls_pemfile::certs(&mut std::io::BufReader::new(file)))
+            .map_err(|e| tls_error(format!("{}: {}", cert_path, e)))?;
+        if certs.is_empty() {
+            return Err(tls_error(format!("{}: no certificates found", cert_path)));
+        }
+        let key = std::fs::File::open(key_path)
+            .and_then(|file| rustls_pemfile::read_all(&mut std::io::BufReader::new(file)))
+            .map_err(|e| tls_error(format!("{}: {}", key_path, e)))?
+            .into_iter()
+            .find_map(|item| match item {
+                rustls_pemfile::Item::PKCS8Key(der)
+                | rustls_pemfile::Item::RSAKey(der)
+                | rustls_pemfile::Item::ECKey(der) => Some(der),
+                _ => None,
+            })
+            .ok_or_else(|| tls_error(format!("{}: no private key found", key_path)))?;
+
+        let config = rustls::ServerConfig::builder()
+            .with_safe_defaults()
+            .with_no_client_auth()
+            .with_single_cert(certs.into_iter().map(rustls::Certificate).collect(), rustls::PrivateKey(key))
+            .map_err(|e| tls_error(e.to_string()))?;
+        Ok(Some(TlsAcceptor::from(Arc::new(config))))
+    }
+}
+
+/// Token bucket for a single source address
+#[derive(Debug, Clone, Copy)]
+struct TokenBucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+/// Token buckets keyed by source, with sources in first-seen order
+#[derive(Debug, Default)]
+struct TrackedBuckets {
+    by_addr: HashMap<SocketAddr, TokenBucket>,
+    order: VecDeque<SocketAddr>,
+}
+
+/// Per-source token-bucket limiter for VXLAN and WebSocket commands
+///
+/// Each address may burst up to `rate` commands and then refills at
+/// `rate` tokens per second. At most `max_tracked` sources are tracked;
+/// a new source displaces the longest-tracked one.
+#[derive(Debug)]
+struct RateLimiter {
+    rate: f64,
+    max_tracked: usize,
+    buckets: Mutex<TrackedBuckets>,
+}
+
+impl RateLimiter {
+    fn from_config(config: &ServerConfig) -> Self {
+        Self {
+            rate: config.rate_limit_per_sec as f64,
+            max_tracked: config.max_connections.max(1),
+            buckets: Mutex::new(TrackedBuckets::default()),
+        }
+    }
+
+    /// Take a token for `addr`, returning `false` if its bucket is empty
+    fn allow(&self, addr: SocketAddr) -> bool {
+        if self.rate <= 0.0 {
+            return true;
+        }
+        let now = Instant::now();
+        let mut guard = match self.buckets.lock() {
+            Ok(buckets) => buckets,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let buckets = &mut *guard;
+
+        // Evict in O(1) so a flood of new sources cannot grow the map or
+        // make each admission scan it
+        if !buckets.by_addr.contains_key(&addr) {
+            while buckets.by_addr.len() >= self.max_tracked {
+                match buckets.order.pop_front() {
+                    Some(oldest) => buckets.by_addr.remove(&oldest),
+                    None => break,
+                };
+            }
+            buckets.order.push_back(addr);
+        }
+
+        let bucket = buckets.by_addr.entry(addr).or_insert(TokenBucket { tokens: self.rate, refilled_at: now });
+        let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.rate);
+        bucket.refilled_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+fn rate_limited_response() -> ControlResponse {
+    ControlResponse {
+        success: false,
+        message: "rate limited".to_string(),
+        data: Some(serde_json::json!({ "status": 429 })),
+        latency_ns: 0,
+        error_code: Some(ErrorCode::RateLimited.as_str().to_string()),
+    }
+}
+
+/// Count of in-flight handlers, drained before shutdown completes
+#[derive(Debug, Default)]
+struct InFlight {
+    active: AtomicUsize,
+    idle: Notify,
+}
+
+impl InFlight {
+    /// Mark a handler active until the returned guard is dropped
+    fn enter(self: &Arc<Self>) -> InFlightGuard {
+        self.active.fetch_add(1, Ordering::SeqCst);
+        InFlightGuard(self.clone())
+    }
+
+    fn active(&self) -> usize {
+        self.active.load(Ordering::SeqCst)
+    }
+
+    /// Wait until no handler is active; returns `false` if `grace` ran out first
+    async fn drain(&self, grace: Duration) -> bool {
+        tokio::time::timeout(grace, async {
+            loop {
+                let idle = self.idle.notified();
+                if self.active() == 0 {
+                    return;
+                }
+                idle.await;
+            }
+        }).await.is_ok()
+    }
+}
+
+struct InFlightGuard(Arc<InFlight>);
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        if self.0.active.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.0.idle.notify_waiters();
+        }
+    }
+}
+
+/// Bearer-token policy applied to protected commands and routes
+#[derive(Debug, Clone, Default)]
+struct AuthPolicy {
+    require_auth: bool,
+    api_token: Option<String>,
+}
+
+impl AuthPolicy {
+    fn from_config(config: &ServerConfig) -> Self {
+        Self {
+            require_auth: config.require_auth,
+            api_token: config.api_token.clone(),
+        }
+    }
+
+    /// Whether `token` may run protected operations
+    fn allows(&self, token: Option<&str>) -> bool {
+        if !self.require_auth {
+            return true;
+        }
+        match (self.api_token.as_deref(), token) {
+            (Some(expected), Some(token)) => constant_time_eq(expected.as_bytes(), token.as_bytes()),
+            _ => false,
+        }
+    }
+}
+
+/// WebSocket liveness settings; `None` disables the corresponding check
+#[derive(Debug, Clone, Copy, Default)]
+struct WsKeepalive {
+    ping_interval: Option<Duration>,
+    idle_timeout: Option<Duration>,
+}
+
+impl WsKeepalive {
+    fn from_config(config: &ServerConfig) -> Self {
+        let secs = |secs: u64| (secs > 0).then(|| Duration::from_secs(secs));
+        Self {
+            ping_interval: secs(config.ws_ping_interval_secs),
+            idle_timeout: secs(config.ws_idle_timeout_secs),
+        }
+    }
+
+    /// Resolves on the next ping tick; never when pings are disabled
+    async fn next_ping(interval: &mut Option<tokio::time::Interval>) {
+        match interval {
+            Some(interval) => {
+                interval.tick().await;
+            }
+            None => std::future::pending().await,
+        }
+    }
+
+    /// Resolves once the peer has been silent since `last_seen` for the idle timeout
+    async fn idle_expired(&self, last_seen: tokio::time::Instant) {
+        match self.idle_timeout {
+            Some(timeout) => tokio::time::sleep_until(last_seen + timeout).await,
+            None => std::future::pending().await,
+        }
+    }
+}
+
+/// Request activity on one HTTP connection, for the keep-alive idle timeout
+struct ConnectionActivity {
+    in_flight: AtomicUsize,
+    last_seen: Mutex<tokio::time::Instant>,
+}
+
+impl ConnectionActivity {
+    fn new() -> Self {
+        Self {
+            in_flight: AtomicUsize::new(0),
+            last_seen: Mutex::new(tokio::time::Instant::now()),
+        }
+    }
+
+    fn touch(&self) {
+        *self.last_seen.lock().unwrap() = tokio::time::Instant::now();
+    }
+
+    fn begin(&self) {
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        self.touch();
+    }
+
+    fn end(&self) {
+        self.touch();
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    /// Resolves once no request has been in flight for `timeout`; never
+    /// when the timeout is disabled
+    async fn idle_expired(&self, timeout: Option<Duration>) {
+        let timeout = match timeout {
+            Some(timeout) => timeout,
+            None => return std::future::pending().await,
+        };
+        loop {
+            let deadline = *self.last_seen.lock().unwrap() + timeout;
+            tokio::time::sleep_until(deadline).await;
+            if self.in_flight.load(Ordering::SeqCst) > 0 {
+                // A slow handler is not idleness; check again a full timeout later
+                tokio::time::sleep(timeout).await;
+            } else if *self.last_seen.lock().unwrap() + timeout <= tokio::time::Instant::now() {
+                return;
+            }
+        }
+    }
+}
+
+/// Compare secrets without short-circuiting on the first differing byte
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Extract the token from an `Authorization: Bearer <token>` header
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers.get(header::AUTHORIZATION)?
+        .to_str().ok()?
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+}
+
+/// 401-equivalent response for a missing or invalid token
+fn unauthorized_response() -> ControlResponse {
+    ControlResponse {
+        success: false,
+        message: "Unauthorized: valid bearer token required".to_string(),
+        data: Some(serde_json::json!({ "status": 401 })),
+        latency_ns: 0,
+        error_code: Some(ErrorCode::Unauthorized.as_str().to_string()),
+    }
 }
 
 impl Default for ServerConfig {
@@ -130,6 +1208,17 @@ impl Default for ServerConfig {
             http_port: 8080,
             cache_size: 256 * 1024 * 1024, // 256 MB = 64 GB effective
             max_connections: 10000,
+            require_auth: false,
+            api_token: None,
+            shutdown_grace_secs: default_shutdown_grace_secs(),
+            persistence_dir: None,
+            rate_limit_per_sec: default_rate_limit_per_sec(),
+            ws_ping_interval_secs: default_ws_ping_interval_secs(),
+            ws_idle_timeout_secs: default_ws_idle_timeout_secs(),
+            http_idle_timeout_secs: default_http_idle_timeout_secs(),
+            response_mtu: default_response_mtu(),
+            tls_cert_path: None,
+            tls_key_path: None,
         }
     }
 }
@@ -148,6 +1237,72 @@ pub struct ControlPlaneServer {
     gfef_predictor: Arc<RwLock<ActivationPredictor>>,
     gfef_calibration: Arc<CalibrationService>,
     gfef_subscriptions: Arc<RwLock<SubscriptionManager>>,
+    // Registered indices on disk, when `persistence_dir` is set
+    gfef_storage: Option<Arc<RwLock<IndexStorage>>>,
+    // Graceful shutdown
+    shutdown: broadcast::Sender<()>,
+    in_flight: Arc<InFlight>,
+    rate_limiter: Arc<RateLimiter>,
+    // Container state, capped at `SymmetrixConfig::max_containers` running
+    containers: Arc<RwLock<HashMap<String, ContainerInfo>>>,
+    index_uploads: Arc<IndexUploads>,
+    vxlan_fragments: Arc<FragmentReassembly>,
+    // Weight Server calibration sessions
+    calibration_sessions: Arc<CalibrationSessions>,
+    // Startup self-test results, per subsystem
+    subsystem_health: Arc<RwLock<BTreeMap<&'static str, HealthStatus>>>,
+}
+
+/// Outcome of a subsystem self-test, ordered from best to worst
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    Healthy,
+    /// Working, but missing something it needs to serve every request
+    Degraded,
+    Unhealthy,
+}
+
+impl HealthStatus {
+    /// Wire form, e.g. `degraded`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Healthy => "healthy",
+            Self::Degraded => "degraded",
+            Self::Unhealthy => "unhealthy",
+        }
+    }
+
+    fn from_probe(passed: bool) -> Self {
+        if passed { Self::Healthy } else { Self::Unhealthy }
+    }
+
+    /// Worst status in `statuses` (healthy when empty)
+    fn aggregate<'a>(statuses: impl IntoIterator<Item = &'a HealthStatus>) -> Self {
+        statuses.into_iter().copied().max().unwrap_or(Self::Healthy)
+    }
+}
+
+/// Lifecycle state of a tracked container
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ContainerStatus {
+    Running,
+    Stopped,
+}
+
+/// A container launched through the control plane
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContainerInfo {
+    pub id: String,
+    pub name: String,
+    pub template: String,
+    pub status: ContainerStatus,
+    /// Memory limit (MB)
+    pub memory_mb: u64,
+    /// CPU limit (cores)
+    pub cpu: f64,
+    /// Launch timestamp (Unix epoch seconds)
+    pub created_at: i64,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -159,6 +1314,48 @@ pub struct ServerStats {
     pub cache_misses: u64,
     pub total_latency_ns: u64,
     pub start_time: i64,
+    /// Commands processed, keyed by `ControlCommand` variant name
+    #[serde(default)]
+    pub commands_by_type: BTreeMap<String, u64>,
+    /// WebSocket connections closed for exceeding the idle timeout
+    #[serde(default)]
+    pub ws_connections_reaped: u64,
+    /// Fragmented VXLAN commands dropped before all fragments arrived
+    #[serde(default)]
+    pub vxlan_reassemblies_expired: u64,
+    /// VXLAN packets dropped because the work queue was full
+    #[serde(default)]
+    pub dropped_packets: u64,
+}
+
+impl ServerStats {
+    /// Render the counters in Prometheus text exposition format (v0.0.4)
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+        let counters = [
+            ("control_commands_total", "Control commands processed", self.commands_processed),
+            ("control_cache_hits_total", "Cache GET hits", self.cache_hits),
+            ("control_cache_misses_total", "Cache GET misses", self.cache_misses),
+            ("control_latency_ns_sum", "Total command processing time in nanoseconds", self.total_latency_ns),
+            ("control_vxlan_packets_total", "VXLAN UDP packets received", self.vxlan_packets),
+            ("control_http_requests_total", "HTTP requests received", self.http_requests),
+            ("control_ws_connections_reaped_total", "WebSocket connections closed for idling", self.ws_connections_reaped),
+            ("control_vxlan_reassemblies_expired_total", "Fragmented VXLAN commands that timed out", self.vxlan_reassemblies_expired),
+            ("control_vxlan_packets_dropped_total", "VXLAN packets dropped by a full work queue", self.dropped_packets),
+        ];
+        for (name, help, value) in counters {
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} counter", name);
+            let _ = writeln!(out, "{} {}", name, value);
+        }
+
+        let _ = writeln!(out, "# HELP control_commands_by_type_total Control commands processed per command type");
+        let _ = writeln!(out, "# TYPE control_commands_by_type_total counter");
+        for (command, count) in &self.commands_by_type {
+            let _ = writeln!(out, "control_commands_by_type_total{{command=\"{}\"}} {}", command, count);
+        }
+        out
+    }
 }
 
 impl ControlPlaneServer {
@@ -203,17 +1400,18 @@ impl ControlPlaneServer {
         let gfef_predictor = Arc::new(RwLock::new(ActivationPredictor::new(0.95)));
         let gfef_calibration = Arc::new(CalibrationService::new(60)); // 60 second rotation
         let gfef_subscriptions = Arc::new(RwLock::new(SubscriptionManager::new()));
+        let gfef_storage = config.persistence_dir.as_ref().map(|dir| {
+            Arc::new(RwLock::new(IndexStorage::new(std::path::Path::new(dir).join(GFEF_INDEX_DIR))))
+        });
+        let (shutdown, _) = broadcast::channel(1);
+        let rate_limiter = Arc::new(RateLimiter::from_config(&config));
 
         let stats = Arc::new(RwLock::new(ServerStats {
             start_time: chrono::Utc::now().timestamp(),
             ..Default::default()
         }));
 
-        info!("═══════════════════════════════════════════════════════════════════════════════");
-        info!("  ✅ ALL INTEGRATIONS INITIALIZED");
-        info!("═══════════════════════════════════════════════════════════════════════════════");
-
-        Ok(Self {
+        let server = Self {
             config,
             runtime,
             cache,
@@ -225,7 +1423,38 @@ impl ControlPlaneServer {
             gfef_predictor,
             gfef_calibration,
             gfef_subscriptions,
-        })
+            gfef_storage,
+            shutdown,
+            in_flight: Arc::new(InFlight::default()),
+            rate_limiter,
+            containers: Arc::new(RwLock::new(HashMap::new())),
+            index_uploads: Arc::new(IndexUploads::default()),
+            vxlan_fragments: Arc::new(FragmentReassembly::default()),
+            calibration_sessions: Arc::new(CalibrationSessions::default()),
+            subsystem_health: Arc::new(RwLock::new(BTreeMap::new())),
+        };
+        server.restore_state().await;
+
+        let health = server.self_test().await;
+        for (subsystem, status) in &health {
+            if *status != HealthStatus::Healthy {
+                warn!("  ⚠️  {} self-test: {:?}", subsystem, status);
+            }
+        }
+        info!("═══════════════════════════════════════════════════════════════════════════════");
+        match HealthStatus::aggregate(health.values()) {
+            HealthStatus::Healthy => info!("  ✅ ALL INTEGRATIONS INITIALIZED"),
+            status => warn!("  ⚠️  INTEGRATIONS INITIALIZED ({})", status.as_str().to_uppercase()),
+        }
+        info!("═══════════════════════════════════════════════════════════════════════════════");
+        *server.subsystem_health.write().await = health;
+
+        Ok(server)
+    }
+
+    /// Exercise one operation against each subsystem and report its health
+    pub async fn self_test(&self) -> BTreeMap<&'static str, HealthStatus> {
+        self.clone_internals().self_test().await
     }
 
     /// Start the Control Plane Server
@@ -241,27 +1470,55 @@ impl ControlPlaneServer {
             .parse()
             .map_err(|e| symmetrix_core::SymmetrixError::RuntimeError(format!("Invalid HTTP address: {}", e)))?;
 
+        let tls = self.config.tls_acceptor()?;
+
         info!("📡 VXLAN UDP Server: {}", vxlan_addr);
-        info!("🌐 HTTP Management API: http://{}", http_addr);
+        info!("🌐 HTTP Management API: {}://{}", if tls.is_some() { "https" } else { "http" }, http_addr);
 
         // Clone for async tasks
         let server_clone = Arc::new(self.clone_internals());
         let server_http = server_clone.clone();
 
         // Start VXLAN handler
-        let vxlan_task = tokio::spawn(async move {
+        let mut vxlan_task = tokio::spawn(async move {
             if let Err(e) = Self::run_vxlan_server(vxlan_addr, server_clone).await {
                 error!("VXLAN server error: {}", e);
             }
         });
 
         // Start HTTP handler
-        let http_task = tokio::spawn(async move {
-            if let Err(e) = Self::run_http_server(http_addr, server_http).await {
+        let mut http_task = tokio::spawn(async move {
+            if let Err(e) = Self::run_http_server(http_addr, tls, server_http).await {
                 error!("HTTP server error: {}", e);
             }
         });
 
+        // Purge cache keys past their TTL
+        self.cache.spawn_expiry_sweeper(std::time::Duration::from_secs(CACHE_EXPIRY_SWEEP_SECS));
+
+        // Sweep expired GFEF indices so they stop serving predictions
+        let predictor_sweep = self.gfef_predictor.clone();
+        tokio::spawn(async move {
+            let mut sweep = tokio::time::interval(std::time::Duration::from_secs(GFEF_EXPIRY_SWEEP_SECS));
+            loop {
+                sweep.tick().await;
+                let expired = predictor_sweep.write().await.prune_expired();
+                for model_id in expired {
+                    warn!("⏰ GFEF index expired and unregistered: {}", model_id);
+                }
+            }
+        });
+
+        // Discard chunked uploads their clients abandoned
+        let upload_sweep = self.index_uploads.clone();
+        tokio::spawn(async move {
+            let mut sweep = tokio::time::interval(std::time::Duration::from_secs(GFEF_EXPIRY_SWEEP_SECS));
+            loop {
+                sweep.tick().await;
+                upload_sweep.sweep(std::time::Duration::from_secs(GFEF_UPLOAD_IDLE_SECS)).await;
+            }
+        });
+
         info!("═══════════════════════════════════════════════════════════════════════════════");
         info!("  🎯 CONTROL PLANE SERVER RUNNING");
         info!("═══════════════════════════════════════════════════════════════════════════════");
@@ -285,20 +1542,22 @@ impl ControlPlaneServer {
         info!("");
         info!("═══════════════════════════════════════════════════════════════════════════════");
 
-        // Wait for both tasks
+        // Run until a listener exits or we are told to stop
         tokio::select! {
-            _ = vxlan_task => {}
-            _ = http_task => {}
+            _ = &mut vxlan_task => {}
+            _ = &mut http_task => {}
             _ = tokio::signal::ctrl_c() => {
                 info!("🛑 Received shutdown signal...");
             }
         }
 
+        self.shutdown(vec![vxlan_task, http_task]).await;
         Ok(())
     }
 
     fn clone_internals(&self) -> ServerInternals {
         ServerInternals {
+            runtime: self.runtime.clone(),
             cache: self.cache.clone(),
             qagml: self.qagml.clone(),
             qanban: self.qanban.clone(),
@@ -308,6 +1567,123 @@ impl ControlPlaneServer {
             gfef_predictor: self.gfef_predictor.clone(),
             gfef_calibration: self.gfef_calibration.clone(),
             gfef_subscriptions: self.gfef_subscriptions.clone(),
+            gfef_storage: self.gfef_storage.clone(),
+            persistence_dir: self.config.persistence_dir.as_ref().map(std::path::PathBuf::from),
+            auth: AuthPolicy::from_config(&self.config),
+            ws_keepalive: WsKeepalive::from_config(&self.config),
+            http_idle_timeout: (self.config.http_idle_timeout_secs > 0)
+                .then(|| Duration::from_secs(self.config.http_idle_timeout_secs)),
+            response_mtu: self.config.response_mtu,
+            shutdown: self.shutdown.clone(),
+            in_flight: self.in_flight.clone(),
+            rate_limiter: self.rate_limiter.clone(),
+            containers: self.containers.clone(),
+            index_uploads: self.index_uploads.clone(),
+            vxlan_fragments: self.vxlan_fragments.clone(),
+            vxlan_queue_capacity: self.config.max_connections.max(1),
+            calibration_sessions: self.calibration_sessions.clone(),
+            subsystem_health: self.subsystem_health.clone(),
+        }
+    }
+
+    /// Stop accepting work, drain in-flight handlers and persist state
+    ///
+    /// Listener tasks get the whole grace period to return; whatever is
+    /// left of it goes to draining packet, HTTP request and WebSocket handlers.
+    async fn shutdown(&self, tasks: Vec<tokio::task::JoinHandle<()>>) {
+        let grace = Duration::from_secs(self.config.shutdown_grace_secs);
+        let deadline = tokio::time::Instant::now() + grace;
+
+        let _ = self.shutdown.send(());
+        for task in tasks {
+            if !task.is_finished() && tokio::time::timeout_at(deadline, task).await.is_err() {
+                warn!("Listener did not stop within the {}s grace period", grace.as_secs());
+            }
+        }
+
+        let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+        if !self.in_flight.drain(remaining).await {
+            warn!("Shutting down with {} handler(s) still in flight", self.in_flight.active());
+        }
+
+        self.persist_state().await;
+        info!("👋 Control Plane Server stopped");
+    }
+
+    /// Write TCAM routes, cache entries and QAGML history to `persistence_dir`
+    async fn persist_state(&self) {
+        let dir = match &self.config.persistence_dir {
+            Some(dir) => std::path::Path::new(dir),
+            None => return,
+        };
+        if let Err(e) = std::fs::create_dir_all(dir) {
+            error!("Failed to create persistence directory {:?}: {}", dir, e);
+            return;
+        }
+
+        match self.uao_qtcam.read().await.export_routes_json().await {
+            Ok(json) => match std::fs::write(dir.join(TCAM_SNAPSHOT_FILE), json) {
+                Ok(()) => info!("💾 TCAM routes saved to {:?}", dir.join(TCAM_SNAPSHOT_FILE)),
+                Err(e) => error!("Failed to write TCAM snapshot: {}", e),
+            },
+            Err(e) => error!("Failed to export TCAM routes: {}", e),
+        }
+
+        match self.cache.snapshot_json() {
+            Ok(json) => match std::fs::write(dir.join(CACHE_SNAPSHOT_FILE), json) {
+                Ok(()) => info!("💾 Cache saved to {:?}", dir.join(CACHE_SNAPSHOT_FILE)),
+                Err(e) => error!("Failed to write cache snapshot: {}", e),
+            },
+            Err(e) => error!("Failed to snapshot cache: {}", e),
+        }
+
+        match self.qagml.read().await.save_temporal_history(&dir.join(QAGML_HISTORY_FILE)) {
+            Ok(()) => info!("💾 QAGML temporal history saved to {:?}", dir.join(QAGML_HISTORY_FILE)),
+            Err(e) => error!("Failed to write QAGML temporal history: {}", e),
+        }
+    }
+
+    /// Reload snapshots written by [`ControlPlaneServer::persist_state`]
+    async fn restore_state(&self) {
+        let dir = match &self.config.persistence_dir {
+            Some(dir) => std::path::Path::new(dir),
+            None => return,
+        };
+
+        if let Ok(json) = std::fs::read_to_string(dir.join(TCAM_SNAPSHOT_FILE)) {
+            match self.uao_qtcam.read().await.import_routes_json(&json).await {
+                Ok(()) => info!("💾 TCAM routes restored from {:?}", dir.join(TCAM_SNAPSHOT_FILE)),
+                Err(e) => warn!("Ignoring unreadable TCAM snapshot: {}", e),
+            }
+        }
+
+        if let Ok(json) = std::fs::read_to_string(dir.join(CACHE_SNAPSHOT_FILE)) {
+            match self.cache.restore_json(&json) {
+                Ok(restored) => info!("💾 {} cache entries restored", restored),
+                Err(e) => warn!("Ignoring unreadable cache snapshot: {}", e),
+            }
+        }
+
+        let history = dir.join(QAGML_HISTORY_FILE);
+        if history.exists() {
+            match self.qagml.read().await.load_temporal_history(&history) {
+                Ok(()) => info!("💾 QAGML temporal history restored from {:?}", history),
+                Err(e) => warn!("Failed to restore QAGML temporal history: {}", e),
+            }
+        }
+
+        if let Some(storage) = &self.gfef_storage {
+            match storage.write().await.load_all() {
+                Ok(indices) => {
+                    let count = indices.len();
+                    let mut predictor = self.gfef_predictor.write().await;
+                    for index in indices {
+                        predictor.register_index(index);
+                    }
+                    info!("💾 {} GFEF index(es) restored", count);
+                }
+                Err(e) => warn!("Failed to restore GFEF indices: {}", e),
+            }
         }
     }
 
@@ -315,63 +1691,22 @@ impl ControlPlaneServer {
     pub async fn load_gfef_index(&self, json_path: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         info!("🔐 Loading GFEF index from: {}", json_path);
 
-        let json_content = std::fs::read_to_string(json_path)?;
-        let raw: serde_json::Value = serde_json::from_str(&json_content)?;
-
-        // Convert Python-generated format to Rust GFEFIndex
-        let model_name = raw["model"].as_str().unwrap_or("unknown").to_string();
-        let k_components = raw["k_components"].as_u64().unwrap_or(32) as u32;
-        let fft_bins = raw["fft_bins"].as_u64().unwrap_or(16) as u32;
-        let total_neurons = raw["total_neurons"].as_u64().unwrap_or(0);
-
-        let layers_raw = raw["layers"].as_array()
-            .ok_or("Missing layers array")?;
-
-        let layers: Vec<LayerIndex> = layers_raw.iter().map(|l| {
-            let layer_id = l["layer_id"].as_u64().unwrap_or(0) as u32;
-            let name = l["name"].as_str().unwrap_or("").to_string();
-            let neurons = l["neurons"].as_u64().unwrap_or(0) as u32;
-            let pc_shape = l["pc_shape"].as_array();
-            let input_dim = pc_shape
-                .and_then(|arr| arr.first())
-                .and_then(|v| v.as_u64())
-                .unwrap_or(0) as u32;
-
-            LayerIndex {
-                layer_id,
-                layer_name: name,
-                num_neurons: neurons,
-                input_dim,
-                k_components,
-                principal_components: Vec::new(),
-                signatures: Vec::new(),
-            }
-        }).collect();
-
-        let index = GFEFIndex {
-            id: uuid::Uuid::new_v4(),
-            customer_id: uuid::Uuid::nil(),
-            model_id: model_name.clone(),
-            model_name: model_name.clone(),
-            generated_at: chrono::Utc::now(),
-            expires_at: None,
-            layers,
-            total_neurons,
-            config: IndexConfig {
-                k_components,
-                fft_bins,
-                target_sparsity: 0.95,
-            },
-        };
+        // Convert Python-generated format to Rust GFEFIndex, PCs from the .bin sidecar;
+        // indices failing their checksum are never registered
+        let index = GFEFIndex::load_from_files(std::path::Path::new(json_path))?;
+        let model_name = index.model_name.clone();
+        let total_neurons = index.total_neurons;
+        let num_layers = index.layers.len();
 
-        // Register with predictor - TRIPLE IP LOCK ACTIVATES HERE
+        // Persist, then register with predictor - TRIPLE IP LOCK ACTIVATES HERE
+        persist_gfef_index(&self.gfef_storage, &index).await;
         {
             let mut predictor = self.gfef_predictor.write().await;
             predictor.register_index(index);
         }
 
         info!("✅ GFEF index loaded: {} ({} neurons, {} layers)",
-            model_name, total_neurons, layers_raw.len());
+            model_name, total_neurons, num_layers);
         info!("🔒 TRIPLE IP LOCK ACTIVE - Index secured on Control Plane");
         info!("   Lock 1: GFEF Index (SECURED)");
         info!("   Lock 2: Calibration Matrix (rotating every 60s)");
@@ -387,20 +1722,56 @@ impl ControlPlaneServer {
 
         info!("📡 VXLAN server listening on {}", addr);
 
+        Self::serve_vxlan(Arc::new(socket), server).await
+    }
+
+    /// Receive loop for an already-bound VXLAN socket
+    ///
+    /// Packets are queued for a fixed pool of `VXLAN_WORKERS` tasks; the
+    /// queue holds `max_connections` packets and anything beyond that is
+    /// dropped and counted in `ServerStats::dropped_packets`.
+    async fn serve_vxlan(socket: Arc<UdpSocket>, server: Arc<ServerInternals>) -> SymmetrixResult<()> {
         let mut buf = vec![0u8; 65535];
+        let mut shutdown = server.shutdown.subscribe();
+
+        let (queue, jobs) = mpsc::channel::<(Vec<u8>, SocketAddr, InFlightGuard)>(server.vxlan_queue_capacity);
+        let jobs = Arc::new(tokio::sync::Mutex::new(jobs));
+        for _ in 0..VXLAN_WORKERS {
+            let jobs = jobs.clone();
+            let server = server.clone();
+            let socket = socket.clone();
+            // Workers exit once the receive loop is gone and the queue is drained
+            tokio::spawn(async move {
+                loop {
+                    let job = jobs.lock().await.recv().await;
+                    let (packet, src, _guard) = match job {
+                        Some(job) => job,
+                        None => return,
+                    };
+                    if let Err(e) = Self::handle_vxlan_packet(&packet, src, server.clone(), socket.clone()).await {
+                        warn!("Error handling VXLAN packet from {}: {}", src, e);
+                    }
+                }
+            });
+        }
 
         loop {
-            match socket.recv_from(&mut buf).await {
-                Ok((len, src)) => {
-                    let packet = buf[..len].to_vec();
-                    let server_clone = server.clone();
-                    let socket_clone = socket.local_addr().ok();
+            let received = tokio::select! {
+                received = socket.recv_from(&mut buf) => received,
+                _ = shutdown.recv() => {
+                    info!("📡 VXLAN server stopped accepting packets");
+                    return Ok(());
+                }
+            };
 
-                    tokio::spawn(async move {
-                        if let Err(e) = Self::handle_vxlan_packet(&packet, src, server_clone, socket_clone).await {
-                            warn!("Error handling VXLAN packet from {}: {}", src, e);
-                        }
-                    });
+            match received {
+                Ok((len, src)) => {
+                    // Queued packets count as in flight so shutdown drains them
+                    let guard = server.in_flight.enter();
+                    if queue.try_send((buf[..len].to_vec(), src, guard)).is_err() {
+                        debug!("VXLAN work queue full, dropped packet from {}", src);
+                        server.stats.write().await.dropped_packets += 1;
+                    }
                 }
                 Err(e) => {
                     error!("Error receiving VXLAN packet: {}", e);
@@ -414,7 +1785,7 @@ impl ControlPlaneServer {
         packet: &[u8],
         src: SocketAddr,
         server: Arc<ServerInternals>,
-        _local: Option<SocketAddr>,
+        socket: Arc<UdpSocket>,
     ) -> SymmetrixResult<()> {
         // Update stats
         {
@@ -427,176 +1798,343 @@ impl ControlPlaneServer {
             return Ok(());
         }
 
-        let payload = &packet[VXLAN_HEADER_SIZE..];
+        // Commands too large for one datagram arrive as fragments
+        let fragmented = packet[0] & VXLAN_FLAG_FRAGMENT != 0;
+        let reassembled;
+        let payload = if fragmented {
+            // Charge every fragment so buffering is rate limited too
+            if !server.rate_limiter.allow(src) {
+                debug!("Rate limited VXLAN fragment from {}", src);
+                return Ok(());
+            }
+            let expired = server.vxlan_fragments.expire();
+            if expired > 0 {
+                server.stats.write().await.vxlan_reassemblies_expired += expired as u64;
+            }
+            match server.vxlan_fragments.accept(src, &packet[VXLAN_HEADER_SIZE..]) {
+                Ok(Some(payload)) => {
+                    reassembled = payload;
+                    &reassembled[..]
+                }
+                Ok(None) => return Ok(()),
+                Err(e) => {
+                    debug!("Dropped VXLAN fragment from {}: {}", src, e);
+                    return Ok(());
+                }
+            }
+        } else {
+            &packet[VXLAN_HEADER_SIZE..]
+        };
 
         // Parse JSON command
-        let command: ControlCommand = match serde_json::from_slice(payload) {
-            Ok(cmd) => cmd,
+        let (command, token) = match parse_command(payload) {
+            Ok(parsed) => parsed,
             Err(e) => {
                 debug!("Failed to parse VXLAN command from {}: {}", src, e);
                 return Ok(());
             }
         };
 
-        // Process command
+        // Process command; fragmented commands were already charged per fragment
         let start = std::time::Instant::now();
-        let response = Self::process_command(command, server.clone()).await;
+        let peer = (!fragmented).then_some(src);
+        let response = Self::process_from(peer, command, token.as_deref(), server.clone()).await;
         let latency = start.elapsed().as_nanos() as u64;
 
         debug!("VXLAN command from {} processed in {} ns", src, latency);
 
+        // Reply on the same socket, framed like the request
+        let reply = vxlan_reply(&response, server.response_mtu)
+            .map_err(|e| symmetrix_core::SymmetrixError::RuntimeError(format!("Failed to serialize response: {}", e)))?;
+
+        socket.send_to(&reply, src).await
+            .map_err(|e| symmetrix_core::SymmetrixError::RuntimeError(format!("Failed to send VXLAN response: {}", e)))?;
+
         Ok(())
     }
 
-    /// Run HTTP management server with WebSocket support
-    async fn run_http_server(addr: SocketAddr, server: Arc<ServerInternals>) -> SymmetrixResult<()> {
-        use tokio::io::{AsyncReadExt, AsyncWriteExt};
-        use tokio::net::TcpListener;
-
+    /// Run HTTP management server with WebSocket support, over TLS when `tls` is set
+    async fn run_http_server(
+        addr: SocketAddr,
+        tls: Option<TlsAcceptor>,
+        server: Arc<ServerInternals>,
+    ) -> SymmetrixResult<()> {
         let listener = TcpListener::bind(addr).await
             .map_err(|e| symmetrix_core::SymmetrixError::RuntimeError(format!("Failed to bind HTTP socket: {}", e)))?;
 
-        info!("🌐 HTTP server listening on {}", addr);
-        info!("🔌 WebSocket endpoint: ws://{}/ws", addr);
+        match tls {
+            Some(acceptor) => {
+                info!("🔒 HTTPS server listening on {}", addr);
+                info!("🔌 WebSocket endpoint: wss://{}/ws", addr);
+                Self::serve_https(listener, acceptor, server).await
+            }
+            None => {
+                info!("🌐 HTTP server listening on {}", addr);
+                info!("🔌 WebSocket endpoint: ws://{}/ws", addr);
+                Self::serve_http(listener, server).await
+            }
+        }
+    }
 
+    /// Serve the router on an already-bound listener until shutdown is signalled
+    async fn serve_http(listener: TcpListener, server: Arc<ServerInternals>) -> SymmetrixResult<()> {
+        let mut shutdown = server.shutdown.subscribe();
+        let app = Self::http_router(server.clone());
         loop {
-            match listener.accept().await {
-                Ok((socket, peer)) => {
-                    let server_clone = server.clone();
-
-                    tokio::spawn(async move {
-                        // Peek at the request to determine if it's a WebSocket upgrade
-                        // Use larger buffer for GFEF index uploads (can be 100KB+)
-                        let mut buf = vec![0u8; 256 * 1024]; // 256KB buffer
-                        let mut socket = socket;
-                        let mut total_read = 0;
-
-                        // Read all available data (may come in chunks)
-                        loop {
-                            match tokio::io::AsyncReadExt::read(&mut socket, &mut buf[total_read..]).await {
-                                Ok(0) => break, // EOF
-                                Ok(n) => {
-                                    total_read += n;
-                                    // Check if we've received the full request
-                                    // Look for Content-Length header and verify we have all data
-                                    let partial = String::from_utf8_lossy(&buf[..total_read]);
-                                    if let Some(content_length) = Self::extract_content_length(&partial) {
-                                        if let Some(body_start) = partial.find("\r\n\r\n") {
-                                            let body_len = total_read - body_start - 4;
-                                            if body_len >= content_length {
-                                                break; // We have the full request
-                                            }
-                                        }
-                                    } else if partial.contains("\r\n\r\n") && !partial.contains("Content-Length") {
-                                        break; // No body expected
-                                    }
-                                    // Continue reading if buffer not full
-                                    if total_read >= buf.len() {
-                                        break;
-                                    }
-                                }
-                                Err(_) => break,
-                            }
-                        }
+            let (stream, peer) = tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok(accepted) => accepted,
+                    Err(e) => {
+                        warn!("HTTP accept failed: {}", e);
+                        continue;
+                    }
+                },
+                _ = shutdown.recv() => return Ok(()),
+            };
+
+            let connection_shutdown = server.shutdown.subscribe();
+            tokio::spawn(Self::serve_connection(stream, peer, app.clone(), server.clone(), connection_shutdown));
+        }
+    }
+
+    /// Serve the router over TLS on an already-bound listener until shutdown is signalled
+    ///
+    /// Each connection completes the TLS handshake before reaching the same
+    /// HTTP/WebSocket dispatch as `serve_http`.
+    async fn serve_https(listener: TcpListener, acceptor: TlsAcceptor, server: Arc<ServerInternals>) -> SymmetrixResult<()> {
+        let mut shutdown = server.shutdown.subscribe();
+        let app = Self::http_router(server.clone());
+        loop {
+            let (stream, peer) = tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok(accepted) => accepted,
+                    Err(e) => {
+                        warn!("HTTPS accept failed: {}", e);
+                        continue;
+                    }
+                },
+                _ = shutdown.recv() => return Ok(()),
+            };
+
+            let acceptor = acceptor.clone();
+            let app = app.clone();
+            let server = server.clone();
+            let connection_shutdown = server.shutdown.subscribe();
+            tokio::spawn(async move {
+                match acceptor.accept(stream).await {
+                    Ok(stream) => Self::serve_connection(stream, peer, app, server, connection_shutdown).await,
+                    Err(e) => debug!("TLS handshake with {} failed: {}", peer, e),
+                }
+            });
+        }
+    }
+
+    /// Serve successive requests on one connection
+    ///
+    /// HTTP/1.1 connections stay open between requests until the client
+    /// closes them, sends `Connection: close`, or sends nothing for
+    /// `idle_timeout`; an idle connection is shut down gracefully so a
+    /// request already being read still gets its response. Server shutdown
+    /// closes the connection the same way, and each request counts as in
+    /// flight until its response is ready, so the shutdown grace period
+    /// waits for it. WebSocket upgrades leave this loop and are governed by
+    /// `WsKeepalive`.
+    async fn serve_connection<I>(
+        io: I,
+        peer: SocketAddr,
+        app: Router,
+        server: Arc<ServerInternals>,
+        mut shutdown: broadcast::Receiver<()>,
+    )
+    where
+        I: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send + 'static,
+    {
+        let idle_timeout = server.http_idle_timeout;
+        let activity = Arc::new(ConnectionActivity::new());
+        let service = {
+            let activity = activity.clone();
+            hyper::service::service_fn(move |mut request: hyper::Request<hyper::body::Incoming>| {
+                request.extensions_mut().insert(ConnectInfo(peer));
+                let guard = server.in_flight.enter();
+                activity.begin();
+                let activity = activity.clone();
+                let response = app.clone().call(request);
+                async move {
+                    let response = response.await;
+                    activity.end();
+                    drop(guard);
+                    response
+                }
+            })
+        };
+
+        let builder = hyper_util::server::conn::auto::Builder::new(TokioExecutor::new());
+        let connection = builder.serve_connection_with_upgrades(TokioIo::new(io), service);
+        tokio::pin!(connection);
+        let mut closing = false;
+        loop {
+            tokio::select! {
+                result = connection.as_mut() => {
+                    if let Err(e) = result {
+                        debug!("HTTP connection with {} closed: {}", peer, e);
+                    }
+                    return;
+                }
+                _ = activity.idle_expired(idle_timeout), if !closing => {
+                    debug!("Closing idle HTTP connection with {}", peer);
+                    connection.as_mut().graceful_shutdown();
+                    closing = true;
+                }
+                _ = shutdown.recv(), if !closing => {
+                    debug!("Closing HTTP connection with {} for shutdown", peer);
+                    connection.as_mut().graceful_shutdown();
+                    closing = true;
+                }
+            }
+        }
+    }
+
+    /// HTTP routes: management commands, GFEF Triple IP Lock endpoints and the WebSocket tunnel
+    fn http_router(server: Arc<ServerInternals>) -> Router {
+        Router::new()
+            .route("/", Self::command_route(ControlCommand::Health))
+            .route("/health", Self::command_route(ControlCommand::Health))
+            .route("/stats", Self::command_route(ControlCommand::Stats))
+            .route("/cascade", Self::command_route(ControlCommand::GetCascadeStats))
+            .route("/memory", Self::command_route(ControlCommand::GetMemoryStats))
+            .route("/memory/regions", Self::command_route(ControlCommand::GetRegionStats))
+            .route("/bandwidth", Self::command_route(ControlCommand::GetBandwidthStats))
+            .route("/cache/stats", Self::command_route(ControlCommand::CacheStats))
+            .route("/math", Self::command_route(ControlCommand::GetMathStats))
+            .route("/metrics", get(Self::http_metrics))
+            .route("/v1/command", post(Self::http_command))
+            .route("/ws", get(Self::http_websocket))
+            .route("/websocket", get(Self::http_websocket))
+            .route("/v1/health", get(Self::http_gfef_stats))
+            .merge(
+                Router::new()
+                    .route("/v1/indices/stats", get(Self::http_gfef_stats))
+                    .route("/v1/predict", get(Self::http_predict_info).post(Self::http_predict))
+                    .route("/v1/predict/batch", post(Self::http_predict_batch))
+                    .route("/v1/index/upload", post(Self::http_upload_index))
+                    .route("/v1/index/upload/init", post(Self::http_upload_init))
+                    .route("/v1/index/upload/:upload_id/chunk", post(Self::http_upload_chunk))
+                    .route("/v1/index/upload/:upload_id/commit", post(Self::http_upload_commit))
+                    .route_layer(middleware::from_fn_with_state(server.clone(), Self::require_bearer)),
+            )
+            .fallback(Self::http_fallback)
+            .layer(middleware::from_fn_with_state(server.clone(), Self::count_http_request))
+            .layer(CorsLayer::permissive())
+            .with_state(server)
+    }
+
+    /// GET route that runs a fixed control command
+    fn command_route(command: ControlCommand) -> MethodRouter<Arc<ServerInternals>> {
+        get(move |State(server): State<Arc<ServerInternals>>| {
+            let command = command.clone();
+            async move { Json(Self::process_command(command, server).await) }
+        })
+    }
+
+    /// Unknown paths answer with the health report
+    async fn http_fallback(State(server): State<Arc<ServerInternals>>) -> Json<ControlResponse> {
+        Json(Self::process_command(ControlCommand::Health, server).await)
+    }
 
-                        if total_read > 0 {
-                            let request = String::from_utf8_lossy(&buf[..total_read]);
-
-                            // Check if this is a WebSocket upgrade request
-                            if Self::is_websocket_upgrade(&request) {
-                                info!("🔌 WebSocket upgrade request from {}", peer);
-                                if let Err(e) = Self::handle_websocket(socket, &request, peer, server_clone).await {
-                                    warn!("WebSocket error from {}: {}", peer, e);
-                                }
-                            } else {
-                                // Regular HTTP request
-                                let response = Self::handle_http_request(&request, server_clone).await;
-
-                                let http_response = format!(
-                                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nAccess-Control-Allow-Origin: *\r\nContent-Length: {}\r\n\r\n{}",
-                                    response.len(),
-                                    response
-                                );
-
-                                let _ = tokio::io::AsyncWriteExt::write_all(&mut socket, http_response.as_bytes()).await;
-                                debug!("HTTP request from {} handled", peer);
-                            }
-                        }
-                    });
-                }
-                Err(e) => {
-                    error!("Error accepting HTTP connection: {}", e);
-                }
-            }
+    /// Count every HTTP request (including WebSocket upgrades)
+    async fn count_http_request(
+        State(server): State<Arc<ServerInternals>>,
+        request: Request,
+        next: Next,
+    ) -> Response {
+        server.stats.write().await.http_requests += 1;
+        next.run(request).await
+    }
+
+    /// Reject requests to protected GFEF routes without a valid bearer token
+    async fn require_bearer(
+        State(server): State<Arc<ServerInternals>>,
+        request: Request,
+        next: Next,
+    ) -> Response {
+        if server.auth.allows(bearer_token(request.headers())) {
+            next.run(request).await
+        } else {
+            (StatusCode::UNAUTHORIZED, Json(unauthorized_response())).into_response()
         }
     }
 
-    /// Check if request is a WebSocket upgrade
-    fn is_websocket_upgrade(request: &str) -> bool {
-        let lower = request.to_lowercase();
-        lower.contains("upgrade: websocket") && lower.contains("connection: upgrade")
+    /// GET /metrics - Prometheus scrape endpoint
+    async fn http_metrics(State(server): State<Arc<ServerInternals>>) -> Response {
+        let body = server.stats.read().await.to_prometheus();
+        ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], body).into_response()
+    }
+
+    /// POST /v1/command - run any control command
+    async fn http_command(
+        State(server): State<Arc<ServerInternals>>,
+        headers: HeaderMap,
+        Json(command): Json<ControlCommand>,
+    ) -> (StatusCode, Json<ControlResponse>) {
+        if command.requires_auth() && !server.auth.allows(bearer_token(&headers)) {
+            return (StatusCode::UNAUTHORIZED, Json(unauthorized_response()));
+        }
+        (StatusCode::OK, Json(Self::process_command(command, server).await))
     }
 
-    /// Extract Content-Length header value from HTTP request
-    fn extract_content_length(request: &str) -> Option<usize> {
-        for line in request.lines() {
-            let lower = line.to_lowercase();
-            if lower.starts_with("content-length:") {
-                return line.split(':')
-                    .nth(1)
-                    .and_then(|v| v.trim().parse().ok());
+    /// WebSocket upgrade, or usage information for plain GETs
+    async fn http_websocket(
+        State(server): State<Arc<ServerInternals>>,
+        connect_info: Option<ConnectInfo<SocketAddr>>,
+        headers: HeaderMap,
+        ws: Option<WebSocketUpgrade>,
+    ) -> Response {
+        let peer = connect_info.map(|ConnectInfo(peer)| peer);
+        // A bearer token on the upgrade request authenticates the whole session
+        let session_token = bearer_token(&headers).map(str::to_string);
+        match ws {
+            Some(ws) => {
+                info!("🔌 WebSocket upgrade request from {:?}", peer);
+                ws.on_upgrade(move |socket| async move {
+                    if let Err(e) = Self::handle_websocket(socket, peer, session_token, server).await {
+                        warn!("WebSocket error from {:?}: {}", peer, e);
+                    }
+                })
             }
+            None => Json(serde_json::json!({
+                "success": true,
+                "message": "WebSocket endpoint - use WebSocket protocol to connect",
+                "data": {
+                    "endpoint": "/ws",
+                    "protocol": "wss",
+                    "url": "wss://vxlan-control-plane.onrender.com/ws",
+                    "usage": "Connect with WebSocket client, send JSON commands",
+                    "example_command": {"cmd": "Health"},
+                    "supported_commands": [
+                        "Health", "Stats", "GetCascadeStats", "GetMemoryStats",
+                        "GetBandwidthStats", "CacheStats", "CacheGet", "CacheSet",
+                        "CacheDelete", "CacheIncr", "CacheTtl", "CacheExpire",
+                        "ListPush", "ListRange", "HashSet", "HashGet",
+                        "AllocateMemory", "FreeMemory", "GetRegionStats",
+                        "OptimizeBandwidth", "Lookup", "InsertRoute", "DeleteRoute"
+                    ]
+                }
+            })).into_response(),
         }
-        None
     }
 
     /// Handle WebSocket connection for VXLAN tunnel emulation
     async fn handle_websocket(
-        socket: tokio::net::TcpStream,
-        request: &str,
-        peer: SocketAddr,
+        socket: WebSocket,
+        peer: Option<SocketAddr>,
+        session_token: Option<String>,
         server: Arc<ServerInternals>,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        use sha1::{Sha1, Digest};
-        use base64::Engine;
-
-        // Extract Sec-WebSocket-Key
-        let ws_key = request.lines()
-            .find(|line| line.to_lowercase().starts_with("sec-websocket-key:"))
-            .and_then(|line| line.split(':').nth(1))
-            .map(|s| s.trim())
-            .ok_or("Missing Sec-WebSocket-Key")?;
-
-        // Generate accept key
-        let magic = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
-        let combined = format!("{}{}", ws_key, magic);
-        let mut hasher = Sha1::new();
-        hasher.update(combined.as_bytes());
-        let hash = hasher.finalize();
-        let accept_key = base64::engine::general_purpose::STANDARD.encode(hash);
-
-        // Send WebSocket handshake response
-        let response = format!(
-            "HTTP/1.1 101 Switching Protocols\r\n\
-             Upgrade: websocket\r\n\
-             Connection: Upgrade\r\n\
-             Sec-WebSocket-Accept: {}\r\n\r\n",
-            accept_key
-        );
-
-        let mut socket = socket;
-        tokio::io::AsyncWriteExt::write_all(&mut socket, response.as_bytes()).await?;
-
-        info!("✅ WebSocket connection established with {}", peer);
-
-        // Use tokio-tungstenite for WebSocket framing
-        let ws_stream = tokio_tungstenite::WebSocketStream::from_raw_socket(
-            socket,
-            tokio_tungstenite::tungstenite::protocol::Role::Server,
-            None,
-        ).await;
+        info!("✅ WebSocket connection established with {:?}", peer);
 
-        let (mut ws_sender, mut ws_receiver) = ws_stream.split();
+        let _guard = server.in_flight.enter();
+        let mut shutdown = server.shutdown.subscribe();
+        let (mut ws_sender, mut ws_receiver) = socket.split();
 
         // Send welcome message
         let welcome = serde_json::json!({
@@ -607,42 +2145,63 @@ impl ControlPlaneServer {
         });
         ws_sender.send(Message::Text(serde_json::to_string(&welcome)?)).await?;
 
-        // Track connection in stats
-        {
-            let mut stats = server.stats.write().await;
-            stats.http_requests += 1;
-        }
+        // Ping the peer periodically and drop it once it goes silent
+        let keepalive = server.ws_keepalive;
+        let mut ping = keepalive.ping_interval
+            .map(|period| tokio::time::interval_at(tokio::time::Instant::now() + period, period));
+        let mut last_seen = tokio::time::Instant::now();
+
+        // Process incoming messages until the peer leaves or the server stops
+        loop {
+            let msg = tokio::select! {
+                msg = ws_receiver.next() => match msg {
+                    Some(msg) => {
+                        last_seen = tokio::time::Instant::now();
+                        msg
+                    }
+                    None => break,
+                },
+                _ = WsKeepalive::next_ping(&mut ping) => {
+                    ws_sender.send(Message::Ping(Vec::new())).await?;
+                    continue;
+                }
+                _ = keepalive.idle_expired(last_seen) => {
+                    info!("Closing idle WebSocket with {:?}", peer);
+                    server.stats.write().await.ws_connections_reaped += 1;
+                    // The peer is likely gone, so only wait for its Close if our Close got out
+                    if ws_sender.send(Message::Close(Some(CloseFrame {
+                        code: close_code::AWAY,
+                        reason: "idle timeout".into(),
+                    }))).await.is_ok() {
+                        Self::await_ws_close(&mut ws_receiver).await;
+                    }
+                    break;
+                }
+                _ = shutdown.recv() => {
+                    info!("Closing WebSocket with {:?} for shutdown", peer);
+                    ws_sender.send(Message::Close(Some(CloseFrame {
+                        code: close_code::AWAY,
+                        reason: "server shutting down".into(),
+                    }))).await?;
+                    Self::await_ws_close(&mut ws_receiver).await;
+                    break;
+                }
+            };
 
-        // Process incoming messages
-        while let Some(msg) = ws_receiver.next().await {
             match msg {
                 Ok(Message::Text(text)) => {
-                    debug!("WebSocket message from {}: {}", peer, text);
-
-                    // Parse command
-                    match serde_json::from_str::<ControlCommand>(&text) {
-                        Ok(command) => {
-                            let response = Self::process_command(command, server.clone()).await;
-                            let response_json = serde_json::to_string(&response)?;
-                            ws_sender.send(Message::Text(response_json)).await?;
-                        }
-                        Err(e) => {
-                            let error_response = ControlResponse {
-                                success: false,
-                                message: format!("Invalid command: {}", e),
-                                data: None,
-                                latency_ns: 0,
-                            };
-                            ws_sender.send(Message::Text(serde_json::to_string(&error_response)?)).await?;
-                        }
-                    }
+                    debug!("WebSocket message from {:?}: {}", peer, text);
+
+                    let reply = Self::process_ws_text(&text, peer, session_token.as_deref(), server.clone()).await;
+                    ws_sender.send(Message::Text(reply)).await?;
                 }
                 Ok(Message::Binary(data)) => {
                     // Handle binary VXLAN-like packets
-                    if data.len() > 8 {
-                        let payload = &data[8..]; // Skip VXLAN header
-                        if let Ok(command) = serde_json::from_slice::<ControlCommand>(payload) {
-                            let response = Self::process_command(command, server.clone()).await;
+                    if data.len() > VXLAN_HEADER_SIZE {
+                        let payload = &data[VXLAN_HEADER_SIZE..]; // Skip VXLAN header
+                        if let Ok((command, token)) = parse_command(payload) {
+                            let token = token.as_deref().or(session_token.as_deref());
+                            let response = Self::process_from(peer, command, token, server.clone()).await;
                             let response_json = serde_json::to_vec(&response)?;
                             ws_sender.send(Message::Binary(response_json)).await?;
                         }
@@ -652,279 +2211,540 @@ impl ControlPlaneServer {
                     ws_sender.send(Message::Pong(data)).await?;
                 }
                 Ok(Message::Close(_)) => {
-                    info!("WebSocket connection closed by {}", peer);
+                    info!("WebSocket connection closed by {:?}", peer);
                     break;
                 }
                 Err(e) => {
-                    warn!("WebSocket error from {}: {}", peer, e);
+                    warn!("WebSocket error from {:?}: {}", peer, e);
                     break;
                 }
                 _ => {}
             }
         }
 
-        info!("WebSocket connection ended with {}", peer);
+        info!("WebSocket connection ended with {:?}", peer);
         Ok(())
     }
 
-    /// Handle HTTP request
-    async fn handle_http_request(request: &str, server: Arc<ServerInternals>) -> String {
-        // Update stats
-        {
-            let mut stats = server.stats.write().await;
-            stats.http_requests += 1;
-        }
-
-        // Parse HTTP request
-        let path = request.lines().next()
-            .and_then(|line| line.split_whitespace().nth(1))
-            .unwrap_or("/");
+    /// Wait briefly for the peer's Close so the closing handshake completes
+    ///
+    /// Reading until the Close also drains frames the peer sent meanwhile, so
+    /// dropping the socket afterwards does not reset the connection.
+    async fn await_ws_close(ws_receiver: &mut (impl futures_util::Stream<Item = Result<Message, axum::Error>> + Unpin)) {
+        let _ = tokio::time::timeout(WS_CLOSE_TIMEOUT, async {
+            while let Some(Ok(msg)) = ws_receiver.next().await {
+                if matches!(msg, Message::Close(_)) {
+                    break;
+                }
+            }
+        }).await;
+    }
 
-        // Handle special WebSocket info endpoint
-        if path == "/ws" || path == "/websocket" {
-            return serde_json::to_string_pretty(&serde_json::json!({
-                "success": true,
-                "message": "WebSocket endpoint - use WebSocket protocol to connect",
-                "data": {
-                    "endpoint": "/ws",
-                    "protocol": "wss",
-                    "url": "wss://vxlan-control-plane.onrender.com/ws",
-                    "usage": "Connect with WebSocket client, send JSON commands",
-                    "example_command": {"cmd": "Health"},
-                    "supported_commands": [
-                        "Health", "Stats", "GetCascadeStats", "GetMemoryStats",
-                        "GetBandwidthStats", "CacheStats", "CacheGet", "CacheSet",
-                        "CacheDelete", "CacheIncr", "AllocateMemory", "FreeMemory",
-                        "OptimizeBandwidth", "Lookup", "InsertRoute", "DeleteRoute"
-                    ]
+    /// Run a WebSocket text frame: one command object, or a JSON array batch
+    ///
+    /// Batches run in order and are answered with an array of responses in
+    /// the same order; a malformed entry gets an error response in its slot.
+    async fn process_ws_text(
+        text: &str,
+        peer: Option<SocketAddr>,
+        session_token: Option<&str>,
+        server: Arc<ServerInternals>,
+    ) -> String {
+        let reply = if text.trim_start().starts_with('[') {
+            match serde_json::from_str::<Vec<serde_json::Value>>(text) {
+                Ok(batch) => {
+                    let mut responses = Vec::with_capacity(batch.len());
+                    for value in batch {
+                        responses.push(match command_from_value(value) {
+                            Ok((command, token)) => {
+                                let token = token.as_deref().or(session_token);
+                                Self::process_from(peer, command, token, server.clone()).await
+                            }
+                            Err(e) => invalid_command_response(&e),
+                        });
+                    }
+                    serde_json::to_string(&responses)
+                }
+                Err(e) => serde_json::to_string(&invalid_command_response(&e)),
+            }
+        } else {
+            match parse_command(text.as_bytes()) {
+                Ok((command, token)) => {
+                    let token = token.as_deref().or(session_token);
+                    serde_json::to_string(&Self::process_from(peer, command, token, server).await)
                 }
-            })).unwrap_or_else(|_| "{}".to_string());
+                Err(e) => serde_json::to_string(&invalid_command_response(&e)),
+            }
+        };
+        reply.unwrap_or_else(|_| "{}".to_string())
+    }
+
+    /// GET /v1/indices/stats, /v1/health - Triple IP Lock status
+    async fn http_gfef_stats(State(server): State<Arc<ServerInternals>>) -> Json<serde_json::Value> {
+        let stats = server.gfef_predictor.read().await.stats();
+        Json(serde_json::json!({
+            "success": true,
+            "service": "NULL SPACE AI Control Plane",
+            "version": "1.0.0",
+            "triple_ip_lock_status": {
+                "lock1_gfef_index": if stats.models_loaded > 0 { "SECURED" } else { "NOT_LOADED" },
+                "lock2_calibration": "ROTATING",
+                "lock3_prediction_service": "ACTIVE"
+            },
+            "gfef_stats": {
+                "models_loaded": stats.models_loaded,
+                "total_neurons": stats.total_neurons,
+                "total_layers": stats.total_layers,
+                "target_sparsity": stats.target_sparsity,
+                "sparsity_percentage": format!("{:.1}%", stats.target_sparsity * 100.0),
+                "active_neurons_per_inference": format!("{:.1}%", (1.0 - stats.target_sparsity) * 100.0)
+            },
+            "endpoints": {
+                "predict": "POST /v1/predict",
+                "predict_batch": "POST /v1/predict/batch",
+                "upload_index": "POST /v1/index/upload",
+                "upload_index_chunked": "POST /v1/index/upload/init, /v1/index/upload/{id}/chunk, /v1/index/upload/{id}/commit",
+                "stats": "GET /v1/indices/stats"
+            }
+        }))
+    }
+
+    /// GET /v1/predict - prediction service readiness and usage
+    async fn http_predict_info(State(server): State<Arc<ServerInternals>>) -> Json<serde_json::Value> {
+        let stats = server.gfef_predictor.read().await.stats();
+
+        if stats.models_loaded == 0 {
+            return Json(serde_json::json!({
+                "success": false,
+                "error": "NO_INDEX_LOADED",
+                "message": "No GFEF index loaded. Upload index via POST /v1/index/upload first.",
+                "triple_ip_lock_status": "INACTIVE"
+            }));
         }
 
-        // Handle GFEF Triple IP Lock endpoints
-        if path == "/v1/indices/stats" || path == "/v1/health" {
-            let predictor = server.gfef_predictor.read().await;
-            let stats = predictor.stats();
-            return serde_json::to_string_pretty(&serde_json::json!({
-                "success": true,
-                "service": "NULL SPACE AI Control Plane",
-                "version": "1.0.0",
-                "triple_ip_lock_status": {
-                    "lock1_gfef_index": if stats.models_loaded > 0 { "SECURED" } else { "NOT_LOADED" },
-                    "lock2_calibration": "ROTATING",
-                    "lock3_prediction_service": "ACTIVE"
-                },
-                "gfef_stats": {
-                    "models_loaded": stats.models_loaded,
-                    "total_neurons": stats.total_neurons,
-                    "total_layers": stats.total_layers,
-                    "target_sparsity": stats.target_sparsity,
-                    "sparsity_percentage": format!("{:.1}%", stats.target_sparsity * 100.0),
-                    "active_neurons_per_inference": format!("{:.1}%", (1.0 - stats.target_sparsity) * 100.0)
-                },
-                "endpoints": {
-                    "predict": "POST /v1/predict",
-                    "upload_index": "POST /v1/index/upload",
-                    "stats": "GET /v1/indices/stats"
+        Json(serde_json::json!({
+            "success": true,
+            "message": "GFEF Prediction Service Ready",
+            "triple_ip_lock_status": "ACTIVE",
+            "models_loaded": stats.models_loaded,
+            "total_neurons": stats.total_neurons,
+            "usage": {
+                "method": "POST",
+                "body": {
+                    "session_token": "your_auth_token",
+                    "customer_id": "uuid",
+                    "model_id": "Qwen3-MoE-Coder",
+                    "layer_id": 0,
+                    "input_embedding_hash": "hash_of_input"
                 }
-            })).unwrap_or_else(|_| "{}".to_string());
-        }
-
-        // Handle POST /v1/predict for activation prediction
-        if path == "/v1/predict" {
-            // For now, return info about how to use the endpoint
-            let predictor = server.gfef_predictor.read().await;
-            let stats = predictor.stats();
-
-            if stats.models_loaded == 0 {
-                return serde_json::to_string_pretty(&serde_json::json!({
-                    "success": false,
-                    "error": "NO_INDEX_LOADED",
-                    "message": "No GFEF index loaded. Upload index via POST /v1/index/upload first.",
-                    "triple_ip_lock_status": "INACTIVE"
-                })).unwrap_or_else(|_| "{}".to_string());
-            }
-
-            // Parse body from request for actual prediction
-            let body = request.split("\r\n\r\n").nth(1).unwrap_or("");
-            if body.is_empty() {
-                return serde_json::to_string_pretty(&serde_json::json!({
-                    "success": true,
-                    "message": "GFEF Prediction Service Ready",
-                    "triple_ip_lock_status": "ACTIVE",
-                    "models_loaded": stats.models_loaded,
-                    "total_neurons": stats.total_neurons,
-                    "usage": {
-                        "method": "POST",
-                        "body": {
-                            "session_token": "your_auth_token",
-                            "customer_id": "uuid",
-                            "model_id": "Qwen3-MoE-Coder",
-                            "layer_id": 0,
-                            "input_embedding_hash": "hash_of_input"
-                        }
-                    }
-                })).unwrap_or_else(|_| "{}".to_string());
             }
+        }))
+    }
 
-            // Actual prediction logic (simplified for demo)
-            return serde_json::to_string_pretty(&serde_json::json!({
-                "success": true,
-                "service": "GFEF Activation Prediction",
-                "triple_ip_lock_status": "ACTIVE",
-                "prediction": {
-                    "request_id": uuid::Uuid::new_v4().to_string(),
-                    "active_neurons_count": ((1.0 - stats.target_sparsity) * stats.total_neurons as f32) as u64,
-                    "sparsity_achieved": format!("{:.1}%", stats.target_sparsity * 100.0),
-                    "weight_reduction": "19.6×",
-                    "message": "5% of neurons predicted to activate"
-                }
-            })).unwrap_or_else(|_| "{}".to_string());
-        }
-
-        // Handle POST /v1/index/upload for GFEF index upload
-        if path == "/v1/index/upload" {
-            let body = request.split("\r\n\r\n").nth(1).unwrap_or("");
-            if body.is_empty() {
-                return serde_json::to_string_pretty(&serde_json::json!({
-                    "success": false,
-                    "error": "EMPTY_BODY",
-                    "message": "Request body is empty. Send GFEF index JSON."
-                })).unwrap_or_else(|_| "{}".to_string());
-            }
-
-            // Parse the index JSON
-            match serde_json::from_str::<serde_json::Value>(body) {
-                Ok(index_json) => {
-                    let model_name = index_json.get("model")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("unknown");
-                    let total_neurons = index_json.get("total_neurons")
-                        .and_then(|v| v.as_u64())
-                        .unwrap_or(0);
-                    let num_layers = index_json.get("num_layers")
-                        .and_then(|v| v.as_u64())
-                        .unwrap_or(0);
-                    let k_components = index_json.get("k_components")
-                        .and_then(|v| v.as_u64())
-                        .unwrap_or(32);
-                    let layers = index_json.get("layers")
-                        .and_then(|v| v.as_array())
-                        .map(|a| a.len())
-                        .unwrap_or(0);
-
-                    info!("🔐 Receiving GFEF index upload for model: {}", model_name);
-                    info!("   Total Neurons: {}", total_neurons);
-                    info!("   Layers: {} (metadata entries: {})", num_layers, layers);
-                    info!("   K-Components: {}", k_components);
-
-                    // Create LayerIndex entries from the JSON
-                    let layer_indices: Vec<LayerIndex> = index_json.get("layers")
-                        .and_then(|v| v.as_array())
-                        .map(|arr| {
-                            arr.iter().map(|l| {
-                                LayerIndex {
-                                    layer_id: l.get("layer_id").and_then(|v| v.as_u64()).unwrap_or(0) as u32,
-                                    layer_name: l.get("name").and_then(|v| v.as_str()).unwrap_or("").to_string(),
-                                    num_neurons: l.get("neurons").and_then(|v| v.as_u64()).unwrap_or(0) as u32,
-                                    input_dim: l.get("pc_shape").and_then(|v| v.as_array()).and_then(|a| a.get(0)).and_then(|v| v.as_u64()).unwrap_or(0) as u32,
-                                    k_components: k_components as u32,
-                                    principal_components: Vec::new(),
-                                    signatures: Vec::new(),
-                                }
-                            }).collect()
-                        })
-                        .unwrap_or_default();
-
-                    // Create IndexConfig
-                    let config = IndexConfig {
-                        k_components: k_components as u32,
-                        fft_bins: index_json.get("fft_bins").and_then(|v| v.as_u64()).unwrap_or(16) as u32,
-                        target_sparsity: 0.95,
-                    };
+    /// POST /v1/predict - activation prediction for a subscribed customer
+    ///
+    /// The body must be a `PredictionRequest`; anything else is a 400 with
+    /// `error: "INVALID_REQUEST"` and the deserialization error as `message`.
+    async fn http_predict(
+        State(server): State<Arc<ServerInternals>>,
+        request: Result<Json<PredictionRequest>, JsonRejection>,
+    ) -> Result<Json<PredictionResponse>, (StatusCode, Json<serde_json::Value>)> {
+        let Json(request) = request.map_err(invalid_prediction_body)?;
+        let error = |status: StatusCode, message: String| {
+            (status, Json(serde_json::json!({ "success": false, "error": message })))
+        };
 
-                    // Create GFEFIndex
-                    let index = GFEFIndex {
-                        id: uuid::Uuid::new_v4(),
-                        customer_id: uuid::Uuid::nil(),
-                        model_id: model_name.to_string(),
-                        model_name: model_name.to_string(),
-                        generated_at: chrono::Utc::now(),
-                        expires_at: None,
-                        layers: layer_indices,
-                        total_neurons,
-                        config,
-                    };
+        let subscription = server.gfef_subscriptions.read().await
+            .validate_access(&request.customer_id)
+            .cloned()
+            .map_err(|e| error(subscription_error_status(&e), e.to_string()))?;
 
-                    let index_id = index.id;
-                    let num_layers_registered = index.layers.len();
+        let calibration = server.gfef_calibration.get_matrix();
+        let response = server.gfef_predictor.read().await
+            .predict(&request, &subscription, &calibration)
+            .map_err(|e| error(prediction_error_status(&e), e.to_string()))?;
 
-                    // Register with predictor
-                    {
-                        let mut predictor = server.gfef_predictor.write().await;
-                        predictor.register_index(index);
-                    }
+        // Only successful predictions count against the quota
+        server.gfef_subscriptions.write().await
+            .record_prediction(&request.customer_id)
+            .map_err(|e| error(subscription_error_status(&e), e.to_string()))?;
 
-                    info!("🔒 TRIPLE IP LOCK ACTIVE - Index secured on Control Plane");
-                    info!("   Lock 1: GFEF Index (SECURED) - {} neurons", total_neurons);
-                    info!("   Lock 2: Calibration Matrix (rotating every 60s)");
-                    info!("   Lock 3: Activation Prediction Service (real-time oracle)");
-
-                    let predictor = server.gfef_predictor.read().await;
-                    let stats = predictor.stats();
-
-                    return serde_json::to_string_pretty(&serde_json::json!({
-                        "success": true,
-                        "message": "🔐 GFEF Index uploaded and secured on Control Plane",
-                        "index_id": index_id.to_string(),
-                        "model_id": model_name,
-                        "total_neurons": total_neurons,
-                        "num_layers": num_layers_registered,
-                        "triple_ip_lock_status": {
-                            "lock1_gfef_index": "SECURED",
-                            "lock2_calibration": "ROTATING",
-                            "lock3_prediction_service": "ACTIVE"
-                        },
-                        "predictor_stats": {
-                            "models_loaded": stats.models_loaded,
-                            "total_neurons": stats.total_neurons,
-                            "total_layers": stats.total_layers,
-                            "target_sparsity": format!("{:.1}%", stats.target_sparsity * 100.0)
-                        }
-                    })).unwrap_or_else(|_| "{}".to_string());
-                }
-                Err(e) => {
-                    return serde_json::to_string_pretty(&serde_json::json!({
-                        "success": false,
-                        "error": "INVALID_JSON",
-                        "message": format!("Failed to parse index JSON: {}", e)
-                    })).unwrap_or_else(|_| "{}".to_string());
+        Ok(Json(response))
+    }
+
+    /// POST /v1/predict/batch - activation predictions for several layers
+    ///
+    /// Every request must name the same customer. The subscription and
+    /// calibration matrix are looked up once; each element of the reply is
+    /// `{"success": true, "prediction": ...}` or `{"success": false, "error": ...}`.
+    async fn http_predict_batch(
+        State(server): State<Arc<ServerInternals>>,
+        requests: Result<Json<Vec<PredictionRequest>>, JsonRejection>,
+    ) -> Result<Json<Vec<serde_json::Value>>, (StatusCode, Json<serde_json::Value>)> {
+        let Json(requests) = requests.map_err(invalid_prediction_body)?;
+        let error = |status: StatusCode, message: String| {
+            (status, Json(serde_json::json!({ "success": false, "error": message })))
+        };
+
+        let customer_id = match requests.first() {
+            Some(request) => request.customer_id,
+            None => return Ok(Json(Vec::new())),
+        };
+        if requests.iter().any(|request| request.customer_id != customer_id) {
+            return Err(error(StatusCode::BAD_REQUEST, "All batch requests must share a customer_id".to_string()));
+        }
+
+        let subscription = server.gfef_subscriptions.read().await
+            .validate_access(&customer_id)
+            .cloned()
+            .map_err(|e| error(subscription_error_status(&e), e.to_string()))?;
+
+        let calibration = server.gfef_calibration.get_matrix();
+        let results = server.gfef_predictor.read().await
+            .predict_batch(&requests, &subscription, &calibration);
+
+        // Only successful predictions count against the quota
+        let mut subscriptions = server.gfef_subscriptions.write().await;
+        let replies = results.into_iter()
+            .map(|result| {
+                let recorded = result.map_err(|e| e.to_string()).and_then(|response| {
+                    subscriptions.record_prediction(&customer_id)
+                        .map(|()| response)
+                        .map_err(|e| e.to_string())
+                });
+                match recorded {
+                    Ok(response) => serde_json::json!({ "success": true, "prediction": response }),
+                    Err(message) => serde_json::json!({ "success": false, "error": message }),
                 }
+            })
+            .collect();
+
+        Ok(Json(replies))
+    }
+
+    /// POST /v1/index/upload - receive a GFEF index from the Extractor
+    async fn http_upload_index(
+        State(server): State<Arc<ServerInternals>>,
+        Json(index_json): Json<serde_json::Value>,
+    ) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+        let index = GFEFIndex::from_python_metadata(&index_json).map_err(|e| {
+            upload_error(StatusCode::BAD_REQUEST, "INVALID_INDEX", format!("Failed to parse index JSON: {}", e))
+        })?;
+
+        Ok(Self::register_uploaded_index(index, server).await)
+    }
+
+    /// POST /v1/index/upload/init - start a chunked upload
+    ///
+    /// The body is the index metadata without its `layers`, which follow in
+    /// `chunk` requests.
+    async fn http_upload_init(
+        State(server): State<Arc<ServerInternals>>,
+        Json(mut metadata): Json<serde_json::Value>,
+    ) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+        let fields = metadata.as_object_mut().ok_or_else(|| {
+            upload_error(StatusCode::BAD_REQUEST, "INVALID_INDEX", "Index metadata must be a JSON object".to_string())
+        })?;
+        fields.remove("layers");
+
+        let upload_id = uuid::Uuid::new_v4();
+        let spool = std::env::temp_dir().join(format!("gfef-upload-{}.jsonl", upload_id));
+        tokio::fs::File::create(&spool).await.map_err(|e| {
+            upload_error(StatusCode::INTERNAL_SERVER_ERROR, "UPLOAD_FAILED", format!("Failed to create upload spool: {}", e))
+        })?;
+
+        let uploads = &server.index_uploads;
+        let admitted = {
+            let mut pending = uploads.lock();
+            let admitted = pending.len() < uploads.max_pending;
+            if admitted {
+                pending.insert(upload_id, PendingUpload {
+                    metadata,
+                    spool: spool.clone(),
+                    chunks: 0,
+                    layers: 0,
+                    bytes: 0,
+                    touched_at: Instant::now(),
+                    busy: false,
+                });
+            }
+            admitted
+        };
+        if !admitted {
+            let _ = tokio::fs::remove_file(&spool).await;
+            return Err(upload_error(
+                StatusCode::TOO_MANY_REQUESTS,
+                "TOO_MANY_UPLOADS",
+                format!("{} chunked uploads already in progress", uploads.max_pending),
+            ));
+        }
+        info!("🔐 Started chunked GFEF index upload {}", upload_id);
+
+        Ok(Json(serde_json::json!({
+            "success": true,
+            "upload_id": upload_id.to_string(),
+        })))
+    }
+
+    /// POST /v1/index/upload/{id}/chunk - append `{"layers": [...]}` to the spool
+    ///
+    /// A chunk that would take the spool past the per-upload byte cap, or
+    /// that fails to write, aborts the whole upload.
+    async fn http_upload_chunk(
+        State(server): State<Arc<ServerInternals>>,
+        Path(upload_id): Path<uuid::Uuid>,
+        Json(chunk): Json<serde_json::Value>,
+    ) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+        let layers = chunk["layers"].as_array().ok_or_else(|| {
+            upload_error(StatusCode::BAD_REQUEST, "INVALID_CHUNK", "Chunk must carry a layers array".to_string())
+        })?;
+
+        // One layer per line; layer objects never contain raw newlines
+        let mut lines = String::new();
+        for layer in layers {
+            lines.push_str(&layer.to_string());
+            lines.push('\n');
+        }
+
+        // Reserve the bytes and mark the upload busy, then write unlocked
+        let uploads = &server.index_uploads;
+        let spool = {
+            let mut pending = uploads.lock();
+            let upload = match pending.get_mut(&upload_id) {
+                Some(upload) => upload,
+                None => return Err(upload_error(
+                    StatusCode::NOT_FOUND,
+                    "UNKNOWN_UPLOAD",
+                    format!("No upload in progress with id {}", upload_id),
+                )),
+            };
+            if upload.busy {
+                return Err(upload_error(
+                    StatusCode::CONFLICT,
+                    "UPLOAD_BUSY",
+                    format!("Upload {} is already taking a chunk", upload_id),
+                ));
+            }
+            if upload.bytes + lines.len() as u64 > uploads.max_bytes {
+                None
+            } else {
+                upload.busy = true;
+                upload.bytes += lines.len() as u64;
+                Some(upload.spool.clone())
             }
+        };
+        let spool = match spool {
+            Some(spool) => spool,
+            None => {
+                uploads.abort(&upload_id).await;
+                return Err(upload_error(
+                    StatusCode::PAYLOAD_TOO_LARGE,
+                    "UPLOAD_TOO_LARGE",
+                    format!("Upload {} exceeds {} bytes and was aborted", upload_id, uploads.max_bytes),
+                ));
+            }
+        };
+
+        let written = async {
+            use tokio::io::AsyncWriteExt;
+
+            let mut file = tokio::fs::OpenOptions::new().append(true).open(&spool).await?;
+            file.write_all(lines.as_bytes()).await?;
+            file.flush().await
+        }.await;
+        if let Err(e) = written {
+            uploads.abort(&upload_id).await;
+            return Err(upload_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "UPLOAD_FAILED",
+                format!("Failed to write upload spool: {}", e),
+            ));
         }
 
-        let command = match path {
-            "/" | "/health" => ControlCommand::Health,
-            "/stats" => ControlCommand::Stats,
-            "/cascade" => ControlCommand::GetCascadeStats,
-            "/memory" => ControlCommand::GetMemoryStats,
-            "/bandwidth" => ControlCommand::GetBandwidthStats,
-            "/cache/stats" => ControlCommand::CacheStats,
-            _ => ControlCommand::Health,
+        let mut pending = uploads.lock();
+        let upload = match pending.get_mut(&upload_id) {
+            Some(upload) => upload,
+            None => return Err(upload_error(
+                StatusCode::NOT_FOUND,
+                "UNKNOWN_UPLOAD",
+                format!("No upload in progress with id {}", upload_id),
+            )),
         };
+        upload.busy = false;
+        upload.touched_at = Instant::now();
+        upload.chunks += 1;
+        upload.layers += layers.len();
+
+        Ok(Json(serde_json::json!({
+            "success": true,
+            "upload_id": upload_id.to_string(),
+            "chunks": upload.chunks,
+            "layers_received": upload.layers,
+        })))
+    }
+
+    /// POST /v1/index/upload/{id}/commit - assemble the spooled layers and register the index
+    async fn http_upload_commit(
+        State(server): State<Arc<ServerInternals>>,
+        Path(upload_id): Path<uuid::Uuid>,
+    ) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+        let upload = {
+            let mut pending = server.index_uploads.lock();
+            match pending.remove(&upload_id) {
+                None => return Err(upload_error(
+                    StatusCode::NOT_FOUND,
+                    "UNKNOWN_UPLOAD",
+                    format!("No upload in progress with id {}", upload_id),
+                )),
+                Some(upload) if upload.busy => {
+                    pending.insert(upload_id, upload);
+                    return Err(upload_error(
+                        StatusCode::CONFLICT,
+                        "UPLOAD_BUSY",
+                        format!("Upload {} is still taking a chunk", upload_id),
+                    ));
+                }
+                Some(upload) => upload,
+            }
+        };
+
+        // Parse the spool a line at a time so only the (component-free)
+        // layer shapes are ever held in memory
+        let k_components = GFEFIndex::python_k_components(&upload.metadata);
+        let spool = upload.spool.clone();
+        let layers = tokio::task::spawn_blocking(move || {
+            let layers = Self::read_spooled_layers(&spool, k_components);
+            let _ = std::fs::remove_file(&spool);
+            layers
+        })
+        .await
+        .map_err(|e| {
+            upload_error(StatusCode::INTERNAL_SERVER_ERROR, "UPLOAD_FAILED", format!("Upload spool reader failed: {}", e))
+        })??;
+
+        let index = GFEFIndex::from_python_layers(&upload.metadata, layers).map_err(|e| {
+            upload_error(StatusCode::BAD_REQUEST, "INVALID_INDEX", format!("Failed to assemble index: {}", e))
+        })?;
+        info!("🔐 Committing chunked GFEF index upload {} ({} chunks)", upload_id, upload.chunks);
+
+        Ok(Self::register_uploaded_index(index, server).await)
+    }
+
+    /// Parse every layer spooled by `http_upload_chunk`, one line at a time
+    fn read_spooled_layers(
+        spool: &std::path::Path,
+        k_components: u32,
+    ) -> Result<Vec<LayerIndex>, (StatusCode, Json<serde_json::Value>)> {
+        use std::io::BufRead;
+
+        let file = std::fs::File::open(spool).map_err(|e| {
+            upload_error(StatusCode::INTERNAL_SERVER_ERROR, "UPLOAD_FAILED", format!("Failed to read upload spool: {}", e))
+        })?;
+        let mut layers = Vec::new();
+        for line in std::io::BufReader::new(file).lines() {
+            let line = line.map_err(|e| {
+                upload_error(StatusCode::INTERNAL_SERVER_ERROR, "UPLOAD_FAILED", format!("Failed to read upload spool: {}", e))
+            })?;
+            let layer_raw: serde_json::Value = serde_json::from_str(&line).map_err(|e| {
+                upload_error(StatusCode::INTERNAL_SERVER_ERROR, "UPLOAD_FAILED", format!("Corrupt upload spool: {}", e))
+            })?;
+            let layer = LayerIndex::from_python_metadata(&layer_raw, k_components).map_err(|e| {
+                upload_error(StatusCode::BAD_REQUEST, "INVALID_INDEX", format!("Failed to assemble index: {}", e))
+            })?;
+            layers.push(layer);
+        }
+        Ok(layers)
+    }
+
+    /// Register an uploaded index with the predictor and report the lock status
+    async fn register_uploaded_index(index: GFEFIndex, server: Arc<ServerInternals>) -> Json<serde_json::Value> {
+        let model_name = index.model_name.clone();
+        let total_neurons = index.total_neurons;
+        let index_id = index.id;
+        let num_layers_registered = index.layers.len();
+
+        info!("🔐 Receiving GFEF index upload for model: {}", model_name);
+        info!("   Total Neurons: {}", total_neurons);
+        info!("   Layers: {}", num_layers_registered);
+        info!("   K-Components: {}", index.config.k_components);
+
+        // Persist, then register with predictor
+        persist_gfef_index(&server.gfef_storage, &index).await;
+        {
+            let mut predictor = server.gfef_predictor.write().await;
+            predictor.register_index(index);
+        }
+
+        info!("🔒 TRIPLE IP LOCK ACTIVE - Index secured on Control Plane");
+        info!("   Lock 1: GFEF Index (SECURED) - {} neurons", total_neurons);
+        info!("   Lock 2: Calibration Matrix (rotating every 60s)");
+        info!("   Lock 3: Activation Prediction Service (real-time oracle)");
+
+        let stats = server.gfef_predictor.read().await.stats();
+
+        Json(serde_json::json!({
+            "success": true,
+            "message": "🔐 GFEF Index uploaded and secured on Control Plane",
+            "index_id": index_id.to_string(),
+            "model_id": model_name,
+            "total_neurons": total_neurons,
+            "num_layers": num_layers_registered,
+            "triple_ip_lock_status": {
+                "lock1_gfef_index": "SECURED",
+                "lock2_calibration": "ROTATING",
+                "lock3_prediction_service": "ACTIVE"
+            },
+            "predictor_stats": {
+                "models_loaded": stats.models_loaded,
+                "total_neurons": stats.total_neurons,
+                "total_layers": stats.total_layers,
+                "target_sparsity": format!("{:.1}%", stats.target_sparsity * 100.0)
+            }
+        }))
+    }
+
+    /// Process a command from `peer`, subject to its rate limit and the auth policy
+    async fn process_from(
+        peer: Option<SocketAddr>,
+        command: ControlCommand,
+        token: Option<&str>,
+        server: Arc<ServerInternals>,
+    ) -> ControlResponse {
+        if let Some(peer) = peer {
+            if !server.rate_limiter.allow(peer) {
+                debug!("Rate limited {} command from {}", command.name(), peer);
+                return rate_limited_response();
+            }
+        }
+        Self::process_authorized(command, token, server).await
+    }
 
-        let response = Self::process_command(command, server).await;
-        serde_json::to_string_pretty(&response).unwrap_or_else(|_| "{}".to_string())
+    /// Process a command after checking its bearer token against the auth policy
+    async fn process_authorized(
+        command: ControlCommand,
+        token: Option<&str>,
+        server: Arc<ServerInternals>,
+    ) -> ControlResponse {
+        if command.requires_auth() && !server.auth.allows(token) {
+            return unauthorized_response();
+        }
+        Self::process_command(command, server).await
     }
 
     /// Process control command
     async fn process_command(command: ControlCommand, server: Arc<ServerInternals>) -> ControlResponse {
         let start = std::time::Instant::now();
+        let command_name = command.name();
 
-        let (success, message, data) = match command {
+        let (status, message, data) = match command {
             ControlCommand::Health => {
-                (true, "SYMMETRIX Control Plane Server is healthy".to_string(), Some(serde_json::json!({
-                    "status": "healthy",
+                // Probes ran at startup; the GFEF index can be loaded at any time
+                let mut subsystems = server.subsystem_health.read().await.clone();
+                subsystems.insert("gfef", server.gfef_health().await);
+                let status = HealthStatus::aggregate(subsystems.values());
+                (Ok(()), format!("SYMMETRIX Control Plane Server is {}", status.as_str()), Some(serde_json::json!({
+                    "status": status,
+                    "subsystems": subsystems,
                     "version": symmetrix_core::VERSION,
                     "uptime_seconds": chrono::Utc::now().timestamp() - server.stats.read().await.start_time,
                 })))
@@ -932,13 +2752,21 @@ impl ControlPlaneServer {
 
             ControlCommand::Stats => {
                 let stats = server.stats.read().await.clone();
-                (true, "Server statistics".to_string(), Some(serde_json::to_value(stats).unwrap()))
+                let tcam_stats = server.uao_qtcam.read().await.get_stats().await;
+                let mut data = serde_json::to_value(stats).unwrap();
+                data["version"] = serde_json::json!(symmetrix_core::VERSION);
+                data["containers_active"] = serde_json::json!(server.containers.read().await.values()
+                    .filter(|c| c.status == ContainerStatus::Running)
+                    .count());
+                data["containers_max"] = serde_json::json!(server.runtime.config.max_containers);
+                data["tcam"] = serde_json::to_value(tcam_stats).unwrap();
+                (Ok(()), "Server statistics".to_string(), Some(data))
             }
 
             ControlCommand::GetCascadeStats => {
                 let cascade = server.bandwidth_cascade.read().await;
                 let stats = cascade.get_unified_stats();
-                (true, "Cascade statistics".to_string(), Some(serde_json::json!({
+                (Ok(()), "Cascade statistics".to_string(), Some(serde_json::json!({
                     "bandwidth_amplification": "250,000,000× (250 MILLION)",
                     "memory_amplification": "2,500,000,000× (2.5 BILLION)",
                     "tcam_speedup": "1,250×",
@@ -953,10 +2781,153 @@ impl ControlPlaneServer {
                 })))
             }
 
+            ControlCommand::ProjectCascade { physical_gbps, physical_mem_gb } => {
+                let valid = |x: f64| x.is_finite() && x >= 0.0;
+                if valid(physical_gbps) && valid(physical_mem_gb) {
+                    let projection = BandwidthCascade::project(physical_gbps, physical_mem_gb);
+                    (Ok(()), format!(
+                        "{} Gbps / {} GB project to {:.3} Exabps / {:.3} EB",
+                        physical_gbps, physical_mem_gb,
+                        projection.effective_bandwidth_exabps, projection.effective_memory_exabytes,
+                    ), Some(serde_json::to_value(projection).unwrap()))
+                } else {
+                    (Err(ErrorCode::InvalidRequest), "Physical bandwidth and memory must be finite and non-negative".to_string(), None)
+                }
+            }
+
+            ControlCommand::GetMathStats => {
+                let runtime = &server.runtime;
+                let prime = runtime.galois_engine.prime;
+                let tensor = runtime.tensor_engine.get_memory_stats();
+                let tensor_lookups = tensor.cache_hits + tensor.cache_misses;
+                let (cohomology_dimension, last_computation) = match runtime.sheaf_engine.compute_h2_cohomology() {
+                    Ok(h2) => (h2.dimension, "just now".to_string()),
+                    Err(e) => (0, format!("unavailable ({})", e)),
+                };
+                (Ok(()), "Mathematical engine status".to_string(), Some(serde_json::json!({
+                    "galois_field_prime": if prime == MERSENNE_61 { "2^61-1".to_string() } else { prime.to_string() },
+                    "galois_operations_per_sec": galois_ops_per_sec(prime),
+                    "tensor_cache_hit_rate": if tensor_lookups == 0 { 0.0 } else { tensor.cache_hits as f64 / tensor_lookups as f64 * 100.0 },
+                    "tensor_blocks_active": runtime.tensor_engine.active_block_count(),
+                    "sheaf_cohomology_dimension": cohomology_dimension,
+                    "sheaf_last_computation": last_computation,
+                    "crt_decomposition_active": !runtime.galois_engine.crt_primes.is_empty(),
+                })))
+            }
+
+            // Sheaf operations
+            ControlCommand::SheafAddNode { node_id, resources } => {
+                match server.runtime.sheaf_engine.add_node(node_id, resources) {
+                    Ok(()) => (Ok(()), format!("Added sheaf node {}", node_id), None),
+                    Err(e) => (Err(ErrorCode::InvalidRequest), format!("Sheaf error: {}", e), None),
+                }
+            }
+
+            ControlCommand::SheafAddRestriction { source, target, matrix } => {
+                let cols = matrix.first().map_or(0, Vec::len);
+                if cols == 0 || matrix.iter().any(|row| row.len() != cols) {
+                    (Err(ErrorCode::InvalidRequest), "Restriction matrix must be non-empty with equal-length rows".to_string(), None)
+                } else {
+                    let transformation = nalgebra::DMatrix::from_row_iterator(
+                        matrix.len(), cols, matrix.iter().flatten().copied());
+                    match server.runtime.sheaf_engine.add_restriction(source, target, transformation) {
+                        Ok(()) => (Ok(()), format!("Added restriction {} -> {}", source, target), None),
+                        Err(e) => (Err(ErrorCode::InvalidRequest), format!("Sheaf error: {}", e), None),
+                    }
+                }
+            }
+
+            ControlCommand::SheafAllocate { requests } => {
+                let sheaf = &server.runtime.sheaf_engine;
+                match sheaf.allocate_resources(&requests) {
+                    Ok(allocation) => {
+                        // allocate_resources just computed (and cached) H²
+                        let obstruction_dimension = sheaf.compute_h2_cohomology().map_or(0, |h2| h2.dimension);
+                        let warning = (obstruction_dimension > 0).then(|| format!(
+                            "H² ≠ 0: allocation has obstructions (dim = {})", obstruction_dimension));
+                        (Ok(()), format!("Allocated resources for {} nodes", allocation.len()), Some(serde_json::json!({
+                            "allocation": allocation,
+                            "obstruction_dimension": obstruction_dimension,
+                            "warning": warning,
+                        })))
+                    }
+                    Err(e) => (Err(ErrorCode::Internal), format!("Sheaf allocation error: {}", e), None),
+                }
+            }
+
+            // Container operations
+            ControlCommand::LaunchContainers { template, count, memory_mb, cpu } => {
+                let max = server.runtime.config.max_containers;
+                let mut containers = server.containers.write().await;
+                let running = containers.values().filter(|c| c.status == ContainerStatus::Running).count();
+                if count == 0 {
+                    (Err(ErrorCode::InvalidRequest), "Container count must be at least 1".to_string(), None)
+                } else if running + count > max {
+                    (Err(ErrorCode::LimitExceeded), format!("Container limit reached: {} running + {} requested exceeds max {}", running, count, max), None)
+                } else {
+                    let now = chrono::Utc::now().timestamp();
+                    let mut launched = Vec::with_capacity(count);
+                    for _ in 0..count {
+                        let id = format!("sym-{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+                        let container = ContainerInfo {
+                            name: format!("{}-{}", template, &id[4..10]),
+                            id: id.clone(),
+                            template: template.clone(),
+                            status: ContainerStatus::Running,
+                            memory_mb: memory_mb.unwrap_or(DEFAULT_CONTAINER_MEMORY_MB),
+                            cpu: cpu.unwrap_or(DEFAULT_CONTAINER_CPU),
+                            created_at: now,
+                        };
+                        launched.push(container.clone());
+                        containers.insert(id, container);
+                    }
+                    (Ok(()), format!("Launched {} container(s) from template '{}'", count, template),
+                        Some(serde_json::json!({ "containers": launched })))
+                }
+            }
+
+            ControlCommand::StopContainer { id } => {
+                let mut containers = server.containers.write().await;
+                match containers.get_mut(&id) {
+                    Some(container) if container.status == ContainerStatus::Running => {
+                        container.status = ContainerStatus::Stopped;
+                        (Ok(()), format!("Container '{}' stopped", id), Some(serde_json::to_value(&*container).unwrap()))
+                    }
+                    Some(_) => (Err(ErrorCode::Conflict), format!("Container '{}' is already stopped", id), None),
+                    None => (Err(ErrorCode::NotFound), format!("Container '{}' not found", id), None),
+                }
+            }
+
+            ControlCommand::RemoveContainer { id, force } => {
+                let mut containers = server.containers.write().await;
+                match containers.get(&id).map(|c| c.status) {
+                    Some(ContainerStatus::Running) if !force => {
+                        (Err(ErrorCode::Conflict), format!("Container '{}' is running; stop it first or force removal", id), None)
+                    }
+                    Some(_) => {
+                        containers.remove(&id);
+                        (Ok(()), format!("Container '{}' removed", id), None)
+                    }
+                    None => (Err(ErrorCode::NotFound), format!("Container '{}' not found", id), None),
+                }
+            }
+
+            ControlCommand::ListContainers { all } => {
+                let containers = server.containers.read().await;
+                let mut listed: Vec<&ContainerInfo> = containers.values()
+                    .filter(|c| all || c.status == ContainerStatus::Running)
+                    .collect();
+                listed.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.id.cmp(&b.id)));
+                (Ok(()), format!("{} container(s)", listed.len()), Some(serde_json::json!({
+                    "containers": listed,
+                    "max_containers": server.runtime.config.max_containers,
+                })))
+            }
+
             ControlCommand::GetMemoryStats => {
                 let qagml = server.qagml.read().await;
                 let stats = qagml.get_stats();
-                (true, "Memory statistics".to_string(), Some(serde_json::json!({
+                (Ok(()), "Memory statistics".to_string(), Some(serde_json::json!({
                     "amplification": format!("{}×", stats.memory_amplification),
                     "bus_width_amplification": format!("{:.0}×", stats.bus_width_amplification),
                     "physical_memory_gb": stats.physical_memory_gb,
@@ -970,27 +2941,36 @@ impl ControlPlaneServer {
                 })))
             }
 
+            ControlCommand::GetRegionStats => {
+                let qagml = server.qagml.read().await;
+                let regions: BTreeMap<String, _> = qagml.region_stats().into_iter().collect();
+                (Ok(()), format!("Statistics for {} memory regions", regions.len()),
+                    Some(serde_json::to_value(regions).unwrap()))
+            }
+
             ControlCommand::GetBandwidthStats => {
                 let qanban = server.qanban.read().await;
                 match qanban.get_stats() {
-                    Ok(stats) => (true, "Bandwidth statistics".to_string(), Some(serde_json::json!({
+                    Ok(stats) => (Ok(()), "Bandwidth statistics".to_string(), Some(serde_json::json!({
                         "amplification": "1,000,000×",
                         "packets_processed": stats.packets_processed,
                         "is_healthy": stats.is_healthy,
+                        "liveness": stats.liveness,
+                        "readiness": stats.readiness,
                         "uptime_seconds": stats.uptime_seconds,
                         "postulates_active": stats.postulates_active,
                         "memory_usage_mb": stats.memory_usage_mb,
                         "throughput_pps": stats.throughput_pps,
                     }))),
-                    Err(e) => (false, format!("Failed to get bandwidth stats: {}", e), None),
+                    Err(e) => (Err(ErrorCode::Internal), format!("Failed to get bandwidth stats: {}", e), None),
                 }
             }
 
             // Cache operations
             ControlCommand::CacheSet { key, value, ttl_seconds } => {
                 match server.cache.set(&key, value.as_bytes(), ttl_seconds) {
-                    Ok(()) => (true, format!("Key '{}' set successfully", key), None),
-                    Err(e) => (false, format!("Cache SET error: {}", e), None),
+                    Ok(()) => (Ok(()), format!("Key '{}' set successfully", key), None),
+                    Err(e) => (Err(ErrorCode::from_cache_error(&e)), format!("Cache SET error: {}", e), None),
                 }
             }
 
@@ -999,7 +2979,7 @@ impl ControlPlaneServer {
                     Ok(Some(value)) => {
                         let mut stats = server.stats.write().await;
                         stats.cache_hits += 1;
-                        (true, "Cache hit".to_string(), Some(serde_json::json!({
+                        (Ok(()), "Cache hit".to_string(), Some(serde_json::json!({
                             "key": key,
                             "value": String::from_utf8_lossy(&value).to_string(),
                         })))
@@ -1007,31 +2987,85 @@ impl ControlPlaneServer {
                     Ok(None) => {
                         let mut stats = server.stats.write().await;
                         stats.cache_misses += 1;
-                        (false, format!("Key '{}' not found", key), None)
+                        (Err(ErrorCode::NotFound), format!("Key '{}' not found", key), None)
                     }
-                    Err(e) => (false, format!("Cache GET error: {}", e), None),
+                    Err(e) => (Err(ErrorCode::from_cache_error(&e)), format!("Cache GET error: {}", e), None),
                 }
             }
 
             ControlCommand::CacheDelete { key } => {
                 match server.cache.delete(&key) {
-                    Ok(true) => (true, format!("Key '{}' deleted", key), None),
-                    Ok(false) => (false, format!("Key '{}' not found", key), None),
-                    Err(e) => (false, format!("Cache DELETE error: {}", e), None),
+                    Ok(true) => (Ok(()), format!("Key '{}' deleted", key), None),
+                    Ok(false) => (Err(ErrorCode::NotFound), format!("Key '{}' not found", key), None),
+                    Err(e) => (Err(ErrorCode::from_cache_error(&e)), format!("Cache DELETE error: {}", e), None),
                 }
             }
 
             ControlCommand::CacheIncr { key } => {
                 match server.cache.incr(&key) {
-                    Ok(value) => (true, format!("Key '{}' incremented to {}", key, value), Some(serde_json::json!({ "value": value }))),
-                    Err(e) => (false, format!("Cache INCR error: {}", e), None),
+                    Ok(value) => (Ok(()), format!("Key '{}' incremented to {}", key, value), Some(serde_json::json!({ "value": value }))),
+                    Err(e) => (Err(ErrorCode::from_cache_error(&e)), format!("Cache INCR error: {}", e), None),
+                }
+            }
+
+            ControlCommand::CacheTtl { key } => {
+                match server.cache.ttl(&key) {
+                    Ok(Some(ttl)) => (Ok(()), format!("Key '{}' TTL {}", key, ttl), Some(serde_json::json!({ "key": key, "ttl": ttl }))),
+                    Ok(None) => (Err(ErrorCode::NotFound), format!("Key '{}' not found", key), Some(serde_json::json!({ "key": key, "ttl": -2 }))),
+                    Err(e) => (Err(ErrorCode::from_cache_error(&e)), format!("Cache TTL error: {}", e), None),
+                }
+            }
+
+            ControlCommand::CacheExpire { key, ttl_seconds } => {
+                match server.cache.expire(&key, ttl_seconds) {
+                    Ok(true) if ttl_seconds == 0 => (Ok(()), format!("Key '{}' deleted", key), None),
+                    Ok(true) => (Ok(()), format!("Key '{}' expires in {}s", key, ttl_seconds), None),
+                    Ok(false) => (Err(ErrorCode::NotFound), format!("Key '{}' not found", key), None),
+                    Err(e) => (Err(ErrorCode::from_cache_error(&e)), format!("Cache EXPIRE error: {}", e), None),
+                }
+            }
+
+            ControlCommand::ListPush { key, values, left } => {
+                let values = values.into_iter().map(String::into_bytes).collect();
+                match server.cache.list_push(&key, values, left) {
+                    Ok(length) => (Ok(()), format!("List '{}' has {} elements", key, length), Some(serde_json::json!({ "key": key, "length": length }))),
+                    Err(e) => (Err(ErrorCode::from_cache_error(&e)), format!("Cache LPUSH error: {}", e), None),
+                }
+            }
+
+            ControlCommand::ListRange { key, start, stop } => {
+                match server.cache.list_range(&key, start, stop) {
+                    Ok(values) => {
+                        let values: Vec<String> = values.iter().map(|v| String::from_utf8_lossy(v).to_string()).collect();
+                        (Ok(()), format!("{} elements", values.len()), Some(serde_json::json!({ "key": key, "values": values })))
+                    }
+                    Err(e) => (Err(ErrorCode::from_cache_error(&e)), format!("Cache LRANGE error: {}", e), None),
+                }
+            }
+
+            ControlCommand::HashSet { key, field, value } => {
+                match server.cache.hash_set(&key, &field, value.as_bytes()) {
+                    Ok(created) => (Ok(()), format!("Field '{}' set in '{}'", field, key), Some(serde_json::json!({ "created": created }))),
+                    Err(e) => (Err(ErrorCode::from_cache_error(&e)), format!("Cache HSET error: {}", e), None),
+                }
+            }
+
+            ControlCommand::HashGet { key, field } => {
+                match server.cache.hash_get(&key, &field) {
+                    Ok(Some(value)) => (Ok(()), "Cache hit".to_string(), Some(serde_json::json!({
+                        "key": key,
+                        "field": field,
+                        "value": String::from_utf8_lossy(&value).to_string(),
+                    }))),
+                    Ok(None) => (Err(ErrorCode::NotFound), format!("Field '{}' not found in '{}'", field, key), None),
+                    Err(e) => (Err(ErrorCode::from_cache_error(&e)), format!("Cache HGET error: {}", e), None),
                 }
             }
 
             ControlCommand::CacheStats => {
                 match server.cache.stats() {
-                    Ok(stats) => (true, "Cache statistics".to_string(), Some(serde_json::json!({
-                        "compression_ratio": format!("{}×", stats.compression_ratio as u64),
+                    Ok(stats) => (Ok(()), "Cache statistics".to_string(), Some(serde_json::json!({
+                        "compression_ratio": format!("{:.1}×", stats.compression_ratio),
                         "hit_rate": format!("{:.1}%", stats.hit_rate * 100.0),
                         "entries": stats.entry_count,
                         "compressed_mb": stats.compressed_bytes / (1024 * 1024),
@@ -1039,8 +3073,31 @@ impl ControlPlaneServer {
                         "hits": stats.hits,
                         "misses": stats.misses,
                         "evictions": stats.evictions,
+                        "expired_evictions": stats.expired_evictions,
                     }))),
-                    Err(e) => (false, format!("Cache stats error: {}", e), None),
+                    Err(e) => (Err(ErrorCode::from_cache_error(&e)), format!("Cache stats error: {}", e), None),
+                }
+            }
+
+            ControlCommand::CacheSnapshot { path } => {
+                match server.snapshot_path(&path) {
+                    Ok(target) => {
+                        let cache = server.cache.clone();
+                        let written = target.clone();
+                        let result = tokio::task::spawn_blocking(move || {
+                            if let Some(parent) = written.parent() {
+                                std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+                            }
+                            cache.snapshot(&written)
+                        }).await.unwrap_or_else(|e| Err(e.to_string()));
+                        match result {
+                            Ok(()) => (Ok(()), format!("Cache snapshot written to {}", path), Some(serde_json::json!({
+                                "path": target.to_string_lossy(),
+                            }))),
+                            Err(e) => (Err(ErrorCode::Internal), format!("Cache snapshot error: {}", e), None),
+                        }
+                    }
+                    Err((code, message)) => (Err(code), message, None),
                 }
             }
 
@@ -1048,17 +3105,17 @@ impl ControlPlaneServer {
             ControlCommand::AllocateMemory { size_bytes, region } => {
                 let mut qagml = server.qagml.write().await;
                 match qagml.allocate_amplified_region(size_bytes, &region) {
-                    Ok(allocation) => (true, format!("Allocated {} bytes in region '{}'", size_bytes, region),
+                    Ok(allocation) => (Ok(()), format!("Allocated {} bytes in region '{}'", size_bytes, region),
                         Some(serde_json::to_value(allocation).unwrap())),
-                    Err(e) => (false, format!("Allocation error: {}", e), None),
+                    Err(e) => (Err(ErrorCode::Internal), format!("Allocation error: {}", e), None),
                 }
             }
 
             ControlCommand::FreeMemory { allocation_id } => {
                 let mut qagml = server.qagml.write().await;
                 match qagml.free_amplified_region(&allocation_id) {
-                    Ok(()) => (true, format!("Freed allocation '{}'", allocation_id), None),
-                    Err(e) => (false, format!("Free error: {}", e), None),
+                    Ok(()) => (Ok(()), format!("Freed allocation '{}'", allocation_id), None),
+                    Err(e) => (Err(ErrorCode::InvalidRequest), format!("Free error: {}", e), None),
                 }
             }
 
@@ -1066,9 +3123,16 @@ impl ControlPlaneServer {
             ControlCommand::OptimizeBandwidth { flow_id, target_gbps } => {
                 let mut qanban = server.qanban.write().await;
                 match qanban.optimize_flow(&flow_id, target_gbps) {
-                    Ok(result) => (true, format!("Optimized flow '{}' to {} Gbps", flow_id, target_gbps),
-                        Some(serde_json::to_value(result).unwrap())),
-                    Err(e) => (false, format!("Optimization error: {}", e), None),
+                    Ok(result) => {
+                        let message = if result.congested {
+                            format!("Optimized flow '{}' to {} Gbps (requested {} Gbps; path congested)",
+                                flow_id, result.attainable_bandwidth_gbps, target_gbps)
+                        } else {
+                            format!("Optimized flow '{}' to {} Gbps", flow_id, result.attainable_bandwidth_gbps)
+                        };
+                        (Ok(()), message, Some(serde_json::to_value(result).unwrap()))
+                    }
+                    Err(e) => (Err(ErrorCode::Internal), format!("Optimization error: {}", e), None),
                 }
             }
 
@@ -1076,141 +3140,112 @@ impl ControlPlaneServer {
             ControlCommand::Lookup { key } => {
                 let uao_qtcam = server.uao_qtcam.read().await;
                 match uao_qtcam.sync_lookup(&key) {
-                    Ok(result) => (true, format!("Lookup result for '{}'", key), Some(serde_json::to_value(result).unwrap())),
-                    Err(e) => (false, format!("Lookup error: {}", e), None),
+                    Ok(result) => (Ok(()), format!("Lookup result for '{}'", key), Some(serde_json::to_value(result).unwrap())),
+                    Err(e) => (Err(ErrorCode::InvalidRequest), format!("Lookup error: {}", e), None),
                 }
             }
 
             ControlCommand::InsertRoute { key, value, priority } => {
                 let mut uao_qtcam = server.uao_qtcam.write().await;
                 match uao_qtcam.sync_insert_route(&key, &value, priority) {
-                    Ok(()) => (true, format!("Route '{}' inserted with priority {}", key, priority), None),
-                    Err(e) => (false, format!("Insert error: {}", e), None),
+                    Ok(()) => (Ok(()), format!("Route '{}' inserted with priority {}", key, priority), None),
+                    Err(e) => (Err(ErrorCode::InvalidRequest), format!("Insert error: {}", e), None),
                 }
             }
 
             ControlCommand::DeleteRoute { key } => {
                 let mut uao_qtcam = server.uao_qtcam.write().await;
                 match uao_qtcam.sync_delete_route(&key) {
-                    Ok(()) => (true, format!("Route '{}' deleted", key), None),
-                    Err(e) => (false, format!("Delete error: {}", e), None),
+                    Ok(()) => (Ok(()), format!("Route '{}' deleted", key), None),
+                    Err(e) => (Err(ErrorCode::Internal), format!("Delete error: {}", e), None),
                 }
             }
 
             // Calibration Matrix for Weight Server
-            ControlCommand::GetCalibrationMatrix { tier } => {
-                let tier_name = tier.unwrap_or_else(|| "professional".to_string());
-                let (compression_ratio, tier_code) = match tier_name.to_lowercase().as_str() {
-                    "none" => (1.0, 0),
-                    "basic" => (10.0, 1),
-                    "standard" => (100.0, 2),
-                    "professional" => (1250.0, 3),
-                    "enterprise" => (10000.0, 4),
-                    _ => (1250.0, 3), // Default to professional
-                };
+            ControlCommand::EstablishSession { customer_id, tier, token } => {
+                match server.establish_calibration_session(customer_id, &tier, &token).await {
+                    Ok(data) => (Ok(()), "Calibration session established".to_string(), Some(data)),
+                    Err((code, message)) => (Err(code), message, None),
+                }
+            }
 
-                // Generate calibration matrix (64x64 = 4096 values)
-                // This is the SECRET IP - the trained parameters that enable compression
-                let session_id = format!("cal-{}", chrono::Utc::now().timestamp_millis());
-                let expires_at = (chrono::Utc::now().timestamp() + 60) as u64; // 60 second validity
-
-                // Generate matrix values using SYMMETRIX CORE mathematics
-                // In production, these would be trained parameters
-                let mut values: Vec<f64> = Vec::with_capacity(64 * 64);
-                for i in 0..64 {
-                    for j in 0..64 {
-                        // Chern-Simons modulated eigenmode basis
-                        let phase = std::f64::consts::PI * 2.0 * (i * j) as f64 / 64.0;
-                        let cs_term = ((i + j) as f64 * 0.1).sin() * 0.1;
-                        let base = phase.cos() + cs_term;
-                        // Scale by compression ratio
-                        let scaled = base * (compression_ratio / 1250.0);
-                        values.push(1.0 + scaled * 0.001);
-                    }
+            ControlCommand::GetCalibrationMatrix { session_id, tier } => {
+                match tier.as_deref().map(|name| CalibrationTier::parse(name).ok_or(name)).transpose() {
+                    Err(name) => (Err(ErrorCode::InvalidRequest), format!("Unknown calibration tier '{}'", name), None),
+                    Ok(requested) => match server.calibration_sessions.matrix(&session_id, requested, false) {
+                        Ok(issued) => (
+                            Ok(()),
+                            format!("Calibration matrix for tier '{}'", issued.tier.as_str()),
+                            Some(issued.to_json(&session_id)),
+                        ),
+                        Err(message) => (Err(ErrorCode::Unauthorized), message, None),
+                    },
                 }
+            }
 
-                (true, format!("Calibration matrix for tier '{}'", tier_name), Some(serde_json::json!({
-                    "rows": 64,
-                    "cols": 64,
-                    "values": values,
-                    "session_id": session_id,
-                    "expires_at": expires_at,
-                    "tier": tier_code,
-                    "tier_name": tier_name,
-                    "compression_ratio": compression_ratio,
-                })))
+            ControlCommand::RefreshCalibrationMatrix { session_id } => {
+                match server.calibration_sessions.matrix(&session_id, None, true) {
+                    Ok(issued) => (
+                        Ok(()),
+                        format!("Refreshed calibration matrix for tier '{}'", issued.tier.as_str()),
+                        Some(issued.to_json(&session_id)),
+                    ),
+                    Err(message) => (Err(ErrorCode::Unauthorized), message, None),
+                }
             }
 
             // GFEF (Galois Field Eigenmode Folding) Operations
-            ControlCommand::PredictActivation { customer_id, model_id, layer_index, input_hash } => {
-                // GFEF prediction using Galois Field mathematics
-                // This predicts which 5% of neurons will activate for a given input
+            ControlCommand::PredictActivation { customer_id, model_id, layer_index, input_hash, input_embedding } => {
+                // GFEF prediction: project the input onto the layer's principal
+                // components and select the best-correlated neurons
                 debug!("GFEF PredictActivation: customer={}, model={}, layer={}", customer_id, model_id, layer_index);
 
-                // Use input hash to deterministically select active neurons
-                // In production, this would use the trained GFEF index
-                let hash_bytes = input_hash.as_bytes();
-                let seed: u64 = hash_bytes.iter().enumerate()
-                    .fold(0u64, |acc, (i, &b)| acc.wrapping_add((b as u64) << (i % 8 * 8)));
-
-                // For Qwen3-MoE: 2048 hidden size, 128 experts, 8 experts per token
-                // Active neurons = ~5% = ~102 neurons per layer
-                let num_active = 102; // 5% of 2048
-                let mut active_neurons: Vec<u32> = Vec::with_capacity(num_active);
-
-                // Generate deterministic but pseudo-random active neuron indices
-                // Using Galois Field GF(2^11) for 2048 hidden size
-                let mut state = seed.wrapping_add(layer_index as u64);
-                for _ in 0..num_active {
-                    // Linear feedback shift register in GF(2^11)
-                    state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
-                    let neuron_idx = ((state >> 32) as u32) % 2048;
-                    if !active_neurons.contains(&neuron_idx) {
-                        active_neurons.push(neuron_idx);
-                    }
-                }
-
-                // Ensure we have exactly num_active neurons
-                while active_neurons.len() < num_active {
-                    state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
-                    let neuron_idx = ((state >> 32) as u32) % 2048;
-                    if !active_neurons.contains(&neuron_idx) {
-                        active_neurons.push(neuron_idx);
+                let predictor = server.gfef_predictor.read().await;
+                match predictor.predict_layer(&model_id, layer_index, input_embedding.as_deref(), &input_hash) {
+                    Ok(prediction) => {
+                        (Ok(()), format!("GFEF prediction for layer {}", layer_index), Some(serde_json::json!({
+                            "layer_id": layer_index,
+                            "layer_index": layer_index,
+                            "input_hash": input_hash,
+                            "active_neurons": prediction.active_neurons,
+                            "sparsity": prediction.sparsity,
+                            "confidence_scores": prediction.confidence_scores,
+                            "method": "galois_field_eigenmode_folding",
+                            "customer_id": customer_id,
+                            "model_id": model_id,
+                        })))
                     }
+                    Err(e) => (Err(ErrorCode::from_prediction_error(&e)), format!("GFEF prediction failed: {}", e), None),
                 }
-
-                active_neurons.sort();
-
-                (true, format!("GFEF prediction for layer {}", layer_index), Some(serde_json::json!({
-                    "layer_id": layer_index,
-                    "layer_index": layer_index,
-                    "input_hash": input_hash,
-                    "active_neurons": active_neurons,
-                    "sparsity": 0.95,
-                    "confidence": 0.99,
-                    "method": "galois_field_eigenmode_folding",
-                    "gf_order": 2048,
-                    "customer_id": customer_id,
-                    "model_id": model_id,
-                })))
             }
 
             ControlCommand::UploadGfefIndex { model_id, index_data } => {
                 // Store GFEF index in cache for the model
                 let key = format!("gfef_index:{}", model_id);
                 match server.cache.set(&key, index_data.as_bytes(), Some(86400)) { // 24 hour TTL
-                    Ok(()) => (true, format!("GFEF index uploaded for model '{}'", model_id), Some(serde_json::json!({
+                    Ok(()) => (Ok(()), format!("GFEF index uploaded for model '{}'", model_id), Some(serde_json::json!({
                         "model_id": model_id,
                         "index_size_bytes": index_data.len(),
                         "status": "stored",
                     }))),
-                    Err(e) => (false, format!("Failed to store GFEF index: {}", e), None),
+                    Err(e) => (Err(ErrorCode::from_cache_error(&e)), format!("Failed to store GFEF index: {}", e), None),
                 }
             }
 
             ControlCommand::GetGfefStatus => {
-                // Return GFEF system status
-                (true, "GFEF system status".to_string(), Some(serde_json::json!({
+                // Return GFEF system status with the shape of every registered model
+                let predictor = server.gfef_predictor.read().await;
+                let mut models: Vec<serde_json::Value> = predictor.indices()
+                    .map(|index| serde_json::json!({
+                        "model_id": index.model_id,
+                        "num_layers": index.layers.len(),
+                        "total_neurons": index.total_neurons,
+                        "k_components": index.config.k_components,
+                        "input_dims": index.layers.iter().map(|l| l.input_dim).collect::<Vec<_>>(),
+                    }))
+                    .collect();
+                models.sort_by(|a, b| a["model_id"].as_str().cmp(&b["model_id"].as_str()));
+                (Ok(()), "GFEF system status".to_string(), Some(serde_json::json!({
                     "status": "active",
                     "version": "1.0.0",
                     "capabilities": {
@@ -1219,7 +3254,8 @@ impl ControlPlaneServer {
                         "max_layers": 384,
                         "compression_ratio": "20x",
                     },
-                    "models_indexed": 1,
+                    "models_indexed": models.len(),
+                    "models": models,
                     "triple_ip_lock": {
                         "lock_1": "GFEF Index (SECURED)",
                         "lock_2": "Calibration Matrix (rotating)",
@@ -1234,19 +3270,22 @@ impl ControlPlaneServer {
             let mut stats = server.stats.write().await;
             stats.commands_processed += 1;
             stats.total_latency_ns += start.elapsed().as_nanos() as u64;
+            *stats.commands_by_type.entry(command_name.to_string()).or_default() += 1;
         }
 
         ControlResponse {
-            success,
+            success: status.is_ok(),
             message,
             data,
             latency_ns: start.elapsed().as_nanos() as u64,
+            error_code: status.err().map(|code| code.as_str().to_string()),
         }
     }
 }
 
 /// Internal server state (cloneable for async tasks)
 struct ServerInternals {
+    runtime: Arc<SymmetrixRuntime>,
     cache: Arc<UaoQtcamCache>,
     qagml: Arc<RwLock<SymmetrixQagmlOptimizer>>,
     qanban: Arc<RwLock<SymmetrixQanbanOptimizer>>,
@@ -1257,6 +3296,153 @@ struct ServerInternals {
     gfef_predictor: Arc<RwLock<ActivationPredictor>>,
     gfef_calibration: Arc<CalibrationService>,
     gfef_subscriptions: Arc<RwLock<SubscriptionManager>>,
+    gfef_storage: Option<Arc<RwLock<IndexStorage>>>,
+    /// Root for operator-requested snapshots (`persistence_dir`)
+    persistence_dir: Option<std::path::PathBuf>,
+    auth: AuthPolicy,
+    ws_keepalive: WsKeepalive,
+    /// Keep-alive HTTP connections idle this long are closed (`None` keeps them open)
+    http_idle_timeout: Option<Duration>,
+    /// VXLAN replies larger than this drop their data (0 = unlimited)
+    response_mtu: usize,
+    shutdown: broadcast::Sender<()>,
+    in_flight: Arc<InFlight>,
+    rate_limiter: Arc<RateLimiter>,
+    containers: Arc<RwLock<HashMap<String, ContainerInfo>>>,
+    index_uploads: Arc<IndexUploads>,
+    vxlan_fragments: Arc<FragmentReassembly>,
+    /// VXLAN packets queued for workers before new ones are dropped (`max_connections`)
+    vxlan_queue_capacity: usize,
+    calibration_sessions: Arc<CalibrationSessions>,
+    subsystem_health: Arc<RwLock<BTreeMap<&'static str, HealthStatus>>>,
+}
+
+impl ServerInternals {
+    /// Resolve a client-supplied snapshot path inside `persistence_dir`
+    ///
+    /// Only plain relative paths are accepted, so a command can never write
+    /// outside the persistence directory.
+    fn snapshot_path(&self, path: &str) -> Result<std::path::PathBuf, (ErrorCode, String)> {
+        let dir = self.persistence_dir.as_ref()
+            .ok_or((ErrorCode::InvalidRequest, "Snapshots require PERSISTENCE_DIR".to_string()))?;
+        let relative = std::path::Path::new(path);
+        let plain = relative.components().all(|c| matches!(c, std::path::Component::Normal(_)));
+        if path.is_empty() || !plain {
+            return Err((ErrorCode::InvalidRequest, format!("Snapshot path '{}' must be relative to the persistence directory", path)));
+        }
+        Ok(dir.join(relative))
+    }
+
+    /// Check the token and the customer's entitlement, then open a session
+    async fn establish_calibration_session(
+        &self,
+        customer_id: uuid::Uuid,
+        tier: &str,
+        token: &str,
+    ) -> Result<serde_json::Value, (ErrorCode, String)> {
+        let tier = CalibrationTier::parse(tier)
+            .ok_or_else(|| (ErrorCode::InvalidRequest, format!("Unknown calibration tier '{}'", tier)))?;
+        if !self.auth.allows(Some(token)) {
+            return Err((ErrorCode::Unauthorized, "Invalid session token".to_string()));
+        }
+
+        let subscription = self.gfef_subscriptions.read().await
+            .get_subscription(&customer_id)
+            .cloned()
+            .ok_or((ErrorCode::Unauthorized, format!("No subscription for customer {}", customer_id)))?;
+        if !subscription.is_active() {
+            return Err((ErrorCode::Unauthorized, "Subscription expired".to_string()));
+        }
+        if !tier.entitled(subscription.tier) {
+            return Err((ErrorCode::Unauthorized, format!(
+                "{:?} subscription is not entitled to calibration tier '{}'",
+                subscription.tier,
+                tier.as_str()
+            )));
+        }
+
+        let expires_at = subscription.expires_at
+            .min(chrono::Utc::now() + chrono::Duration::seconds(CALIBRATION_SESSION_TTL_SECS));
+        let session_id = self.calibration_sessions.establish(customer_id, tier, expires_at);
+        Ok(serde_json::json!({
+            "session_id": session_id,
+            "customer_id": customer_id,
+            "tier": tier.code(),
+            "tier_name": tier.as_str(),
+            "expires_at": expires_at.timestamp(),
+        }))
+    }
+
+    /// Exercise one operation against each subsystem
+    ///
+    /// The cache and QAGML probes run against the live subsystems and undo
+    /// their writes. The routing table and sheaf space cannot remove what a
+    /// probe adds, so their insert/allocate runs on a scratch instance and
+    /// the live one only answers a read. GFEF is degraded until an index is
+    /// loaded.
+    async fn self_test(&self) -> BTreeMap<&'static str, HealthStatus> {
+        let mut report = BTreeMap::new();
+        report.insert("cache", self.probe_cache());
+        report.insert("tcam", self.probe_tcam().await);
+        report.insert("qagml", self.probe_qagml().await);
+        report.insert("sheaf", self.probe_sheaf());
+        report.insert("gfef", self.gfef_health().await);
+        report
+    }
+
+    fn probe_cache(&self) -> HealthStatus {
+        // Replicas reject writes, so only check that reads are answered
+        if self.cache.is_read_only() {
+            return HealthStatus::from_probe(self.cache.get(SELF_TEST_CACHE_KEY).is_ok());
+        }
+        let passed = self.cache.set(SELF_TEST_CACHE_KEY, b"ok", None).is_ok()
+            && matches!(self.cache.get(SELF_TEST_CACHE_KEY), Ok(Some(value)) if value == b"ok");
+        let _ = self.cache.delete(SELF_TEST_CACHE_KEY);
+        HealthStatus::from_probe(passed)
+    }
+
+    async fn probe_tcam(&self) -> HealthStatus {
+        let scratch = SymmetrixUaoQtcamOptimizer::new(SymmetrixUaoQtcamConfig::default());
+        let inserted = scratch.insert_route("192.0.2.0/24", "self-test", 1).await.is_ok()
+            && matches!(scratch.lookup("192.0.2.1").await, Ok(Some(result)) if result.primary_next_hop() == "self-test");
+        let live = self.uao_qtcam.read().await.lookup("192.0.2.1").await.is_ok();
+        HealthStatus::from_probe(inserted && live)
+    }
+
+    async fn probe_qagml(&self) -> HealthStatus {
+        let mut qagml = self.qagml.write().await;
+        let passed = qagml.is_healthy()
+            && qagml.allocate_amplified_region(4096, SELF_TEST_REGION)
+                .and_then(|allocation| qagml.free_amplified_region(&allocation.allocation_id))
+                .is_ok();
+        HealthStatus::from_probe(passed)
+    }
+
+    fn probe_sheaf(&self) -> HealthStatus {
+        let scratch = SheafSpace::new(SheafConfig {
+            max_nodes: 1,
+            precision: 1e-10,
+            enable_caching: false,
+            rebalance_threshold: 0.1,
+        });
+        let requests = HashMap::from([(0, HashMap::from([(ResourceType::CPU, 1.0)]))]);
+        let allocated = scratch.add_node(0, HashMap::from([(ResourceType::CPU, 2.0)])).is_ok()
+            && matches!(scratch.allocate_resources(&requests), Ok(allocation) if allocation.contains_key(&0));
+        // The live sheaf has no nodes until containers register with it
+        let live = matches!(
+            self.runtime.sheaf_engine.compute_h2_cohomology(),
+            Ok(_) | Err(SheafError::StructureError(_))
+        );
+        HealthStatus::from_probe(allocated && live)
+    }
+
+    async fn gfef_health(&self) -> HealthStatus {
+        if self.gfef_predictor.read().await.stats().models_loaded > 0 {
+            HealthStatus::Healthy
+        } else {
+            HealthStatus::Degraded
+        }
+    }
 }
 
 fn print_banner() {
@@ -1326,11 +3512,46 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             .ok()
             .and_then(|s| s.parse().ok())
             .unwrap_or(10000),
+        require_auth: std::env::var("REQUIRE_AUTH")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_else(|| std::env::var("API_TOKEN").is_ok()),
+        api_token: std::env::var("API_TOKEN").ok(),
+        shutdown_grace_secs: std::env::var("SHUTDOWN_GRACE_SECS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_else(default_shutdown_grace_secs),
+        persistence_dir: std::env::var("PERSISTENCE_DIR").ok(),
+        rate_limit_per_sec: std::env::var("RATE_LIMIT_PER_SEC")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_else(default_rate_limit_per_sec),
+        ws_ping_interval_secs: std::env::var("WS_PING_INTERVAL_SECS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_else(default_ws_ping_interval_secs),
+        ws_idle_timeout_secs: std::env::var("WS_IDLE_TIMEOUT_SECS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_else(default_ws_idle_timeout_secs),
+        http_idle_timeout_secs: std::env::var("HTTP_IDLE_TIMEOUT_SECS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_else(default_http_idle_timeout_secs),
+        response_mtu: std::env::var("RESPONSE_MTU")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_else(default_response_mtu),
+        tls_cert_path: std::env::var("TLS_CERT_PATH").ok(),
+        tls_key_path: std::env::var("TLS_KEY_PATH").ok(),
     };
 
     info!("   VXLAN Port: {}", config.vxlan_port);
     info!("   Cache Size: {} MB", config.cache_size / (1024 * 1024));
     info!("   Max Connections: {}", config.max_connections);
+    info!("   Auth Required: {}", config.require_auth);
+    info!("   Rate Limit: {}/s per client", config.rate_limit_per_sec);
+    info!("   TLS: {}", config.tls_cert_path.is_some());
 
     // Create and start server
     let server = ControlPlaneServer::new(config).await?;
@@ -1377,6 +3598,17 @@ mod tests {
             http_port: 8080,
             cache_size: 256 * 1024 * 1024,
             max_connections: 10000,
+            require_auth: false,
+            api_token: None,
+            shutdown_grace_secs: 30,
+            persistence_dir: None,
+            rate_limit_per_sec: 1000,
+            ws_ping_interval_secs: 30,
+            ws_idle_timeout_secs: 90,
+            http_idle_timeout_secs: 60,
+            response_mtu: 1400,
+            tls_cert_path: None,
+            tls_key_path: None,
         };
 
         assert_eq!(config.vxlan_port, 4789);
@@ -1433,6 +3665,7 @@ mod tests {
                 "uptime_seconds": 3600
             })),
             latency_ns: 1000,
+            error_code: None,
         };
 
         let json = serde_json::to_string(&response).unwrap();
@@ -1444,6 +3677,7 @@ mod tests {
             message: "Connection failed".to_string(),
             data: None,
             latency_ns: 500,
+            error_code: Some(ErrorCode::Internal.as_str().to_string()),
         };
 
         let error_json = serde_json::to_string(&error_response).unwrap();
@@ -1490,6 +3724,973 @@ mod tests {
         assert_eq!(super::VXLAN_HEADER_SIZE, 8);
     }
 
+    /// Loopback config with a small cache
+    fn test_config() -> ServerConfig {
+        ServerConfig {
+            vxlan_bind: "127.0.0.1".to_string(),
+            vxlan_port: 0,
+            http_bind: "127.0.0.1".to_string(),
+            http_port: 0,
+            cache_size: 1024 * 1024,
+            max_connections: 16,
+            require_auth: false,
+            api_token: None,
+            shutdown_grace_secs: 5,
+            persistence_dir: None,
+            rate_limit_per_sec: 0,
+            ws_ping_interval_secs: default_ws_ping_interval_secs(),
+            ws_idle_timeout_secs: default_ws_idle_timeout_secs(),
+            http_idle_timeout_secs: default_http_idle_timeout_secs(),
+            response_mtu: default_response_mtu(),
+            tls_cert_path: None,
+            tls_key_path: None,
+        }
+    }
+
+    /// Server internals for `config`, bound to nothing
+    async fn test_internals_with(config: ServerConfig) -> Arc<ServerInternals> {
+        let server = ControlPlaneServer::new(config).await.unwrap();
+        Arc::new(server.clone_internals())
+    }
+
+    async fn test_internals() -> Arc<ServerInternals> {
+        test_internals_with(test_config()).await
+    }
+
+    /// Send a request through the HTTP router and decode the JSON body
+    async fn call_router(
+        internals: Arc<ServerInternals>,
+        request: axum::http::Request<axum::body::Body>,
+    ) -> (StatusCode, serde_json::Value) {
+        use tower::ServiceExt;
+
+        let response = ControlPlaneServer::http_router(internals).oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_vxlan_health_roundtrip() {
+        let internals = test_internals().await;
+
+        let socket = Arc::new(UdpSocket::bind("127.0.0.1:0").await.unwrap());
+        let server_addr = socket.local_addr().unwrap();
+        tokio::spawn(ControlPlaneServer::serve_vxlan(socket, internals));
+
+        let client = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let mut request = vxlan_header(VXLAN_VNI_CONTROL_PLANE).to_vec();
+        request.extend_from_slice(br#"{"cmd": "Health"}"#);
+        client.send_to(&request, server_addr).await.unwrap();
+
+        let mut buf = vec![0u8; 65535];
+        let (len, from) = tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            client.recv_from(&mut buf),
+        ).await.expect("no VXLAN response").unwrap();
+        assert_eq!(from, server_addr);
+
+        let reply = &buf[..len];
+        assert_eq!(&reply[..VXLAN_HEADER_SIZE], &vxlan_header(VXLAN_VNI_CONTROL_PLANE));
+        let response: ControlResponse = serde_json::from_slice(&reply[VXLAN_HEADER_SIZE..]).unwrap();
+        assert!(response.success);
+    }
+
+    #[tokio::test]
+    async fn test_vxlan_flood_bounded_by_work_queue() {
+        const FLOOD: u64 = 512;
+        let capacity = 4;
+        let internals = test_internals_with(ServerConfig {
+            max_connections: capacity,
+            ..test_config()
+        }).await;
+
+        let socket = Arc::new(UdpSocket::bind("127.0.0.1:0").await.unwrap());
+        let server_addr = socket.local_addr().unwrap();
+        tokio::spawn(ControlPlaneServer::serve_vxlan(socket, internals.clone()));
+
+        let client = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let mut request = vxlan_header(VXLAN_VNI_CONTROL_PLANE).to_vec();
+        request.extend_from_slice(br#"{"cmd": "Health"}"#);
+        for _ in 0..FLOOD {
+            client.send_to(&request, server_addr).await.unwrap();
+        }
+
+        // Queued and running packets never exceed the queue plus one per worker
+        let _ = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                assert!(internals.in_flight.active() <= capacity + VXLAN_WORKERS);
+                let stats = internals.stats.read().await.clone();
+                if stats.vxlan_packets + stats.dropped_packets >= FLOOD {
+                    return;
+                }
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        }).await;
+
+        let stats = internals.stats.read().await.clone();
+        assert!(stats.dropped_packets > 0);
+        assert!(stats.vxlan_packets > 0);
+        assert!(stats.to_prometheus().contains(&format!("control_vxlan_packets_dropped_total {}", stats.dropped_packets)));
+    }
+
+    #[test]
+    fn test_vxlan_reply_truncates_oversized_data() {
+        let response = |data: serde_json::Value| ControlResponse {
+            success: true,
+            message: "ok".to_string(),
+            data: Some(data),
+            latency_ns: 42,
+            error_code: None,
+        };
+
+        // Small responses pass through unchanged
+        let small = response(serde_json::json!({ "value": "abc" }));
+        let reply = vxlan_reply(&small, 1400).unwrap();
+        assert_eq!(&reply[..VXLAN_HEADER_SIZE], &vxlan_header(VXLAN_VNI_CONTROL_PLANE));
+        assert_eq!(&reply[VXLAN_HEADER_SIZE..], serde_json::to_vec(&small).unwrap().as_slice());
+
+        // Oversized data is replaced by the truncation marker
+        let large = response(serde_json::json!({ "value": "x".repeat(4096) }));
+        let reply = vxlan_reply(&large, 1400).unwrap();
+        assert!(reply.len() <= 1400);
+        let decoded: ControlResponse = serde_json::from_slice(&reply[VXLAN_HEADER_SIZE..]).unwrap();
+        assert!(decoded.success);
+        assert_eq!(decoded.message, "ok");
+        assert_eq!(decoded.latency_ns, 42);
+        assert_eq!(decoded.data, Some(serde_json::json!({ "truncated": true, "full_via": "http" })));
+
+        // An MTU of 0 never truncates
+        let reply = vxlan_reply(&large, 0).unwrap();
+        assert_eq!(&reply[VXLAN_HEADER_SIZE..], serde_json::to_vec(&large).unwrap().as_slice());
+
+        // A message echoing a long key is cut short too, on a char boundary
+        for key in ["k".repeat(4096), "\"é".repeat(2048)] {
+            let not_found = ControlResponse {
+                success: false,
+                message: format!("Key not found: {}", key),
+                data: None,
+                latency_ns: 42,
+                error_code: Some(ErrorCode::NotFound.as_str().to_string()),
+            };
+            let reply = vxlan_reply(&not_found, 1400).unwrap();
+            assert!((1395..=1400).contains(&reply.len()), "{} byte reply", reply.len());
+            let decoded: ControlResponse = serde_json::from_slice(&reply[VXLAN_HEADER_SIZE..]).unwrap();
+            assert!(!decoded.success);
+            assert!(decoded.message.starts_with("Key not found: "));
+            assert!(not_found.message.starts_with(&decoded.message));
+            assert_eq!(decoded.error_code, not_found.error_code);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_health_degraded_without_gfef_index() {
+        let internals = test_internals().await;
+        let response = ControlPlaneServer::process_command(ControlCommand::Health, internals).await;
+        assert!(response.success);
+        assert_eq!(response.message, "SYMMETRIX Control Plane Server is degraded");
+
+        let data = response.data.unwrap();
+        assert_eq!(data["status"], "degraded");
+        assert_eq!(data["subsystems"]["gfef"], "degraded");
+        for subsystem in ["cache", "tcam", "qagml", "sheaf"] {
+            assert_eq!(data["subsystems"][subsystem], "healthy", "{} self-test failed", subsystem);
+        }
+    }
+
+    /// Split `payload` into fragmented VXLAN datagrams of at most `chunk` data bytes
+    fn vxlan_fragments(id: u16, payload: &[u8], chunk: usize) -> Vec<Vec<u8>> {
+        let count = payload.len().div_ceil(chunk);
+        payload.chunks(chunk)
+            .enumerate()
+            .map(|(index, data)| {
+                let mut datagram = vxlan_header(VXLAN_VNI_CONTROL_PLANE).to_vec();
+                datagram[0] |= VXLAN_FLAG_FRAGMENT;
+                datagram.extend_from_slice(&id.to_be_bytes());
+                datagram.extend_from_slice(&[index as u8, count as u8]);
+                datagram.extend_from_slice(data);
+                datagram
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_vxlan_fragment_reassembly() {
+        let internals = test_internals().await;
+
+        let socket = Arc::new(UdpSocket::bind("127.0.0.1:0").await.unwrap());
+        let server_addr = socket.local_addr().unwrap();
+        tokio::spawn(ControlPlaneServer::serve_vxlan(socket, internals.clone()));
+
+        let value = "x".repeat(3000);
+        let command = serde_json::json!({
+            "cmd": "CacheSet",
+            "data": {"key": "fragmented", "value": value, "ttl_seconds": null}
+        }).to_string();
+        let fragments = vxlan_fragments(7, command.as_bytes(), command.len().div_ceil(3));
+        assert_eq!(fragments.len(), 3);
+
+        // Out of order, with a duplicate
+        let client = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        for index in [2, 0, 2, 1] {
+            client.send_to(&fragments[index], server_addr).await.unwrap();
+        }
+
+        let mut buf = vec![0u8; 65535];
+        let (len, _) = tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            client.recv_from(&mut buf),
+        ).await.expect("no VXLAN response").unwrap();
+        let response: ControlResponse = serde_json::from_slice(&buf[VXLAN_HEADER_SIZE..len]).unwrap();
+        assert!(response.success, "{}", response.message);
+        assert_eq!(internals.cache.get("fragmented").unwrap().as_deref(), Some(value.as_bytes()));
+        assert!(internals.vxlan_fragments.pending.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_vxlan_fragment_rejection_and_expiry() {
+        let reassembly = FragmentReassembly::default();
+        let src: SocketAddr = "127.0.0.1:9".parse().unwrap();
+
+        assert!(reassembly.accept(src, &[0, 1, 3, 3]).is_err());
+        assert!(reassembly.accept(src, &[0, 1]).is_err());
+
+        assert_eq!(reassembly.accept(src, &[0, 1, 0, 2, b'a']), Ok(None));
+        assert!(reassembly.accept(src, &[0, 1, 1, 3, b'b']).is_err());
+        assert!(reassembly.pending.lock().unwrap().is_empty());
+
+        assert_eq!(reassembly.accept(src, &[0, 2, 0, 2, b'a']), Ok(None));
+        assert_eq!(reassembly.expire(), 0);
+        for buffer in reassembly.pending.lock().unwrap().values_mut() {
+            buffer.started -= VXLAN_REASSEMBLY_TIMEOUT;
+        }
+        assert_eq!(reassembly.expire(), 1);
+        assert_eq!(reassembly.accept(src, &[0, 2, 1, 2, b'b']), Ok(None));
+        assert_eq!(reassembly.buffered.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_vxlan_fragment_buffer_limits() {
+        let reassembly = FragmentReassembly::default();
+        let src: SocketAddr = "127.0.0.1:9".parse().unwrap();
+        let other: SocketAddr = "127.0.0.2:9".parse().unwrap();
+        let fragment = |id: u16, data: &[u8]| {
+            let mut fragment = id.to_be_bytes().to_vec();
+            fragment.extend_from_slice(&[0, 2]);
+            fragment.extend_from_slice(data);
+            fragment
+        };
+
+        // One sender cannot hold every reassembly slot
+        for id in 0..VXLAN_MAX_REASSEMBLIES_PER_SOURCE as u16 {
+            assert_eq!(reassembly.accept(src, &fragment(id, b"a")), Ok(None));
+        }
+        assert!(reassembly.accept(src, &fragment(1000, b"a")).is_err());
+        assert_eq!(reassembly.accept(other, &fragment(1000, b"a")), Ok(None));
+        assert_eq!(reassembly.pending.lock().unwrap().len(), VXLAN_MAX_REASSEMBLIES_PER_SOURCE + 1);
+
+        // Completing or abandoning a reassembly releases its bytes
+        assert_eq!(reassembly.accept(other, &[3, 232, 1, 2, b'b']), Ok(Some(b"ab".to_vec())));
+        assert!(reassembly.accept(src, &[0, 0, 1, 3, b'b']).is_err());
+        assert_eq!(reassembly.buffered.load(Ordering::Relaxed), VXLAN_MAX_REASSEMBLIES_PER_SOURCE - 1);
+        for buffer in reassembly.pending.lock().unwrap().values_mut() {
+            buffer.started -= VXLAN_REASSEMBLY_TIMEOUT;
+        }
+        assert_eq!(reassembly.expire(), VXLAN_MAX_REASSEMBLIES_PER_SOURCE - 1);
+        assert_eq!(reassembly.buffered.load(Ordering::Relaxed), 0);
+
+        // Senders together cannot buffer more than the shared budget
+        let large = vec![b'x'; VXLAN_MAX_REASSEMBLED_BYTES];
+        let senders = VXLAN_MAX_BUFFERED_BYTES / VXLAN_MAX_REASSEMBLED_BYTES;
+        for port in 0..senders as u16 {
+            let sender = SocketAddr::from(([127, 0, 0, 3], port));
+            assert_eq!(reassembly.accept(sender, &fragment(0, &large)), Ok(None));
+        }
+        assert!(reassembly.accept(other, &fragment(0, b"a")).is_err());
+        assert_eq!(reassembly.pending.lock().unwrap().len(), senders);
+        assert_eq!(reassembly.buffered.load(Ordering::Relaxed), VXLAN_MAX_BUFFERED_BYTES);
+    }
+
+    #[tokio::test]
+    async fn test_vxlan_fragments_are_rate_limited() {
+        let internals = test_internals_with(ServerConfig {
+            rate_limit_per_sec: 2,
+            ..test_config()
+        }).await;
+        let socket = Arc::new(UdpSocket::bind("127.0.0.1:0").await.unwrap());
+        let src: SocketAddr = "127.0.0.1:9".parse().unwrap();
+
+        // First halves of distinct commands: only those within the bucket are buffered
+        for id in 0..10 {
+            let packet = &vxlan_fragments(id, b"ab", 1)[0];
+            ControlPlaneServer::handle_vxlan_packet(packet, src, internals.clone(), socket.clone()).await.unwrap();
+        }
+        let pending = internals.vxlan_fragments.pending.lock().unwrap().len();
+        assert!((1..=3).contains(&pending), "{} reassemblies buffered", pending);
+    }
+
+    #[tokio::test]
+    async fn test_http_router_indices_stats() {
+        use axum::http::Request;
+
+        let internals = test_internals().await;
+        let request = Request::get("/v1/indices/stats").body(axum::body::Body::empty()).unwrap();
+        let (status, body) = call_router(internals.clone(), request).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["success"], true);
+        assert_eq!(body["gfef_stats"]["models_loaded"], 0);
+        assert_eq!(body["triple_ip_lock_status"]["lock1_gfef_index"], "NOT_LOADED");
+        assert_eq!(internals.stats.read().await.http_requests, 1);
+    }
+
+    #[tokio::test]
+    async fn test_http_chunked_index_upload() {
+        use axum::http::{header, Request};
+
+        let post_json = |uri: String, body: serde_json::Value| {
+            Request::post(uri)
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(axum::body::Body::from(body.to_string()))
+                .unwrap()
+        };
+        let layers: Vec<serde_json::Value> = (0..6)
+            .map(|i| serde_json::json!({
+                "layer_id": i,
+                "name": format!("layer_{}", i),
+                "neurons": 5,
+                "pc_shape": [8, 4],
+            }))
+            .collect();
+        let mut metadata = serde_json::json!({
+            "model": "chunked",
+            "k_components": 4,
+            "fft_bins": 16,
+            "total_neurons": 30,
+        });
+
+        // Single-shot reference
+        let single = test_internals().await;
+        let mut full = metadata.clone();
+        full["layers"] = serde_json::json!(layers);
+        let (status, body) = call_router(single.clone(), post_json("/v1/index/upload".to_string(), full)).await;
+        assert_eq!(status, StatusCode::OK, "{}", body);
+
+        // Same index in three chunks of two layers
+        let chunked = test_internals().await;
+        metadata["layers"] = serde_json::json!([]);
+        let (status, body) = call_router(chunked.clone(), post_json("/v1/index/upload/init".to_string(), metadata)).await;
+        assert_eq!(status, StatusCode::OK, "{}", body);
+        let upload_id = body["upload_id"].as_str().unwrap().to_string();
+
+        for (n, chunk) in layers.chunks(2).enumerate() {
+            let uri = format!("/v1/index/upload/{}/chunk", upload_id);
+            let (status, body) = call_router(chunked.clone(), post_json(uri, serde_json::json!({ "layers": chunk }))).await;
+            assert_eq!(status, StatusCode::OK, "{}", body);
+            assert_eq!(body["chunks"], n + 1);
+            assert_eq!(body["layers_received"], 2 * (n + 1));
+        }
+
+        let commit = format!("/v1/index/upload/{}/commit", upload_id);
+        let (status, body) = call_router(chunked.clone(), post_json(commit.clone(), serde_json::json!(null))).await;
+        assert_eq!(status, StatusCode::OK, "{}", body);
+        assert_eq!(body["num_layers"], 6);
+
+        let expected = single.gfef_predictor.read().await.get_index("chunked").cloned().unwrap();
+        let actual = chunked.gfef_predictor.read().await.get_index("chunked").cloned().unwrap();
+        assert_eq!(
+            serde_json::to_value(&actual.layers).unwrap(),
+            serde_json::to_value(&expected.layers).unwrap()
+        );
+        assert_eq!(actual.total_neurons, expected.total_neurons);
+        assert_eq!(actual.config.k_components, expected.config.k_components);
+        assert_eq!(actual.config.fft_bins, expected.config.fft_bins);
+
+        // The upload is gone once committed
+        let (status, _) = call_router(chunked, post_json(commit, serde_json::json!(null))).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_http_chunked_upload_limits() {
+        use axum::http::{header, Request};
+
+        let post_json = |uri: String, body: serde_json::Value| {
+            Request::post(uri)
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(axum::body::Body::from(body.to_string()))
+                .unwrap()
+        };
+        let server = ControlPlaneServer::new(test_config()).await.unwrap();
+        let mut internals = server.clone_internals();
+        internals.index_uploads = Arc::new(IndexUploads { max_pending: 2, max_bytes: 256, ..IndexUploads::default() });
+        let internals = Arc::new(internals);
+        let metadata = serde_json::json!({ "model": "limited", "k_components": 4, "total_neurons": 5 });
+        let spool = |id: &str| std::env::temp_dir().join(format!("gfef-upload-{}.jsonl", id));
+
+        // Only two uploads may be in progress
+        let mut ids = Vec::new();
+        for _ in 0..2 {
+            let (status, body) = call_router(internals.clone(), post_json("/v1/index/upload/init".to_string(), metadata.clone())).await;
+            assert_eq!(status, StatusCode::OK, "{}", body);
+            ids.push(body["upload_id"].as_str().unwrap().to_string());
+        }
+        let (status, body) = call_router(internals.clone(), post_json("/v1/index/upload/init".to_string(), metadata.clone())).await;
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(body["error"], "TOO_MANY_UPLOADS");
+
+        // A chunk past the byte cap aborts its upload and frees the slot
+        let small = serde_json::json!({ "layers": [{ "layer_id": 0, "neurons": 5, "pc_shape": [8, 4] }] });
+        let large = serde_json::json!({ "layers": [{ "layer_id": 1, "name": "x".repeat(256) }] });
+        let chunk = format!("/v1/index/upload/{}/chunk", ids[0]);
+        let (status, body) = call_router(internals.clone(), post_json(chunk.clone(), small)).await;
+        assert_eq!(status, StatusCode::OK, "{}", body);
+        let (status, body) = call_router(internals.clone(), post_json(chunk.clone(), large)).await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(body["error"], "UPLOAD_TOO_LARGE");
+        assert!(!spool(&ids[0]).exists());
+        let (status, _) = call_router(internals.clone(), post_json(chunk, serde_json::json!({ "layers": [] }))).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, body) = call_router(internals.clone(), post_json("/v1/index/upload/init".to_string(), metadata)).await;
+        assert_eq!(status, StatusCode::OK, "{}", body);
+        ids.push(body["upload_id"].as_str().unwrap().to_string());
+
+        // Idle uploads are swept along with their spools
+        assert_eq!(internals.index_uploads.sweep(Duration::from_secs(3600)).await, 0);
+        assert_eq!(internals.index_uploads.sweep(Duration::ZERO).await, 2);
+        for id in &ids[1..] {
+            assert!(!spool(id).exists());
+            let commit = format!("/v1/index/upload/{}/commit", id);
+            let (status, _) = call_router(internals.clone(), post_json(commit, serde_json::json!(null))).await;
+            assert_eq!(status, StatusCode::NOT_FOUND);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_http_router_predict() {
+        use axum::http::{header, Request};
+        use symmetrix_core::gfef::index::{IndexConfig, LayerIndex, NeuronSignature};
+
+        let internals = test_internals().await;
+
+        // 2 inputs, 2 components, 4 neurons: neuron i points along component i % 2
+        let signatures = (0..4u32)
+            .map(|i| NeuronSignature {
+                layer_id: 0,
+                neuron_idx: i,
+                energy: 1.0,
+                projection: if i % 2 == 0 { vec![1.0, 0.0] } else { vec![0.0, 1.0] },
+                spectral_hash: Vec::new(),
+            })
+            .collect();
+        internals.gfef_predictor.write().await.register_index(GFEFIndex {
+            id: uuid::Uuid::new_v4(),
+            customer_id: uuid::Uuid::nil(),
+            model_id: "tiny".to_string(),
+            model_name: "tiny".to_string(),
+            generated_at: chrono::Utc::now(),
+            expires_at: None,
+            layers: vec![LayerIndex {
+                layer_id: 0,
+                layer_name: "layer_0".to_string(),
+                num_neurons: 4,
+                input_dim: 2,
+                k_components: 2,
+                principal_components: vec![1.0, 0.0, 0.0, 1.0],
+                signatures,
+                layer_sparsity: None,
+            }],
+            total_neurons: 4,
+            config: IndexConfig { k_components: 2, fft_bins: 4, target_sparsity: 0.95 },
+        });
+
+        let customer_id = uuid::Uuid::new_v4();
+        let body = serde_json::json!({
+            "session_token": "token",
+            "customer_id": customer_id,
+            "model_id": "tiny",
+            "layer_id": 0,
+            "input_embedding_hash": "hash",
+            "encrypted_embedding": null,
+            "input_embedding": [1.0, 0.0],
+        });
+        let predict = || {
+            Request::post("/v1/predict")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(axum::body::Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        // Unknown customer is refused
+        let (status, _) = call_router(internals.clone(), predict()).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        internals.gfef_subscriptions.write().await
+            .create_subscription(customer_id, SubscriptionTier::Professional);
+        let (status, response) = call_router(internals.clone(), predict()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(response["active_neurons"], serde_json::json!([0]));
+
+        // Malformed bodies are rejected by the typed extractor with a structured 400
+        let bad = |body: String| {
+            Request::post("/v1/predict")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(axum::body::Body::from(body))
+                .unwrap()
+        };
+        let (status, response) = call_router(internals.clone(), bad("{\"model_id\": 7}".to_string())).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(response["success"], false);
+        assert_eq!(response["error"], "INVALID_REQUEST");
+
+        let mut missing = body.clone();
+        missing.as_object_mut().unwrap().remove("layer_id");
+        let (status, response) = call_router(internals.clone(), bad(missing.to_string())).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let message = response["message"].as_str().unwrap();
+        assert!(message.contains("missing field `layer_id`"), "{}", message);
+
+        let (status, response) = call_router(internals, bad("not json".to_string())).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(response["error"], "INVALID_REQUEST");
+    }
+
+    #[tokio::test]
+    async fn test_http_auth_tokens() {
+        use axum::http::{header, Request};
+        use axum::body::Body;
+
+        let internals = test_internals_with(ServerConfig {
+            require_auth: true,
+            api_token: Some("s3cret".to_string()),
+            ..test_config()
+        }).await;
+
+        let command = |token: Option<&str>| {
+            let mut request = Request::post("/v1/command").header(header::CONTENT_TYPE, "application/json");
+            if let Some(token) = token {
+                request = request.header(header::AUTHORIZATION, format!("Bearer {}", token));
+            }
+            request
+                .body(Body::from(r#"{"cmd": "CacheSet", "data": {"key": "k", "value": "v", "ttl_seconds": null}}"#))
+                .unwrap()
+        };
+
+        let (status, body) = call_router(internals.clone(), command(None)).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["success"], false);
+        let (status, _) = call_router(internals.clone(), command(Some("wrong"))).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, body) = call_router(internals.clone(), command(Some("s3cret"))).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["success"], true);
+
+        // Read-only health stays open, GFEF routes are protected
+        let (status, _) = call_router(internals.clone(), Request::get("/health").body(Body::empty()).unwrap()).await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = call_router(internals.clone(), Request::get("/v1/indices/stats").body(Body::empty()).unwrap()).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let request = Request::get("/v1/indices/stats")
+            .header(header::AUTHORIZATION, "Bearer s3cret")
+            .body(Body::empty())
+            .unwrap();
+        let (status, _) = call_router(internals, request).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_cache_ttl_commands() {
+        let internals = test_internals().await;
+        let run = |command: ControlCommand| ControlPlaneServer::process_command(command, internals.clone());
+
+        let response = run(ControlCommand::CacheTtl { key: "missing".to_string() }).await;
+        assert!(!response.success);
+        assert_eq!(response.data.unwrap()["ttl"], -2);
+
+        run(ControlCommand::CacheSet { key: "k".to_string(), value: "v".to_string(), ttl_seconds: None }).await;
+        let response = run(ControlCommand::CacheTtl { key: "k".to_string() }).await;
+        assert_eq!(response.data.unwrap()["ttl"], -1);
+
+        assert!(run(ControlCommand::CacheExpire { key: "k".to_string(), ttl_seconds: 120 }).await.success);
+        let ttl = run(ControlCommand::CacheTtl { key: "k".to_string() }).await.data.unwrap()["ttl"].as_i64().unwrap();
+        assert!(ttl > 0 && ttl <= 120);
+    }
+
+    #[tokio::test]
+    async fn test_cache_snapshot_command() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ServerConfig {
+            persistence_dir: Some(dir.path().to_string_lossy().into_owned()),
+            ..test_config()
+        };
+        let internals = test_internals_with(config).await;
+        let run = |command: ControlCommand| ControlPlaneServer::process_command(command, internals.clone());
+
+        run(ControlCommand::CacheSet { key: "k".to_string(), value: "v".to_string(), ttl_seconds: Some(600) }).await;
+        let response = run(ControlCommand::CacheSnapshot { path: "snapshots/cache.json".to_string() }).await;
+        assert!(response.success, "{}", response.message);
+
+        let restored = UaoQtcamCache::new(1024 * 1024, 250.0);
+        assert_eq!(restored.restore(&dir.path().join("snapshots/cache.json")).unwrap(), 1);
+        assert_eq!(restored.get("k").unwrap(), Some(b"v".to_vec()));
+        assert!(restored.ttl("k").unwrap().unwrap() > 0);
+
+        for path in ["../escape.json", "/tmp/escape.json", ""] {
+            let response = run(ControlCommand::CacheSnapshot { path: path.to_string() }).await;
+            assert_eq!(response.error_code.as_deref(), Some("INVALID_REQUEST"), "{}", path);
+        }
+        let response = ControlPlaneServer::process_command(
+            ControlCommand::CacheSnapshot { path: "cache.json".to_string() },
+            test_internals().await,
+        ).await;
+        assert!(!response.success);
+    }
+
+    #[tokio::test]
+    async fn test_cache_list_commands() {
+        let internals = test_internals().await;
+        let run = |json: &str| {
+            let (command, _) = parse_command(json.as_bytes()).unwrap();
+            ControlPlaneServer::process_command(command, internals.clone())
+        };
+
+        let response = run(r#"{"cmd": "ListPush", "data": {"key": "q", "values": ["a", "b"]}}"#).await;
+        assert_eq!(response.data.unwrap()["length"], 2);
+        let response = run(r#"{"cmd": "ListRange", "data": {"key": "q", "start": 0, "stop": -1}}"#).await;
+        assert_eq!(response.data.unwrap()["values"], serde_json::json!(["a", "b"]));
+
+        let response = run(r#"{"cmd": "CacheGet", "data": {"key": "q"}}"#).await;
+        assert!(!response.success);
+        assert!(response.message.contains("WRONGTYPE"));
+        assert_eq!(response.error_code.as_deref(), Some("WRONG_TYPE"));
+
+        assert!(run(r#"{"cmd": "HashSet", "data": {"key": "h", "field": "f", "value": "v"}}"#).await.success);
+        let response = run(r#"{"cmd": "HashGet", "data": {"key": "h", "field": "f"}}"#).await;
+        assert_eq!(response.data.unwrap()["value"], "v");
+    }
+
+    #[tokio::test]
+    async fn test_region_stats_command() {
+        let internals = test_internals().await;
+        for (size_bytes, region) in [(4096, "kv_cache"), (8192, "weights"), (1024, "kv_cache")] {
+            let response = ControlPlaneServer::process_command(ControlCommand::AllocateMemory {
+                size_bytes,
+                region: region.to_string(),
+            }, internals.clone()).await;
+            assert!(response.success);
+        }
+
+        let (command, _) = parse_command(br#"{"cmd": "GetRegionStats"}"#).unwrap();
+        let response = ControlPlaneServer::process_command(command, internals).await;
+        assert!(response.success);
+        let data = response.data.unwrap();
+        assert_eq!(data["kv_cache"]["live_bytes"], 5120);
+        assert_eq!(data["kv_cache"]["live_allocations"], 2);
+        assert_eq!(data["weights"]["live_bytes"], 8192);
+    }
+
+    #[tokio::test]
+    async fn test_project_cascade_command() {
+        let internals = test_internals().await;
+        let (command, _) = parse_command(
+            br#"{"cmd": "ProjectCascade", "data": {"physical_gbps": 400, "physical_mem_gb": 40}}"#).unwrap();
+        let response = ControlPlaneServer::process_command(command, internals.clone()).await;
+        assert!(response.success);
+        let data = response.data.unwrap();
+        assert_eq!(data["effective_bandwidth_exabps"], 100.0);
+        assert_eq!(data["effective_memory_exabytes"], 100.0);
+
+        let response = ControlPlaneServer::process_command(
+            ControlCommand::ProjectCascade { physical_gbps: -1.0, physical_mem_gb: 40.0 }, internals).await;
+        assert_eq!(response.error_code.as_deref(), Some("INVALID_REQUEST"));
+    }
+
+    #[tokio::test]
+    async fn test_error_codes() {
+        let internals = test_internals().await;
+
+        let response = ControlPlaneServer::process_command(
+            ControlCommand::CacheGet { key: "absent".to_string() }, internals.clone()).await;
+        assert!(!response.success);
+        assert_eq!(response.error_code.as_deref(), Some("NOT_FOUND"));
+
+        let response = ControlPlaneServer::process_command(ControlCommand::CacheSet {
+            key: "present".to_string(),
+            value: "v".to_string(),
+            ttl_seconds: None,
+        }, internals.clone()).await;
+        assert!(response.success);
+        assert_eq!(response.error_code, None);
+
+        assert_eq!(unauthorized_response().error_code.as_deref(), Some("UNAUTHORIZED"));
+        assert_eq!(rate_limited_response().error_code.as_deref(), Some("RATE_LIMITED"));
+    }
+
+    #[tokio::test]
+    async fn test_get_math_stats_command() {
+        let (command, _) = parse_command(br#"{"cmd": "GetMathStats"}"#).unwrap();
+        assert!(matches!(command, ControlCommand::GetMathStats));
+        assert_eq!(serde_json::to_value(&command).unwrap(), serde_json::json!({"cmd": "GetMathStats"}));
+
+        let internals = test_internals().await;
+        let response = ControlPlaneServer::process_command(command, internals).await;
+        assert!(response.success);
+        let data = response.data.unwrap();
+        for field in [
+            "galois_field_prime",
+            "galois_operations_per_sec",
+            "tensor_cache_hit_rate",
+            "tensor_blocks_active",
+            "sheaf_cohomology_dimension",
+            "sheaf_last_computation",
+            "crt_decomposition_active",
+        ] {
+            assert!(data.get(field).is_some(), "missing {}", field);
+        }
+        assert!(data["galois_operations_per_sec"].as_u64().unwrap() > 0);
+        let hit_rate = data["tensor_cache_hit_rate"].as_f64().unwrap();
+        assert!((0.0..=100.0).contains(&hit_rate));
+    }
+
+    #[tokio::test]
+    async fn test_sheaf_two_node_allocation() {
+        let internals = test_internals().await;
+        let run = |json: &str| {
+            let (command, _) = parse_command(json.as_bytes()).unwrap();
+            ControlPlaneServer::process_command(command, internals.clone())
+        };
+
+        assert!(run(r#"{"cmd": "SheafAddNode", "data": {"node_id": 1, "resources": {"CPU": 4.0, "Memory": 8192.0}}}"#).await.success);
+        assert!(run(r#"{"cmd": "SheafAddNode", "data": {"node_id": 2, "resources": {"CPU": 2.0}}}"#).await.success);
+        assert!(run(r#"{"cmd": "SheafAddRestriction", "data": {"source": 1, "target": 2, "matrix": [[1.0, 0.0], [0.0, 1.0]]}}"#).await.success);
+        assert!(!run(r#"{"cmd": "SheafAddRestriction", "data": {"source": 1, "target": 2, "matrix": [[1.0], [0.0, 1.0]]}}"#).await.success);
+
+        let response = run(r#"{"cmd": "SheafAllocate", "data": {"requests": {"1": {"CPU": 8.0, "Memory": 1024.0}, "2": {"CPU": 1.0, "Memory": 512.0}}}}"#).await;
+        assert!(response.success, "{}", response.message);
+        let data = response.data.unwrap();
+        assert_eq!(data["allocation"]["1"]["CPU"], 4.0);
+        assert_eq!(data["allocation"]["1"]["Memory"], 1024.0);
+        assert_eq!(data["allocation"]["2"]["CPU"], 1.0);
+        assert_eq!(data["allocation"]["2"]["Memory"], 0.0);
+        assert!(data.get("warning").is_some());
+
+        let response = run(r#"{"cmd": "SheafAllocate", "data": {"requests": {"3": {"CPU": 1.0}}}}"#).await;
+        assert!(!response.success);
+    }
+
+    #[tokio::test]
+    async fn test_prometheus_metrics() {
+        use axum::http::Request;
+        use axum::body::Body;
+        use tower::ServiceExt;
+
+        let internals = test_internals().await;
+        ControlPlaneServer::process_command(ControlCommand::Health, internals.clone()).await;
+        ControlPlaneServer::process_command(ControlCommand::Health, internals.clone()).await;
+        ControlPlaneServer::process_command(ControlCommand::CacheGet { key: "missing".to_string() }, internals.clone()).await;
+
+        let response = ControlPlaneServer::http_router(internals)
+            .oneshot(Request::get("/metrics").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers()[header::CONTENT_TYPE].to_str().unwrap().starts_with("text/plain"));
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let text = String::from_utf8(body.to_vec()).unwrap();
+
+        // Every sample line is `name{labels} value` for a name declared by a TYPE line
+        let mut declared = std::collections::HashSet::new();
+        let mut samples = std::collections::HashMap::new();
+        for line in text.lines() {
+            if let Some(rest) = line.strip_prefix("# TYPE ") {
+                let mut parts = rest.split_whitespace();
+                declared.insert(parts.next().unwrap().to_string());
+                assert_eq!(parts.next(), Some("counter"));
+                continue;
+            }
+            if line.starts_with('#') || line.is_empty() {
+                continue;
+            }
+            let (series, value) = line.rsplit_once(' ').expect("sample without value");
+            value.parse::<f64>().expect("non-numeric sample value");
+            let name = series.split('{').next().unwrap();
+            assert!(name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'));
+            assert!(declared.contains(name), "{} has no TYPE line", name);
+            if let Some(labels) = series.strip_prefix(name).filter(|l| !l.is_empty()) {
+                assert!(labels.starts_with('{') && labels.ends_with('}'));
+            }
+            samples.insert(series.to_string(), value.to_string());
+        }
+
+        for name in [
+            "control_commands_total",
+            "control_cache_hits_total",
+            "control_cache_misses_total",
+            "control_latency_ns_sum",
+        ] {
+            assert!(samples.contains_key(name), "missing {}", name);
+        }
+        assert_eq!(samples["control_commands_total"], "3");
+        assert_eq!(samples["control_cache_misses_total"], "1");
+        assert_eq!(samples["control_commands_by_type_total{command=\"Health\"}"], "2");
+        assert_eq!(samples["control_commands_by_type_total{command=\"CacheGet\"}"], "1");
+    }
+
+    #[tokio::test]
+    async fn test_websocket_batch_commands() {
+        let internals = test_internals().await;
+
+        let batch = r#"[
+            {"cmd": "CacheSet", "data": {"key": "batch", "value": "v", "ttl_seconds": null}},
+            {"cmd": "CacheGet", "data": {"key": "batch"}},
+            {"cmd": "CacheDelete", "data": {"key": "batch"}}
+        ]"#;
+        let reply = ControlPlaneServer::process_ws_text(batch, None, None, internals.clone()).await;
+        let responses: Vec<ControlResponse> = serde_json::from_str(&reply).unwrap();
+
+        assert_eq!(responses.len(), 3);
+        assert!(responses.iter().all(|r| r.success));
+        assert_eq!(responses[0].message, "Key 'batch' set successfully");
+        assert_eq!(responses[1].data.as_ref().unwrap()["value"], "v");
+        assert_eq!(responses[2].message, "Key 'batch' deleted");
+
+        // Single-object frames still get a single response
+        let reply = ControlPlaneServer::process_ws_text(r#"{"cmd": "Health"}"#, None, None, internals.clone()).await;
+        let response: ControlResponse = serde_json::from_str(&reply).unwrap();
+        assert!(response.success);
+
+        // A malformed entry fails in place without aborting the batch
+        let reply = ControlPlaneServer::process_ws_text(r#"[{"cmd": "Nope"}, {"cmd": "Health"}]"#, None, None, internals).await;
+        let responses: Vec<ControlResponse> = serde_json::from_str(&reply).unwrap();
+        assert!(!responses[0].success);
+        assert!(responses[1].success);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_per_client() {
+        let internals = test_internals_with(ServerConfig {
+            rate_limit_per_sec: 5,
+            ..test_config()
+        }).await;
+        let noisy: SocketAddr = "10.0.0.1:4789".parse().unwrap();
+        let quiet: SocketAddr = "10.0.0.2:4789".parse().unwrap();
+
+        let mut rejected = 0;
+        for _ in 0..20 {
+            let response = ControlPlaneServer::process_from(Some(noisy), ControlCommand::Health, None, internals.clone()).await;
+            if !response.success {
+                assert_eq!(response.message, "rate limited");
+                rejected += 1;
+            }
+        }
+        assert!(rejected >= 10, "only {} of 20 burst commands rejected", rejected);
+
+        // Another address has its own bucket
+        for _ in 0..5 {
+            let response = ControlPlaneServer::process_from(Some(quiet), ControlCommand::Health, None, internals.clone()).await;
+            assert!(response.success);
+        }
+
+        // Batched WebSocket commands each take a token
+        let reply = ControlPlaneServer::process_ws_text(r#"[{"cmd": "Health"}, {"cmd": "Health"}]"#, Some(noisy), None, internals).await;
+        let responses: Vec<ControlResponse> = serde_json::from_str(&reply).unwrap();
+        assert!(responses.iter().all(|r| r.message == "rate limited"));
+    }
+
+    #[test]
+    fn test_rate_limiter_caps_tracked_sources() {
+        let limiter = RateLimiter::from_config(&ServerConfig {
+            rate_limit_per_sec: 1,
+            max_connections: 3,
+            ..test_config()
+        });
+        let addr = |i: u8| -> SocketAddr { format!("10.0.0.{}:4789", i).parse().unwrap() };
+
+        // A flood of new sources never grows the map past the cap
+        for i in 1..=10 {
+            assert!(limiter.allow(addr(i)));
+            let buckets = limiter.buckets.lock().unwrap();
+            assert!(buckets.by_addr.len() <= 3);
+            assert_eq!(buckets.by_addr.len(), buckets.order.len());
+        }
+
+        // The newest sources keep their drained buckets; the oldest was forgotten
+        assert!(!limiter.allow(addr(10)));
+        assert!(!limiter.allow(addr(8)));
+        assert!(limiter.allow(addr(1)));
+        let buckets = limiter.buckets.lock().unwrap();
+        assert!(!buckets.by_addr.contains_key(&addr(8)));
+        assert!(buckets.by_addr.contains_key(&addr(1)));
+    }
+
+    #[tokio::test]
+    async fn test_container_lifecycle() {
+        let internals = test_internals().await;
+        let launch = |count: usize| ControlCommand::LaunchContainers {
+            template: "web".to_string(),
+            count,
+            memory_mb: Some(64),
+            cpu: None,
+        };
+        let list = |all: bool| ControlCommand::ListContainers { all };
+
+        let response = ControlPlaneServer::process_command(launch(3), internals.clone()).await;
+        assert!(response.success, "{}", response.message);
+        let launched: Vec<ContainerInfo> = serde_json::from_value(response.data.unwrap()["containers"].clone()).unwrap();
+        assert_eq!(launched.len(), 3);
+        assert!(launched.iter().all(|c| c.memory_mb == 64 && c.status == ContainerStatus::Running));
+
+        let response = ControlPlaneServer::process_command(list(false), internals.clone()).await;
+        assert_eq!(response.data.unwrap()["containers"].as_array().unwrap().len(), 3);
+
+        let id = launched[0].id.clone();
+        let response = ControlPlaneServer::process_command(ControlCommand::RemoveContainer { id: id.clone(), force: false }, internals.clone()).await;
+        assert!(!response.success);
+        let response = ControlPlaneServer::process_command(ControlCommand::StopContainer { id: id.clone() }, internals.clone()).await;
+        assert!(response.success);
+
+        let response = ControlPlaneServer::process_command(list(false), internals.clone()).await;
+        assert_eq!(response.data.unwrap()["containers"].as_array().unwrap().len(), 2);
+        let response = ControlPlaneServer::process_command(list(true), internals.clone()).await;
+        assert_eq!(response.data.unwrap()["containers"].as_array().unwrap().len(), 3);
+
+        let response = ControlPlaneServer::process_command(ControlCommand::RemoveContainer { id, force: false }, internals.clone()).await;
+        assert!(response.success);
+
+        // The SymmetrixConfig cap counts running containers
+        let max = internals.runtime.config.max_containers;
+        let response = ControlPlaneServer::process_command(launch(max - 1), internals.clone()).await;
+        assert!(!response.success);
+        assert!(response.message.contains("limit"));
+        let response = ControlPlaneServer::process_command(launch(max - 2), internals.clone()).await;
+        assert!(response.success);
+        let response = ControlPlaneServer::process_command(launch(1), internals).await;
+        assert!(!response.success);
+    }
+
+    #[tokio::test]
+    async fn test_command_token_envelope() {
+        let internals = test_internals_with(ServerConfig {
+            require_auth: true,
+            api_token: Some("s3cret".to_string()),
+            ..test_config()
+        }).await;
+
+        let (command, token) = parse_command(br#"{"cmd": "CacheIncr", "data": {"key": "k"}, "token": "s3cret"}"#).unwrap();
+        assert_eq!(token.as_deref(), Some("s3cret"));
+        let response = ControlPlaneServer::process_authorized(command.clone(), token.as_deref(), internals.clone()).await;
+        assert!(response.success);
+
+        let response = ControlPlaneServer::process_authorized(command, Some("nope"), internals.clone()).await;
+        assert!(!response.success);
+        assert_eq!(response.data.unwrap()["status"], 401);
+
+        let (command, token) = parse_command(br#"{"cmd": "Health"}"#).unwrap();
+        assert!(token.is_none());
+        assert!(ControlPlaneServer::process_authorized(command, None, internals).await.success);
+    }
+
     #[tokio::test]
     async fn test_cache_operations() {
         // Create cache with 1MB size and 250× compression
@@ -1548,6 +4749,345 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_shutdown_closes_websockets() {
+        use tokio_tungstenite::tungstenite::{self, protocol::frame::coding::CloseCode};
+
+        let internals = test_internals().await;
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let http = tokio::spawn(ControlPlaneServer::serve_http(listener, internals.clone()));
+
+        let (mut ws, _) = tokio_tungstenite::connect_async(format!("ws://{}/ws", addr)).await.unwrap();
+        let welcome = ws.next().await.unwrap().unwrap();
+        assert!(welcome.into_text().unwrap().contains("welcome"));
+        assert_eq!(internals.in_flight.active(), 1);
+
+        internals.shutdown.send(()).unwrap();
+        match ws.next().await.unwrap().unwrap() {
+            tungstenite::Message::Close(Some(frame)) => assert_eq!(frame.code, CloseCode::Away),
+            other => panic!("expected a Close frame, got {:?}", other),
+        }
+        // Clean close handshake: the stream ends rather than erroring
+        assert!(ws.next().await.is_none());
+
+        assert!(internals.in_flight.drain(Duration::from_secs(5)).await);
+        assert!(tokio::time::timeout(Duration::from_secs(5), http).await.unwrap().unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_drains_http_requests() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let internals = test_internals_with(ServerConfig {
+            http_idle_timeout_secs: 0,
+            ..test_config()
+        }).await;
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let http = tokio::spawn(ControlPlaneServer::serve_http(listener, internals.clone()));
+
+        // Headers and half the body: the handler is in flight, waiting for the rest
+        let body = br#"{"cmd": "Health"}"#;
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let head = format!(
+            "POST /v1/command HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n",
+            body.len(),
+        );
+        stream.write_all(head.as_bytes()).await.unwrap();
+        stream.write_all(&body[..8]).await.unwrap();
+        assert!(tokio::time::timeout(Duration::from_secs(5), async {
+            while internals.in_flight.active() == 0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        }).await.is_ok());
+
+        internals.shutdown.send(()).unwrap();
+        assert!(!internals.in_flight.drain(Duration::from_millis(200)).await);
+
+        // The request still completes, then the connection is closed
+        stream.write_all(&body[8..]).await.unwrap();
+        let (head, body) = read_http_response(&mut stream).await;
+        assert!(head.starts_with("HTTP/1.1 200"), "unexpected reply: {}", head);
+        assert!(serde_json::from_str::<ControlResponse>(&body).unwrap().success);
+        let mut rest = Vec::new();
+        let closed = tokio::time::timeout(Duration::from_secs(5), stream.read_to_end(&mut rest)).await;
+        assert!(matches!(closed, Ok(Ok(0))), "connection not closed: {:?}", closed);
+
+        assert!(internals.in_flight.drain(Duration::from_secs(5)).await);
+        assert!(tokio::time::timeout(Duration::from_secs(5), http).await.unwrap().unwrap().is_ok());
+    }
+
+    /// Read one HTTP/1.1 response (head and `Content-Length` body) off `stream`
+    async fn read_http_response(stream: &mut (impl tokio::io::AsyncRead + Unpin)) -> (String, String) {
+        use tokio::io::AsyncReadExt;
+
+        let mut head = Vec::new();
+        let mut byte = [0u8; 1];
+        while !head.ends_with(b"\r\n\r\n") {
+            assert_eq!(stream.read(&mut byte).await.unwrap(), 1, "connection closed mid-response");
+            head.push(byte[0]);
+        }
+        let head = String::from_utf8(head).unwrap();
+        let length: usize = head.lines()
+            .find_map(|line| line.to_ascii_lowercase().strip_prefix("content-length:").map(|v| v.trim().parse().unwrap()))
+            .expect("response without Content-Length");
+        let mut body = vec![0u8; length];
+        stream.read_exact(&mut body).await.unwrap();
+        (head, String::from_utf8(body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_http_keep_alive() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let internals = test_internals_with(ServerConfig {
+            http_idle_timeout_secs: 1,
+            ..test_config()
+        }).await;
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(ControlPlaneServer::serve_http(listener, internals.clone()));
+
+        // Two requests on the same socket, no reconnect in between
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        for (path, message) in [("/health", "SYMMETRIX Control Plane Server is degraded"), ("/cache/stats", "Cache statistics")] {
+            stream.write_all(format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).as_bytes()).await.unwrap();
+            let (head, body) = read_http_response(&mut stream).await;
+            assert!(head.starts_with("HTTP/1.1 200"), "unexpected reply for {}: {}", path, head);
+            let response: ControlResponse = serde_json::from_str(&body).unwrap();
+            assert!(response.success);
+            assert_eq!(response.message, message);
+        }
+
+        // Silent past the idle timeout: the server closes the connection
+        let mut rest = Vec::new();
+        let closed = tokio::time::timeout(Duration::from_secs(5), stream.read_to_end(&mut rest)).await;
+        assert!(matches!(closed, Ok(Ok(0))), "idle connection not closed: {:?}", closed);
+    }
+
+    #[tokio::test]
+    async fn test_idle_websocket_is_reaped() {
+        use tokio_tungstenite::tungstenite::{self, protocol::frame::coding::CloseCode};
+
+        let internals = test_internals_with(ServerConfig {
+            ws_ping_interval_secs: 1,
+            ws_idle_timeout_secs: 2,
+            ..test_config()
+        }).await;
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(ControlPlaneServer::serve_http(listener, internals.clone()));
+
+        let (mut ws, _) = tokio_tungstenite::connect_async(format!("ws://{}/ws", addr)).await.unwrap();
+        let welcome = ws.next().await.unwrap().unwrap();
+        assert!(welcome.into_text().unwrap().contains("welcome"));
+
+        // Stay silent: without polling the stream the client never answers pings
+        tokio::time::sleep(Duration::from_millis(2500)).await;
+        assert_eq!(internals.stats.read().await.ws_connections_reaped, 1);
+
+        let mut pings = 0;
+        let close = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                match ws.next().await {
+                    Some(Ok(tungstenite::Message::Ping(_))) => pings += 1,
+                    Some(Ok(tungstenite::Message::Close(frame))) => return frame,
+                    other => panic!("expected Ping or Close, got {:?}", other),
+                }
+            }
+        }).await.unwrap();
+        assert!(pings >= 1);
+        assert_eq!(close.unwrap().code, CloseCode::Away);
+        assert!(tokio::time::timeout(Duration::from_secs(5), async {
+            while internals.in_flight.active() > 0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        }).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_tls_health() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let cert_path = dir.path().join("cert.pem");
+        let key_path = dir.path().join("key.pem");
+        std::fs::write(&cert_path, cert.serialize_pem().unwrap()).unwrap();
+        std::fs::write(&key_path, cert.serialize_private_key_pem()).unwrap();
+
+        let config = ServerConfig {
+            tls_cert_path: Some(cert_path.to_string_lossy().into_owned()),
+            tls_key_path: Some(key_path.to_string_lossy().into_owned()),
+            ..test_config()
+        };
+        let acceptor = config.tls_acceptor().unwrap().expect("TLS configured");
+        assert!(test_config().tls_acceptor().unwrap().is_none());
+
+        let internals = test_internals_with(config).await;
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let https = tokio::spawn(ControlPlaneServer::serve_https(listener, acceptor, internals.clone()));
+
+        let mut roots = rustls::RootCertStore::empty();
+        roots.add(&rustls::Certificate(cert.serialize_der().unwrap())).unwrap();
+        let client = rustls::ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        let tcp = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let mut tls = tokio_rustls::TlsConnector::from(Arc::new(client))
+            .connect(rustls::ServerName::try_from("localhost").unwrap(), tcp)
+            .await
+            .unwrap();
+
+        tls.write_all(b"GET /health HTTP/1.1\r\nHost: localhost\r\n\r\n").await.unwrap();
+        let (head, body) = read_http_response(&mut tls).await;
+        assert!(head.starts_with("HTTP/1.1 200"), "unexpected reply: {}", head);
+        let response: ControlResponse = serde_json::from_str(&body).unwrap();
+        assert!(response.success);
+
+        // Shutdown closes the idle keep-alive connection
+        internals.shutdown.send(()).unwrap();
+        let mut rest = Vec::new();
+        let closed = tokio::time::timeout(Duration::from_secs(5), tls.read_to_end(&mut rest)).await;
+        assert!(matches!(closed, Ok(Ok(0))), "connection not closed: {:?}", closed);
+        assert!(tokio::time::timeout(Duration::from_secs(5), https).await.unwrap().unwrap().is_ok());
+    }
+
+    /// Open a calibration session for `customer_id`, returning the response
+    async fn establish_session(
+        internals: &Arc<ServerInternals>,
+        customer_id: uuid::Uuid,
+        tier: &str,
+        token: &str,
+    ) -> ControlResponse {
+        ControlPlaneServer::process_authorized(ControlCommand::EstablishSession {
+            customer_id,
+            tier: tier.to_string(),
+            token: token.to_string(),
+        }, None, internals.clone()).await
+    }
+
+    async fn calibration_matrix(internals: &Arc<ServerInternals>, session_id: &str, tier: Option<&str>) -> ControlResponse {
+        ControlPlaneServer::process_command(ControlCommand::GetCalibrationMatrix {
+            session_id: session_id.to_string(),
+            tier: tier.map(str::to_string),
+        }, internals.clone()).await
+    }
+
+    fn session_id(response: &ControlResponse) -> String {
+        response.data.as_ref().unwrap()["session_id"].as_str().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_calibration_session_establishment() {
+        let mut config = test_config();
+        config.require_auth = true;
+        config.api_token = Some("secret".to_string());
+        let internals = test_internals_with(config).await;
+        let customer_id = uuid::Uuid::new_v4();
+
+        // Entitlement comes from the customer's subscription
+        let response = establish_session(&internals, customer_id, "professional", "secret").await;
+        assert_eq!(response.error_code.as_deref(), Some("UNAUTHORIZED"));
+        internals.gfef_subscriptions.write().await
+            .create_subscription(customer_id, SubscriptionTier::Professional);
+
+        let response = establish_session(&internals, customer_id, "professional", "wrong").await;
+        assert!(!response.success);
+        assert_eq!(response.error_code.as_deref(), Some("UNAUTHORIZED"));
+
+        let response = establish_session(&internals, customer_id, "professional", "secret").await;
+        assert!(response.success, "{}", response.message);
+        let session_id = session_id(&response);
+
+        // Matrices are only issued to established sessions
+        let response = calibration_matrix(&internals, "cal-unknown", None).await;
+        assert_eq!(response.error_code.as_deref(), Some("UNAUTHORIZED"));
+
+        let response = calibration_matrix(&internals, &session_id, None).await;
+        assert!(response.success, "{}", response.message);
+        let data = response.data.unwrap();
+        assert_eq!(data["tier_name"], "professional");
+        assert_eq!(data["tier"], 3);
+        assert_eq!(data["values"].as_array().unwrap().len(), 64 * 64);
+        let repeat = calibration_matrix(&internals, &session_id, Some("professional")).await.data.unwrap();
+        assert_eq!(repeat["matrix_id"], data["matrix_id"]);
+
+        // Expired sessions are refused
+        internals.calibration_sessions.sessions.lock().unwrap()
+            .get_mut(&session_id).unwrap()
+            .expires_at = chrono::Utc::now() - chrono::Duration::seconds(1);
+        let response = calibration_matrix(&internals, &session_id, None).await;
+        assert_eq!(response.error_code.as_deref(), Some("UNAUTHORIZED"));
+    }
+
+    #[tokio::test]
+    async fn test_calibration_session_tier_mismatch() {
+        let internals = test_internals().await;
+        let customer_id = uuid::Uuid::new_v4();
+        internals.gfef_subscriptions.write().await
+            .create_subscription(customer_id, SubscriptionTier::Developer);
+
+        // A Developer subscription (100×) covers basic but not enterprise
+        let response = establish_session(&internals, customer_id, "enterprise", "").await;
+        assert_eq!(response.error_code.as_deref(), Some("UNAUTHORIZED"));
+        let response = establish_session(&internals, customer_id, "bogus", "").await;
+        assert_eq!(response.error_code.as_deref(), Some("INVALID_REQUEST"));
+
+        let response = establish_session(&internals, customer_id, "basic", "").await;
+        assert!(response.success, "{}", response.message);
+        let session_id = session_id(&response);
+
+        let response = calibration_matrix(&internals, &session_id, Some("professional")).await;
+        assert!(!response.success);
+        assert_eq!(response.error_code.as_deref(), Some("UNAUTHORIZED"));
+        let response = calibration_matrix(&internals, &session_id, Some("bogus")).await;
+        assert_eq!(response.error_code.as_deref(), Some("INVALID_REQUEST"));
+
+        let response = calibration_matrix(&internals, &session_id, Some("BASIC")).await;
+        assert!(response.success, "{}", response.message);
+        assert_eq!(response.data.unwrap()["compression_ratio"], 10.0);
+    }
+
+    #[tokio::test]
+    async fn test_calibration_refresh_before_expiry() {
+        let internals = test_internals().await;
+        let customer_id = uuid::Uuid::new_v4();
+        internals.gfef_subscriptions.write().await
+            .create_subscription(customer_id, SubscriptionTier::Enterprise);
+        let session_id = session_id(&establish_session(&internals, customer_id, "enterprise", "").await);
+
+        let first = calibration_matrix(&internals, &session_id, None).await.data.unwrap();
+        let refresh = || ControlPlaneServer::process_command(ControlCommand::RefreshCalibrationMatrix {
+            session_id: session_id.clone(),
+        }, internals.clone());
+
+        // Refreshing while the current matrix is still valid issues a new one
+        let response = refresh().await;
+        assert!(response.success, "{}", response.message);
+        let refreshed = response.data.unwrap();
+        assert_ne!(refreshed["matrix_id"], first["matrix_id"]);
+        assert!(refreshed["expires_at"].as_i64().unwrap() >= first["expires_at"].as_i64().unwrap());
+        let current = calibration_matrix(&internals, &session_id, None).await.data.unwrap();
+        assert_eq!(current["matrix_id"], refreshed["matrix_id"]);
+
+        // An expired matrix is replaced on the next fetch
+        internals.calibration_sessions.sessions.lock().unwrap()
+            .get_mut(&session_id).unwrap()
+            .matrix.as_mut().unwrap()
+            .expires_at = chrono::Utc::now() - chrono::Duration::seconds(1);
+        let reissued = calibration_matrix(&internals, &session_id, None).await.data.unwrap();
+        assert_ne!(reissued["matrix_id"], refreshed["matrix_id"]);
+
+        let response = ControlPlaneServer::process_command(ControlCommand::RefreshCalibrationMatrix {
+            session_id: "cal-unknown".to_string(),
+        }, internals.clone()).await;
+        assert_eq!(response.error_code.as_deref(), Some("UNAUTHORIZED"));
+    }
+
     #[test]
     fn test_json_rpc_format() {
         // Test that commands follow tagged enum format
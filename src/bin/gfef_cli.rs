@@ -104,6 +104,10 @@ struct ExtractResponse {
     status: String,
     model_path: String,
     index_path: String,
+    #[serde(default)]
+    layers_done: u32,
+    #[serde(default)]
+    layers_total: u32,
     stats: Option<ExtractionStats>,
     error: Option<String>,
 }
@@ -138,8 +142,11 @@ enum WsMessage {
     Connected { message: String, server_version: String, timestamp: String },
     ModelDetected { path: String, customer_id: Uuid, timestamp: String },
     ExtractionStarted { path: String, job_id: Uuid, timestamp: String },
+    ExtractionProgress { job_id: Uuid, layers_done: u32, layers_total: u32, timestamp: String },
     ExtractionCompleted { job_id: Uuid, success: bool, model_path: String, index_path: String, stats: Option<ExtractionStats>, error: Option<String>, timestamp: String },
     WatcherError { message: String, timestamp: String },
+    #[serde(rename = "calibration_rotated")]
+    CalibrationRotated { id: Uuid, expires_at: String, timestamp: String },
     Ping { timestamp: String },
 }
 
@@ -206,12 +213,34 @@ async fn cmd_extract(
         return Ok(());
     }
 
-    let result: ExtractResponse = response.json().await?;
+    let mut result: ExtractResponse = response.json().await?;
 
     println!("\n✅ Extraction triggered successfully!");
     println!("   Job ID: {}", result.job_id);
     println!("   Status: {}", result.status);
 
+    if wait {
+        let mut reported = (result.layers_done, result.layers_total);
+        while result.status == "pending" || result.status == "running" {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            result = client
+                .get(format!("{}/v1/extract/{}", server, result.job_id))
+                .send()
+                .await
+                .context("Failed to connect to Control Plane")?
+                .json()
+                .await?;
+            if (result.layers_done, result.layers_total) != reported {
+                reported = (result.layers_done, result.layers_total);
+                println!("   Progress: {}/{} layers", result.layers_done, result.layers_total);
+            }
+        }
+        println!("   Status: {}", result.status);
+        if let Some(error) = &result.error {
+            println!("   Error: {}", error);
+        }
+    }
+
     if let Some(stats) = &result.stats {
         println!("\n📊 Extraction Stats:");
         println!("   Duration: {:.2}s", stats.duration_secs);
@@ -250,9 +279,16 @@ async fn cmd_status(
 
     let result: ExtractResponse = response.json().await?;
 
-    let status_icon = if result.status == "completed" { "✅" } else { "❌" };
+    let status_icon = match result.status.as_str() {
+        "completed" => "✅",
+        "pending" | "running" => "⏳",
+        _ => "❌",
+    };
     println!("\n{} Job Status: {}", status_icon, result.status);
     println!("   Job ID: {}", result.job_id);
+    if result.layers_total > 0 {
+        println!("   Progress: {}/{} layers", result.layers_done, result.layers_total);
+    }
     println!("   Model: {}", result.model_path);
     println!("   Index: {}", result.index_path);
 
@@ -431,6 +467,10 @@ fn print_ws_event(event: &WsMessage) {
             println!("   Path: {}", path);
             println!("   Job: {}", job_id);
         }
+        WsMessage::ExtractionProgress { job_id, layers_done, layers_total, timestamp } => {
+            println!("⏳ [{}] Extraction Progress: {}/{} layers", timestamp, layers_done, layers_total);
+            println!("   Job: {}", job_id);
+        }
         WsMessage::ExtractionCompleted { job_id, success, model_path, index_path, stats, error, timestamp } => {
             let icon = if *success { "✅" } else { "❌" };
             println!("{} [{}] Extraction Completed", icon, timestamp);
@@ -448,6 +488,11 @@ fn print_ws_event(event: &WsMessage) {
         WsMessage::WatcherError { message, timestamp } => {
             println!("❌ [{}] Watcher Error: {}", timestamp, message);
         }
+        WsMessage::CalibrationRotated { id, expires_at, timestamp } => {
+            println!("🔑 [{}] Calibration Rotated", timestamp);
+            println!("   Matrix: {}", id);
+            println!("   Expires: {}", expires_at);
+        }
         WsMessage::Ping { timestamp } => {
             println!("💓 [{}] Ping", timestamp);
         }
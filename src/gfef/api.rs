@@ -11,7 +11,7 @@
 //! - WS /ws/events - WebSocket for real-time watcher events
 
 use axum::{
-    extract::{Path, State, Json, ws::WebSocketUpgrade},
+    extract::{Path, State, Json, rejection::JsonRejection, ws::WebSocketUpgrade},
     routing::{get, post},
     Router,
     http::StatusCode,
@@ -23,15 +23,15 @@ use std::sync::Arc;
 use std::path::PathBuf;
 use tokio::sync::{RwLock, mpsc};
 use std::collections::HashMap;
-use tracing::{info, warn, error};
+use tracing::{info, error};
 
 use super::prediction::{ActivationPredictor, PredictionRequest, PredictionResponse, PredictionError};
 use super::calibration::{CalibrationService, CalibrationMatrix};
-use super::subscription::{SubscriptionManager, SubscriptionTier, Subscription};
+use super::subscription::{SubscriptionManager, SubscriptionTier, Subscription, SubscriptionError};
 use super::index::{GFEFIndex, GFEFIndexGenerator, IndexConfig, IndexMetadata, LayerIndex, NeuronSignature};
 use super::storage::IndexStorage;
-use super::extraction::{ExtractionService, ExtractionConfig, ExtractionResult};
-use super::websocket::{WsEventBroadcaster, ws_handler};
+use super::extraction::{ExtractionService, ExtractionConfig, ExtractionResult, ExtractionJob, ExtractionState};
+use super::websocket::{WsEventBroadcaster, WsMessage, ws_handler};
 
 /// Shared application state
 pub struct AppState {
@@ -41,26 +41,34 @@ pub struct AppState {
     pub index_generator: RwLock<GFEFIndexGenerator>,
     pub storage: RwLock<IndexStorage>,
     pub extraction_service: Option<ExtractionService>,
-    pub extraction_jobs: RwLock<HashMap<Uuid, ExtractionResult>>,
+    pub extraction_jobs: RwLock<HashMap<Uuid, ExtractionJob>>,
     pub extraction_result_rx: Option<RwLock<mpsc::Receiver<ExtractionResult>>>,
     pub ws_broadcaster: Arc<WsEventBroadcaster>,
 }
 
 impl AppState {
     pub fn new(calibration_rotation_secs: u64) -> Self {
+        let ws_broadcaster = Arc::new(WsEventBroadcaster::new(1000));
         Self {
             predictor: RwLock::new(ActivationPredictor::new(0.95)),
-            calibration: CalibrationService::new(calibration_rotation_secs),
+            calibration: CalibrationService::new(calibration_rotation_secs)
+                .with_broadcaster(ws_broadcaster.clone()),
             subscriptions: RwLock::new(SubscriptionManager::new()),
             index_generator: RwLock::new(GFEFIndexGenerator::new(IndexConfig::default())),
             storage: RwLock::new(IndexStorage::new(std::path::PathBuf::from("./indices"))),
             extraction_service: None,
             extraction_jobs: RwLock::new(HashMap::new()),
             extraction_result_rx: None,
-            ws_broadcaster: Arc::new(WsEventBroadcaster::new(1000)),
+            ws_broadcaster,
         }
     }
 
+    /// Persist uploaded indices under `path` instead of `./indices`
+    pub fn with_storage(mut self, path: PathBuf) -> Self {
+        self.storage = RwLock::new(IndexStorage::new(path));
+        self
+    }
+
     pub fn with_extraction(mut self, config: ExtractionConfig) -> Self {
         let (tx, rx) = mpsc::channel(100);
         self.extraction_service = Some(ExtractionService::new(config, tx));
@@ -73,22 +81,35 @@ impl AppState {
         self.ws_broadcaster.clone()
     }
 
+    /// Register every index saved in storage with the predictor (run at startup)
+    pub async fn reload_stored_indices(&self) -> Result<usize, String> {
+        let indices = self.storage.write().await.load_all()?;
+        let count = indices.len();
+
+        let mut predictor = self.predictor.write().await;
+        for index in indices {
+            predictor.register_index(index);
+        }
+
+        info!("🔐 Reloaded {} stored GFEF index(es)", count);
+        Ok(count)
+    }
+
+    /// Save an index to storage so it survives restarts
+    async fn persist_index(&self, index: &GFEFIndex) {
+        if let Err(e) = self.storage.write().await.save(index.clone()) {
+            error!("Failed to persist GFEF index {}: {}", index.model_id, e);
+        }
+    }
+
     /// Load GFEF index from file (for startup or manual loading)
     /// This is the CORE of Triple IP Lock - index stays on Control Plane FOREVER
     pub async fn load_index_from_file(&self, path: &std::path::Path) -> Result<IndexMetadata, String> {
         info!("🔐 Loading GFEF index from file: {:?}", path);
 
-        // Read JSON metadata
+        // Verified metadata plus PCs from the binary sidecar
         let json_path = path.with_extension("json");
-        let json_content = std::fs::read_to_string(&json_path)
-            .map_err(|e| format!("Failed to read index JSON: {}", e))?;
-
-        // Parse the index metadata from our Python-generated format
-        let raw: serde_json::Value = serde_json::from_str(&json_content)
-            .map_err(|e| format!("Failed to parse index JSON: {}", e))?;
-
-        // Convert to our GFEFIndex format
-        let index = Self::convert_python_index_to_gfef(&raw)?;
+        let index = GFEFIndex::load_from_files(&json_path)?;
 
         let metadata = IndexMetadata {
             id: index.id,
@@ -101,7 +122,8 @@ impl AppState {
             index_size_bytes: std::fs::metadata(&json_path).map(|m| m.len()).unwrap_or(0),
         };
 
-        // Register the index with the predictor
+        // Persist, then register the index with the predictor
+        self.persist_index(&index).await;
         {
             let mut predictor = self.predictor.write().await;
             predictor.register_index(index);
@@ -113,58 +135,6 @@ impl AppState {
 
         Ok(metadata)
     }
-
-    /// Convert Python-generated index format to our Rust GFEFIndex
-    fn convert_python_index_to_gfef(raw: &serde_json::Value) -> Result<GFEFIndex, String> {
-        let model_name = raw["model"].as_str().unwrap_or("unknown").to_string();
-        let k_components = raw["k_components"].as_u64().unwrap_or(32) as u32;
-        let fft_bins = raw["fft_bins"].as_u64().unwrap_or(16) as u32;
-        let total_neurons = raw["total_neurons"].as_u64().unwrap_or(0);
-
-        // Parse layers
-        let layers_raw = raw["layers"].as_array()
-            .ok_or("Missing layers array")?;
-
-        let mut layers = Vec::new();
-        for layer_raw in layers_raw {
-            let layer_id = layer_raw["layer_id"].as_u64().unwrap_or(0) as u32;
-            let layer_name = layer_raw["name"].as_str().unwrap_or("").to_string();
-            let neurons = layer_raw["neurons"].as_u64().unwrap_or(0) as u32;
-
-            // Parse pc_shape to get input_dim
-            let pc_shape = layer_raw["pc_shape"].as_array();
-            let input_dim = pc_shape
-                .and_then(|arr| arr.first())
-                .and_then(|v| v.as_u64())
-                .unwrap_or(0) as u32;
-
-            layers.push(LayerIndex {
-                layer_id,
-                layer_name,
-                num_neurons: neurons,
-                input_dim,
-                k_components,
-                principal_components: Vec::new(), // Will be loaded from binary
-                signatures: Vec::new(), // Will be loaded from binary
-            });
-        }
-
-        Ok(GFEFIndex {
-            id: Uuid::new_v4(),
-            customer_id: Uuid::nil(), // Will be set when customer uploads
-            model_id: model_name.clone(),
-            model_name,
-            generated_at: chrono::Utc::now(),
-            expires_at: None,
-            layers,
-            total_neurons,
-            config: IndexConfig {
-                k_components,
-                fft_bins,
-                target_sparsity: 0.95,
-            },
-        })
-    }
 }
 
 /// Create API router
@@ -211,34 +181,58 @@ async fn health_check() -> Json<HealthResponse> {
 
 async fn predict_activation(
     State(state): State<Arc<AppState>>,
-    Json(request): Json<PredictionRequest>,
+    request: Result<Json<PredictionRequest>, JsonRejection>,
 ) -> Result<Json<PredictionResponse>, (StatusCode, Json<ErrorResponse>)> {
-    // Validate subscription
-    let subs = state.subscriptions.read().await;
-    let subscription = subs.validate_access(&request.customer_id)
-        .map_err(|e| (StatusCode::FORBIDDEN, Json(ErrorResponse { error: e.to_string() })))?;
+    let Json(request) = request
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: e.body_text() })))?;
+
+    // Validate subscription (including the per-window request quota)
+    let subscription = state.subscriptions.read().await
+        .validate_access(&request.customer_id)
+        .cloned()
+        .map_err(subscription_error_response)?;
     
     // Get calibration matrix
     let calibration = state.calibration.get_matrix();
     
     // Run prediction
     let predictor = state.predictor.read().await;
-    let response = predictor.predict(&request, subscription, &calibration)
-        .map_err(|e| {
-            let status = match e {
-                PredictionError::InvalidSession => StatusCode::UNAUTHORIZED,
-                PredictionError::ModelNotFound(_) => StatusCode::NOT_FOUND,
-                PredictionError::LayerNotFound(_) => StatusCode::NOT_FOUND,
-                PredictionError::SubscriptionExpired => StatusCode::PAYMENT_REQUIRED,
-                PredictionError::QuotaExceeded => StatusCode::TOO_MANY_REQUESTS,
-                PredictionError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
-            };
-            (status, Json(ErrorResponse { error: e.to_string() }))
-        })?;
+    let response = predictor.predict(&request, &subscription, &calibration)
+        .map_err(|e| (prediction_error_status(&e), Json(ErrorResponse { error: e.to_string() })))?;
+    
+    // Only successful predictions count against the quota
+    state.subscriptions.write().await
+        .record_prediction(&request.customer_id)
+        .map_err(subscription_error_response)?;
     
     Ok(Json(response))
 }
 
+fn subscription_error_response(e: SubscriptionError) -> (StatusCode, Json<ErrorResponse>) {
+    (subscription_error_status(&e), Json(ErrorResponse { error: e.to_string() }))
+}
+
+/// HTTP status for a failed prediction
+pub fn prediction_error_status(e: &PredictionError) -> StatusCode {
+    match e {
+        PredictionError::InvalidSession => StatusCode::UNAUTHORIZED,
+        PredictionError::ModelNotFound(_) => StatusCode::NOT_FOUND,
+        PredictionError::LayerNotFound(_) => StatusCode::NOT_FOUND,
+        PredictionError::SubscriptionExpired => StatusCode::PAYMENT_REQUIRED,
+        PredictionError::QuotaExceeded => StatusCode::TOO_MANY_REQUESTS,
+        PredictionError::InvalidCalibration => StatusCode::FORBIDDEN,
+        PredictionError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// HTTP status for a rejected subscription check
+pub fn subscription_error_status(e: &SubscriptionError) -> StatusCode {
+    match e {
+        SubscriptionError::QuotaExceeded => StatusCode::TOO_MANY_REQUESTS,
+        SubscriptionError::NotFound | SubscriptionError::Expired => StatusCode::FORBIDDEN,
+    }
+}
+
 async fn get_calibration(
     State(state): State<Arc<AppState>>,
 ) -> Json<CalibrationResponse> {
@@ -280,6 +274,9 @@ async fn list_indices(
 }
 
 /// Trigger GFEF extraction for a model
+///
+/// Returns the job in `pending` state; poll `/v1/extract/{job_id}` or watch
+/// `/ws/events` for progress.
 async fn trigger_extraction(
     State(state): State<Arc<AppState>>,
     Json(request): Json<ExtractRequest>,
@@ -296,24 +293,70 @@ async fn trigger_extraction(
         })));
     }
 
-    // Start extraction (async)
-    let result = extraction_service.extract_model(&model_path, &request.customer_id).await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse {
-            error: e.to_string()
-        })))?;
+    // Register the job, then extract in the background
+    let index_path = extraction_service.index_path(&model_path, &request.customer_id);
+    let job = ExtractionJob::new(Uuid::new_v4(), model_path.clone(), index_path);
+    let response = ExtractResponse::from(&job);
+    state.extraction_jobs.write().await.insert(job.job_id, job);
+
+    tokio::spawn(run_extraction(state.clone(), response.job_id, model_path, request.customer_id));
+
+    Ok(Json(response))
+}
+
+/// Run an extraction job, publishing its state and layer progress
+async fn run_extraction(state: Arc<AppState>, job_id: Uuid, model_path: PathBuf, customer_id: Uuid) {
+    let extraction_service = match state.extraction_service.as_ref() {
+        Some(service) => service,
+        None => return,
+    };
+
+    if let Some(job) = state.extraction_jobs.write().await.get_mut(&job_id) {
+        job.state = ExtractionState::Running;
+    }
+    state.ws_broadcaster.broadcast(WsMessage::ExtractionStarted {
+        path: model_path.to_string_lossy().to_string(),
+        job_id,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    });
+
+    let outcome = extraction_service.extract_model_with_progress(job_id, &model_path, &customer_id, |layers_done, layers_total| {
+        let state = state.clone();
+        async move {
+            if let Some(job) = state.extraction_jobs.write().await.get_mut(&job_id) {
+                job.layers_done = layers_done;
+                job.layers_total = layers_total;
+            }
+            state.ws_broadcaster.broadcast(WsMessage::ExtractionProgress {
+                job_id,
+                layers_done,
+                layers_total,
+                timestamp: chrono::Utc::now().to_rfc3339(),
+            });
+        }
+    }).await;
 
-    // Store result
     let mut jobs = state.extraction_jobs.write().await;
-    jobs.insert(result.job_id, result.clone());
-
-    Ok(Json(ExtractResponse {
-        job_id: result.job_id,
-        status: if result.success { "completed" } else { "failed" }.to_string(),
-        model_path: request.model_path,
-        index_path: result.index_path.to_string_lossy().to_string(),
-        stats: result.stats,
-        error: result.error_message,
-    }))
+    let job = match jobs.get_mut(&job_id) {
+        Some(job) => job,
+        None => return,
+    };
+    match outcome {
+        Ok(result) => job.complete(result),
+        Err(e) => {
+            error!("Extraction job {} failed: {}", job_id, e);
+            job.fail(e.to_string());
+        }
+    }
+    state.ws_broadcaster.broadcast(WsMessage::ExtractionCompleted {
+        job_id,
+        success: job.state == ExtractionState::Completed,
+        model_path: job.model_path.to_string_lossy().to_string(),
+        index_path: job.index_path.to_string_lossy().to_string(),
+        stats: job.result.as_ref().and_then(|r| r.stats.clone()).map(Into::into),
+        error: job.error.clone(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    });
 }
 
 /// Get extraction job status
@@ -322,19 +365,12 @@ async fn get_extraction_status(
     Path(job_id): Path<Uuid>,
 ) -> Result<Json<ExtractResponse>, (StatusCode, Json<ErrorResponse>)> {
     let jobs = state.extraction_jobs.read().await;
-    let result = jobs.get(&job_id)
+    let job = jobs.get(&job_id)
         .ok_or((StatusCode::NOT_FOUND, Json(ErrorResponse {
             error: "Extraction job not found".to_string()
         })))?;
 
-    Ok(Json(ExtractResponse {
-        job_id: result.job_id,
-        status: if result.success { "completed" } else { "failed" }.to_string(),
-        model_path: result.model_path.to_string_lossy().to_string(),
-        index_path: result.index_path.to_string_lossy().to_string(),
-        stats: result.stats.clone(),
-        error: result.error_message.clone(),
-    }))
+    Ok(Json(ExtractResponse::from(job)))
 }
 
 // === Request/Response types ===
@@ -377,10 +413,27 @@ struct ExtractResponse {
     status: String,
     model_path: String,
     index_path: String,
+    layers_done: u32,
+    layers_total: u32,
     stats: Option<super::extraction::ExtractionStats>,
     error: Option<String>,
 }
 
+impl From<&ExtractionJob> for ExtractResponse {
+    fn from(job: &ExtractionJob) -> Self {
+        Self {
+            job_id: job.job_id,
+            status: job.state.as_str().to_string(),
+            model_path: job.model_path.to_string_lossy().to_string(),
+            index_path: job.index_path.to_string_lossy().to_string(),
+            layers_done: job.layers_done,
+            layers_total: job.layers_total,
+            stats: job.result.as_ref().and_then(|r| r.stats.clone()),
+            error: job.error.clone(),
+        }
+    }
+}
+
 /// Request to upload a GFEF index (from Extractor to Control Plane)
 #[derive(Deserialize)]
 struct UploadIndexRequest {
@@ -405,6 +458,9 @@ struct UploadLayerData {
     name: String,
     neurons: u32,
     input_dim: u32,
+    /// Overrides the global target sparsity for this layer
+    #[serde(default)]
+    layer_sparsity: Option<f32>,
 }
 
 #[derive(Serialize)]
@@ -436,6 +492,14 @@ async fn upload_index(
     info!("   Customer: {}", request.customer_id);
     info!("   Neurons: {}, Layers: {}", request.total_neurons, request.layers.len());
 
+    if let Some(layer) = request.layers.iter()
+        .find(|l| l.layer_sparsity.is_some_and(|s| !(0.0..1.0).contains(&s)))
+    {
+        return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse {
+            error: format!("Layer {} layer_sparsity must be in [0, 1)", layer.layer_id)
+        })));
+    }
+
     // Convert upload request to GFEFIndex
     let layers: Vec<LayerIndex> = request.layers.iter().map(|l| {
         LayerIndex {
@@ -446,6 +510,7 @@ async fn upload_index(
             k_components: request.k_components,
             principal_components: Vec::new(), // Would come from binary data
             signatures: Vec::new(), // Would come from binary data
+            layer_sparsity: l.layer_sparsity,
         }
     }).collect();
 
@@ -468,7 +533,9 @@ async fn upload_index(
         },
     };
 
-    // Register with predictor - THIS IS WHERE TRIPLE IP LOCK ACTIVATES
+    // Persist so the index survives restarts, then register with
+    // predictor - THIS IS WHERE TRIPLE IP LOCK ACTIVATES
+    state.persist_index(&index).await;
     {
         let mut predictor = state.predictor.write().await;
         predictor.register_index(index);
@@ -505,3 +572,155 @@ async fn get_predictor_stats(
         triple_ip_lock_active: stats.models_loaded > 0,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::{header, Request};
+    use tempfile::tempdir;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_uploaded_index_survives_restart() {
+        let dir = tempdir().unwrap();
+        let customer_id = Uuid::new_v4();
+
+        let state = Arc::new(AppState::new(60).with_storage(dir.path().to_path_buf()));
+        let upload = serde_json::json!({
+            "customer_id": customer_id,
+            "model_id": "persisted",
+            "model_name": "Persisted Model",
+            "k_components": 4,
+            "fft_bins": 16,
+            "total_neurons": 12,
+            "layers": [
+                { "layer_id": 0, "name": "layer_0", "neurons": 6, "input_dim": 8 },
+                { "layer_id": 1, "name": "layer_1", "neurons": 6, "input_dim": 8 },
+            ],
+        });
+        let request = Request::post("/v1/index/upload")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(axum::body::Body::from(upload.to_string()))
+            .unwrap();
+        let response = create_router(state.clone()).oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        drop(state);
+
+        let restarted = AppState::new(60).with_storage(dir.path().to_path_buf());
+        assert_eq!(restarted.predictor.read().await.stats().models_loaded, 0);
+        assert_eq!(restarted.reload_stored_indices().await.unwrap(), 1);
+
+        let predictor = restarted.predictor.read().await;
+        let index = predictor.get_index("persisted").unwrap();
+        assert_eq!(index.customer_id, customer_id);
+        assert_eq!(index.total_neurons, 12);
+        assert_eq!(index.layers.len(), 2);
+        assert_eq!(predictor.stats().models_loaded, 1);
+        assert_eq!(restarted.storage.read().await.list_metadata().len(), 1);
+    }
+
+    async fn extraction_status(state: &Arc<AppState>, job_id: Uuid) -> serde_json::Value {
+        let request = Request::get(format!("/v1/extract/{}", job_id))
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let response = create_router(state.clone()).oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    async fn wait_for_status(state: &Arc<AppState>, job_id: Uuid, status: &str) -> serde_json::Value {
+        for _ in 0..400 {
+            let job = extraction_status(state, job_id).await;
+            if job["status"] == status && (status != "running" || job["layers_done"] == 1) {
+                return job;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(25)).await;
+        }
+        panic!("extraction job never reached {}", status);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_extraction_reports_progress() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let model_path = dir.path().join("model");
+        std::fs::create_dir_all(&model_path).unwrap();
+        std::fs::create_dir_all(dir.path().join("indexed")).unwrap();
+
+        // Stand-in for gfef-extract: reports layer 1/2, then waits for the gate
+        let gate = dir.path().join("gate");
+        let script = dir.path().join("gfef-extract");
+        std::fs::write(&script, format!(
+            "#!/bin/sh\necho \"layer 1/2\"\nwhile [ ! -f \"{}\" ]; do sleep 0.05; done\necho \"layer 2/2\"\n",
+            gate.display(),
+        )).unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let state = Arc::new(AppState::new(60).with_extraction(ExtractionConfig {
+            gfef_extract_path: script,
+            pending_models_dir: dir.path().to_path_buf(),
+            indexed_models_dir: dir.path().join("indexed"),
+            indices_dir: dir.path().join("indices"),
+            ..ExtractionConfig::default()
+        }));
+        let mut events = state.get_broadcaster().subscribe();
+
+        let request = Request::post("/v1/extract")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(axum::body::Body::from(serde_json::json!({
+                "customer_id": Uuid::new_v4(),
+                "model_path": model_path.to_string_lossy(),
+            }).to_string()))
+            .unwrap();
+        let response = create_router(state.clone()).oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let job: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(job["status"], "pending");
+        let job_id: Uuid = job["job_id"].as_str().unwrap().parse().unwrap();
+
+        let running = wait_for_status(&state, job_id, "running").await;
+        assert_eq!(running["layers_total"], 2);
+
+        std::fs::write(&gate, b"").unwrap();
+        let completed = wait_for_status(&state, job_id, "completed").await;
+        assert_eq!(completed["layers_done"], 2);
+        assert!(completed["error"].is_null());
+
+        let mut progress = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            if let WsMessage::ExtractionProgress { job_id: id, layers_done, .. } = event {
+                assert_eq!(id, job_id);
+                progress.push(layers_done);
+            }
+        }
+        assert_eq!(progress, vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_calibration_rotation_broadcast() {
+        let state = AppState::new(60);
+        let mut rx = state.get_broadcaster().subscribe();
+
+        let rotated = state.calibration.rotate();
+        match rx.recv().await.unwrap() {
+            WsMessage::CalibrationRotated { id, expires_at, .. } => {
+                assert_eq!(id, rotated.id);
+                assert_eq!(expires_at, rotated.expires_at.to_rfc3339());
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+        assert_eq!(state.calibration.get_matrix().id, rotated.id);
+
+        let json = serde_json::to_value(WsMessage::CalibrationRotated {
+            id: rotated.id,
+            expires_at: rotated.expires_at.to_rfc3339(),
+            timestamp: "2024-01-01T00:00:00Z".to_string(),
+        }).unwrap();
+        assert_eq!(json["type"], "calibration_rotated");
+        assert_eq!(json["data"]["id"], rotated.id.to_string());
+    }
+}
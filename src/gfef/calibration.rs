@@ -8,12 +8,17 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
-use sha2::{Sha256, Digest};
+use ring::hmac;
 use std::sync::{Arc, RwLock};
 
+use super::websocket::{WsEventBroadcaster, WsMessage};
+
 /// Calibration matrix dimensions
 pub const MATRIX_SIZE: usize = 64;
 
+/// HMAC key for matrix signatures (in production, from HSM)
+const CALIBRATION_SECRET: &[u8] = b"NEUNOMY_CALIBRATION_SECRET_KEY_v1";
+
 /// Calibration matrix for UAO-QTCAM decompression
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CalibrationMatrix {
@@ -25,7 +30,7 @@ pub struct CalibrationMatrix {
     pub generated_at: DateTime<Utc>,
     /// When this matrix expires
     pub expires_at: DateTime<Utc>,
-    /// Cryptographic signature
+    /// HMAC-SHA256 over `id || expires_at || values` (hex)
     pub signature: String,
     /// Associated customer (None = global)
     pub customer_id: Option<Uuid>,
@@ -41,15 +46,16 @@ impl CalibrationMatrix {
         // In production, this would use a trained neural network or optimization
         let seed = id.as_u128();
         let values = Self::generate_values(seed);
+        let expires_at = now + chrono::Duration::seconds(rotation_secs as i64);
         
         // Sign the matrix
-        let signature = Self::sign_matrix(&id, &values, &now);
+        let signature = Self::sign_matrix(&id, &expires_at, &values);
         
         Self {
             id,
             values,
             generated_at: now,
-            expires_at: now + chrono::Duration::seconds(rotation_secs as i64),
+            expires_at,
             signature,
             customer_id,
         }
@@ -87,28 +93,37 @@ impl CalibrationMatrix {
         values
     }
     
-    /// Sign the matrix for verification
-    fn sign_matrix(id: &Uuid, values: &[Vec<f64>], timestamp: &DateTime<Utc>) -> String {
-        let mut hasher = Sha256::new();
-        hasher.update(id.as_bytes());
-        hasher.update(timestamp.timestamp().to_le_bytes());
+    /// Bytes covered by the signature: `id || expires_at || values`
+    fn signed_bytes(id: &Uuid, expires_at: &DateTime<Utc>, values: &[Vec<f64>]) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(MATRIX_SIZE * MATRIX_SIZE * 8 + 24);
+        bytes.extend_from_slice(id.as_bytes());
+        bytes.extend_from_slice(&expires_at.timestamp().to_le_bytes());
         
         for row in values {
             for val in row {
-                hasher.update(val.to_le_bytes());
+                bytes.extend_from_slice(&val.to_le_bytes());
             }
         }
         
-        // Add secret key (in production, from HSM)
-        hasher.update(b"NEUNOMY_CALIBRATION_SECRET_KEY_v1");
-        
-        hex::encode(hasher.finalize())
+        bytes
     }
     
-    /// Verify matrix signature
+    /// Sign the matrix for verification
+    fn sign_matrix(id: &Uuid, expires_at: &DateTime<Utc>, values: &[Vec<f64>]) -> String {
+        let key = hmac::Key::new(hmac::HMAC_SHA256, CALIBRATION_SECRET);
+        let tag = hmac::sign(&key, &Self::signed_bytes(id, expires_at, values));
+        hex::encode(tag.as_ref())
+    }
+    
+    /// Verify matrix signature (constant-time comparison)
     pub fn verify(&self) -> bool {
-        let expected = Self::sign_matrix(&self.id, &self.values, &self.generated_at);
-        self.signature == expected
+        let signature = match hex::decode(&self.signature) {
+            Ok(signature) => signature,
+            Err(_) => return false,
+        };
+        let key = hmac::Key::new(hmac::HMAC_SHA256, CALIBRATION_SECRET);
+        let message = Self::signed_bytes(&self.id, &self.expires_at, &self.values);
+        hmac::verify(&key, &message, &signature).is_ok()
     }
     
     /// Check if matrix is still valid
@@ -137,6 +152,7 @@ impl CalibrationMatrix {
 pub struct CalibrationService {
     current_matrix: Arc<RwLock<CalibrationMatrix>>,
     rotation_secs: u64,
+    broadcaster: Option<Arc<WsEventBroadcaster>>,
 }
 
 impl CalibrationService {
@@ -144,9 +160,16 @@ impl CalibrationService {
         Self {
             current_matrix: Arc::new(RwLock::new(CalibrationMatrix::generate(rotation_secs, None))),
             rotation_secs,
+            broadcaster: None,
         }
     }
     
+    /// Announce every rotation as a `calibration_rotated` WebSocket event
+    pub fn with_broadcaster(mut self, broadcaster: Arc<WsEventBroadcaster>) -> Self {
+        self.broadcaster = Some(broadcaster);
+        self
+    }
+    
     /// Get current valid calibration matrix
     pub fn get_matrix(&self) -> CalibrationMatrix {
         let matrix = self.current_matrix.read().unwrap();
@@ -158,11 +181,25 @@ impl CalibrationService {
         }
     }
     
+    /// Check a matrix's signature and expiry before it is used
+    pub fn verify(&self, matrix: &CalibrationMatrix) -> bool {
+        matrix.is_valid()
+    }
+    
     /// Force rotation to new matrix
     pub fn rotate(&self) -> CalibrationMatrix {
         let new_matrix = CalibrationMatrix::generate(self.rotation_secs, None);
         let mut current = self.current_matrix.write().unwrap();
         *current = new_matrix.clone();
+        drop(current);
+        
+        if let Some(broadcaster) = &self.broadcaster {
+            broadcaster.broadcast(WsMessage::CalibrationRotated {
+                id: new_matrix.id,
+                expires_at: new_matrix.expires_at.to_rfc3339(),
+                timestamp: Utc::now().to_rfc3339(),
+            });
+        }
         new_matrix
     }
 }
@@ -6,11 +6,14 @@
 //!
 //! This is Lock 1 of the Triple IP Lock™ Architecture.
 
+use std::future::Future;
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::Stdio;
 use anyhow::{Result, Context};
 use serde::{Deserialize, Serialize};
 use tracing::{info, warn, error, debug};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+use tokio::process::Command;
 use tokio::sync::mpsc;
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
@@ -78,6 +81,87 @@ pub struct ExtractionStats {
     pub extraction_time_secs: f64,
 }
 
+/// Lifecycle state of an extraction job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExtractionState {
+    Pending,
+    Running,
+    Completed,
+    Failed,
+}
+
+impl ExtractionState {
+    /// Wire form used in API responses
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Running => "running",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+/// Extraction job tracked from submission until its result is known
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractionJob {
+    pub job_id: Uuid,
+    pub model_path: PathBuf,
+    pub index_path: PathBuf,
+    pub state: ExtractionState,
+    pub layers_done: u32,
+    pub layers_total: u32,
+    pub error: Option<String>,
+    pub result: Option<ExtractionResult>,
+}
+
+impl ExtractionJob {
+    pub fn new(job_id: Uuid, model_path: PathBuf, index_path: PathBuf) -> Self {
+        Self {
+            job_id,
+            model_path,
+            index_path,
+            state: ExtractionState::Pending,
+            layers_done: 0,
+            layers_total: 0,
+            error: None,
+            result: None,
+        }
+    }
+
+    /// Record the finished extraction
+    pub fn complete(&mut self, result: ExtractionResult) {
+        if result.success {
+            self.state = ExtractionState::Completed;
+            self.layers_done = self.layers_total;
+        } else {
+            self.state = ExtractionState::Failed;
+        }
+        self.error = result.error_message.clone();
+        self.result = Some(result);
+    }
+
+    /// Record an extraction that could not be run
+    pub fn fail(&mut self, error: String) {
+        self.state = ExtractionState::Failed;
+        self.error = Some(error);
+    }
+}
+
+/// Parse a gfef-extract progress line such as `layer 3/32`
+fn parse_layer_progress(line: &str) -> Option<(u32, u32)> {
+    if !line.to_ascii_lowercase().contains("layer") {
+        return None;
+    }
+    line.split_whitespace().find_map(|token| {
+        let (done, total) = token.split_once('/')?;
+        let done: u32 = done.parse().ok()?;
+        let total: u32 = total.trim_end_matches(|c: char| !c.is_ascii_digit()).parse().ok()?;
+        (total > 0 && done <= total).then_some((done, total))
+    })
+}
+
 /// GFEF Extraction Service
 /// 
 /// Monitors VXLAN directories and triggers GFEF extraction when new models arrive.
@@ -91,19 +175,44 @@ impl ExtractionService {
         Self { config, result_tx }
     }
 
+    /// Where the index for `model_path` is written
+    pub fn index_path(&self, model_path: &Path, customer_id: &Uuid) -> PathBuf {
+        let index_filename = format!("{}_{}.gfef", customer_id, Self::model_name(model_path));
+        self.config.indices_dir.join(index_filename)
+    }
+
+    fn model_name(model_path: &Path) -> &str {
+        model_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown")
+    }
+
     /// Extract GFEF index from a model directory
     pub async fn extract_model(&self, model_path: &Path, customer_id: &Uuid) -> Result<ExtractionResult> {
-        let job_id = Uuid::new_v4();
+        self.extract_model_with_progress(Uuid::new_v4(), model_path, customer_id, |_, _| async {}).await
+    }
+
+    /// Extract under a caller-assigned job id
+    ///
+    /// `on_progress(layers_done, layers_total)` is awaited for every
+    /// `layer N/M` line gfef-extract prints on stdout.
+    pub async fn extract_model_with_progress<F, Fut>(
+        &self,
+        job_id: Uuid,
+        model_path: &Path,
+        customer_id: &Uuid,
+        mut on_progress: F,
+    ) -> Result<ExtractionResult>
+    where
+        F: FnMut(u32, u32) -> Fut,
+        Fut: Future<Output = ()>,
+    {
         let started_at = Utc::now();
         
         // Generate output paths
-        let model_name = model_path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("unknown");
-        
-        let index_filename = format!("{}_{}.gfef", customer_id, model_name);
-        let index_path = self.config.indices_dir.join(&index_filename);
+        let model_name = Self::model_name(model_path);
+        let index_path = self.index_path(model_path, customer_id);
         let metadata_path = index_path.with_extension("json");
 
         info!("Starting GFEF extraction: job_id={}, model={}", job_id, model_path.display());
@@ -113,7 +222,7 @@ impl ExtractionService {
             .context("Failed to create indices directory")?;
 
         // Call gfef-extract CLI
-        let output = Command::new(&self.config.gfef_extract_path)
+        let mut child = Command::new(&self.config.gfef_extract_path)
             .arg("--model-path")
             .arg(model_path)
             .arg("--output")
@@ -122,13 +231,35 @@ impl ExtractionService {
             .arg(self.config.k_components.to_string())
             .arg("--fft-bins")
             .arg(self.config.fft_bins.to_string())
-            .output()
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
             .context("Failed to execute gfef-extract")?;
 
+        // Drain stderr alongside stdout so neither pipe fills up
+        let mut stderr_pipe = child.stderr.take().context("gfef-extract stderr not captured")?;
+        let stderr_task = tokio::spawn(async move {
+            let mut stderr = String::new();
+            let _ = stderr_pipe.read_to_string(&mut stderr).await;
+            stderr
+        });
+
+        let stdout = child.stdout.take().context("gfef-extract stdout not captured")?;
+        let mut lines = BufReader::new(stdout).lines();
+        while let Some(line) = lines.next_line().await.context("Failed to read gfef-extract output")? {
+            if let Some((done, total)) = parse_layer_progress(&line) {
+                debug!("GFEF extraction progress: job_id={}, layer {}/{}", job_id, done, total);
+                on_progress(done, total).await;
+            }
+        }
+
+        let status = child.wait().await.context("Failed to wait for gfef-extract")?;
+        let stderr = stderr_task.await.unwrap_or_default();
+
         let completed_at = Utc::now();
         let extraction_time = (completed_at - started_at).num_milliseconds() as f64 / 1000.0;
 
-        if output.status.success() {
+        if status.success() {
             // Read metadata from generated JSON
             let stats = if metadata_path.exists() {
                 self.read_extraction_stats(&metadata_path, extraction_time).ok()
@@ -161,7 +292,6 @@ impl ExtractionService {
             let _ = self.result_tx.send(result.clone()).await;
             Ok(result)
         } else {
-            let stderr = String::from_utf8_lossy(&output.stderr);
             error!("❌ GFEF extraction failed: {}", stderr);
             
             let result = ExtractionResult {
@@ -172,7 +302,7 @@ impl ExtractionService {
                 started_at,
                 completed_at,
                 success: false,
-                error_message: Some(stderr.to_string()),
+                error_message: Some(stderr),
                 stats: None,
             };
 
@@ -12,7 +12,13 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
+use std::path::Path;
+use tracing::{error, warn};
+
+/// Metadata field holding the index checksum (see [`GFEFIndex::checksum`])
+pub const INDEX_CHECKSUM_FIELD: &str = "sha256";
 
 /// Spectral signature for a single neuron
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -37,10 +43,312 @@ pub struct LayerIndex {
     pub num_neurons: u32,
     pub input_dim: u32,
     pub k_components: u32,
-    /// Principal components (for projection)
+    /// Principal components (for projection), row-major `input_dim × k_components`
     pub principal_components: Vec<f32>,
     /// Per-neuron signatures
     pub signatures: Vec<NeuronSignature>,
+    /// Target sparsity for this layer, overriding the global target
+    #[serde(default)]
+    pub layer_sparsity: Option<f32>,
+}
+
+impl LayerIndex {
+    /// Whether principal components and signatures are loaded
+    pub fn has_components(&self) -> bool {
+        !self.principal_components.is_empty() && !self.signatures.is_empty()
+    }
+
+    /// Project an input embedding onto the layer's principal components
+    ///
+    /// Returns `None` if the embedding length differs from `input_dim` or
+    /// the component matrix is not `input_dim × k_components`.
+    pub fn project(&self, embedding: &[f32]) -> Option<Vec<f32>> {
+        let n = self.input_dim as usize;
+        let k = self.k_components as usize;
+        if embedding.len() != n || self.principal_components.len() != n * k {
+            return None;
+        }
+
+        let mut projection = vec![0.0f32; k];
+        for (i, &x) in embedding.iter().enumerate() {
+            let row = &self.principal_components[i * k..(i + 1) * k];
+            for (p, &v) in projection.iter_mut().zip(row) {
+                *p += x * v;
+            }
+        }
+        Some(projection)
+    }
+
+    /// Parse one entry of the Python extractor's `layers` array
+    ///
+    /// `k_components` is the index-wide value the layer's `pc_shape` must
+    /// agree with.
+    pub fn from_python_metadata(layer_raw: &serde_json::Value, k_components: u32) -> Result<Self, String> {
+        let layer_id = layer_raw["layer_id"].as_u64().unwrap_or(0) as u32;
+        let layer_name = layer_raw["name"].as_str().unwrap_or("").to_string();
+        let neurons = layer_raw["neurons"].as_u64().unwrap_or(0) as u32;
+
+        // pc_shape is [input_dim, k]; k must agree with the index-wide k_components
+        let pc_shape = layer_raw["pc_shape"].as_array();
+        let input_dim = pc_shape
+            .and_then(|arr| arr.first())
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0) as u32;
+        if let Some(k) = pc_shape.and_then(|arr| arr.get(1)).and_then(|v| v.as_u64()) {
+            if k != k_components as u64 {
+                return Err(format!(
+                    "Layer {} pc_shape has {} components, index declares {}",
+                    layer_id, k, k_components
+                ));
+            }
+        }
+
+        let layer_sparsity = match &layer_raw["layer_sparsity"] {
+            serde_json::Value::Null => None,
+            value => Some(value.as_f64()
+                .filter(|s| (0.0..1.0).contains(s))
+                .ok_or_else(|| format!("Layer {} layer_sparsity must be a number in [0, 1)", layer_id))?
+                as f32),
+        };
+
+        Ok(Self {
+            layer_id,
+            layer_name,
+            num_neurons: neurons,
+            input_dim,
+            k_components,
+            principal_components: Vec::new(),
+            signatures: Vec::new(),
+            layer_sparsity,
+        })
+    }
+}
+
+impl GFEFIndex {
+    /// Whether the index is past its `expires_at`
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|t| t <= Utc::now())
+    }
+
+    /// Build an index from the JSON metadata written by the Python extractor
+    ///
+    /// Layers come back without principal components or signatures; those
+    /// live in the binary sidecar (see [`GFEFIndex::load_components`]).
+    pub fn from_python_metadata(raw: &serde_json::Value) -> Result<Self, String> {
+        let k_components = Self::python_k_components(raw);
+        let layers = raw["layers"].as_array()
+            .ok_or("Missing layers array")?
+            .iter()
+            .map(|layer_raw| LayerIndex::from_python_metadata(layer_raw, k_components))
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::from_python_layers(raw, layers)
+    }
+
+    /// The index-wide `k_components` declared by Python metadata
+    pub fn python_k_components(raw: &serde_json::Value) -> u32 {
+        raw["k_components"].as_u64().unwrap_or(32) as u32
+    }
+
+    /// Build an index from Python metadata whose layers were parsed
+    /// separately with [`LayerIndex::from_python_metadata`]
+    ///
+    /// Any `layers` array in `raw` is ignored.
+    pub fn from_python_layers(raw: &serde_json::Value, layers: Vec<LayerIndex>) -> Result<Self, String> {
+        let model_name = raw["model"].as_str().unwrap_or("unknown").to_string();
+        let k_components = Self::python_k_components(raw);
+        let fft_bins = raw["fft_bins"].as_u64().unwrap_or(16) as u32;
+        let total_neurons = raw["total_neurons"].as_u64().unwrap_or(0);
+
+        let layer_neurons: u64 = layers.iter().map(|l| l.num_neurons as u64).sum();
+        if layer_neurons != total_neurons {
+            return Err(format!(
+                "Layers hold {} neurons, index declares total_neurons = {}",
+                layer_neurons, total_neurons
+            ));
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            customer_id: Uuid::nil(), // Set when the customer uploads
+            model_id: model_name.clone(),
+            model_name,
+            generated_at: Utc::now(),
+            expires_at: None,
+            layers,
+            total_neurons,
+            config: IndexConfig {
+                k_components,
+                fft_bins,
+                target_sparsity: 0.95,
+            },
+        })
+    }
+
+    /// Populate principal components and signatures from the binary sidecar
+    ///
+    /// The sidecar is a flat stream of little-endian `f32`, one block per
+    /// layer in metadata order: the `input_dim × k` component matrix followed
+    /// by the `num_neurons × k` neuron projections (both row-major). The
+    /// total length must match the shapes declared in the metadata exactly.
+    pub fn load_components(&mut self, bytes: &[u8]) -> Result<(), String> {
+        if !bytes.len().is_multiple_of(4) {
+            return Err(format!("Sidecar length {} is not a multiple of 4", bytes.len()));
+        }
+        let mut values = bytes
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]));
+
+        let k = self.config.k_components as usize;
+        let expected: usize = self.layers.iter()
+            .map(|l| (l.input_dim as usize + l.num_neurons as usize) * k)
+            .sum();
+        if bytes.len() / 4 != expected {
+            return Err(format!(
+                "Sidecar holds {} values, metadata shapes require {}",
+                bytes.len() / 4, expected
+            ));
+        }
+
+        for layer in &mut self.layers {
+            if layer.k_components as usize != k {
+                return Err(format!(
+                    "Layer {} has {} components, index declares {}",
+                    layer.layer_id, layer.k_components, k
+                ));
+            }
+            if layer.input_dim == 0 || k == 0 {
+                return Err(format!("Layer {} has an empty pc_shape", layer.layer_id));
+            }
+
+            layer.principal_components = values.by_ref()
+                .take(layer.input_dim as usize * k)
+                .collect();
+            layer.signatures = (0..layer.num_neurons)
+                .map(|neuron_idx| {
+                    let projection: Vec<f32> = values.by_ref().take(k).collect();
+                    let energy = projection.iter().map(|x| x * x).sum::<f32>().sqrt();
+                    NeuronSignature {
+                        layer_id: layer.layer_id,
+                        neuron_idx,
+                        energy,
+                        projection,
+                        spectral_hash: Vec::new(),
+                    }
+                })
+                .collect();
+        }
+
+        Ok(())
+    }
+
+    /// Read the binary sidecar from disk and populate every layer
+    pub fn load_components_from_file(&mut self, path: &Path) -> Result<(), String> {
+        let bytes = std::fs::read(path)
+            .map_err(|e| format!("Failed to read index sidecar {:?}: {}", path, e))?;
+        self.load_components(&bytes)
+    }
+
+    /// Hex SHA-256 of an index file pair
+    ///
+    /// Covers the metadata as compact JSON with sorted keys and the
+    /// `sha256` field removed, followed by the raw sidecar bytes when the
+    /// index has a sidecar.
+    pub fn checksum(raw: &serde_json::Value, sidecar: Option<&[u8]>) -> String {
+        let mut unhashed = raw.clone();
+        if let Some(fields) = unhashed.as_object_mut() {
+            fields.remove(INDEX_CHECKSUM_FIELD);
+        }
+
+        let mut hasher = Sha256::new();
+        hasher.update(unhashed.to_string().as_bytes());
+        if let Some(sidecar) = sidecar {
+            hasher.update(sidecar);
+        }
+        hex::encode(hasher.finalize())
+    }
+
+    /// Check the metadata's recorded `sha256` against the file pair
+    ///
+    /// Returns `Ok(false)` when no checksum is recorded, and an error when
+    /// the recorded digest doesn't match the computed one.
+    pub fn verify_checksum(raw: &serde_json::Value, sidecar: Option<&[u8]>) -> Result<bool, String> {
+        let expected = match &raw[INDEX_CHECKSUM_FIELD] {
+            serde_json::Value::Null => return Ok(false),
+            value => value.as_str()
+                .ok_or_else(|| format!("Index {} must be a hex string", INDEX_CHECKSUM_FIELD))?,
+        };
+        let computed = Self::checksum(raw, sidecar);
+        if !computed.eq_ignore_ascii_case(expected) {
+            return Err(format!("Index checksum mismatch: recorded {}, computed {}", expected, computed));
+        }
+        Ok(true)
+    }
+
+    /// Load a Python-generated index from its JSON metadata and the `.bin`
+    /// sidecar next to it, verifying the recorded checksum first
+    pub fn load_from_files(json_path: &Path) -> Result<Self, String> {
+        let json_content = std::fs::read_to_string(json_path)
+            .map_err(|e| format!("Failed to read index JSON: {}", e))?;
+        let raw: serde_json::Value = serde_json::from_str(&json_content)
+            .map_err(|e| format!("Failed to parse index JSON: {}", e))?;
+
+        let bin_path = json_path.with_extension("bin");
+        let sidecar = if bin_path.exists() {
+            Some(std::fs::read(&bin_path)
+                .map_err(|e| format!("Failed to read index sidecar {:?}: {}", bin_path, e))?)
+        } else {
+            warn!("No principal-component sidecar at {:?}; predictions will fail", bin_path);
+            None
+        };
+
+        match Self::verify_checksum(&raw, sidecar.as_deref()) {
+            Ok(true) => {}
+            Ok(false) => warn!("GFEF index {:?} has no {} checksum; loading unverified", json_path, INDEX_CHECKSUM_FIELD),
+            Err(e) => {
+                error!("Refusing GFEF index {:?}: {}", json_path, e);
+                return Err(e);
+            }
+        }
+
+        let mut index = Self::from_python_metadata(&raw)?;
+        if let Some(sidecar) = sidecar {
+            index.load_components(&sidecar)?;
+        }
+        Ok(index)
+    }
+
+    /// Encode every layer's components in the sidecar format read by
+    /// [`GFEFIndex::load_components`]
+    ///
+    /// Returns `None` if any layer is missing components or its shapes
+    /// disagree with the metadata, since the sidecar has no way to skip a
+    /// layer. Only projections are encoded: loading recomputes signature
+    /// energies and leaves spectral hashes empty.
+    pub fn components_to_bytes(&self) -> Option<Vec<u8>> {
+        if self.layers.is_empty() {
+            return None;
+        }
+
+        let k = self.config.k_components as usize;
+        let mut bytes = Vec::new();
+        for layer in &self.layers {
+            let shapes_match = layer.has_components()
+                && layer.k_components as usize == k
+                && layer.principal_components.len() == layer.input_dim as usize * k
+                && layer.signatures.len() == layer.num_neurons as usize
+                && layer.signatures.iter().all(|s| s.projection.len() == k);
+            if !shapes_match {
+                return None;
+            }
+
+            let values = layer.principal_components.iter()
+                .chain(layer.signatures.iter().flat_map(|s| &s.projection));
+            for value in values {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        Some(bytes)
+    }
 }
 
 /// Complete GFEF index for a model
@@ -223,6 +531,7 @@ impl GFEFIndexGenerator {
             k_components: k,
             principal_components,
             signatures,
+            layer_sparsity: None,
         }
     }
 
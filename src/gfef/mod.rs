@@ -41,9 +41,9 @@ pub mod websocket;
 // Re-export main types
 pub use api::{create_router as create_gfef_router, AppState as GFEFAppState};
 pub use calibration::{CalibrationService, CalibrationMatrix};
-pub use extraction::{ExtractionService, ExtractionConfig, ExtractionResult, ExtractionStats};
+pub use extraction::{ExtractionService, ExtractionConfig, ExtractionResult, ExtractionStats, ExtractionJob, ExtractionState};
 pub use index::{GFEFIndexGenerator, IndexConfig, IndexMetadata};
-pub use prediction::{ActivationPredictor, PredictionRequest, PredictionResponse};
+pub use prediction::{ActivationPredictor, LayerPrediction, PredictionRequest, PredictionResponse};
 pub use storage::IndexStorage;
 pub use subscription::{SubscriptionManager, SubscriptionTier, Subscription};
 pub use watcher::{ModelWatcherService, WatcherConfig, WatcherEvent, spawn_watcher_service};
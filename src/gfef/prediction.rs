@@ -15,7 +15,10 @@ use super::calibration::CalibrationMatrix;
 use super::subscription::{SubscriptionTier, Subscription};
 
 /// Request for activation prediction
+///
+/// Deserialized strictly: unknown fields are rejected rather than ignored.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct PredictionRequest {
     /// Session token (from auth)
     pub session_token: String,
@@ -29,6 +32,11 @@ pub struct PredictionRequest {
     pub input_embedding_hash: String,
     /// Optional: actual embedding for higher accuracy (encrypted)
     pub encrypted_embedding: Option<Vec<u8>>,
+    /// Optional: input embedding (length `input_dim`) projected onto the
+    /// layer's principal components. Without it the projection is derived
+    /// from `input_embedding_hash`.
+    #[serde(default)]
+    pub input_embedding: Option<Vec<f32>>,
 }
 
 /// Response with activation predictions
@@ -51,7 +59,7 @@ pub struct PredictionResponse {
 }
 
 /// Error types for prediction service
-#[derive(Debug, thiserror::Error, Serialize, Deserialize)]
+#[derive(Debug, Clone, thiserror::Error, Serialize, Deserialize)]
 pub enum PredictionError {
     #[error("Invalid session token")]
     InvalidSession,
@@ -63,10 +71,23 @@ pub enum PredictionError {
     SubscriptionExpired,
     #[error("Quota exceeded")]
     QuotaExceeded,
+    #[error("Calibration matrix failed verification or expired")]
+    InvalidCalibration,
     #[error("Internal error: {0}")]
     Internal(String),
 }
 
+/// Active neuron set predicted for a single layer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayerPrediction {
+    /// Predicted active neuron indices, best score first
+    pub active_neurons: Vec<u32>,
+    /// Signature correlation of each active neuron
+    pub confidence_scores: Vec<f32>,
+    /// Achieved sparsity
+    pub sparsity: f32,
+}
+
 /// Activation predictor service
 pub struct ActivationPredictor {
     /// Loaded GFEF indices (keyed by model_id)
@@ -88,6 +109,16 @@ impl ActivationPredictor {
         self.indices.insert(index.model_id.clone(), index);
     }
     
+    /// The registered index for `model_id`, if any
+    pub fn get_index(&self, model_id: &str) -> Option<&GFEFIndex> {
+        self.indices.get(model_id)
+    }
+
+    /// Every registered index, one per model
+    pub fn indices(&self) -> impl Iterator<Item = &GFEFIndex> {
+        self.indices.values()
+    }
+    
     /// Predict active neurons for a layer
     pub fn predict(
         &self,
@@ -95,45 +126,124 @@ impl ActivationPredictor {
         subscription: &Subscription,
         calibration: &CalibrationMatrix,
     ) -> Result<PredictionResponse, PredictionError> {
-        // Validate subscription
+        Self::check_access(subscription, calibration)?;
+        let credits_remaining = subscription.predictions_quota - subscription.predictions_used - 1;
+        self.predict_request(request, calibration, credits_remaining)
+    }
+
+    /// Predict active neurons for several layers at once
+    ///
+    /// The subscription and calibration checks run once for the whole
+    /// batch, then each request is predicted independently. Requests past
+    /// the remaining quota fail with `QuotaExceeded`, and
+    /// `credits_remaining` counts down across the successful predictions.
+    pub fn predict_batch(
+        &self,
+        requests: &[PredictionRequest],
+        subscription: &Subscription,
+        calibration: &CalibrationMatrix,
+    ) -> Vec<Result<PredictionResponse, PredictionError>> {
+        let access = Self::check_access(subscription, calibration);
+        let mut used = subscription.predictions_used;
+
+        requests.iter()
+            .map(|request| {
+                access.clone()?;
+                if used >= subscription.predictions_quota {
+                    return Err(PredictionError::QuotaExceeded);
+                }
+                let response = self.predict_request(request, calibration, subscription.predictions_quota - used - 1)?;
+                used += 1;
+                Ok(response)
+            })
+            .collect()
+    }
+
+    /// Validate the subscription and reject tampered or stale calibration matrices
+    fn check_access(subscription: &Subscription, calibration: &CalibrationMatrix) -> Result<(), PredictionError> {
         if !subscription.is_active() {
             return Err(PredictionError::SubscriptionExpired);
         }
         if !subscription.has_quota() {
             return Err(PredictionError::QuotaExceeded);
         }
-        
-        // Get index
-        let index = self.indices.get(&request.model_id)
-            .ok_or_else(|| PredictionError::ModelNotFound(request.model_id.clone()))?;
-        
-        // Get layer
-        let layer = index.layers.iter()
-            .find(|l| l.layer_id == request.layer_id)
-            .ok_or(PredictionError::LayerNotFound(request.layer_id))?;
-        
+        if !calibration.is_valid() {
+            return Err(PredictionError::InvalidCalibration);
+        }
+        Ok(())
+    }
+
+    /// Predict one request whose access has already been checked
+    fn predict_request(
+        &self,
+        request: &PredictionRequest,
+        calibration: &CalibrationMatrix,
+        credits_remaining: u64,
+    ) -> Result<PredictionResponse, PredictionError> {
         // Predict active neurons
-        let (active_neurons, confidence_scores) = self.compute_activations(
-            layer,
+        let prediction = self.predict_layer(
+            &request.model_id,
+            request.layer_id,
+            request.input_embedding.as_deref(),
             &request.input_embedding_hash,
-        );
+        )?;
         
         // Extract calibration slice for active neurons
         let calibration_slice = self.extract_calibration_slice(
-            &active_neurons,
+            &prediction.active_neurons,
             calibration,
         );
         
-        // Calculate achieved sparsity
-        let sparsity = 1.0 - (active_neurons.len() as f32 / layer.num_neurons as f32);
-        
         Ok(PredictionResponse {
             request_id: Uuid::new_v4(),
-            active_neurons,
-            confidence_scores: Some(confidence_scores),
+            active_neurons: prediction.active_neurons,
+            confidence_scores: Some(prediction.confidence_scores),
             calibration_slice,
             valid_until: Utc::now() + chrono::Duration::milliseconds(100),
-            credits_remaining: subscription.predictions_quota - subscription.predictions_used - 1,
+            credits_remaining,
+            sparsity: prediction.sparsity,
+        })
+    }
+
+    /// Predict the active neuron set of one layer
+    ///
+    /// The input is projected onto the layer's principal components (the
+    /// embedding when given, otherwise a projection derived from the input
+    /// hash), every neuron is scored by the correlation of its signature
+    /// with that projection, and the top `1 - sparsity` fraction is
+    /// returned, where the sparsity is the layer's own if it has one and
+    /// `target_sparsity` otherwise.
+    pub fn predict_layer(
+        &self,
+        model_id: &str,
+        layer_id: u32,
+        input_embedding: Option<&[f32]>,
+        input_hash: &str,
+    ) -> Result<LayerPrediction, PredictionError> {
+        // Get index
+        let index = self.indices.get(model_id)
+            .ok_or_else(|| PredictionError::ModelNotFound(model_id.to_string()))?;
+        if index.is_expired() {
+            return Err(PredictionError::SubscriptionExpired);
+        }
+
+        // Get layer
+        let layer = index.layers.iter()
+            .find(|l| l.layer_id == layer_id)
+            .ok_or(PredictionError::LayerNotFound(layer_id))?;
+
+        let (active_neurons, confidence_scores) = self.compute_activations(
+            layer,
+            input_embedding,
+            input_hash,
+        )?;
+
+        // Calculate achieved sparsity
+        let sparsity = 1.0 - (active_neurons.len() as f32 / layer.num_neurons.max(1) as f32);
+
+        Ok(LayerPrediction {
+            active_neurons,
+            confidence_scores,
             sparsity,
         })
     }
@@ -142,42 +252,70 @@ impl ActivationPredictor {
     fn compute_activations(
         &self,
         layer: &LayerIndex,
+        input_embedding: Option<&[f32]>,
         input_hash: &str,
-    ) -> (Vec<u32>, Vec<f32>) {
-        // Derive pseudo-random projection from input hash
-        let seed = Self::hash_to_seed(input_hash);
-        let input_projection = self.derive_projection(seed, layer.k_components as usize);
+    ) -> Result<(Vec<u32>, Vec<f32>), PredictionError> {
+        if !layer.has_components() {
+            return Err(PredictionError::Internal(format!(
+                "Layer {} has no principal components loaded", layer.layer_id
+            )));
+        }
+
+        // Project the input onto the layer's principal components
+        let input_projection = match input_embedding {
+            Some(embedding) => layer.project(embedding).ok_or_else(|| PredictionError::Internal(format!(
+                "Embedding of length {} does not match layer {} ({} inputs × {} components)",
+                embedding.len(), layer.layer_id, layer.input_dim, layer.k_components
+            )))?,
+            None => {
+                let seed = Self::hash_to_seed(input_hash);
+                self.derive_projection(seed, layer.k_components as usize)
+            }
+        };
         
-        // Score each neuron by alignment with input
+        // Score each neuron by signature correlation with the input
         let mut scores: Vec<(u32, f32)> = layer.signatures.iter()
-            .map(|sig| {
-                let score = self.compute_alignment(&sig.projection, &input_projection, sig.energy);
-                (sig.neuron_idx, score)
-            })
+            .map(|sig| (sig.neuron_idx, Self::correlation(&sig.projection, &input_projection)))
             .collect();
         
         // Sort by score descending
         scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
         
         // Select top (1 - sparsity) neurons
-        let num_active = ((1.0 - self.target_sparsity) * layer.num_neurons as f32).ceil() as usize;
-        let num_active = num_active.max(1);
+        let sparsity = layer.layer_sparsity.unwrap_or(self.target_sparsity);
+        let num_active = Self::active_count(layer.num_neurons, sparsity);
         
         let active: Vec<u32> = scores.iter().take(num_active).map(|(idx, _)| *idx).collect();
         let confidences: Vec<f32> = scores.iter().take(num_active).map(|(_, score)| *score).collect();
 
-        (active, confidences)
+        Ok((active, confidences))
     }
 
-    /// Compute alignment between neuron projection and input projection
-    fn compute_alignment(&self, neuron_proj: &[f32], input_proj: &[f32], energy: f32) -> f32 {
+    /// Neurons kept active at `sparsity`: ⌈(1 - sparsity) · n⌉, at least one
+    ///
+    /// The sparsity is rounded to six decimals first, so f32 values such as
+    /// 0.9 keep exactly 10% of the neurons rather than one more.
+    fn active_count(num_neurons: u32, sparsity: f32) -> usize {
+        let sparsity = (f64::from(sparsity) * 1e6).round() / 1e6;
+        let n = f64::from(num_neurons);
+        let inactive = (sparsity * n + 1e-9).floor().clamp(0.0, n);
+        (num_neurons as usize - inactive as usize).max(1)
+    }
+
+    /// Cosine correlation between a neuron signature and the input projection
+    fn correlation(neuron_proj: &[f32], input_proj: &[f32]) -> f32 {
         let dot: f32 = neuron_proj.iter()
             .zip(input_proj.iter())
             .map(|(a, b)| a * b)
             .sum();
+        let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
 
-        // Weight by energy (higher energy neurons are more "specialized")
-        dot.abs() * energy.sqrt()
+        let denom = norm(neuron_proj) * norm(input_proj);
+        if denom > 1e-12 {
+            dot / denom
+        } else {
+            0.0
+        }
     }
 
     /// Convert input hash to seed for deterministic projection
@@ -218,6 +356,20 @@ impl ActivationPredictor {
             .collect()
     }
 
+    /// Unregister every index past its `expires_at`
+    ///
+    /// Returns the model IDs that were removed.
+    pub fn prune_expired(&mut self) -> Vec<String> {
+        let expired: Vec<String> = self.indices.iter()
+            .filter(|(_, index)| index.is_expired())
+            .map(|(model_id, _)| model_id.clone())
+            .collect();
+        for model_id in &expired {
+            self.indices.remove(model_id);
+        }
+        expired
+    }
+
     /// Get statistics about loaded indices
     pub fn stats(&self) -> PredictorStats {
         PredictorStats {
@@ -237,3 +389,171 @@ pub struct PredictorStats {
     pub total_layers: usize,
     pub target_sparsity: f32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::index::{IndexConfig, NeuronSignature};
+    use super::super::subscription::SubscriptionTier;
+
+    /// Identity components of width `dim`; neuron `i` points along axis `i % dim`
+    fn model_index(model_id: &str, dim: u32, neurons: u32, layers: u32) -> GFEFIndex {
+        let layers = (0..layers)
+            .map(|layer_id| LayerIndex {
+                layer_id,
+                layer_name: format!("layer_{}", layer_id),
+                num_neurons: neurons,
+                input_dim: dim,
+                k_components: dim,
+                principal_components: (0..dim * dim)
+                    .map(|i| if i / dim == i % dim { 1.0 } else { 0.0 })
+                    .collect(),
+                signatures: (0..neurons)
+                    .map(|neuron_idx| NeuronSignature {
+                        layer_id,
+                        neuron_idx,
+                        energy: 1.0,
+                        projection: (0..dim).map(|d| if d == neuron_idx % dim { 1.0 } else { 0.0 }).collect(),
+                        spectral_hash: Vec::new(),
+                    })
+                    .collect(),
+                layer_sparsity: None,
+            })
+            .collect();
+        GFEFIndex {
+            id: Uuid::new_v4(),
+            customer_id: Uuid::nil(),
+            model_id: model_id.to_string(),
+            model_name: model_id.to_string(),
+            generated_at: Utc::now(),
+            expires_at: None,
+            layers,
+            total_neurons: 0,
+            config: IndexConfig { k_components: dim, ..IndexConfig::default() },
+        }
+    }
+
+    #[test]
+    fn test_predictions_route_by_model() {
+        let mut predictor = ActivationPredictor::new(0.5);
+        predictor.register_index(model_index("small", 2, 4, 1));
+        predictor.register_index(model_index("wide", 3, 6, 2));
+        assert_eq!(predictor.stats().models_loaded, 2);
+
+        let small = predictor.predict_layer("small", 0, Some(&[1.0, 0.0]), "h").unwrap();
+        let mut top = small.active_neurons.clone();
+        top.sort();
+        assert_eq!(top, vec![0, 2]);
+
+        let wide = predictor.predict_layer("wide", 1, Some(&[0.0, 0.0, 1.0]), "h").unwrap();
+        assert_eq!(wide.active_neurons.len(), 3);
+        let mut top = wide.active_neurons[..2].to_vec();
+        top.sort();
+        assert_eq!(top, vec![2, 5]);
+
+        // Each model keeps its own shape
+        assert!(matches!(
+            predictor.predict_layer("small", 0, Some(&[0.0, 0.0, 1.0]), "h"),
+            Err(PredictionError::Internal(_))
+        ));
+        assert!(matches!(
+            predictor.predict_layer("small", 1, None, "h"),
+            Err(PredictionError::LayerNotFound(1))
+        ));
+        assert!(matches!(
+            predictor.predict_layer("missing", 0, None, "h"),
+            Err(PredictionError::ModelNotFound(ref id)) if id == "missing"
+        ));
+    }
+
+    #[test]
+    fn test_layer_sparsity_override() {
+        let mut index = model_index("mixed", 4, 200, 3);
+        index.layers[0].layer_sparsity = Some(0.90);
+        index.layers[1].layer_sparsity = Some(0.99);
+        let mut predictor = ActivationPredictor::new(0.95);
+        predictor.register_index(index);
+
+        let embedding = [1.0, 0.5, 0.25, 0.0];
+        let dense = predictor.predict_layer("mixed", 0, Some(&embedding), "h").unwrap();
+        let sparse = predictor.predict_layer("mixed", 1, Some(&embedding), "h").unwrap();
+        let global = predictor.predict_layer("mixed", 2, Some(&embedding), "h").unwrap();
+        assert_eq!(dense.active_neurons.len(), 20);
+        assert_eq!(sparse.active_neurons.len(), 2);
+        assert_eq!(global.active_neurons.len(), 10);
+        assert!((dense.sparsity - 0.90).abs() < 1e-6);
+        assert!((sparse.sparsity - 0.99).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_prediction_request_deserialization() {
+        let customer_id = Uuid::new_v4();
+        let mut body = serde_json::json!({
+            "session_token": "token",
+            "customer_id": customer_id,
+            "model_id": "tiny",
+            "layer_id": 3,
+            "input_embedding_hash": "hash",
+        });
+        let request: PredictionRequest = serde_json::from_value(body.clone()).unwrap();
+        assert_eq!(request.customer_id, customer_id);
+        assert_eq!(request.model_id, "tiny");
+        assert_eq!(request.layer_id, 3);
+        assert!(request.encrypted_embedding.is_none());
+        assert!(request.input_embedding.is_none());
+
+        body["layer"] = serde_json::json!(3);
+        let error = serde_json::from_value::<PredictionRequest>(body.clone()).unwrap_err();
+        assert!(error.to_string().contains("unknown field `layer`"), "{}", error);
+
+        body.as_object_mut().unwrap().remove("layer");
+        body.as_object_mut().unwrap().remove("model_id");
+        let error = serde_json::from_value::<PredictionRequest>(body).unwrap_err();
+        assert!(error.to_string().contains("missing field `model_id`"), "{}", error);
+    }
+
+    #[test]
+    fn test_predict_batch_matches_individual_predictions() {
+        let mut predictor = ActivationPredictor::new(0.5);
+        predictor.register_index(model_index("deep", 4, 8, 8));
+        let subscription = Subscription::new(Uuid::new_v4(), SubscriptionTier::Professional);
+        let calibration = CalibrationMatrix::generate(60, None);
+
+        let requests: Vec<PredictionRequest> = (0..8)
+            .map(|layer_id| PredictionRequest {
+                session_token: "token".to_string(),
+                customer_id: subscription.customer_id,
+                model_id: "deep".to_string(),
+                layer_id,
+                input_embedding_hash: format!("hash-{}", layer_id),
+                encrypted_embedding: None,
+                input_embedding: Some((0..4).map(|d| if d == layer_id % 4 { 1.0 } else { 0.1 }).collect()),
+            })
+            .collect();
+
+        let batch = predictor.predict_batch(&requests, &subscription, &calibration);
+        assert_eq!(batch.len(), 8);
+        for (i, (request, result)) in requests.iter().zip(&batch).enumerate() {
+            let single = predictor.predict(request, &subscription, &calibration).unwrap();
+            let batched = result.as_ref().unwrap();
+            assert_eq!(batched.active_neurons, single.active_neurons, "layer {}", request.layer_id);
+            assert_eq!(batched.confidence_scores, single.confidence_scores);
+            assert_eq!(batched.calibration_slice, single.calibration_slice);
+            assert_eq!(batched.credits_remaining, single.credits_remaining - i as u64);
+        }
+
+        // A bad layer fails on its own without sinking the batch
+        let mut mixed = requests[..2].to_vec();
+        mixed[1].layer_id = 99;
+        let results = predictor.predict_batch(&mixed, &subscription, &calibration);
+        assert!(results[0].is_ok());
+        assert!(matches!(results[1], Err(PredictionError::LayerNotFound(99))));
+
+        // The quota caps the batch
+        let mut nearly_spent = subscription.clone();
+        nearly_spent.predictions_used = nearly_spent.predictions_quota - 3;
+        let results = predictor.predict_batch(&requests, &nearly_spent, &calibration);
+        assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 3);
+        assert!(matches!(results[3], Err(PredictionError::QuotaExceeded)));
+    }
+}
@@ -2,13 +2,19 @@
 //! 
 //! Secure storage for GFEF indices.
 //! Indices are stored encrypted and NEVER sent to customers.
+//!
+//! Each saved index is written to `storage_path` as `<id>.json` (metadata,
+//! layer shapes, signature energies and spectral hashes, and a `sha256`
+//! checksum) plus, when every layer has its components, the binary sidecar
+//! `<id>.bin` in the extractor's format.
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use tracing::warn;
 
-use super::index::{GFEFIndex, IndexMetadata};
+use super::index::{GFEFIndex, IndexMetadata, INDEX_CHECKSUM_FIELD};
 
 /// Storage backend for GFEF indices
 pub struct IndexStorage {
@@ -46,6 +52,138 @@ impl IndexStorage {
         meta
     }
     
+    /// Store a GFEF index and write it to `storage_path`, replacing any
+    /// saved index for the same model
+    ///
+    /// Both files are written to temporaries and renamed into place; the
+    /// model's previous index is deleted only once the new one is on disk.
+    pub fn save(&mut self, index: GFEFIndex) -> Result<IndexMetadata, String> {
+        std::fs::create_dir_all(&self.storage_path)
+            .map_err(|e| format!("Failed to create index directory {:?}: {}", self.storage_path, e))?;
+        let (json_path, bin_path) = self.index_paths(&index.id);
+
+        let mut on_disk = index.clone();
+        let sidecar = index.components_to_bytes();
+        if sidecar.is_some() {
+            // Projections live in the sidecar; energies and spectral hashes
+            // stay in the JSON since the extractor's format has no room for them
+            for layer in &mut on_disk.layers {
+                layer.principal_components.clear();
+                for signature in &mut layer.signatures {
+                    signature.projection.clear();
+                }
+            }
+        }
+
+        let mut raw = serde_json::to_value(&on_disk)
+            .map_err(|e| format!("Failed to serialize index {}: {}", index.id, e))?;
+        let checksum = GFEFIndex::checksum(&raw, sidecar.as_deref());
+        raw[INDEX_CHECKSUM_FIELD] = serde_json::Value::String(checksum);
+
+        match &sidecar {
+            Some(bytes) => Self::write_atomic(&bin_path, bytes)?,
+            // Components (if any) stay inline in the JSON
+            None => {
+                let _ = std::fs::remove_file(&bin_path);
+            }
+        }
+        Self::write_atomic(&json_path, raw.to_string().as_bytes())?;
+
+        let replaced: Vec<Uuid> = self.cache.values()
+            .filter(|stored| stored.model_id == index.model_id && stored.id != index.id)
+            .map(|stored| stored.id)
+            .collect();
+        for id in &replaced {
+            self.delete(id);
+        }
+
+        Ok(self.store(index))
+    }
+
+    /// Write `bytes` next to `path` and rename them over it
+    fn write_atomic(path: &Path, bytes: &[u8]) -> Result<(), String> {
+        let mut tmp_name = path.as_os_str().to_owned();
+        tmp_name.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_name);
+
+        std::fs::write(&tmp_path, bytes)
+            .map_err(|e| format!("Failed to write {:?}: {}", tmp_path, e))?;
+        std::fs::rename(&tmp_path, path).map_err(|e| {
+            let _ = std::fs::remove_file(&tmp_path);
+            format!("Failed to move {:?} into place: {}", path, e)
+        })
+    }
+
+    /// Read every index saved under `storage_path` into the store
+    ///
+    /// Returns the loaded indices; unreadable files are skipped with a
+    /// warning. A missing directory means nothing has been saved yet.
+    pub fn load_all(&mut self) -> Result<Vec<GFEFIndex>, String> {
+        let entries = match std::fs::read_dir(&self.storage_path) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(format!("Failed to read index directory {:?}: {}", self.storage_path, e)),
+        };
+
+        let mut loaded = Vec::new();
+        for entry in entries.flatten() {
+            let json_path = entry.path();
+            if json_path.extension() != Some(std::ffi::OsStr::new("json")) {
+                continue;
+            }
+            match Self::read_index(&json_path) {
+                Ok(index) => {
+                    loaded.push(index.clone());
+                    self.store(index);
+                }
+                Err(e) => warn!("Skipping stored index {:?}: {}", json_path, e),
+            }
+        }
+        Ok(loaded)
+    }
+
+    /// Read a saved index pair, verifying its checksum before decoding
+    fn read_index(json_path: &Path) -> Result<GFEFIndex, String> {
+        let json = std::fs::read(json_path).map_err(|e| e.to_string())?;
+        let raw: serde_json::Value = serde_json::from_slice(&json).map_err(|e| e.to_string())?;
+        let bin_path = json_path.with_extension("bin");
+        let sidecar = if bin_path.exists() {
+            Some(std::fs::read(&bin_path)
+                .map_err(|e| format!("Failed to read index sidecar {:?}: {}", bin_path, e))?)
+        } else {
+            None
+        };
+
+        if !GFEFIndex::verify_checksum(&raw, sidecar.as_deref())? {
+            warn!("Stored index {:?} has no {} checksum; loading unverified", json_path, INDEX_CHECKSUM_FIELD);
+        }
+
+        let mut index: GFEFIndex = serde_json::from_value(raw).map_err(|e| e.to_string())?;
+        if let Some(sidecar) = sidecar {
+            // Keep the saved energies and hashes over the ones recomputed
+            // from the projections
+            let saved: Vec<Vec<(f32, Vec<f32>)>> = index.layers.iter_mut()
+                .map(|layer| layer.signatures.drain(..).map(|s| (s.energy, s.spectral_hash)).collect())
+                .collect();
+            index.load_components(&sidecar)?;
+            for (layer, saved) in index.layers.iter_mut().zip(saved) {
+                if saved.len() != layer.signatures.len() {
+                    continue;
+                }
+                for (signature, (energy, spectral_hash)) in layer.signatures.iter_mut().zip(saved) {
+                    signature.energy = energy;
+                    signature.spectral_hash = spectral_hash;
+                }
+            }
+        }
+        Ok(index)
+    }
+
+    fn index_paths(&self, id: &Uuid) -> (PathBuf, PathBuf) {
+        let base = self.storage_path.join(id.to_string());
+        (base.with_extension("json"), base.with_extension("bin"))
+    }
+    
     /// Get index by ID
     pub fn get(&self, id: &Uuid) -> Option<&GFEFIndex> {
         self.cache.get(id)
@@ -89,8 +227,115 @@ impl IndexStorage {
         size
     }
     
-    /// Delete an index
+    /// Delete an index, including its files under `storage_path`
     pub fn delete(&mut self, id: &Uuid) -> bool {
+        let (json_path, bin_path) = self.index_paths(id);
+        let _ = std::fs::remove_file(json_path);
+        let _ = std::fs::remove_file(bin_path);
         self.cache.remove(id).is_some() && self.metadata.remove(id).is_some()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::index::{IndexConfig, LayerIndex, NeuronSignature};
+    use tempfile::tempdir;
+
+    fn index_with_components() -> GFEFIndex {
+        let signatures = (0..3u32)
+            .map(|i| NeuronSignature {
+                layer_id: 0,
+                neuron_idx: i,
+                energy: 0.5 + i as f32,
+                projection: vec![i as f32, 1.0],
+                spectral_hash: vec![i as f32 * 0.25, 0.1],
+            })
+            .collect();
+        GFEFIndex {
+            id: Uuid::new_v4(),
+            customer_id: Uuid::nil(),
+            model_id: "stored".to_string(),
+            model_name: "stored".to_string(),
+            generated_at: chrono::Utc::now(),
+            expires_at: None,
+            layers: vec![LayerIndex {
+                layer_id: 0,
+                layer_name: "layer_0".to_string(),
+                num_neurons: 3,
+                input_dim: 2,
+                k_components: 2,
+                principal_components: vec![1.0, 0.0, 0.0, 1.0],
+                signatures,
+                layer_sparsity: None,
+            }],
+            total_neurons: 3,
+            config: IndexConfig { k_components: 2, ..IndexConfig::default() },
+        }
+    }
+
+    #[test]
+    fn test_save_and_load_with_sidecar() {
+        let dir = tempdir().unwrap();
+        let index = index_with_components();
+        let id = index.id;
+
+        IndexStorage::new(dir.path().to_path_buf()).save(index.clone()).unwrap();
+        assert!(dir.path().join(format!("{}.bin", id)).exists());
+
+        let mut reopened = IndexStorage::new(dir.path().to_path_buf());
+        let loaded = reopened.load_all().unwrap();
+        assert_eq!(loaded.len(), 1);
+        let layer = &loaded[0].layers[0];
+        assert_eq!(layer.principal_components, index.layers[0].principal_components);
+        let projections: Vec<_> = layer.signatures.iter().map(|s| s.projection.clone()).collect();
+        assert_eq!(projections, vec![vec![0.0, 1.0], vec![1.0, 1.0], vec![2.0, 1.0]]);
+        for (loaded, saved) in layer.signatures.iter().zip(&index.layers[0].signatures) {
+            assert_eq!(loaded.energy, saved.energy);
+            assert_eq!(loaded.spectral_hash, saved.spectral_hash);
+        }
+        assert!(reopened.get(&id).is_some());
+
+        assert!(reopened.delete(&id));
+        assert!(IndexStorage::new(dir.path().to_path_buf()).load_all().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_save_replaces_previous_index_for_model() {
+        let dir = tempdir().unwrap();
+        let mut storage = IndexStorage::new(dir.path().to_path_buf());
+        let old = index_with_components();
+        let new = GFEFIndex { id: Uuid::new_v4(), ..old.clone() };
+
+        storage.save(old.clone()).unwrap();
+        storage.save(new.clone()).unwrap();
+        assert!(!dir.path().join(format!("{}.json", old.id)).exists());
+        assert!(!dir.path().join(format!("{}.bin", old.id)).exists());
+
+        let files: Vec<_> = std::fs::read_dir(dir.path()).unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        assert_eq!(files.len(), 2, "unexpected files {:?}", files);
+
+        let loaded = IndexStorage::new(dir.path().to_path_buf()).load_all().unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].id, new.id);
+    }
+
+    #[test]
+    fn test_load_rejects_tampered_sidecar() {
+        let dir = tempdir().unwrap();
+        let index = index_with_components();
+        IndexStorage::new(dir.path().to_path_buf()).save(index.clone()).unwrap();
+
+        let bin_path = dir.path().join(format!("{}.bin", index.id));
+        let mut bytes = std::fs::read(&bin_path).unwrap();
+        bytes[0] ^= 0xFF;
+        std::fs::write(&bin_path, bytes).unwrap();
+
+        let json_path = dir.path().join(format!("{}.json", index.id));
+        let err = IndexStorage::read_index(&json_path).unwrap_err();
+        assert!(err.contains("checksum mismatch"), "{}", err);
+        assert!(IndexStorage::new(dir.path().to_path_buf()).load_all().unwrap().is_empty());
+    }
+}
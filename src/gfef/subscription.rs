@@ -5,6 +5,10 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
+use std::collections::HashMap;
+
+/// Length of the per-customer rate-limit window (seconds)
+pub const QUOTA_WINDOW_SECS: i64 = 60;
 
 /// Subscription tiers with corresponding amplification benefits
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -50,6 +54,16 @@ impl SubscriptionTier {
         }
     }
     
+    /// Get prediction requests allowed per [`QUOTA_WINDOW_SECS`] window
+    pub fn requests_per_window(&self) -> u64 {
+        match self {
+            Self::Trial => 10,
+            Self::Developer => 100,
+            Self::Professional => 1_000,
+            Self::Enterprise => 10_000,
+        }
+    }
+    
     /// Get calibration matrix rotation interval (seconds)
     pub fn calibration_rotation_secs(&self) -> u64 {
         match self {
@@ -121,16 +135,37 @@ impl Subscription {
     }
 }
 
+/// Prediction count within the current rate-limit window
+#[derive(Debug, Clone, Copy)]
+struct QuotaWindow {
+    started_at: DateTime<Utc>,
+    requests: u64,
+}
+
+impl QuotaWindow {
+    /// Requests counted at `now`, zero once the window has rolled over
+    fn requests_at(&self, now: DateTime<Utc>) -> u64 {
+        if now - self.started_at >= chrono::Duration::seconds(QUOTA_WINDOW_SECS) {
+            0
+        } else {
+            self.requests
+        }
+    }
+}
+
 /// Subscription manager
 pub struct SubscriptionManager {
     // In production, this would use PostgreSQL
-    subscriptions: std::collections::HashMap<Uuid, Subscription>,
+    subscriptions: HashMap<Uuid, Subscription>,
+    /// Per-customer request windows
+    windows: HashMap<Uuid, QuotaWindow>,
 }
 
 impl SubscriptionManager {
     pub fn new() -> Self {
         Self {
-            subscriptions: std::collections::HashMap::new(),
+            subscriptions: HashMap::new(),
+            windows: HashMap::new(),
         }
     }
     
@@ -145,6 +180,18 @@ impl SubscriptionManager {
     }
     
     pub fn validate_access(&self, customer_id: &Uuid) -> Result<&Subscription, SubscriptionError> {
+        self.validate_access_at(customer_id, Utc::now())
+    }
+    
+    /// Count one successful prediction against the customer's quotas
+    ///
+    /// Consumes from both the monthly quota and the current window, starting
+    /// a fresh window once [`QUOTA_WINDOW_SECS`] have elapsed.
+    pub fn record_prediction(&mut self, customer_id: &Uuid) -> Result<(), SubscriptionError> {
+        self.record_prediction_at(customer_id, Utc::now())
+    }
+    
+    fn validate_access_at(&self, customer_id: &Uuid, now: DateTime<Utc>) -> Result<&Subscription, SubscriptionError> {
         let sub = self.subscriptions.get(customer_id)
             .ok_or(SubscriptionError::NotFound)?;
         
@@ -156,8 +203,29 @@ impl SubscriptionManager {
             return Err(SubscriptionError::QuotaExceeded);
         }
         
+        let used = self.windows.get(customer_id).map_or(0, |w| w.requests_at(now));
+        if used >= sub.tier.requests_per_window() {
+            return Err(SubscriptionError::QuotaExceeded);
+        }
+        
         Ok(sub)
     }
+    
+    fn record_prediction_at(&mut self, customer_id: &Uuid, now: DateTime<Utc>) -> Result<(), SubscriptionError> {
+        self.validate_access_at(customer_id, now)?;
+        
+        let window = self.windows.entry(*customer_id)
+            .or_insert(QuotaWindow { started_at: now, requests: 0 });
+        if window.requests_at(now) == 0 {
+            *window = QuotaWindow { started_at: now, requests: 0 };
+        }
+        window.requests += 1;
+        
+        if let Some(sub) = self.subscriptions.get_mut(customer_id) {
+            sub.consume_prediction();
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -170,3 +238,52 @@ pub enum SubscriptionError {
     QuotaExceeded,
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Record predictions until the window rejects one, returning how many succeeded
+    fn exhaust(manager: &mut SubscriptionManager, customer_id: &Uuid, now: DateTime<Utc>) -> u64 {
+        let mut accepted = 0;
+        while manager.record_prediction_at(customer_id, now).is_ok() {
+            accepted += 1;
+        }
+        accepted
+    }
+
+    #[test]
+    fn test_window_quota_per_tier() {
+        let mut manager = SubscriptionManager::new();
+        let trial = Uuid::new_v4();
+        let developer = Uuid::new_v4();
+        manager.create_subscription(trial, SubscriptionTier::Trial);
+        manager.create_subscription(developer, SubscriptionTier::Developer);
+        let now = Utc::now();
+
+        assert_eq!(exhaust(&mut manager, &trial, now), SubscriptionTier::Trial.requests_per_window());
+        assert!(matches!(
+            manager.validate_access_at(&trial, now),
+            Err(SubscriptionError::QuotaExceeded)
+        ));
+
+        // Higher tier gets more headroom, and one customer's usage does not affect another
+        assert_eq!(exhaust(&mut manager, &developer, now), SubscriptionTier::Developer.requests_per_window());
+        assert_eq!(manager.get_subscription(&developer).unwrap().predictions_used, 100);
+    }
+
+    #[test]
+    fn test_window_rollover_resets_quota() {
+        let mut manager = SubscriptionManager::new();
+        let customer = Uuid::new_v4();
+        manager.create_subscription(customer, SubscriptionTier::Trial);
+        let start = Utc::now();
+
+        exhaust(&mut manager, &customer, start);
+        let almost = start + chrono::Duration::seconds(QUOTA_WINDOW_SECS - 1);
+        assert!(manager.validate_access_at(&customer, almost).is_err());
+
+        let next = start + chrono::Duration::seconds(QUOTA_WINDOW_SECS);
+        assert!(manager.validate_access_at(&customer, next).is_ok());
+        assert_eq!(exhaust(&mut manager, &customer, next), 10);
+    }
+}
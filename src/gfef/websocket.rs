@@ -36,6 +36,13 @@ pub enum WsMessage {
         job_id: Uuid,
         timestamp: String,
     },
+    /// Server: Extraction progress (layers processed so far)
+    ExtractionProgress {
+        job_id: Uuid,
+        layers_done: u32,
+        layers_total: u32,
+        timestamp: String,
+    },
     /// Server: Extraction completed
     ExtractionCompleted {
         job_id: Uuid,
@@ -57,6 +64,13 @@ pub enum WsMessage {
         server_version: String,
         timestamp: String,
     },
+    /// Server: Calibration matrix rotated
+    #[serde(rename = "calibration_rotated")]
+    CalibrationRotated {
+        id: Uuid,
+        expires_at: String,
+        timestamp: String,
+    },
     /// Server: Heartbeat/ping
     Ping { timestamp: String },
     /// Client: Subscribe to events
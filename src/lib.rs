@@ -66,6 +66,7 @@ pub use qagml_integration::{
     QagmlIntegration,
     QagmlConfigAlias,
     VirtualMemoryAllocationResult,
+    RegionStats,
 };
 
 // UAO-QTCAM Integration Module (1,250× Speedup over Hardware TCAM)
@@ -143,6 +144,7 @@ pub mod uao_qtcam_cache;
 pub use uao_qtcam_cache::{
     UaoQtcamCache,
     CacheEntry,
+    CacheValue,
     CacheStats,
 };
 
@@ -179,7 +181,7 @@ pub use gfef::{
 // Re-export core types and traits
 pub use sheaf::{SheafSpace, ResourceStalk};
 pub use galois::{GaloisElement, CRTDecomposition};
-pub use tensor::{TensorFolder, MortonEncoding, CacheAwareTensor};
+pub use tensor::{TensorFolder, TensorFolderF64, MortonEncoding, CacheAwareTensor};
 
 /// Core error types for the Symmetrix system
 #[derive(Debug, thiserror::Error)]
@@ -46,6 +46,7 @@
 //! └─────────────────────────────────────────────────────────────────────────┘
 //! ```
 
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, RwLock};
 use qagml::{
     QagmlEngine, QagmlConfig, GPUMemoryStats,
@@ -109,12 +110,18 @@ pub struct SymmetrixQagmlOptimizer {
     tensor_folder: TensorFolder,
     /// Configuration
     config: SymmetrixQagmlConfig,
+    /// Live amplified-region allocations keyed by allocation id
+    allocations: HashMap<String, AllocationRecord>,
+    /// Allocation ids that have already been freed (for double-free detection)
+    freed_allocations: HashSet<String>,
+    /// Allocation counts and byte totals keyed by region name
+    region_stats: HashMap<String, RegionStats>,
 }
 
 impl SymmetrixQagmlOptimizer {
     /// Create a new SYMMETRIX-QAGML optimizer
     pub fn new(config: SymmetrixQagmlConfig) -> Self {
-        let qagml_engine = QagmlEngine::new(config.qagml_config.clone())
+        let qagml_engine = QagmlEngine::new(config.qagml_config.clone(), None)
             .expect("Failed to create QAGML engine");
         let bus_width_engine = BusWidthAmplificationEngine::new(config.bus_width_config.clone());
 
@@ -141,6 +148,9 @@ impl SymmetrixQagmlOptimizer {
             sheaf_space,
             tensor_folder,
             config,
+            allocations: HashMap::new(),
+            freed_allocations: HashSet::new(),
+            region_stats: HashMap::new(),
         }
     }
 
@@ -162,6 +172,19 @@ impl SymmetrixQagmlOptimizer {
             .map_err(|e| e.to_string())
     }
 
+    /// Persist the engine's temporal-coherence history
+    pub fn save_temporal_history(&self, path: &std::path::Path) -> Result<(), String> {
+        let engine = self.qagml_engine.read().map_err(|e| e.to_string())?;
+        engine.save_temporal_history(path).map_err(|e| e.to_string())
+    }
+
+    /// Restore temporal-coherence history (unreadable files start fresh)
+    pub fn load_temporal_history(&self, path: &std::path::Path) -> Result<(), String> {
+        let mut engine = self.qagml_engine.write().map_err(|e| e.to_string())?;
+        engine.load_temporal_history(path);
+        Ok(())
+    }
+
     /// Get QAGML memory statistics
     pub fn get_memory_stats(&self) -> Result<GPUMemoryStats, String> {
         let engine = self.qagml_engine.read().map_err(|e| e.to_string())?;
@@ -250,6 +273,56 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_allocation_double_free_rejected() {
+        let mut optimizer = SymmetrixQagmlOptimizer::new(SymmetrixQagmlConfig::default());
+
+        let allocation = optimizer.allocate_amplified_region(4096, "hbm").unwrap();
+        let listed = optimizer.list_allocations();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].allocation_id, allocation.allocation_id);
+        assert_eq!(listed[0].region_type, "hbm");
+
+        assert!(optimizer.free_amplified_region(&allocation.allocation_id).is_ok());
+        assert!(optimizer.list_allocations().is_empty());
+
+        let err = optimizer.free_amplified_region(&allocation.allocation_id).unwrap_err();
+        assert!(err.contains("already been freed"));
+    }
+
+    #[test]
+    fn test_region_stats_tracked_independently() {
+        let mut optimizer = SymmetrixQagmlOptimizer::new(SymmetrixQagmlConfig::default());
+
+        let kv = optimizer.allocate_amplified_region(4096, "kv_cache").unwrap();
+        optimizer.allocate_amplified_region(1024, "kv_cache").unwrap();
+        optimizer.allocate_amplified_region(65536, "weights").unwrap();
+
+        let stats = optimizer.region_stats();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats["kv_cache"].live_allocations, 2);
+        assert_eq!(stats["kv_cache"].live_bytes, 5120);
+        assert_eq!(stats["weights"].live_allocations, 1);
+        assert_eq!(stats["weights"].live_bytes, 65536);
+
+        optimizer.free_amplified_region(&kv.allocation_id).unwrap();
+        let stats = optimizer.region_stats();
+        assert_eq!(stats["kv_cache"], RegionStats {
+            total_allocations: 2,
+            live_allocations: 1,
+            live_bytes: 1024,
+            total_bytes_allocated: 5120,
+        });
+        assert_eq!(stats["weights"].live_bytes, 65536);
+    }
+
+    #[test]
+    fn test_free_unknown_allocation_rejected() {
+        let mut optimizer = SymmetrixQagmlOptimizer::new(SymmetrixQagmlConfig::default());
+        let err = optimizer.free_amplified_region("not-an-allocation").unwrap_err();
+        assert!(err.contains("unknown allocation id"));
+    }
+
     #[test]
     fn test_metrics() {
         let config = SymmetrixQagmlConfig::default();
@@ -277,11 +350,35 @@ mod tests {
 /// Virtual memory allocation result
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct VirtualMemoryAllocationResult {
+    pub allocation_id: String,
     pub virtual_address: u64,
     pub size: usize,
     pub region_type: String,
 }
 
+/// Registry entry for a live amplified-region allocation
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct AllocationRecord {
+    pub allocation_id: String,
+    pub virtual_address: u64,
+    pub size: usize,
+    pub region_type: String,
+    pub allocated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Allocation totals for one named region ("kv_cache", "weights", ...)
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RegionStats {
+    /// Allocations made in the region since startup
+    pub total_allocations: u64,
+    /// Allocations not yet freed
+    pub live_allocations: u64,
+    /// Bytes held by live allocations
+    pub live_bytes: u64,
+    /// Bytes allocated in the region since startup
+    pub total_bytes_allocated: u64,
+}
+
 /// QagmlIntegration is an alias for SymmetrixQagmlOptimizer
 pub type QagmlIntegration = SymmetrixQagmlOptimizer;
 
@@ -293,7 +390,21 @@ impl SymmetrixQagmlOptimizer {
     pub fn allocate_amplified_region(&mut self, size_bytes: u64, region: &str)
         -> Result<VirtualMemoryAllocationResult, String> {
         let address = self.allocate_virtual_memory(size_bytes as usize)?;
+        let allocation_id = uuid::Uuid::new_v4().to_string();
+        self.allocations.insert(allocation_id.clone(), AllocationRecord {
+            allocation_id: allocation_id.clone(),
+            virtual_address: address,
+            size: size_bytes as usize,
+            region_type: region.to_string(),
+            allocated_at: chrono::Utc::now(),
+        });
+        let stats = self.region_stats.entry(region.to_string()).or_default();
+        stats.total_allocations += 1;
+        stats.live_allocations += 1;
+        stats.live_bytes += size_bytes;
+        stats.total_bytes_allocated += size_bytes;
         Ok(VirtualMemoryAllocationResult {
+            allocation_id,
             virtual_address: address,
             size: size_bytes as usize,
             region_type: region.to_string(),
@@ -301,9 +412,33 @@ impl SymmetrixQagmlOptimizer {
     }
 
     /// Free amplified memory region
-    pub fn free_amplified_region(&mut self, _allocation_id: &str) -> Result<(), String> {
-        // Memory is managed by QAGML engine automatically
-        Ok(())
+    ///
+    /// Fails if the id was never allocated or has already been freed.
+    pub fn free_amplified_region(&mut self, allocation_id: &str) -> Result<(), String> {
+        if let Some(record) = self.allocations.remove(allocation_id) {
+            if let Some(stats) = self.region_stats.get_mut(&record.region_type) {
+                stats.live_allocations -= 1;
+                stats.live_bytes -= record.size as u64;
+            }
+            self.freed_allocations.insert(allocation_id.to_string());
+            Ok(())
+        } else if self.freed_allocations.contains(allocation_id) {
+            Err(format!("allocation '{}' has already been freed", allocation_id))
+        } else {
+            Err(format!("unknown allocation id '{}'", allocation_id))
+        }
+    }
+
+    /// List live allocations, oldest first
+    pub fn list_allocations(&self) -> Vec<AllocationRecord> {
+        let mut records: Vec<AllocationRecord> = self.allocations.values().cloned().collect();
+        records.sort_by_key(|r| r.allocated_at);
+        records
+    }
+
+    /// Allocation counts and byte totals per region
+    pub fn region_stats(&self) -> HashMap<String, RegionStats> {
+        self.region_stats.clone()
     }
 
     /// Get integration stats (alias for get_metrics)
@@ -9,6 +9,7 @@
 //! - Sheaf-cohomological packet routing optimization
 //! - Tensor-folded network state management
 
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::sync::RwLock;
 use thiserror::Error;
@@ -30,6 +31,7 @@ pub use qanban::{
     SIMDVectorizationEngine,
     BranchFreeEngine,
     TemporalCoherenceEngine,
+    CongestionDetector,
 };
 
 use crate::sheaf::{SheafSpace, SheafConfig};
@@ -68,6 +70,12 @@ pub struct SymmetrixQanbanOptimizer {
     /// Tensor folder for cache-optimized state
     tensor_folder: TensorFolder,
 
+    /// Per-path congestion feedback for flow optimization
+    congestion: CongestionDetector,
+
+    /// Path each flow is routed over (flows without an entry use their own id)
+    flow_paths: HashMap<String, String>,
+
     /// Configuration
     config: SymmetrixQanbanConfig,
 }
@@ -128,6 +136,8 @@ impl SymmetrixQanbanOptimizer {
             galois_field: GaloisFieldEngine::new(),
             sheaf_space: Arc::new(RwLock::new(sheaf_space)),
             tensor_folder,
+            congestion: CongestionDetector::default(),
+            flow_paths: HashMap::new(),
             config,
         })
     }
@@ -162,7 +172,9 @@ impl SymmetrixQanbanOptimizer {
 
         Ok(QanbanMetrics {
             packets_processed: health.packets_processed,
-            is_healthy: health.is_healthy,
+            is_healthy: health.readiness,
+            liveness: health.liveness,
+            readiness: health.readiness,
             uptime_seconds: health.uptime_seconds,
             postulates_active: health.postulates_active,
             memory_usage_mb: health.memory_usage_mb,
@@ -184,6 +196,26 @@ impl SymmetrixQanbanOptimizer {
     pub fn sheaf_space(&self) -> Arc<RwLock<SheafSpace>> {
         Arc::clone(&self.sheaf_space)
     }
+
+    /// Congestion detector consulted by `optimize_flow`
+    pub fn congestion_detector(&self) -> &CongestionDetector {
+        &self.congestion
+    }
+
+    /// Mutable congestion detector, for feeding path capacity and utilization
+    pub fn congestion_detector_mut(&mut self) -> &mut CongestionDetector {
+        &mut self.congestion
+    }
+
+    /// Route a flow over `path_id` for congestion checks
+    pub fn set_flow_path(&mut self, flow_id: &str, path_id: &str) {
+        self.flow_paths.insert(flow_id.to_string(), path_id.to_string());
+    }
+
+    /// Path a flow is routed over
+    pub fn flow_path<'a>(&'a self, flow_id: &'a str) -> &'a str {
+        self.flow_paths.get(flow_id).map_or(flow_id, String::as_str)
+    }
 }
 
 /// Performance metrics for the integrated optimizer
@@ -191,12 +223,16 @@ impl SymmetrixQanbanOptimizer {
 pub struct QanbanMetrics {
     /// Total packets processed
     pub packets_processed: u64,
-    /// Whether the engine is healthy
+    /// Whether the engine is healthy (same as `readiness`)
     pub is_healthy: bool,
+    /// The engine is responding
+    pub liveness: bool,
+    /// Every postulate is up and the packet queue is not backed up
+    pub readiness: bool,
     /// Uptime in seconds
     pub uptime_seconds: u64,
-    /// Number of active postulates (should be 10)
-    pub postulates_active: u8,
+    /// Bitmask of postulates that are up (`qanban::ALL_POSTULATES_MASK` when all 10 are)
+    pub postulates_active: u16,
     /// Memory usage in MB
     pub memory_usage_mb: f64,
     /// Calculated throughput (packets per second)
@@ -218,6 +254,10 @@ pub type QanbanConfigAlias = SymmetrixQanbanConfig;
 pub struct FlowOptimizationResult {
     pub flow_id: String,
     pub original_bandwidth_gbps: f64,
+    /// Requested bandwidth capped at the path's congestion headroom
+    pub attainable_bandwidth_gbps: f64,
+    /// The flow's path is congested or could not fit the requested bandwidth
+    pub congested: bool,
     pub optimized_bandwidth_pbps: f64,
     pub amplification_factor: f64,
     pub latency_reduction_percent: f64,
@@ -225,12 +265,23 @@ pub struct FlowOptimizationResult {
 
 impl SymmetrixQanbanOptimizer {
     /// Optimize bandwidth flow
+    ///
+    /// The target is capped at the headroom `CongestionDetector` reports for
+    /// the flow's path; untracked paths are not capped.
     pub fn optimize_flow(&mut self, flow_id: &str, target_gbps: f64)
         -> Result<FlowOptimizationResult, String> {
+        let path = self.flow_path(flow_id);
+        let attainable_gbps = match self.congestion.headroom_gbps(path) {
+            Some(headroom) => target_gbps.min(headroom),
+            None => target_gbps,
+        };
+        let congested = self.congestion.is_congested(path) || attainable_gbps < target_gbps;
         Ok(FlowOptimizationResult {
             flow_id: flow_id.to_string(),
             original_bandwidth_gbps: target_gbps,
-            optimized_bandwidth_pbps: target_gbps * 1_000_000.0 / 1_000.0, // Convert to Pbps
+            attainable_bandwidth_gbps: attainable_gbps,
+            congested,
+            optimized_bandwidth_pbps: attainable_gbps * 1_000_000.0 / 1_000.0, // Convert to Pbps
             amplification_factor: 1_000_000.0,
             latency_reduction_percent: 99.9,
         })
@@ -241,3 +292,28 @@ impl SymmetrixQanbanOptimizer {
         self.get_metrics().map_err(|e| e.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_optimize_flow_capped_on_congested_path() {
+        let mut optimizer = SymmetrixQanbanOptimizer::new(SymmetrixQanbanConfig::default()).unwrap();
+
+        let result = optimizer.optimize_flow("flow-1", 40.0).unwrap();
+        assert_eq!(result.attainable_bandwidth_gbps, 40.0);
+        assert!(!result.congested);
+
+        optimizer.set_flow_path("flow-1", "spine-1");
+        let detector = optimizer.congestion_detector_mut();
+        detector.set_capacity("spine-1", 100.0);
+        detector.record_utilization("spine-1", 90.0);
+
+        let result = optimizer.optimize_flow("flow-1", 40.0).unwrap();
+        assert!(result.congested);
+        assert!(result.attainable_bandwidth_gbps < 40.0);
+        assert_eq!(result.attainable_bandwidth_gbps, 10.0);
+        assert_eq!(result.original_bandwidth_gbps, 40.0);
+    }
+}
@@ -1,20 +1,64 @@
 //! # UAO-QTCAM Cache - Redis Replacement
 //!
-//! High-performance in-memory cache using UAO-QTCAM compression.
-//! Provides 250× more capacity than Redis with 2-3× lower latency.
+//! High-performance in-memory cache that stores values compressed.
+//! Capacity gain over Redis depends on how compressible the values are;
+//! [`CacheStats`] reports the measured ratio.
 //!
 //! ## Features
-//! - 250× compression via tensor folding
+//! - DEFLATE compression of byte values (lossless, transparent on GET)
 //! - 0.2ms latency (vs Redis 0.5-1ms)
 //! - LRU eviction with weighted scoring
+//! - TTL expiry (lazy on read plus a background sweeper)
+//! - List and hash values (`LPUSH`/`RPUSH`/`LRANGE`, `HSET`/`HGET`)
 //! - Thread-safe concurrent access
+//! - Replication: every mutation, including evictions and TTL expiry, is
+//!   recorded in a sequenced op log that read-only replicas pull with
+//!   [`UaoQtcamCache::drain_ops_since`] and replay with
+//!   [`UaoQtcamCache::apply_ops`]; a replica that falls behind the bounded
+//!   log reseeds itself with [`UaoQtcamCache::resync`]
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::io::{Read, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info};
 
+/// Error returned when an operation targets a key holding another value type
+pub const WRONGTYPE_ERROR: &str = "WRONGTYPE Operation against a key holding the wrong kind of value";
+
+/// Error returned when a write reaches a read-only replica
+pub const READONLY_ERROR: &str = "READONLY You can't write against a read only replica";
+
+/// Error returned when the ops a replica asks for were dropped from the log
+pub const OP_LOG_TRUNCATED_ERROR: &str = "ERR op log truncated, replica must resync";
+
+/// Error returned when a replica is handed ops that skip a sequence number
+pub const OP_SEQ_GAP_ERROR: &str = "ERR op sequence gap, replica must resync";
+
+/// Replication ops retained by default before the oldest are dropped
+pub const DEFAULT_OP_LOG_CAPACITY: usize = 10_000;
+
+/// Typed cache value
+///
+/// Byte strings live compressed in [`CacheEntry::compressed_value`]; lists
+/// and hashes are stored uncompressed so elements can be updated in place.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub enum CacheValue {
+    /// Opaque bytes (see `compressed_value`)
+    #[default]
+    Bytes,
+    /// Redis-style list
+    List(VecDeque<Vec<u8>>),
+    /// Redis-style hash
+    Hash(HashMap<String, Vec<u8>>),
+}
+
 /// Cache entry with compression metadata
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CacheEntry {
@@ -30,6 +74,42 @@ pub struct CacheEntry {
     pub access_count: u64,
     /// Time-to-live in seconds (0 = no expiry)
     pub ttl: u64,
+    /// Value type; list and hash payloads are held here
+    #[serde(default)]
+    pub value: CacheValue,
+}
+
+impl CacheEntry {
+    /// Whether the entry's TTL has elapsed at `now` (Unix epoch seconds)
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.ttl > 0 && now > self.created_at + self.ttl as i64
+    }
+
+    /// Unix time (epoch seconds) after which the entry expires, if it has a TTL
+    pub fn expires_at(&self) -> Option<i64> {
+        (self.ttl > 0).then(|| self.created_at + self.ttl as i64)
+    }
+
+    // Expire at an absolute time, or never. A TTL of 0 means no expiry, so
+    // `created_at` moves back if needed to keep the TTL positive.
+    fn set_expires_at(&mut self, expires_at: Option<i64>) {
+        match expires_at {
+            Some(at) => {
+                self.created_at = self.created_at.min(at - 1);
+                self.ttl = (at - self.created_at) as u64;
+            }
+            None => self.ttl = 0,
+        }
+    }
+
+    /// Bytes this entry occupies in the cache
+    pub fn stored_size(&self) -> usize {
+        self.compressed_value.len() + match &self.value {
+            CacheValue::Bytes => 0,
+            CacheValue::List(list) => list.iter().map(Vec::len).sum(),
+            CacheValue::Hash(hash) => hash.iter().map(|(field, value)| field.len() + value.len()).sum(),
+        }
+    }
 }
 
 /// Cache statistics
@@ -40,6 +120,8 @@ pub struct CacheStats {
     pub sets: u64,
     pub deletes: u64,
     pub evictions: u64,
+    /// Entries removed because their TTL elapsed
+    pub expired_evictions: u64,
     pub entry_count: usize,
     pub compressed_bytes: usize,
     pub original_bytes: usize,
@@ -47,6 +129,42 @@ pub struct CacheStats {
     pub hit_rate: f64,
 }
 
+/// Replicated mutation
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op")]
+pub enum CacheOpKind {
+    /// `SET key value [ttl]`, carrying the absolute expiry (epoch seconds)
+    Set { key: String, value: Vec<u8>, expires_at: Option<i64> },
+    /// Removal of an entry by `DEL`, eviction or TTL expiry
+    Delete { key: String },
+    /// `INCR key` on an existing counter, carrying the resulting value
+    Incr { key: String, value: i64 },
+    /// `EXPIRE key ttl`, carrying the absolute expiry (epoch seconds)
+    Expire { key: String, expires_at: i64 },
+    /// `LPUSH`/`RPUSH key values...`
+    ListPush { key: String, values: Vec<Vec<u8>>, left: bool },
+    /// `HSET key field value`
+    HashSet { key: String, field: String, value: Vec<u8> },
+    /// Removal of every entry
+    Clear,
+}
+
+/// Entry in the replication op log
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CacheOp {
+    /// Position in the primary's log, starting at 1
+    pub seq: u64,
+    #[serde(flatten)]
+    pub kind: CacheOpKind,
+}
+
+/// Bounded log of recent mutations
+#[derive(Debug, Default)]
+struct OpLog {
+    ops: VecDeque<CacheOp>,
+    last_seq: u64,
+}
+
 /// UAO-QTCAM Cache - Redis Replacement
 /// 
 /// Uses tensor folding compression for 250× capacity amplification
@@ -59,8 +177,14 @@ pub struct UaoQtcamCache {
     current_size: Arc<RwLock<usize>>,
     /// Statistics
     stats: Arc<RwLock<CacheStats>>,
-    /// Compression ratio (default 250×)
-    compression_ratio: f64,
+    /// Mutations for replicas to follow
+    op_log: Arc<RwLock<OpLog>>,
+    /// Maximum ops kept in `op_log`
+    op_log_capacity: usize,
+    /// Sequence number of the last op applied from a primary
+    applied_seq: Arc<RwLock<u64>>,
+    /// Refuse client writes (replica mode)
+    read_only: AtomicBool,
 }
 
 impl UaoQtcamCache {
@@ -68,11 +192,12 @@ impl UaoQtcamCache {
     /// 
     /// # Arguments
     /// * `max_size` - Maximum compressed cache size in bytes
-    /// * `compression_ratio` - Expected compression ratio (default 250.0)
+    /// * `compression_ratio` - Expected compression ratio, used only to log the
+    ///   estimated capacity; [`CacheStats::compression_ratio`] is measured
     pub fn new(max_size: usize, compression_ratio: f64) -> Self {
         info!("🚀 Initializing UAO-QTCAM Cache (Redis Replacement)");
         info!("   Max compressed size: {} MB", max_size / (1024 * 1024));
-        info!("   Effective capacity: {} MB ({}× compression)", 
+        info!("   Estimated capacity: {} MB (assuming {}× compression)", 
               (max_size as f64 * compression_ratio) as usize / (1024 * 1024),
               compression_ratio);
         
@@ -81,30 +206,147 @@ impl UaoQtcamCache {
             max_size,
             current_size: Arc::new(RwLock::new(0)),
             stats: Arc::new(RwLock::new(CacheStats::default())),
-            compression_ratio,
+            op_log: Arc::new(RwLock::new(OpLog::default())),
+            op_log_capacity: DEFAULT_OP_LOG_CAPACITY,
+            applied_seq: Arc::new(RwLock::new(0)),
+            read_only: AtomicBool::new(false),
+        }
+    }
+
+    /// Keep at most `capacity` ops in the replication log
+    pub fn with_op_log_capacity(mut self, capacity: usize) -> Self {
+        self.op_log_capacity = capacity.max(1);
+        self
+    }
+
+    /// Switch replica mode on or off. A read-only cache rejects client writes
+    /// with [`READONLY_ERROR`] but still accepts [`Self::apply_ops`].
+    pub fn set_read_only(&self, read_only: bool) {
+        self.read_only.store(read_only, Ordering::Relaxed);
+    }
+
+    /// Whether the cache is a read-only replica
+    pub fn is_read_only(&self) -> bool {
+        self.read_only.load(Ordering::Relaxed)
+    }
+
+    /// Ops recorded after `seq`, oldest first
+    ///
+    /// Replicas pass the last sequence number they applied (0 initially).
+    /// The log is bounded: once ops after `seq` have been dropped, or `seq`
+    /// is ahead of the log (the primary restarted), this fails with
+    /// [`OP_LOG_TRUNCATED_ERROR`] and the replica must [`Self::resync`].
+    pub fn drain_ops_since(&self, seq: u64) -> Result<Vec<CacheOp>, String> {
+        let log = self.op_log.read().map_err(|e| e.to_string())?;
+        let first = log.ops.front().map_or(log.last_seq + 1, |op| op.seq);
+        if seq + 1 < first || seq > log.last_seq {
+            return Err(OP_LOG_TRUNCATED_ERROR.to_string());
+        }
+        Ok(log.ops.iter().filter(|op| op.seq > seq).cloned().collect())
+    }
+
+    /// Sequence number of the newest op in the log (0 if none)
+    pub fn last_op_seq(&self) -> Result<u64, String> {
+        Ok(self.op_log.read().map_err(|e| e.to_string())?.last_seq)
+    }
+
+    /// Replay a primary's ops, skipping any already applied
+    ///
+    /// Ops at or below the last applied sequence number are ignored, so the
+    /// same batch can be delivered more than once. An op that skips a
+    /// sequence number fails with [`OP_SEQ_GAP_ERROR`], leaving the ops
+    /// before it applied; the replica must then [`Self::resync`]. Returns
+    /// how many ops were applied.
+    pub fn apply_ops(&self, ops: &[CacheOp]) -> Result<usize, String> {
+        let mut applied_seq = self.applied_seq.write().map_err(|e| e.to_string())?;
+        let mut applied = 0;
+        for op in ops {
+            if op.seq <= *applied_seq {
+                continue;
+            }
+            if op.seq != *applied_seq + 1 {
+                return Err(OP_SEQ_GAP_ERROR.to_string());
+            }
+            match &op.kind {
+                CacheOpKind::Set { key, value, expires_at } => self.store(key, value, *expires_at, false)?,
+                CacheOpKind::Delete { key } => {
+                    self.remove(key, false)?;
+                }
+                CacheOpKind::Incr { key, value } => self.store_counter(key, *value)?,
+                CacheOpKind::Expire { key, expires_at } => {
+                    self.set_expiry(key, *expires_at, false)?;
+                }
+                CacheOpKind::ListPush { key, values, left } => {
+                    self.push_values(key, values.clone(), *left, false)?;
+                }
+                CacheOpKind::HashSet { key, field, value } => {
+                    self.set_field(key, field, value, false)?;
+                }
+                CacheOpKind::Clear => self.clear_entries(false)?,
+            }
+            *applied_seq = op.seq;
+            applied += 1;
         }
+        Ok(applied)
+    }
+
+    /// Snapshot for seeding a replica, with the sequence number it reflects
+    pub fn replication_snapshot(&self) -> Result<(String, u64), String> {
+        let now = chrono::Utc::now().timestamp();
+        let cache = self.cache.read().map_err(|e| e.to_string())?;
+        // Ops are recorded under the cache write lock, so the two agree
+        let seq = self.op_log.read().map_err(|e| e.to_string())?.last_seq;
+        let json = serde_json::to_string(&Self::live_copy(&cache, now)).map_err(|e| e.to_string())?;
+        Ok((json, seq))
+    }
+
+    /// Replace a replica's entries with a [`Self::replication_snapshot`]
+    ///
+    /// Ops after `seq` can be applied afterwards. Returns the number of
+    /// entries restored.
+    pub fn resync(&self, snapshot: &str, seq: u64) -> Result<usize, String> {
+        let entries: HashMap<String, CacheEntry> = serde_json::from_str(snapshot).map_err(|e| e.to_string())?;
+        let mut applied_seq = self.applied_seq.write().map_err(|e| e.to_string())?;
+        let restored = self.restore_entries(entries, true)?;
+        *applied_seq = seq;
+        info!("UAO-QTCAM replica resynced at op {} ({} entries)", seq, restored);
+        Ok(restored)
+    }
+
+    /// Sequence number of the last op applied from a primary
+    pub fn applied_seq(&self) -> Result<u64, String> {
+        Ok(*self.applied_seq.read().map_err(|e| e.to_string())?)
     }
 
     /// SET operation - Store value with optional TTL
     pub fn set(&self, key: &str, value: &[u8], ttl: Option<u64>) -> Result<(), String> {
+        self.check_writable()?;
+        let expires_at = ttl.filter(|&ttl| ttl > 0)
+            .map(|ttl| chrono::Utc::now().timestamp() + ttl as i64);
+        self.store(key, value, expires_at, true)
+    }
+
+    // Store a byte value, recording it in the op log when `log` is set
+    fn store(&self, key: &str, value: &[u8], expires_at: Option<i64>, log: bool) -> Result<(), String> {
         let start = Instant::now();
         
-        // Compress value using tensor folding simulation
-        let compressed = self.compress(value);
+        let compressed = Self::compress(value)?;
         let compressed_size = compressed.len();
         
         // Check if we need to evict
-        self.evict_if_needed(compressed_size)?;
+        self.evict_if_needed(compressed_size, log)?;
         
         let now = chrono::Utc::now().timestamp();
-        let entry = CacheEntry {
+        let mut entry = CacheEntry {
             compressed_value: compressed,
             original_size: value.len(),
             created_at: now,
             last_accessed: now,
             access_count: 0,
-            ttl: ttl.unwrap_or(0),
+            ttl: 0,
+            value: CacheValue::Bytes,
         };
+        entry.set_expires_at(expires_at);
         
         // Update cache
         {
@@ -113,11 +355,15 @@ impl UaoQtcamCache {
             
             // Remove old entry size if exists
             if let Some(old) = cache.get(key) {
-                *current_size -= old.compressed_value.len();
+                *current_size -= old.stored_size();
             }
             
             *current_size += compressed_size;
             cache.insert(key.to_string(), entry);
+
+            if log {
+                self.record(CacheOpKind::Set { key: key.to_string(), value: value.to_vec(), expires_at })?;
+            }
         }
         
         // Update stats
@@ -139,27 +385,31 @@ impl UaoQtcamCache {
     pub fn get(&self, key: &str) -> Result<Option<Vec<u8>>, String> {
         let start = Instant::now();
         
+        let mut expired = false;
         let result = {
             let mut cache = self.cache.write().map_err(|e| e.to_string())?;
             
             if let Some(entry) = cache.get_mut(key) {
                 // Check TTL expiry
                 let now = chrono::Utc::now().timestamp();
-                if entry.ttl > 0 && now > entry.created_at + entry.ttl as i64 {
+                if entry.is_expired(now) {
                     // Expired - remove and return miss
-                    let size = entry.compressed_value.len();
+                    let size = entry.stored_size();
                     cache.remove(key);
+                    self.record(CacheOpKind::Delete { key: key.to_string() })?;
                     let mut current_size = self.current_size.write().map_err(|e| e.to_string())?;
                     *current_size -= size;
+                    expired = true;
                     None
+                } else if !matches!(entry.value, CacheValue::Bytes) {
+                    return Err(WRONGTYPE_ERROR.to_string());
                 } else {
                     // Update access stats
                     entry.last_accessed = now;
                     entry.access_count += 1;
                     
                     // Decompress
-                    let decompressed = self.decompress(&entry.compressed_value, entry.original_size);
-                    Some(decompressed)
+                    Some(Self::decompress(&entry.compressed_value, entry.original_size)?)
                 }
             } else {
                 None
@@ -174,6 +424,9 @@ impl UaoQtcamCache {
                 debug!("UAO-QTCAM GET {} (HIT, {:.2}ms)", key, start.elapsed().as_secs_f64() * 1000.0);
             } else {
                 stats.misses += 1;
+                if expired {
+                    stats.expired_evictions += 1;
+                }
                 debug!("UAO-QTCAM GET {} (MISS)", key);
             }
             self.update_stats_internal(&mut stats);
@@ -184,11 +437,20 @@ impl UaoQtcamCache {
 
     /// DELETE operation
     pub fn delete(&self, key: &str) -> Result<bool, String> {
+        self.check_writable()?;
+        self.remove(key, true)
+    }
+
+    // Remove a key, recording the delete in the op log when `log` is set
+    fn remove(&self, key: &str, log: bool) -> Result<bool, String> {
         let mut cache = self.cache.write().map_err(|e| e.to_string())?;
 
         if let Some(entry) = cache.remove(key) {
+            if log {
+                self.record(CacheOpKind::Delete { key: key.to_string() })?;
+            }
             let mut current_size = self.current_size.write().map_err(|e| e.to_string())?;
-            *current_size -= entry.compressed_value.len();
+            *current_size -= entry.stored_size();
 
             let mut stats = self.stats.write().map_err(|e| e.to_string())?;
             stats.deletes += 1;
@@ -203,23 +465,122 @@ impl UaoQtcamCache {
     /// EXISTS operation
     pub fn exists(&self, key: &str) -> Result<bool, String> {
         let cache = self.cache.read().map_err(|e| e.to_string())?;
-        Ok(cache.contains_key(key))
+        let now = chrono::Utc::now().timestamp();
+        Ok(cache.get(key).is_some_and(|entry| !entry.is_expired(now)))
+    }
+
+    /// TTL operation: remaining seconds, `Some(-1)` for a key without expiry
+    /// and `None` for a missing or expired key (Redis reports `-2`)
+    pub fn ttl(&self, key: &str) -> Result<Option<i64>, String> {
+        let cache = self.cache.read().map_err(|e| e.to_string())?;
+        let now = chrono::Utc::now().timestamp();
+        Ok(match cache.get(key) {
+            Some(entry) if entry.is_expired(now) => None,
+            Some(entry) if entry.ttl == 0 => Some(-1),
+            Some(entry) => Some((entry.created_at + entry.ttl as i64 - now).max(0)),
+            None => None,
+        })
+    }
+
+    /// EXPIRE operation: expire an existing key `ttl_seconds` from now
+    ///
+    /// As in Redis, a TTL of 0 deletes the key. Returns `false` if the key
+    /// is missing or already expired.
+    pub fn expire(&self, key: &str, ttl_seconds: u64) -> Result<bool, String> {
+        self.check_writable()?;
+        if ttl_seconds == 0 {
+            // `remove` ignores TTLs, so an already expired key reports false
+            return Ok(self.exists(key)? && self.remove(key, true)?);
+        }
+        let expires_at = chrono::Utc::now().timestamp() + ttl_seconds as i64;
+        self.set_expiry(key, expires_at, true)
+    }
+
+    // Set the absolute expiry of a live key, recording it when `log` is set
+    fn set_expiry(&self, key: &str, expires_at: i64, log: bool) -> Result<bool, String> {
+        let mut cache = self.cache.write().map_err(|e| e.to_string())?;
+        let now = chrono::Utc::now().timestamp();
+        match cache.get_mut(key) {
+            Some(entry) if !entry.is_expired(now) => {
+                entry.set_expires_at(Some(expires_at));
+                if log {
+                    self.record(CacheOpKind::Expire { key: key.to_string(), expires_at })?;
+                }
+                debug!("UAO-QTCAM EXPIRE {} at {}", key, expires_at);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Remove every entry whose TTL has elapsed, returning how many were dropped
+    pub fn purge_expired(&self) -> Result<usize, String> {
+        let now = chrono::Utc::now().timestamp();
+        let mut cache = self.cache.write().map_err(|e| e.to_string())?;
+        let mut current_size = self.current_size.write().map_err(|e| e.to_string())?;
+
+        let mut expired = Vec::new();
+        cache.retain(|key, entry| {
+            if entry.is_expired(now) {
+                *current_size -= entry.stored_size();
+                debug!("UAO-QTCAM EXPIRE {}", key);
+                expired.push(key.clone());
+                false
+            } else {
+                true
+            }
+        });
+        let purged = expired.len();
+        for key in expired {
+            self.record(CacheOpKind::Delete { key })?;
+        }
+        drop(current_size);
+        drop(cache);
+
+        if purged > 0 {
+            let mut stats = self.stats.write().map_err(|e| e.to_string())?;
+            stats.expired_evictions += purged as u64;
+            self.update_stats_internal(&mut stats);
+        }
+        Ok(purged)
+    }
+
+    /// Spawn a background task that purges expired entries every `interval`
+    ///
+    /// The task holds only a weak reference and exits once the cache is dropped.
+    pub fn spawn_expiry_sweeper(self: &Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let cache = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let cache = match cache.upgrade() {
+                    Some(cache) => cache,
+                    None => break,
+                };
+                if let Err(e) = cache.purge_expired() {
+                    debug!("UAO-QTCAM expiry sweep failed: {}", e);
+                }
+            }
+        })
     }
 
     /// INCR operation for rate limiting
     pub fn incr(&self, key: &str) -> Result<i64, String> {
+        self.check_writable()?;
         let mut cache = self.cache.write().map_err(|e| e.to_string())?;
 
         if let Some(entry) = cache.get_mut(key) {
-            let value = self.decompress(&entry.compressed_value, entry.original_size);
+            if !matches!(entry.value, CacheValue::Bytes) {
+                return Err(WRONGTYPE_ERROR.to_string());
+            }
+            let value = Self::decompress(&entry.compressed_value, entry.original_size)?;
             let counter: i64 = String::from_utf8_lossy(&value)
                 .parse()
                 .unwrap_or(0) + 1;
 
-            let new_value = counter.to_string().into_bytes();
-            entry.compressed_value = self.compress(&new_value);
-            entry.original_size = new_value.len();
-            entry.last_accessed = chrono::Utc::now().timestamp();
+            self.rewrite_bytes(entry, counter.to_string().as_bytes())?;
+            self.record(CacheOpKind::Incr { key: key.to_string(), value: counter })?;
 
             Ok(counter)
         } else {
@@ -229,6 +590,83 @@ impl UaoQtcamCache {
         }
     }
 
+    /// LPUSH/RPUSH operation: push `values` onto the head (`left`) or tail
+    /// of the list at `key`, creating it if missing. Returns the new length.
+    pub fn list_push(&self, key: &str, values: Vec<Vec<u8>>, left: bool) -> Result<usize, String> {
+        self.check_writable()?;
+        self.push_values(key, values, left, true)
+    }
+
+    // Push onto a list, recording the push when `log` is set
+    fn push_values(&self, key: &str, values: Vec<Vec<u8>>, left: bool, log: bool) -> Result<usize, String> {
+        let added = values.iter().map(Vec::len).sum();
+        let op = log.then(|| CacheOpKind::ListPush { key: key.to_string(), values: values.clone(), left });
+        self.update_collection(key, added, CacheValue::List(VecDeque::new()), op, |value| match value {
+            CacheValue::List(list) => {
+                for item in values {
+                    if left {
+                        list.push_front(item);
+                    } else {
+                        list.push_back(item);
+                    }
+                }
+                Some(list.len())
+            }
+            _ => None,
+        })
+    }
+
+    /// LRANGE operation: elements `start..=stop`, negative indices counting
+    /// from the tail. A missing key yields an empty list.
+    pub fn list_range(&self, key: &str, start: i64, stop: i64) -> Result<Vec<Vec<u8>>, String> {
+        let cache = self.cache.read().map_err(|e| e.to_string())?;
+        let now = chrono::Utc::now().timestamp();
+        let list = match cache.get(key) {
+            Some(entry) if !entry.is_expired(now) => match &entry.value {
+                CacheValue::List(list) => list,
+                _ => return Err(WRONGTYPE_ERROR.to_string()),
+            },
+            _ => return Ok(Vec::new()),
+        };
+
+        let len = list.len() as i64;
+        let start = if start < 0 { (len + start).max(0) } else { start };
+        let stop = if stop < 0 { len + stop } else { stop.min(len - 1) };
+        if start > stop || start >= len {
+            return Ok(Vec::new());
+        }
+        Ok(list.range(start as usize..=stop as usize).cloned().collect())
+    }
+
+    /// HSET operation: set `field` in the hash at `key`, creating it if
+    /// missing. Returns `true` if the field is new.
+    pub fn hash_set(&self, key: &str, field: &str, value: &[u8]) -> Result<bool, String> {
+        self.check_writable()?;
+        self.set_field(key, field, value, true)
+    }
+
+    // Set a hash field, recording it when `log` is set
+    fn set_field(&self, key: &str, field: &str, value: &[u8], log: bool) -> Result<bool, String> {
+        let op = log.then(|| CacheOpKind::HashSet { key: key.to_string(), field: field.to_string(), value: value.to_vec() });
+        self.update_collection(key, field.len() + value.len(), CacheValue::Hash(HashMap::new()), op, |entry| match entry {
+            CacheValue::Hash(hash) => Some(hash.insert(field.to_string(), value.to_vec()).is_none()),
+            _ => None,
+        })
+    }
+
+    /// HGET operation
+    pub fn hash_get(&self, key: &str, field: &str) -> Result<Option<Vec<u8>>, String> {
+        let cache = self.cache.read().map_err(|e| e.to_string())?;
+        let now = chrono::Utc::now().timestamp();
+        match cache.get(key) {
+            Some(entry) if !entry.is_expired(now) => match &entry.value {
+                CacheValue::Hash(hash) => Ok(hash.get(field).cloned()),
+                _ => Err(WRONGTYPE_ERROR.to_string()),
+            },
+            _ => Ok(None),
+        }
+    }
+
     /// Get cache statistics
     pub fn stats(&self) -> Result<CacheStats, String> {
         let mut stats = self.stats.write().map_err(|e| e.to_string())?;
@@ -236,80 +674,247 @@ impl UaoQtcamCache {
         Ok(stats.clone())
     }
 
+    /// Copy of every live entry, taken under a brief read lock
+    fn live_entries(&self) -> Result<HashMap<String, CacheEntry>, String> {
+        let now = chrono::Utc::now().timestamp();
+        let cache = self.cache.read().map_err(|e| e.to_string())?;
+        Ok(Self::live_copy(&cache, now))
+    }
+
+    // Entries of `cache` that are still live at `now`
+    fn live_copy(cache: &HashMap<String, CacheEntry>, now: i64) -> HashMap<String, CacheEntry> {
+        cache.iter()
+            .filter(|(_, entry)| !entry.is_expired(now))
+            .map(|(key, entry)| (key.clone(), entry.clone()))
+            .collect()
+    }
+
+    /// Serialize every live entry as JSON (compressed form, TTLs preserved)
+    ///
+    /// Entries are copied out first, so readers are only blocked for the copy.
+    pub fn snapshot_json(&self) -> Result<String, String> {
+        serde_json::to_string(&self.live_entries()?).map_err(|e| e.to_string())
+    }
+
+    /// Write [`UaoQtcamCache::snapshot_json`] to `path`
+    ///
+    /// The snapshot goes to a sibling temp file that is then renamed over
+    /// `path`, so a crash mid-write never leaves a truncated snapshot.
+    pub fn snapshot(&self, path: &Path) -> Result<(), String> {
+        let json = self.snapshot_json()?;
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        std::fs::write(&tmp, json).map_err(|e| e.to_string())?;
+        std::fs::rename(&tmp, path).map_err(|e| e.to_string())?;
+        debug!("Cache snapshot written to {:?}", path);
+        Ok(())
+    }
+
+    /// Load a snapshot written by [`UaoQtcamCache::snapshot`]
+    ///
+    /// Returns the number of entries restored.
+    pub fn restore(&self, path: &Path) -> Result<usize, String> {
+        let json = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        self.restore_json(&json)
+    }
+
+    /// Load entries from [`UaoQtcamCache::snapshot_json`], skipping any that expired meanwhile
+    ///
+    /// A restore is not replicated op by op: the op log is truncated so
+    /// replicas resync. Returns the number of entries restored.
+    pub fn restore_json(&self, json: &str) -> Result<usize, String> {
+        let entries: HashMap<String, CacheEntry> = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        self.restore_entries(entries, false)
+    }
+
+    // Load live `entries`, replacing the whole cache when `replace` is set
+    fn restore_entries(&self, entries: HashMap<String, CacheEntry>, replace: bool) -> Result<usize, String> {
+        let now = chrono::Utc::now().timestamp();
+
+        let mut restored = 0;
+        {
+            let mut cache = self.cache.write().map_err(|e| e.to_string())?;
+            let mut current_size = self.current_size.write().map_err(|e| e.to_string())?;
+            if replace {
+                cache.clear();
+                *current_size = 0;
+            }
+            self.truncate_op_log()?;
+            for (key, entry) in entries {
+                if entry.is_expired(now) {
+                    continue;
+                }
+                *current_size += entry.stored_size();
+                if let Some(old) = cache.insert(key, entry) {
+                    *current_size -= old.stored_size();
+                }
+                restored += 1;
+            }
+        }
+
+        let mut stats = self.stats.write().map_err(|e| e.to_string())?;
+        self.update_stats_internal(&mut stats);
+        Ok(restored)
+    }
+
     /// Clear all cache entries
     pub fn clear(&self) -> Result<(), String> {
+        self.clear_entries(true)
+    }
+
+    // Drop every entry, recording the clear when `log` is set
+    fn clear_entries(&self, log: bool) -> Result<(), String> {
         let mut cache = self.cache.write().map_err(|e| e.to_string())?;
         let mut current_size = self.current_size.write().map_err(|e| e.to_string())?;
 
         cache.clear();
         *current_size = 0;
+        if log {
+            self.record(CacheOpKind::Clear)?;
+        }
 
         info!("UAO-QTCAM Cache cleared");
         Ok(())
     }
 
-    // Internal compression using tensor folding simulation
-    fn compress(&self, data: &[u8]) -> Vec<u8> {
-        // Simulate 250× compression via tensor folding
-        // In production, this would use actual UAO-QTCAM tensor compression
-        let target_size = std::cmp::max(1, data.len() / self.compression_ratio as usize);
+    fn check_writable(&self) -> Result<(), String> {
+        if self.is_read_only() {
+            Err(READONLY_ERROR.to_string())
+        } else {
+            Ok(())
+        }
+    }
 
-        // Simple simulation: store hash + length + sample bytes
-        let mut compressed = Vec::with_capacity(target_size + 16);
+    // Append an op to the replication log, dropping the oldest when full.
+    // Replicas follow their primary's log and keep none of their own.
+    fn record(&self, kind: CacheOpKind) -> Result<(), String> {
+        if self.is_read_only() {
+            return Ok(());
+        }
+        let mut log = self.op_log.write().map_err(|e| e.to_string())?;
+        log.last_seq += 1;
+        let seq = log.last_seq;
+        if log.ops.len() >= self.op_log_capacity {
+            log.ops.pop_front();
+        }
+        log.ops.push_back(CacheOp { seq, kind });
+        Ok(())
+    }
 
-        // Store original length (8 bytes)
-        compressed.extend_from_slice(&(data.len() as u64).to_le_bytes());
+    // Drop every logged op and skip a sequence number, so replicas at any
+    // earlier position get `OP_LOG_TRUNCATED_ERROR` and resync
+    fn truncate_op_log(&self) -> Result<(), String> {
+        let mut log = self.op_log.write().map_err(|e| e.to_string())?;
+        log.ops.clear();
+        log.last_seq += 1;
+        Ok(())
+    }
 
-        // Store checksum (8 bytes)
-        let checksum: u64 = data.iter().map(|&b| b as u64).sum();
-        compressed.extend_from_slice(&checksum.to_le_bytes());
+    // Replace the value of a byte entry in place, keeping its TTL
+    fn rewrite_bytes(&self, entry: &mut CacheEntry, value: &[u8]) -> Result<(), String> {
+        let compressed = Self::compress(value)?;
+        let mut current_size = self.current_size.write().map_err(|e| e.to_string())?;
+        *current_size = *current_size + compressed.len() - entry.compressed_value.len();
+        entry.compressed_value = compressed;
+        entry.original_size = value.len();
+        entry.last_accessed = chrono::Utc::now().timestamp();
+        Ok(())
+    }
 
-        // Store sampled bytes for simulation
-        let sample_rate = std::cmp::max(1, data.len() / target_size);
-        for (i, &byte) in data.iter().enumerate() {
-            if i % sample_rate == 0 {
-                compressed.push(byte);
+    // Replay a primary's INCR result without touching the op log
+    fn store_counter(&self, key: &str, counter: i64) -> Result<(), String> {
+        let value = counter.to_string().into_bytes();
+        let rewritten = {
+            let mut cache = self.cache.write().map_err(|e| e.to_string())?;
+            match cache.get_mut(key) {
+                Some(entry) if matches!(entry.value, CacheValue::Bytes) => {
+                    self.rewrite_bytes(entry, &value)?;
+                    true
+                }
+                _ => false,
             }
+        };
+        if !rewritten {
+            self.store(key, &value, None, false)?;
         }
-
-        compressed
+        Ok(())
     }
 
-    // Internal decompression
-    fn decompress(&self, compressed: &[u8], original_size: usize) -> Vec<u8> {
-        // In production, this would use actual UAO-QTCAM tensor decompression
-        // For simulation, reconstruct from samples
-        if compressed.len() < 16 {
-            return vec![0u8; original_size];
-        }
+    // Apply `update` to the list/hash at `key`, inserting `empty` if the key is
+    // missing or expired. `update` returns `None` when the stored type differs.
+    // On success `op` (if any) is recorded in the op log.
+    fn update_collection<R>(
+        &self,
+        key: &str,
+        added: usize,
+        empty: CacheValue,
+        op: Option<CacheOpKind>,
+        update: impl FnOnce(&mut CacheValue) -> Option<R>,
+    ) -> Result<R, String> {
+        self.evict_if_needed(added, op.is_some())?;
 
-        let mut result = Vec::with_capacity(original_size);
-        let samples = &compressed[16..];
+        let now = chrono::Utc::now().timestamp();
+        let result = {
+            let mut cache = self.cache.write().map_err(|e| e.to_string())?;
+            let mut current_size = self.current_size.write().map_err(|e| e.to_string())?;
 
-        let sample_rate = std::cmp::max(1, original_size / std::cmp::max(1, samples.len()));
-        let mut sample_idx = 0;
+            if cache.get(key).is_some_and(|entry| entry.is_expired(now)) {
+                if let Some(old) = cache.remove(key) {
+                    *current_size -= old.stored_size();
+                    if op.is_some() {
+                        self.record(CacheOpKind::Delete { key: key.to_string() })?;
+                    }
+                }
+            }
 
-        for i in 0..original_size {
-            if i % sample_rate == 0 && sample_idx < samples.len() {
-                result.push(samples[sample_idx]);
-                sample_idx += 1;
-            } else if !result.is_empty() {
-                result.push(*result.last().unwrap_or(&0));
-            } else {
-                result.push(0);
+            let entry = cache.entry(key.to_string()).or_insert_with(|| CacheEntry {
+                compressed_value: Vec::new(),
+                original_size: 0,
+                created_at: now,
+                last_accessed: now,
+                access_count: 0,
+                ttl: 0,
+                value: empty,
+            });
+
+            let before = entry.stored_size();
+            let result = update(&mut entry.value).ok_or_else(|| WRONGTYPE_ERROR.to_string())?;
+            let after = entry.stored_size();
+            entry.original_size = after;
+            entry.last_accessed = now;
+            entry.access_count += 1;
+            *current_size = *current_size + after - before;
+            if let Some(op) = op {
+                self.record(op)?;
             }
-        }
+            result
+        };
 
-        result.truncate(original_size);
-        while result.len() < original_size {
-            result.push(0);
-        }
+        let mut stats = self.stats.write().map_err(|e| e.to_string())?;
+        stats.sets += 1;
+        self.update_stats_internal(&mut stats);
+        Ok(result)
+    }
+
+    // Compress a value with DEFLATE (zlib framing)
+    fn compress(data: &[u8]) -> Result<Vec<u8>, String> {
+        let mut encoder = ZlibEncoder::new(Vec::with_capacity(data.len() / 4 + 16), Compression::default());
+        encoder.write_all(data).map_err(|e| e.to_string())?;
+        encoder.finish().map_err(|e| e.to_string())
+    }
 
-        result
+    // Inflate a value stored by `compress`
+    fn decompress(compressed: &[u8], original_size: usize) -> Result<Vec<u8>, String> {
+        let mut result = Vec::with_capacity(original_size);
+        ZlibDecoder::new(compressed)
+            .read_to_end(&mut result)
+            .map_err(|e| format!("corrupt cache entry: {}", e))?;
+        Ok(result)
     }
 
-    // Evict entries if needed (LRU with weighted scoring)
-    fn evict_if_needed(&self, new_size: usize) -> Result<(), String> {
+    // Evict entries if needed (LRU with weighted scoring), recording the
+    // evictions when `log` is set
+    fn evict_if_needed(&self, new_size: usize, log: bool) -> Result<(), String> {
         let current = *self.current_size.read().map_err(|e| e.to_string())?;
 
         if current + new_size <= self.max_size {
@@ -326,7 +931,7 @@ impl UaoQtcamCache {
                 let recency = v.last_accessed as f64;
                 let frequency = v.access_count as f64;
                 let score = recency * 0.4 + frequency * 0.6;
-                (k.clone(), v.compressed_value.len(), score)
+                (k.clone(), v.stored_size(), score)
             })
             .collect();
 
@@ -345,6 +950,9 @@ impl UaoQtcamCache {
             *current_size -= size;
             stats.evictions += 1;
             debug!("UAO-QTCAM EVICT {} (freed {} bytes)", key, size);
+            if log {
+                self.record(CacheOpKind::Delete { key })?;
+            }
         }
 
         Ok(())
@@ -392,7 +1000,102 @@ mod tests {
         cache.set("test_key", value, None).unwrap();
 
         let result = cache.get("test_key").unwrap();
-        assert!(result.is_some());
+        assert_eq!(result.as_deref(), Some(&value[..]));
+    }
+
+    #[tokio::test]
+    async fn test_cache_ttl_sweeper() {
+        let cache = Arc::new(UaoQtcamCache::new(1024 * 1024, 250.0));
+        let sweeper = cache.spawn_expiry_sweeper(Duration::from_millis(100));
+        cache.set("short", b"expires", Some(1)).unwrap();
+        cache.set("forever", b"stays", None).unwrap();
+        assert!(cache.exists("short").unwrap());
+
+        // No sweeper here: expiry is detected lazily on GET
+        let lazy = UaoQtcamCache::new(1024 * 1024, 250.0);
+        lazy.set("short", b"expires", Some(1)).unwrap();
+
+        // Second-resolution timestamps: a 1s TTL has elapsed within 2s
+        tokio::time::sleep(Duration::from_millis(2200)).await;
+
+        let stats = cache.stats().unwrap();
+        assert_eq!(stats.expired_evictions, 1);
+        assert_eq!(stats.entry_count, 1);
+        assert!(!cache.exists("short").unwrap());
+        assert!(cache.get("forever").unwrap().is_some());
+        sweeper.abort();
+
+        assert!(!lazy.exists("short").unwrap());
+        assert!(lazy.get("short").unwrap().is_none());
+        let stats = lazy.stats().unwrap();
+        assert_eq!(stats.expired_evictions, 1);
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.entry_count, 0);
+    }
+
+    #[test]
+    fn test_cache_snapshot_roundtrip() {
+        let cache = UaoQtcamCache::new(1024 * 1024, 250.0);
+        cache.set("a", b"alpha", None).unwrap();
+        cache.set("b", b"bravo", Some(3600)).unwrap();
+        let snapshot = cache.snapshot_json().unwrap();
+
+        let restored = UaoQtcamCache::new(1024 * 1024, 250.0);
+        assert_eq!(restored.restore_json(&snapshot).unwrap(), 2);
+        assert_eq!(restored.get("a").unwrap(), cache.get("a").unwrap());
+        assert!(restored.exists("b").unwrap());
+        assert_eq!(restored.stats().unwrap().entry_count, 2);
+
+        assert!(restored.restore_json("not json").is_err());
+    }
+
+    #[test]
+    fn test_cache_snapshot_file_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.json");
+
+        let cache = UaoQtcamCache::new(1024 * 1024, 250.0);
+        cache.set("forever", b"alpha", None).unwrap();
+        cache.set("hour", b"bravo", Some(3600)).unwrap();
+        cache.set("minute", b"charlie", Some(60)).unwrap();
+        cache.list_push("list", vec![b"x".to_vec()], true).unwrap();
+        cache.snapshot(&path).unwrap();
+
+        let restored = UaoQtcamCache::new(1024 * 1024, 250.0);
+        assert_eq!(restored.restore(&path).unwrap(), 4);
+        for key in ["forever", "hour", "minute", "list"] {
+            assert!(restored.exists(key).unwrap(), "{} missing", key);
+        }
+        assert_eq!(restored.get("hour").unwrap(), Some(b"bravo".to_vec()));
+        assert_eq!(restored.ttl("forever").unwrap(), Some(-1));
+        for key in ["hour", "minute"] {
+            let before = cache.ttl(key).unwrap().unwrap();
+            let after = restored.ttl(key).unwrap().unwrap();
+            assert!(after > 0 && after <= before && before - after <= 1, "{}: {} -> {}", key, before, after);
+        }
+
+        assert!(restored.restore(&dir.path().join("missing.json")).is_err());
+    }
+
+    #[test]
+    fn test_cache_measures_real_compression() {
+        let cache = UaoQtcamCache::new(16 * 1024 * 1024, 250.0);
+
+        let blob: Vec<u8> = b"vxlan-control-plane "
+            .iter()
+            .cycle()
+            .take(1024 * 1024)
+            .copied()
+            .collect();
+        cache.set("blob", &blob, None).unwrap();
+
+        let stats = cache.stats().unwrap();
+        assert_eq!(stats.original_bytes, blob.len());
+        assert!(stats.compressed_bytes < stats.original_bytes);
+        let expected = stats.original_bytes as f64 / stats.compressed_bytes as f64;
+        assert!((stats.compression_ratio - expected).abs() < f64::EPSILON);
+
+        assert_eq!(cache.get("blob").unwrap().unwrap(), blob);
     }
 
     #[test]
@@ -406,6 +1109,75 @@ mod tests {
         assert!(!cache.exists("key1").unwrap());
     }
 
+    #[test]
+    fn test_cache_ttl() {
+        let cache = UaoQtcamCache::new(1024 * 1024, 250.0);
+
+        cache.set("session", b"abc", Some(60)).unwrap();
+        let remaining = cache.ttl("session").unwrap().unwrap();
+        assert!(remaining > 0 && remaining <= 60);
+
+        cache.set("persistent", b"forever", None).unwrap();
+        assert_eq!(cache.ttl("persistent").unwrap(), Some(-1));
+
+        assert_eq!(cache.ttl("missing").unwrap(), None);
+    }
+
+    #[test]
+    fn test_cache_expire() {
+        let cache = UaoQtcamCache::new(1024 * 1024, 250.0);
+
+        cache.set("key", b"value", None).unwrap();
+        assert!(cache.expire("key", 300).unwrap());
+        let remaining = cache.ttl("key").unwrap().unwrap();
+        assert!(remaining > 290 && remaining <= 300);
+
+        // As in Redis, a zero TTL deletes the key
+        assert!(cache.expire("key", 0).unwrap());
+        assert!(!cache.exists("key").unwrap());
+        assert!(!cache.expire("key", 0).unwrap());
+
+        assert!(!cache.expire("missing", 10).unwrap());
+    }
+
+    #[test]
+    fn test_cache_list() {
+        let cache = UaoQtcamCache::new(1024 * 1024, 250.0);
+
+        assert_eq!(cache.list_push("jobs", vec![b"b".to_vec(), b"c".to_vec()], false).unwrap(), 2);
+        assert_eq!(cache.list_push("jobs", vec![b"a".to_vec()], true).unwrap(), 3);
+
+        let all = cache.list_range("jobs", 0, -1).unwrap();
+        assert_eq!(all, vec![b"a".to_vec(), b"b".to_vec(), b"c".to_vec()]);
+        assert_eq!(cache.list_range("jobs", -2, 10).unwrap(), vec![b"b".to_vec(), b"c".to_vec()]);
+        assert!(cache.list_range("jobs", 2, 1).unwrap().is_empty());
+        assert!(cache.list_range("missing", 0, -1).unwrap().is_empty());
+
+        // Byte-string operations refuse list keys instead of returning garbage
+        assert_eq!(cache.get("jobs").unwrap_err(), WRONGTYPE_ERROR);
+        assert_eq!(cache.incr("jobs").unwrap_err(), WRONGTYPE_ERROR);
+        assert_eq!(cache.hash_get("jobs", "f").unwrap_err(), WRONGTYPE_ERROR);
+
+        cache.set("plain", b"v", None).unwrap();
+        assert_eq!(cache.list_push("plain", vec![b"x".to_vec()], false).unwrap_err(), WRONGTYPE_ERROR);
+        assert_eq!(cache.list_range("plain", 0, -1).unwrap_err(), WRONGTYPE_ERROR);
+    }
+
+    #[test]
+    fn test_cache_hash() {
+        let cache = UaoQtcamCache::new(1024 * 1024, 250.0);
+
+        assert!(cache.hash_set("user:1", "name", b"ada").unwrap());
+        assert!(!cache.hash_set("user:1", "name", b"grace").unwrap());
+        assert_eq!(cache.hash_get("user:1", "name").unwrap(), Some(b"grace".to_vec()));
+        assert_eq!(cache.hash_get("user:1", "email").unwrap(), None);
+        assert_eq!(cache.get("user:1").unwrap_err(), WRONGTYPE_ERROR);
+        assert_eq!(cache.stats().unwrap().compressed_bytes, "name".len() + "grace".len());
+
+        assert!(cache.delete("user:1").unwrap());
+        assert_eq!(cache.stats().unwrap().compressed_bytes, 0);
+    }
+
     #[test]
     fn test_cache_incr() {
         let cache = UaoQtcamCache::new(1024 * 1024, 250.0);
@@ -415,6 +1187,141 @@ mod tests {
         assert_eq!(cache.incr("counter").unwrap(), 3);
     }
 
+    #[test]
+    fn test_replica_converges_from_op_log() {
+        let primary = UaoQtcamCache::new(1024 * 1024, 250.0);
+        primary.set("a", b"alpha", None).unwrap();
+        primary.set("b", b"beta", Some(3600)).unwrap();
+        primary.incr("hits").unwrap();
+        primary.incr("hits").unwrap();
+        primary.incr("hits").unwrap();
+        primary.set("a", b"alpha-2", None).unwrap();
+        primary.delete("b").unwrap();
+        primary.delete("missing").unwrap();
+        assert_eq!(primary.last_op_seq().unwrap(), 7);
+
+        let replica = UaoQtcamCache::new(1024 * 1024, 250.0);
+        replica.set_read_only(true);
+        assert_eq!(replica.set("x", b"y", None).unwrap_err(), READONLY_ERROR);
+        assert_eq!(replica.incr("hits").unwrap_err(), READONLY_ERROR);
+
+        // Pull in two batches, the second overlapping the first
+        let first = primary.drain_ops_since(0).unwrap();
+        assert_eq!(replica.apply_ops(&first[..4]).unwrap(), 4);
+        let rest = primary.drain_ops_since(2).unwrap();
+        assert_eq!(replica.apply_ops(&rest).unwrap(), 3);
+        assert_eq!(replica.applied_seq().unwrap(), 7);
+
+        // Replaying the whole log again changes nothing
+        assert_eq!(replica.apply_ops(&first).unwrap(), 0);
+
+        for key in ["a", "b", "hits", "missing"] {
+            assert_eq!(replica.get(key).unwrap(), primary.get(key).unwrap(), "key {}", key);
+        }
+        assert_eq!(replica.get("hits").unwrap().as_deref(), Some(&b"3"[..]));
+        assert!(replica.drain_ops_since(0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_op_log_is_bounded() {
+        let cache = UaoQtcamCache::new(1024 * 1024, 250.0).with_op_log_capacity(2);
+        for i in 0..5 {
+            cache.set(&format!("k{}", i), b"v", None).unwrap();
+        }
+        let seqs: Vec<u64> = cache.drain_ops_since(3).unwrap().iter().map(|op| op.seq).collect();
+        assert_eq!(seqs, vec![4, 5]);
+
+        // Ops 1-3 are gone, so a replica that has not seen them must resync
+        assert_eq!(cache.drain_ops_since(2).unwrap_err(), OP_LOG_TRUNCATED_ERROR);
+        assert_eq!(cache.drain_ops_since(0).unwrap_err(), OP_LOG_TRUNCATED_ERROR);
+        assert_eq!(cache.drain_ops_since(6).unwrap_err(), OP_LOG_TRUNCATED_ERROR);
+    }
+
+    #[test]
+    fn test_replica_resyncs_after_truncation_or_gap() {
+        let primary = UaoQtcamCache::new(1024 * 1024, 250.0).with_op_log_capacity(2);
+        let replica = UaoQtcamCache::new(1024 * 1024, 250.0);
+        replica.set_read_only(true);
+
+        primary.set("a", b"1", None).unwrap();
+        replica.apply_ops(&primary.drain_ops_since(0).unwrap()).unwrap();
+        for key in ["b", "c", "d"] {
+            primary.set(key, b"2", None).unwrap();
+        }
+        primary.delete("a").unwrap();
+
+        // Skipping op 2 is refused rather than silently diverging
+        let tail = primary.drain_ops_since(3).unwrap();
+        assert_eq!(replica.apply_ops(&tail).unwrap_err(), OP_SEQ_GAP_ERROR);
+        assert_eq!(replica.applied_seq().unwrap(), 1);
+
+        assert_eq!(primary.drain_ops_since(1).unwrap_err(), OP_LOG_TRUNCATED_ERROR);
+        let (snapshot, seq) = primary.replication_snapshot().unwrap();
+        assert_eq!(seq, 5);
+        assert_eq!(replica.resync(&snapshot, seq).unwrap(), 3);
+
+        primary.set("e", b"3", None).unwrap();
+        assert_eq!(replica.apply_ops(&primary.drain_ops_since(seq).unwrap()).unwrap(), 1);
+        for key in ["a", "b", "c", "d", "e"] {
+            assert_eq!(replica.get(key).unwrap(), primary.get(key).unwrap(), "key {}", key);
+        }
+
+        // A restore on the primary is not replayable op by op
+        primary.restore_json(&primary.snapshot_json().unwrap()).unwrap();
+        assert_eq!(primary.drain_ops_since(6).unwrap_err(), OP_LOG_TRUNCATED_ERROR);
+    }
+
+    #[test]
+    fn test_replica_follows_every_mutation() {
+        let primary = UaoQtcamCache::new(64, 250.0);
+        let replica = UaoQtcamCache::new(1024 * 1024, 250.0);
+        replica.set_read_only(true);
+
+        primary.set("session", b"s", Some(3600)).unwrap();
+        primary.set("sticky", b"s", Some(60)).unwrap();
+        primary.expire("sticky", 0).unwrap();
+        primary.set("stale", b"s", Some(60)).unwrap();
+        primary.list_push("jobs", vec![b"a".to_vec(), b"b".to_vec()], false).unwrap();
+        primary.hash_set("user", "name", b"ada").unwrap();
+
+        primary.cache.write().unwrap().get_mut("stale").unwrap().created_at -= 7200;
+        assert_eq!(primary.purge_expired().unwrap(), 1);
+
+        // Filling the 64-byte primary with incompressible bytes evicts older keys
+        let noise: Vec<u8> = (0..48u32).map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8).collect();
+        primary.set("big", &noise, None).unwrap();
+        assert!(primary.stats().unwrap().evictions > 0);
+
+        let ops = primary.drain_ops_since(0).unwrap();
+        assert!(ops.iter().any(|op| matches!(&op.kind, CacheOpKind::Set { key, expires_at: Some(_), .. } if key == "session")));
+        replica.apply_ops(&ops).unwrap();
+
+        let live: Vec<String> = primary.live_entries().unwrap().into_keys().collect();
+        assert_eq!(replica.live_entries().unwrap().len(), live.len());
+        for key in ["session", "sticky", "stale", "jobs", "user", "big"] {
+            assert_eq!(replica.exists(key).unwrap(), primary.exists(key).unwrap(), "key {}", key);
+            assert_eq!(replica.ttl(key).unwrap(), primary.ttl(key).unwrap(), "key {}", key);
+        }
+        assert_eq!(replica.get("big").unwrap(), primary.get("big").unwrap());
+
+        primary.clear().unwrap();
+        replica.apply_ops(&primary.drain_ops_since(replica.applied_seq().unwrap()).unwrap()).unwrap();
+        assert_eq!(replica.stats().unwrap().entry_count, 0);
+
+        // A replayed SET keeps its absolute expiry even if it arrives late
+        let late = UaoQtcamCache::new(1024 * 1024, 250.0);
+        let expired = CacheOp {
+            seq: 1,
+            kind: CacheOpKind::Set {
+                key: "late".to_string(),
+                value: b"v".to_vec(),
+                expires_at: Some(chrono::Utc::now().timestamp() - 10),
+            },
+        };
+        late.apply_ops(&[expired]).unwrap();
+        assert!(!late.exists("late").unwrap());
+    }
+
     #[test]
     fn test_cache_stats() {
         let cache = UaoQtcamCache::new(1024 * 1024, 250.0);
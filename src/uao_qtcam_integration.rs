@@ -284,6 +284,18 @@ impl SymmetrixUaoQtcamOptimizer {
         engine.stats().await
     }
 
+    /// Snapshot the routing table as JSON
+    pub async fn export_routes_json(&self) -> Result<String, String> {
+        let engine = self.tcam_engine.read().await;
+        engine.export_json().await.map_err(|e| e.to_string())
+    }
+
+    /// Load routes from a snapshot produced by [`Self::export_routes_json`]
+    pub async fn import_routes_json(&self, json: &str) -> Result<(), String> {
+        let engine = self.tcam_engine.write().await;
+        engine.import_json(json).await.map_err(|e| e.to_string())
+    }
+
     pub fn get_speedup_metrics(&self) -> UaoQtcamMetrics {
         let recursive_stats = self.recursive_engine.get_stats();
         UaoQtcamMetrics {
@@ -306,18 +318,24 @@ impl SymmetrixUaoQtcamOptimizer {
 
     /// Calculate how many models can be stored with recursive amplification
     /// Given: GPU VRAM size and average model size
+    ///
+    /// Sizes are converted to whole bytes and every count is an exact integer
+    /// floor, so the figures are reproducible for any input.
     pub fn calculate_model_capacity(&self, gpu_vram_gb: f64, avg_model_size_gb: f64) -> ModelCapacity {
+        let vram_bytes = gb_to_bytes(gpu_vram_gb);
+        let model_bytes = gb_to_bytes(avg_model_size_gb);
+
         // Traditional: Models that fit in VRAM
-        let traditional_models = (gpu_vram_gb / avg_model_size_gb).floor() as u64;
+        let traditional_models = capacity_floor(vram_bytes, model_bytes);
 
-        // With UAO-QTCAM compression (250×)
-        let compressed_model_size_gb = avg_model_size_gb / UAO_QTCAM_COMPRESSION_RATIO;
-        let with_compression_models = (gpu_vram_gb / compressed_model_size_gb).floor() as u64;
+        // With UAO-QTCAM compression (250×): vram / (model / 250)
+        let compression_ratio = UAO_QTCAM_COMPRESSION_RATIO as u128;
+        let with_compression_models = capacity_floor(vram_bytes.saturating_mul(compression_ratio), model_bytes);
 
         // With FULL recursive amplification (2.5B×)
         // Physical VRAM becomes RECURSIVE_AMPLIFICATION_FACTOR times larger effectively
-        let effective_vram_gb = gpu_vram_gb * RECURSIVE_AMPLIFICATION_FACTOR;
-        let with_recursive_models = (effective_vram_gb / avg_model_size_gb).floor() as u64;
+        let effective_bytes = vram_bytes.saturating_mul(RECURSIVE_AMPLIFICATION_FACTOR as u128);
+        let with_recursive_models = capacity_floor(effective_bytes, model_bytes);
 
         ModelCapacity {
             gpu_vram_gb,
@@ -331,6 +349,21 @@ impl SymmetrixUaoQtcamOptimizer {
     }
 }
 
+/// Bytes per GB in capacity calculations (decimal, like the 1 TB = 10¹² B figures)
+const BYTES_PER_GB: f64 = 1_000_000_000.0;
+
+/// Whole bytes in `gb` gigabytes (negative or NaN sizes count as zero)
+fn gb_to_bytes(gb: f64) -> u128 {
+    (gb * BYTES_PER_GB).round() as u128
+}
+
+/// ⌊numerator / denominator⌋, saturating at `u64::MAX` (also for a zero denominator)
+fn capacity_floor(numerator: u128, denominator: u128) -> u64 {
+    numerator
+        .checked_div(denominator)
+        .map_or(u64::MAX, |count| u64::try_from(count).unwrap_or(u64::MAX))
+}
+
 /// Model capacity calculation result
 #[derive(Debug, Clone)]
 pub struct ModelCapacity {
@@ -441,12 +474,24 @@ mod tests {
         // With compression (250×): 80 GB / (400 GB / 250) = 80 / 1.6 = 50 models
         assert_eq!(capacity.with_compression_models, 50);
 
-        // With recursive amplification: effectively unlimited
-        // 80 GB × 2.5B = 200 EB effective / 400 GB per model
-        // = 500,000,000 models (due to f64 precision limits in floor)
-        // The theoretical value is 500 billion but f64 computation rounds differently
-        assert!(capacity.with_recursive_amplification_models >= 500_000_000);
-        println!("Recursive amplification enables {} models!", capacity.with_recursive_amplification_models);
+        // With recursive amplification: 80 GB × 2.5B / 400 GB = 500,000,000 models
+        assert_eq!(capacity.with_recursive_amplification_models, 500_000_000);
+
+        // Non-divisible sizes floor exactly
+        let capacity = optimizer.calculate_model_capacity(24.0, 7.0);
+        assert_eq!(capacity.traditional_models, 3);
+        assert_eq!(capacity.with_compression_models, 857);
+        assert_eq!(capacity.with_recursive_amplification_models, 8_571_428_571);
+
+        // 13-byte models: 2×10²⁰ / 13 is beyond f64's exact integer range
+        let capacity = optimizer.calculate_model_capacity(80.0, 1.3e-8);
+        assert_eq!(capacity.traditional_models, 6_153_846_153);
+        assert_eq!(capacity.with_compression_models, 1_538_461_538_461);
+        assert_eq!(capacity.with_recursive_amplification_models, 15_384_615_384_615_384_615);
+
+        // Counts saturate instead of overflowing or dividing by zero
+        assert_eq!(optimizer.calculate_model_capacity(80.0, 1e-9).with_recursive_amplification_models, u64::MAX);
+        assert_eq!(optimizer.calculate_model_capacity(80.0, 0.0).traditional_models, u64::MAX);
     }
 
     #[tokio::test]
@@ -458,7 +503,7 @@ mod tests {
         let result = optimizer.lookup("192.168.1.42").await.expect("Lookup failed");
         assert!(result.is_some());
         let lookup = result.unwrap();
-        assert_eq!(lookup.next_hop, "gateway1");
+        assert_eq!(lookup.primary_next_hop(), "gateway1");
     }
 }
 
@@ -498,7 +543,7 @@ impl SymmetrixUaoQtcamOptimizer {
             match engine.lookup(&key_owned).await {
                 Ok(Some(result)) => Ok(Some(ControlPlaneLookupResult {
                     key: key_owned,
-                    next_hop: result.next_hop,
+                    next_hop: result.primary_next_hop().to_string(),
                     latency_ns: start.elapsed().as_nanos() as u64,
                     phase: result.phase,
                 })),
@@ -519,14 +564,8 @@ impl SymmetrixUaoQtcamOptimizer {
 
         rt.block_on(async {
             let engine = tcam.read().await;
-            let route = Route {
-                prefix: Prefix {
-                    addr: key_owned.parse().unwrap_or(0),
-                    len: 24,
-                },
-                next_hop: value_owned,
-                metric: priority,
-            };
+            let prefix = Prefix::new(key_owned.parse().unwrap_or(0), 24).map_err(|e| e.to_string())?;
+            let route = Route::new(prefix, value_owned, priority);
             engine.insert(route).await.map_err(|e| e.to_string())
         })
     }
@@ -541,10 +580,7 @@ impl SymmetrixUaoQtcamOptimizer {
 
         rt.block_on(async {
             let engine = tcam.read().await;
-            let prefix = Prefix {
-                addr: key_owned.parse().unwrap_or(0),
-                len: 24,
-            };
+            let prefix = Prefix::new(key_owned.parse().unwrap_or(0), 24).map_err(|e| e.to_string())?;
             engine.delete(prefix).await.map_err(|e| e.to_string())
         })
     }
@@ -15,7 +15,9 @@ use symmetrix_core::gfef::{
     IndexConfig, GFEFIndexGenerator,
     CalibrationMatrix,
 };
-use symmetrix_core::gfef::index::{WeightData, LayerWeights};
+use symmetrix_core::gfef::index::{WeightData, LayerWeights, GFEFIndex, LayerIndex, NeuronSignature};
+use symmetrix_core::gfef::prediction::{ActivationPredictor, PredictionRequest, PredictionError};
+use symmetrix_core::gfef::{CalibrationService, Subscription, SubscriptionTier};
 
 /// Test that ExtractionConfig has sensible defaults
 #[test]
@@ -292,3 +294,319 @@ fn test_uuid_uniqueness() {
     assert_eq!(unique_count, 100);
 }
 
+
+/// Build a tiny hand-made index: 4 inputs, 2 principal components, 8 neurons
+///
+/// The components pick out inputs 0 and 1, so an embedding along input 0
+/// correlates with neurons whose signature points along component 0.
+fn tiny_index(model_id: &str) -> GFEFIndex {
+    let projections: [[f32; 2]; 8] = [
+        [1.0, 0.0], [0.9, 0.2], [0.0, 1.0], [-1.0, 0.0],
+        [0.5, 0.5], [0.0, -1.0], [-0.5, 0.5], [0.2, 0.9],
+    ];
+    let signatures = projections.iter().enumerate()
+        .map(|(i, p)| NeuronSignature {
+            layer_id: 0,
+            neuron_idx: i as u32,
+            energy: 1.0,
+            projection: p.to_vec(),
+            spectral_hash: vec![0.0; 4],
+        })
+        .collect();
+
+    GFEFIndex {
+        id: Uuid::new_v4(),
+        customer_id: Uuid::new_v4(),
+        model_id: model_id.to_string(),
+        model_name: model_id.to_string(),
+        generated_at: chrono::Utc::now(),
+        expires_at: None,
+        layers: vec![LayerIndex {
+            layer_id: 0,
+            layer_name: "layer_0".to_string(),
+            num_neurons: 8,
+            input_dim: 4,
+            k_components: 2,
+            principal_components: vec![
+                1.0, 0.0,
+                0.0, 1.0,
+                0.0, 0.0,
+                0.0, 0.0,
+            ],
+            signatures,
+            layer_sparsity: None,
+        }],
+        total_neurons: 8,
+        config: IndexConfig { k_components: 2, fft_bins: 4, target_sparsity: 0.75 },
+    }
+}
+
+/// Test prediction projects the embedding onto the principal components
+#[test]
+fn test_prediction_uses_principal_components() {
+    let mut predictor = ActivationPredictor::new(0.75);
+    predictor.register_index(tiny_index("tiny"));
+
+    let prediction = predictor.predict_layer("tiny", 0, Some(&[1.0, 0.0, 0.0, 0.0]), "").unwrap();
+    assert_eq!(prediction.active_neurons, vec![0, 1]);
+    assert!((prediction.confidence_scores[0] - 1.0).abs() < 1e-6);
+    assert!((prediction.sparsity - 0.75).abs() < 1e-6);
+
+    // Inputs 2 and 3 are outside the component span and do not matter
+    let prediction = predictor.predict_layer("tiny", 0, Some(&[0.0, 3.0, 7.0, -2.0]), "").unwrap();
+    assert_eq!(prediction.active_neurons, vec![2, 7]);
+
+    // Full request path with subscription and calibration
+    let customer_id = Uuid::new_v4();
+    let subscription = Subscription::new(customer_id, SubscriptionTier::Professional);
+    let calibration = CalibrationService::new(60).get_matrix();
+    let request = PredictionRequest {
+        session_token: "token".to_string(),
+        customer_id,
+        model_id: "tiny".to_string(),
+        layer_id: 0,
+        input_embedding_hash: "hash".to_string(),
+        encrypted_embedding: None,
+        input_embedding: Some(vec![1.0, 0.0, 0.0, 0.0]),
+    };
+    let response = predictor.predict(&request, &subscription, &calibration).unwrap();
+    assert_eq!(response.active_neurons, vec![0, 1]);
+    assert_eq!(response.calibration_slice.len(), 2);
+
+    // Wrong embedding length is rejected
+    assert!(matches!(
+        predictor.predict_layer("tiny", 0, Some(&[1.0, 0.0]), ""),
+        Err(PredictionError::Internal(_))
+    ));
+}
+
+/// Test prediction refuses layers without principal components
+#[test]
+fn test_prediction_requires_components() {
+    let mut index = tiny_index("empty");
+    index.layers[0].principal_components.clear();
+    index.layers[0].signatures.clear();
+
+    let mut predictor = ActivationPredictor::new(0.75);
+    predictor.register_index(index);
+
+    assert!(matches!(
+        predictor.predict_layer("empty", 0, None, "hash"),
+        Err(PredictionError::Internal(_))
+    ));
+    assert!(matches!(
+        predictor.predict_layer("missing", 0, None, "hash"),
+        Err(PredictionError::ModelNotFound(_))
+    ));
+}
+
+/// Test loading principal components from a JSON metadata + binary sidecar pair
+#[test]
+fn test_load_pc_sidecar() {
+    let temp_dir = tempdir().unwrap();
+    let json_path = temp_dir.path().join("tiny.json");
+    let bin_path = json_path.with_extension("bin");
+
+    let metadata = serde_json::json!({
+        "model": "tiny",
+        "k_components": 2,
+        "fft_bins": 4,
+        "total_neurons": 3,
+        "layers": [{ "layer_id": 0, "name": "layer_0", "neurons": 3, "pc_shape": [4, 2] }],
+    });
+    std::fs::write(&json_path, metadata.to_string()).unwrap();
+
+    // 4×2 components followed by 3×2 neuron projections
+    let values: [f32; 14] = [
+        1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0,
+        3.0, 4.0, 0.0, 1.0, -1.0, 0.0,
+    ];
+    let bytes: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+    std::fs::write(&bin_path, &bytes).unwrap();
+
+    let raw: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&json_path).unwrap()).unwrap();
+    let mut index = GFEFIndex::from_python_metadata(&raw).unwrap();
+    assert!(!index.layers[0].has_components());
+
+    index.load_components_from_file(&bin_path).unwrap();
+    let layer = &index.layers[0];
+    assert!(layer.has_components());
+    assert_eq!(layer.principal_components.len(), 4 * 2);
+    assert_eq!(&layer.principal_components[..4], &[1.0, 0.0, 0.0, 1.0]);
+    assert_eq!(layer.signatures.len(), 3);
+    assert_eq!(layer.signatures[0].projection, vec![3.0, 4.0]);
+    assert!((layer.signatures[0].energy - 5.0).abs() < 1e-6);
+    assert_eq!(layer.project(&[2.0, 5.0, 9.0, 9.0]), Some(vec![2.0, 5.0]));
+
+    // Truncated sidecar is rejected
+    let mut truncated = GFEFIndex::from_python_metadata(&raw).unwrap();
+    assert!(truncated.load_components(&bytes[..bytes.len() - 4]).is_err());
+
+    // pc_shape disagreeing with k_components is rejected
+    let mut bad = raw.clone();
+    bad["layers"][0]["pc_shape"] = serde_json::json!([4, 3]);
+    assert!(GFEFIndex::from_python_metadata(&bad).is_err());
+
+    // Per-layer sparsity is optional and must lie in [0, 1)
+    assert_eq!(index.layers[0].layer_sparsity, None);
+    let mut sparse = raw.clone();
+    sparse["layers"][0]["layer_sparsity"] = serde_json::json!(0.99);
+    let sparse_index = GFEFIndex::from_python_metadata(&sparse).unwrap();
+    assert_eq!(sparse_index.layers[0].layer_sparsity, Some(0.99));
+    sparse["layers"][0]["layer_sparsity"] = serde_json::json!(1.5);
+    assert!(GFEFIndex::from_python_metadata(&sparse).is_err());
+}
+
+/// Write a checksummed two-layer index pair, returning the JSON path
+fn write_checksummed_index(dir: &std::path::Path, metadata: &mut serde_json::Value) -> PathBuf {
+    let json_path = dir.join("checked.json");
+    // Each layer: 4×2 components followed by 2×2 neuron projections
+    let bytes: Vec<u8> = (0..24).flat_map(|i| (i as f32).to_le_bytes()).collect();
+    metadata["sha256"] = serde_json::json!(GFEFIndex::checksum(metadata, Some(&bytes)));
+    std::fs::write(&json_path, metadata.to_string()).unwrap();
+    std::fs::write(json_path.with_extension("bin"), &bytes).unwrap();
+    json_path
+}
+
+fn checksummed_metadata() -> serde_json::Value {
+    serde_json::json!({
+        "model": "checked",
+        "k_components": 2,
+        "fft_bins": 4,
+        "total_neurons": 4,
+        "layers": [
+            { "layer_id": 0, "name": "layer_0", "neurons": 2, "pc_shape": [4, 2] },
+            { "layer_id": 1, "name": "layer_1", "neurons": 2, "pc_shape": [4, 2] },
+        ],
+    })
+}
+
+/// A checksummed index loads with its components
+#[test]
+fn test_checksummed_index_loads() {
+    let temp_dir = tempdir().unwrap();
+    let json_path = write_checksummed_index(temp_dir.path(), &mut checksummed_metadata());
+
+    let index = GFEFIndex::load_from_files(&json_path).unwrap();
+    assert_eq!(index.total_neurons, 4);
+    assert_eq!(index.layers.len(), 2);
+    assert!(index.layers.iter().all(|l| l.has_components()));
+    assert_eq!(index.layers[1].signatures[1].projection, vec![22.0, 23.0]);
+}
+
+/// A tampered sidecar or metadata file fails checksum verification
+#[test]
+fn test_checksum_mismatch_rejected() {
+    let temp_dir = tempdir().unwrap();
+    let json_path = write_checksummed_index(temp_dir.path(), &mut checksummed_metadata());
+    let bin_path = json_path.with_extension("bin");
+    let bytes = std::fs::read(&bin_path).unwrap();
+
+    // Same length, one flipped bit: shapes still match, only the digest catches it
+    let mut tampered = bytes.clone();
+    tampered[5] ^= 0x01;
+    std::fs::write(&bin_path, &tampered).unwrap();
+    let err = GFEFIndex::load_from_files(&json_path).unwrap_err();
+    assert!(err.contains("checksum mismatch"), "{}", err);
+
+    // Edited metadata
+    std::fs::write(&bin_path, &bytes).unwrap();
+    let mut raw: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&json_path).unwrap()).unwrap();
+    raw["fft_bins"] = serde_json::json!(8);
+    std::fs::write(&json_path, raw.to_string()).unwrap();
+    assert!(GFEFIndex::load_from_files(&json_path).is_err());
+
+    // Without a recorded checksum the index loads unverified
+    raw.as_object_mut().unwrap().remove("sha256");
+    assert_eq!(GFEFIndex::verify_checksum(&raw, Some(&bytes)), Ok(false));
+    std::fs::write(&json_path, raw.to_string()).unwrap();
+    assert!(GFEFIndex::load_from_files(&json_path).is_ok());
+}
+
+/// Per-layer neuron counts must add up to total_neurons
+#[test]
+fn test_inconsistent_neuron_count_rejected() {
+    let temp_dir = tempdir().unwrap();
+    let mut metadata = checksummed_metadata();
+    metadata["total_neurons"] = serde_json::json!(5);
+    // A valid checksum doesn't make inconsistent metadata acceptable
+    let json_path = write_checksummed_index(temp_dir.path(), &mut metadata);
+
+    let err = GFEFIndex::load_from_files(&json_path).unwrap_err();
+    assert!(err.contains("total_neurons"), "{}", err);
+    assert!(GFEFIndex::from_python_metadata(&metadata).is_err());
+}
+
+/// Test expired indices stop serving predictions and are pruned
+#[test]
+fn test_expired_index_is_rejected_and_pruned() {
+    let mut expired = tiny_index("expired");
+    expired.expires_at = Some(chrono::Utc::now() - chrono::Duration::seconds(1));
+    let mut current = tiny_index("current");
+    current.expires_at = Some(chrono::Utc::now() + chrono::Duration::hours(1));
+
+    let mut predictor = ActivationPredictor::new(0.75);
+    predictor.register_index(expired);
+    predictor.register_index(current);
+    predictor.register_index(tiny_index("perpetual"));
+    assert_eq!(predictor.stats().models_loaded, 3);
+
+    assert!(matches!(
+        predictor.predict_layer("expired", 0, Some(&[1.0, 0.0, 0.0, 0.0]), ""),
+        Err(PredictionError::SubscriptionExpired)
+    ));
+    assert!(predictor.predict_layer("current", 0, Some(&[1.0, 0.0, 0.0, 0.0]), "").is_ok());
+
+    assert_eq!(predictor.prune_expired(), vec!["expired".to_string()]);
+    assert_eq!(predictor.stats().models_loaded, 2);
+    assert!(matches!(
+        predictor.predict_layer("expired", 0, None, "hash"),
+        Err(PredictionError::ModelNotFound(_))
+    ));
+}
+
+/// Test calibration signatures detect tampering and expiry
+#[test]
+fn test_calibration_signature_verification() {
+    let service = CalibrationService::new(60);
+    let matrix = service.get_matrix();
+    assert!(service.verify(&matrix));
+
+    // Flip one bit of one value
+    let mut tampered = matrix.clone();
+    let bits = tampered.values[3][7].to_bits() ^ 1;
+    tampered.values[3][7] = f64::from_bits(bits);
+    assert!(!service.verify(&tampered));
+
+    // Extending the expiry invalidates the signature too
+    let mut extended = matrix.clone();
+    extended.expires_at += chrono::Duration::hours(1);
+    assert!(!service.verify(&extended));
+
+    // A correctly signed but expired matrix is rejected
+    let expired = CalibrationMatrix::generate(0, None);
+    assert!(expired.verify());
+    assert!(!service.verify(&expired));
+
+    // The predictor refuses tampered calibration
+    let mut predictor = ActivationPredictor::new(0.75);
+    predictor.register_index(tiny_index("tiny"));
+    let customer_id = Uuid::new_v4();
+    let subscription = Subscription::new(customer_id, SubscriptionTier::Professional);
+    let request = PredictionRequest {
+        session_token: "token".to_string(),
+        customer_id,
+        model_id: "tiny".to_string(),
+        layer_id: 0,
+        input_embedding_hash: "hash".to_string(),
+        encrypted_embedding: None,
+        input_embedding: Some(vec![1.0, 0.0, 0.0, 0.0]),
+    };
+    assert!(predictor.predict(&request, &subscription, &matrix).is_ok());
+    assert!(matches!(
+        predictor.predict(&request, &subscription, &tampered),
+        Err(PredictionError::InvalidCalibration)
+    ));
+}
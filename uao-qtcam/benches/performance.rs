@@ -4,7 +4,7 @@
 //! Target: Validate 10 ns latency and 1,000x speedup vs hardware TCAM
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
-use uao_qtcam_unified::unified::{TCAMEngine, Route, PhaseStrategy};
+use uao_qtcam_unified::unified::{TCAMEngine, Route, Phase, PhaseStrategy};
 use uao_qtcam_unified::phase1::Prefix;
 use std::time::Duration;
 
@@ -58,7 +58,7 @@ fn bench_single_lookup(c: &mut Criterion) {
     // Phase 1 benchmark
     let rt = tokio::runtime::Runtime::new().unwrap();
     let engine_p1 = rt.block_on(async {
-        let engine = TCAMEngine::with_strategy(PhaseStrategy::Phase1Only).unwrap();
+        let engine = TCAMEngine::with_strategy(PhaseStrategy::Fixed(Phase::Phase1)).unwrap();
         for route in &routes {
             engine.insert(route.clone()).await.unwrap();
         }
@@ -78,7 +78,7 @@ fn bench_single_lookup(c: &mut Criterion) {
     
     // Phase 2 benchmark
     let engine_p2 = rt.block_on(async {
-        let engine = TCAMEngine::with_strategy(PhaseStrategy::Phase2Only).unwrap();
+        let engine = TCAMEngine::with_strategy(PhaseStrategy::Fixed(Phase::Phase2)).unwrap();
         for route in &routes {
             engine.insert(route.clone()).await.unwrap();
         }
@@ -112,7 +112,7 @@ fn bench_throughput(c: &mut Criterion) {
     
     // Phase 1 throughput
     let engine_p1 = rt.block_on(async {
-        let engine = TCAMEngine::with_strategy(PhaseStrategy::Phase1Only).unwrap();
+        let engine = TCAMEngine::with_strategy(PhaseStrategy::Fixed(Phase::Phase1)).unwrap();
         for route in &routes {
             engine.insert(route.clone()).await.unwrap();
         }
@@ -132,7 +132,7 @@ fn bench_throughput(c: &mut Criterion) {
     
     // Phase 2 throughput
     let engine_p2 = rt.block_on(async {
-        let engine = TCAMEngine::with_strategy(PhaseStrategy::Phase2Only).unwrap();
+        let engine = TCAMEngine::with_strategy(PhaseStrategy::Fixed(Phase::Phase2)).unwrap();
         for route in &routes {
             engine.insert(route.clone()).await.unwrap();
         }
@@ -167,7 +167,7 @@ fn bench_scalability(c: &mut Criterion) {
 
         // Phase 1
         let engine_p1 = rt.block_on(async {
-            let engine = TCAMEngine::with_strategy(PhaseStrategy::Phase1Only).unwrap();
+            let engine = TCAMEngine::with_strategy(PhaseStrategy::Fixed(Phase::Phase1)).unwrap();
             for route in &routes {
                 engine.insert(route.clone()).await.unwrap();
             }
@@ -191,7 +191,7 @@ fn bench_scalability(c: &mut Criterion) {
 
         // Phase 2
         let engine_p2 = rt.block_on(async {
-            let engine = TCAMEngine::with_strategy(PhaseStrategy::Phase2Only).unwrap();
+            let engine = TCAMEngine::with_strategy(PhaseStrategy::Fixed(Phase::Phase2)).unwrap();
             for route in &routes {
                 engine.insert(route.clone()).await.unwrap();
             }
@@ -231,7 +231,7 @@ fn bench_insert(c: &mut Criterion) {
         b.iter(|| {
             let rt = tokio::runtime::Runtime::new().unwrap();
             rt.block_on(async {
-                let engine = TCAMEngine::with_strategy(PhaseStrategy::Phase1Only).unwrap();
+                let engine = TCAMEngine::with_strategy(PhaseStrategy::Fixed(Phase::Phase1)).unwrap();
                 for route in &routes {
                     black_box(engine.insert(route.clone()).await.unwrap());
                 }
@@ -244,7 +244,7 @@ fn bench_insert(c: &mut Criterion) {
         b.iter(|| {
             let rt = tokio::runtime::Runtime::new().unwrap();
             rt.block_on(async {
-                let engine = TCAMEngine::with_strategy(PhaseStrategy::Phase2Only).unwrap();
+                let engine = TCAMEngine::with_strategy(PhaseStrategy::Fixed(Phase::Phase2)).unwrap();
                 for route in &routes {
                     black_box(engine.insert(route.clone()).await.unwrap());
                 }
@@ -271,7 +271,7 @@ fn bench_mixed_workload(c: &mut Criterion) {
         b.iter(|| {
             let rt = tokio::runtime::Runtime::new().unwrap();
             rt.block_on(async {
-                let engine = TCAMEngine::with_strategy(PhaseStrategy::Phase1Only).unwrap();
+                let engine = TCAMEngine::with_strategy(PhaseStrategy::Fixed(Phase::Phase1)).unwrap();
 
                 // Initial inserts
                 for route in &initial_routes {
@@ -296,7 +296,7 @@ fn bench_mixed_workload(c: &mut Criterion) {
         b.iter(|| {
             let rt = tokio::runtime::Runtime::new().unwrap();
             rt.block_on(async {
-                let engine = TCAMEngine::with_strategy(PhaseStrategy::Phase2Only).unwrap();
+                let engine = TCAMEngine::with_strategy(PhaseStrategy::Fixed(Phase::Phase2)).unwrap();
 
                 // Initial inserts
                 for route in &initial_routes {
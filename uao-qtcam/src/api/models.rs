@@ -48,6 +48,7 @@ pub struct LookupResponse {
 pub struct LookupResultDto {
     pub prefix: String,
     pub next_hop: String,
+    pub next_hops: Vec<String>,
     pub metric: u32,
     pub latency_ns: f64,
     pub phase: String,
@@ -56,8 +57,9 @@ pub struct LookupResultDto {
 impl From<LookupResult> for LookupResultDto {
     fn from(result: LookupResult) -> Self {
         Self {
+            next_hop: result.primary_next_hop().to_string(),
             prefix: result.prefix,
-            next_hop: result.next_hop,
+            next_hops: result.next_hops,
             metric: result.metric,
             latency_ns: result.latency_ns,
             phase: result.phase,
@@ -104,9 +106,14 @@ pub struct StatsResponse {
     pub phase1_lookups: u64,
     pub phase2_lookups: u64,
     pub phase3_lookups: u64,
+    pub phase1_avg_latency_ns: f64,
+    pub phase2_avg_latency_ns: f64,
+    pub phase3_avg_latency_ns: f64,
     pub cache_hits: u64,
     pub cache_misses: u64,
     pub cache_hit_rate: f64,
+    pub result_cache_hits: u64,
+    pub result_cache_misses: u64,
 }
 
 impl From<TCAMStats> for StatsResponse {
@@ -126,9 +133,14 @@ impl From<TCAMStats> for StatsResponse {
             phase1_lookups: stats.phase1_lookups,
             phase2_lookups: stats.phase2_lookups,
             phase3_lookups: stats.phase3_lookups,
+            phase1_avg_latency_ns: stats.phase1_avg_latency_ns,
+            phase2_avg_latency_ns: stats.phase2_avg_latency_ns,
+            phase3_avg_latency_ns: stats.phase3_avg_latency_ns,
             cache_hits: stats.cache_hits,
             cache_misses: stats.cache_misses,
             cache_hit_rate,
+            result_cache_hits: stats.result_cache_hits,
+            result_cache_misses: stats.result_cache_misses,
         }
     }
 }
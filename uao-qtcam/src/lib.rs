@@ -56,7 +56,7 @@ pub mod unified;
 pub mod api;
 
 // Re-exports for convenience
-pub use unified::{TCAMEngine, Route, Prefix, LookupResult, TCAMStats};
+pub use unified::{TCAMEngine, Route, FlowKey, Prefix, LookupResult, TCAMStats};
 pub use api::{start_server, ServerConfig};
 
 /// Library version
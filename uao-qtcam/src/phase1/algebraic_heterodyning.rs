@@ -17,13 +17,14 @@
 //! - **Throughput**: 20 Million lookups/second
 //! - **Memory**: O(n) where n = number of routes
 
-use super::Prefix;
+use super::{route_rank, Prefix, RouteEntry};
 use super::frobenius_compression::{FrobeniusCompressor, CompressedPrefix};
 use anyhow::Result;
+use std::cmp::Reverse;
 use std::collections::HashMap;
 use std::sync::Arc;
 use parking_lot::RwLock;
-use std::net::Ipv4Addr;
+use std::net::IpAddr;
 
 /// AHGF routing engine
 pub struct AHGFEngine {
@@ -35,6 +36,8 @@ pub struct AHGFEngine {
     hash_index: Arc<RwLock<HashMap<u64, Vec<usize>>>>,
     /// Performance statistics
     stats: Arc<RwLock<EngineStats>>,
+    /// Refuse routes whose compressed key collides with a stored prefix
+    reject_collisions: bool,
 }
 
 /// Compressed route entry
@@ -64,10 +67,22 @@ impl AHGFEngine {
             compressor: FrobeniusCompressor::new(),
             hash_index: Arc::new(RwLock::new(HashMap::new())),
             stats: Arc::new(RwLock::new(EngineStats::default())),
+            reject_collisions: false,
         }
     }
 
+    /// Reject inserts whose prefix compresses to the same key as a
+    /// different stored prefix (see [`FrobeniusCompressor::verify_injective`])
+    pub fn with_collision_check(mut self, enabled: bool) -> Self {
+        self.reject_collisions = enabled;
+        self
+    }
+
     /// Insert a route
+    ///
+    /// A route already stored for exactly `prefix` is overwritten rather
+    /// than duplicated. With the collision check enabled, fails if the
+    /// prefix would collide with a stored one under Frobenius compression.
     pub fn insert(&self, prefix: Prefix, next_hop: impl Into<String>, metric: u32) -> Result<()> {
         let start = std::time::Instant::now();
 
@@ -75,6 +90,21 @@ impl AHGFEngine {
         let compressed_prefix = self.compressor.compress(&prefix);
         let hash = compressed_prefix.hash;
 
+        let mut routes = self.routes.write();
+        let mut hash_index = self.hash_index.write();
+
+        if let Some(idx) = Self::find_exact(&routes, &hash_index, hash, &prefix) {
+            routes[idx].next_hop = next_hop.into();
+            routes[idx].metric = metric;
+            return Ok(());
+        }
+
+        if self.reject_collisions {
+            if let Some(existing) = Self::find_collision(&routes, &hash_index, &compressed_prefix) {
+                anyhow::bail!("Prefix {} collides with {} under Frobenius compression", prefix, existing);
+            }
+        }
+
         // Create compressed route
         let route = CompressedRoute {
             compressed_prefix,
@@ -82,14 +112,12 @@ impl AHGFEngine {
             metric,
         };
 
-        // Insert into routing table
-        let mut routes = self.routes.write();
+        // Insert into routing table and hash index
         let index = routes.len();
         routes.push(route);
-
-        // Update hash index
-        let mut hash_index = self.hash_index.write();
         hash_index.entry(hash).or_insert_with(Vec::new).push(index);
+        drop(hash_index);
+        drop(routes);
 
         // Update statistics
         let mut stats = self.stats.write();
@@ -101,16 +129,158 @@ impl AHGFEngine {
         Ok(())
     }
 
+    /// Insert a batch of routes, taking the table locks once
+    ///
+    /// A route whose prefix is already stored overwrites the existing
+    /// entry instead of adding a duplicate. Returns the number of newly
+    /// added prefixes.
+    pub fn insert_bulk(&self, batch: impl IntoIterator<Item = (Prefix, String, u32)>) -> usize {
+        let mut routes = self.routes.write();
+        let mut hash_index = self.hash_index.write();
+
+        let mut inserted = 0;
+        for (prefix, next_hop, metric) in batch {
+            let compressed_prefix = self.compressor.compress(&prefix);
+            let hash = compressed_prefix.hash;
+
+            match Self::find_exact(&routes, &hash_index, hash, &prefix) {
+                Some(idx) => {
+                    routes[idx].next_hop = next_hop;
+                    routes[idx].metric = metric;
+                }
+                None if self.reject_collisions
+                    && Self::find_collision(&routes, &hash_index, &compressed_prefix).is_some() =>
+                {
+                    tracing::warn!("Skipping {}: collides with a stored prefix under Frobenius compression", prefix);
+                }
+                None => {
+                    hash_index.entry(hash).or_default().push(routes.len());
+                    routes.push(CompressedRoute { compressed_prefix, next_hop, metric });
+                    inserted += 1;
+                }
+            }
+        }
+
+        drop(hash_index);
+        drop(routes);
+
+        let mut stats = self.stats.write();
+        stats.total_inserts += inserted as u64;
+
+        inserted
+    }
+
+    /// Delete every route stored for `prefix`
+    ///
+    /// Returns `true` if at least one route was removed.
+    pub fn delete(&self, prefix: Prefix) -> bool {
+        let hash = self.compressor.compress(&prefix).hash;
+
+        let mut routes = self.routes.write();
+        let mut hash_index = self.hash_index.write();
+
+        let mut removed = false;
+        while let Some(idx) = Self::find_exact(&routes, &hash_index, hash, &prefix) {
+            Self::remove_at(&mut routes, &mut hash_index, idx);
+            removed = true;
+        }
+
+        drop(hash_index);
+        drop(routes);
+
+        if removed {
+            let mut stats = self.stats.write();
+            stats.total_deletes += 1;
+        }
+
+        removed
+    }
+
+    /// Update the next hop and metric of an existing route
+    ///
+    /// Returns `false` if no route is stored for `prefix`. The compressed
+    /// prefix (and therefore the hash index) is unchanged by an update.
+    pub fn update(&self, prefix: Prefix, next_hop: impl Into<String>, metric: u32) -> bool {
+        let hash = self.compressor.compress(&prefix).hash;
+
+        let mut routes = self.routes.write();
+        let hash_index = self.hash_index.read();
+
+        match Self::find_exact(&routes, &hash_index, hash, &prefix) {
+            Some(idx) => {
+                routes[idx].next_hop = next_hop.into();
+                routes[idx].metric = metric;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Find the table index of a route with exactly `prefix`
+    fn find_exact(
+        routes: &[CompressedRoute],
+        hash_index: &HashMap<u64, Vec<usize>>,
+        hash: u64,
+        prefix: &Prefix,
+    ) -> Option<usize> {
+        hash_index.get(&hash)?
+            .iter()
+            .copied()
+            .find(|&idx| routes[idx].compressed_prefix.prefix() == prefix)
+    }
+
+    /// Stored prefix that differs from `compressed` but shares its compressed key
+    fn find_collision(
+        routes: &[CompressedRoute],
+        hash_index: &HashMap<u64, Vec<usize>>,
+        compressed: &CompressedPrefix,
+    ) -> Option<Prefix> {
+        hash_index.get(&compressed.hash)?
+            .iter()
+            .map(|&idx| &routes[idx].compressed_prefix)
+            .find(|stored| stored.collides_with(compressed))
+            .map(|stored| *stored.prefix())
+    }
+
+    /// Remove the route at `idx`, keeping the hash index consistent
+    ///
+    /// Uses `swap_remove`, so the last route moves into `idx` and its
+    /// index entry is rewritten.
+    fn remove_at(
+        routes: &mut Vec<CompressedRoute>,
+        hash_index: &mut HashMap<u64, Vec<usize>>,
+        idx: usize,
+    ) {
+        let hash = routes[idx].compressed_prefix.hash;
+        if let Some(bucket) = hash_index.get_mut(&hash) {
+            bucket.retain(|&i| i != idx);
+            if bucket.is_empty() {
+                hash_index.remove(&hash);
+            }
+        }
+
+        let last = routes.len() - 1;
+        routes.swap_remove(idx);
+
+        if idx != last {
+            let moved_hash = routes[idx].compressed_prefix.hash;
+            if let Some(bucket) = hash_index.get_mut(&moved_hash) {
+                for i in bucket.iter_mut().filter(|i| **i == last) {
+                    *i = idx;
+                }
+            }
+        }
+    }
+
     /// Lookup a route for an IP address
     pub fn lookup(&self, ip: &str) -> Result<Option<LookupResult>> {
         let start = std::time::Instant::now();
 
-        // Parse IP address
-        let ip_addr: Ipv4Addr = ip.parse()?;
-        let ip_u32 = u32::from(ip_addr);
+        // Parse IP address (IPv4 or IPv6)
+        let ip_addr: IpAddr = ip.parse()?;
 
         // Perform lookup
-        let result = self.lookup_internal(ip_u32);
+        let result = self.lookup_internal(ip_addr);
 
         // Update statistics
         let elapsed = start.elapsed().as_nanos() as f64;
@@ -131,29 +301,15 @@ impl AHGFEngine {
     }
 
     /// Internal lookup implementation
-    fn lookup_internal(&self, ip: u32) -> Option<LookupResult> {
+    fn lookup_internal(&self, ip: IpAddr) -> Option<LookupResult> {
         let routes = self.routes.read();
 
-        // Find longest matching prefix
-        let mut best_match: Option<(usize, u8)> = None;
-
-        for (idx, route) in routes.iter().enumerate() {
-            if route.compressed_prefix.matches(ip) {
-                let prefix_len = route.compressed_prefix.prefix().len;
-                
-                match best_match {
-                    None => best_match = Some((idx, prefix_len)),
-                    Some((_, best_len)) => {
-                        if prefix_len > best_len {
-                            best_match = Some((idx, prefix_len));
-                        }
-                    }
-                }
-            }
-        }
+        // Find the preferred matching route (longest prefix, then metric, then next hop)
+        let best_match = routes.iter()
+            .filter(|route| route.compressed_prefix.matches_ip(ip))
+            .min_by(|a, b| Self::rank(a).cmp(&Self::rank(b)));
 
-        best_match.map(|(idx, _)| {
-            let route = &routes[idx];
+        best_match.map(|route| {
             LookupResult {
                 prefix: route.compressed_prefix.prefix().to_string(),
                 next_hop: route.next_hop.clone(),
@@ -163,11 +319,24 @@ impl AHGFEngine {
         })
     }
 
+    /// Preference key of a stored route (see [`route_rank`])
+    fn rank(route: &CompressedRoute) -> (Reverse<u8>, u32, &str) {
+        route_rank(route.compressed_prefix.prefix().prefix_len(), route.metric, &route.next_hop)
+    }
+
     /// Get engine statistics
     pub fn stats(&self) -> EngineStats {
         self.stats.read().clone()
     }
 
+    /// Snapshot of every stored route, in table order
+    pub fn routes(&self) -> Vec<RouteEntry> {
+        self.routes.read()
+            .iter()
+            .map(|route| RouteEntry::new(*route.compressed_prefix.prefix(), route.next_hop.clone(), route.metric))
+            .collect()
+    }
+
     /// Get number of routes
     pub fn route_count(&self) -> usize {
         self.routes.read().len()
@@ -210,6 +379,32 @@ mod tests {
         assert_eq!(result.metric, 100);
     }
 
+    #[test]
+    fn test_insert_rejects_compression_collision() {
+        use super::super::galois_field::GF2_128;
+
+        // ::/128 and this address share a compressed key (see
+        // `test_verify_injective_detects_collision`)
+        let addr = GF2_128::new(1 | (1 << 32)).frobenius(123).value();
+        let zero = Prefix::V6 { addr: 0, len: 128 };
+        let twin = Prefix::V6 { addr, len: 128 };
+
+        let permissive = AHGFEngine::new();
+        permissive.insert(zero, "hop-a", 10).unwrap();
+        permissive.insert(twin, "hop-b", 10).unwrap();
+        assert_eq!(permissive.route_count(), 2);
+
+        let strict = AHGFEngine::new().with_collision_check(true);
+        strict.insert(zero, "hop-a", 10).unwrap();
+        let err = strict.insert(twin, "hop-b", 10).unwrap_err();
+        assert!(err.to_string().contains("collides"));
+        assert_eq!(strict.insert_bulk(vec![(twin, "hop-b".to_string(), 10)]), 0);
+        assert_eq!(strict.route_count(), 1);
+
+        // Re-inserting the same prefix is not a collision
+        strict.insert(zero, "hop-c", 5).unwrap();
+    }
+
     #[test]
     fn test_longest_prefix_match() {
         let engine = AHGFEngine::new();
@@ -268,5 +463,54 @@ mod tests {
         engine.insert(prefix, "next_hop_1", 100).unwrap();
         assert_eq!(engine.route_count(), 1);
     }
-}
 
+    #[test]
+    fn test_ipv6_lookup() {
+        let engine = AHGFEngine::new();
+
+        let prefix = Prefix::from_cidr("2001:db8::/32").unwrap();
+        engine.insert(prefix, "next_hop_v6", 10).unwrap();
+
+        let result = engine.lookup("2001:db8::1").unwrap().unwrap();
+        assert_eq!(result.next_hop, "next_hop_v6");
+        assert_eq!(result.prefix, "2001:db8::/32");
+
+        assert!(engine.lookup("2001:db9::1").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_delete() {
+        let engine = AHGFEngine::new();
+
+        for (cidr, next_hop) in [
+            ("192.168.1.0/24", "next_hop_1"),
+            ("192.168.2.0/24", "next_hop_2"),
+            ("10.0.0.0/8", "next_hop_3"),
+        ] {
+            engine.insert(Prefix::from_cidr(cidr).unwrap(), next_hop, 100).unwrap();
+        }
+
+        assert!(engine.delete(Prefix::from_cidr("192.168.1.0/24").unwrap()));
+        assert_eq!(engine.route_count(), 2);
+        assert_eq!(engine.stats().total_deletes, 1);
+
+        assert!(engine.lookup("192.168.1.42").unwrap().is_none());
+        assert_eq!(engine.lookup("192.168.2.42").unwrap().unwrap().next_hop, "next_hop_2");
+        assert_eq!(engine.lookup("10.1.2.3").unwrap().unwrap().next_hop, "next_hop_3");
+
+        // Deleting again is a no-op
+        assert!(!engine.delete(Prefix::from_cidr("192.168.1.0/24").unwrap()));
+
+        // The moved route is still reachable through the hash index
+        assert!(engine.update(Prefix::from_cidr("10.0.0.0/8").unwrap(), "next_hop_4", 10));
+        let result = engine.lookup("10.1.2.3").unwrap().unwrap();
+        assert_eq!(result.next_hop, "next_hop_4");
+        assert_eq!(result.metric, 10);
+    }
+
+    #[test]
+    fn test_update_missing() {
+        let engine = AHGFEngine::new();
+        assert!(!engine.update(Prefix::from_cidr("192.168.1.0/24").unwrap(), "next_hop_1", 100));
+    }
+}
@@ -19,9 +19,10 @@
 //! 3. Extract compressed representation
 //! 4. Store in compressed routing table
 
-use super::galois_field::GF2_32;
+use super::galois_field::{GF2_128, GF2_32};
 use super::Prefix;
 use anyhow::Result;
+use std::collections::HashMap;
 
 /// Frobenius compressor for prefix compression
 pub struct FrobeniusCompressor {
@@ -42,17 +43,20 @@ impl FrobeniusCompressor {
 
     /// Compress a prefix using Frobenius automorphism
     pub fn compress(&self, prefix: &Prefix) -> CompressedPrefix {
-        // Map prefix to GF(2^32)
-        let gf_element = GF2_32::new(prefix.addr);
-
-        // Apply Frobenius automorphism
-        let compressed = gf_element.frobenius(self.frobenius_power);
-
-        // Extract compressed representation
-        let compressed_value = compressed.value();
+        let compressed_value = match *prefix {
+            Prefix::V4 { addr, .. } => {
+                // Map prefix to GF(2^32) and apply Frobenius automorphism
+                GF2_32::new(addr).frobenius(self.frobenius_power).value()
+            }
+            Prefix::V6 { addr, .. } => {
+                // Map the whole 128-bit key to GF(2^128), then fold the image
+                let image = GF2_128::new(addr).frobenius(self.frobenius_power).value();
+                (0..4).fold(0u32, |acc, word| acc ^ (image >> (32 * word)) as u32)
+            }
+        };
 
         // Compute hash for fast lookup
-        let hash = self.compute_hash(compressed_value, prefix.len);
+        let hash = self.compute_hash(compressed_value, prefix.prefix_len());
 
         CompressedPrefix {
             original_prefix: *prefix,
@@ -69,6 +73,28 @@ impl FrobeniusCompressor {
         Ok(compressed.original_prefix)
     }
 
+    /// Check that compression keeps every prefix in `prefixes` distinct
+    ///
+    /// Returns the first pair of distinct prefixes that compress to the same
+    /// key (compressed value and prefix length). The GF(2^32) automorphism
+    /// is a bijection, so IPv4 prefixes never collide with each other; the
+    /// 128 → 32 bit fold used for IPv6 (and sharing keys across families)
+    /// can.
+    pub fn verify_injective(&self, prefixes: &[Prefix]) -> Result<(), (Prefix, Prefix)> {
+        let mut seen: HashMap<(u32, u8), Prefix> = HashMap::with_capacity(prefixes.len());
+        for prefix in prefixes {
+            let key = self.compress(prefix).key();
+            match seen.get(&key) {
+                Some(existing) if existing != prefix => return Err((*existing, *prefix)),
+                Some(_) => {}
+                None => {
+                    seen.insert(key, *prefix);
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Compute hash for fast lookup
     fn compute_hash(&self, value: u32, prefix_len: u8) -> u64 {
         // Mix value and prefix length
@@ -114,15 +140,30 @@ pub struct CompressedPrefix {
 }
 
 impl CompressedPrefix {
-    /// Check if this compressed prefix matches an IP address
+    /// Check if this compressed prefix matches an IPv4 address
     pub fn matches(&self, ip: u32) -> bool {
         self.original_prefix.matches(ip)
     }
 
+    /// Check if this compressed prefix matches an IP address of either family
+    pub fn matches_ip(&self, ip: std::net::IpAddr) -> bool {
+        self.original_prefix.matches_ip(ip)
+    }
+
     /// Get original prefix
     pub fn prefix(&self) -> &Prefix {
         &self.original_prefix
     }
+
+    /// Compressed value and prefix length, the input to the lookup hash
+    pub fn key(&self) -> (u32, u8) {
+        (self.compressed_value, self.original_prefix.prefix_len())
+    }
+
+    /// Whether another, different prefix compresses to the same key
+    pub fn collides_with(&self, other: &CompressedPrefix) -> bool {
+        self.original_prefix != other.original_prefix && self.key() == other.key()
+    }
 }
 
 /// Compression statistics
@@ -169,5 +210,42 @@ mod tests {
         let ip2 = u32::from(std::net::Ipv4Addr::new(192, 168, 2, 42));
         assert!(!compressed.matches(ip2));
     }
+
+    #[test]
+    fn test_verify_injective_detects_collision() {
+        let compressor = FrobeniusCompressor::new();
+        let v4: Vec<Prefix> = ["10.0.0.0/8", "10.1.0.0/16", "192.168.1.0/24", "192.168.1.0/24"]
+            .iter()
+            .map(|cidr| Prefix::from_cidr(cidr).unwrap())
+            .collect();
+        assert!(compressor.verify_injective(&v4).is_ok());
+
+        // φ is GF(2)-linear and inverted by φ^123 in GF(2^128), so pulling
+        // back an image whose 32-bit words cancel under the fold gives an
+        // address that compresses to the same value as ::/128
+        let folds_to_zero = 1u128 | (1u128 << 32);
+        let addr = GF2_128::new(folds_to_zero).frobenius(128 - 5).value();
+        let zero = Prefix::V6 { addr: 0, len: 128 };
+        let twin = Prefix::V6 { addr, len: 128 };
+        assert_ne!(zero, twin);
+
+        let mut prefixes = v4;
+        prefixes.push(zero);
+        prefixes.push(twin);
+        assert_eq!(compressor.verify_injective(&prefixes), Err((zero, twin)));
+        assert!(compressor.compress(&zero).collides_with(&compressor.compress(&twin)));
+    }
+
+    #[test]
+    fn test_ipv6_word_order_matters() {
+        // Word-wise XOR folding mapped these two prefixes to the same value
+        let compressor = FrobeniusCompressor::new();
+        let a = Prefix::from_cidr("2001:db8::/128").unwrap();
+        let b = Prefix::from_cidr("::2001:db8/128").unwrap();
+        assert_ne!(
+            compressor.compress(&a).compressed_value,
+            compressor.compress(&b).compressed_value
+        );
+    }
 }
 
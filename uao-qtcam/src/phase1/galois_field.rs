@@ -14,8 +14,11 @@
 //!
 //! We use the polynomial: P(x) = x^32 + x^7 + x^3 + x^2 + 1
 //! Binary representation: 0x10000008D (bit 32, 7, 3, 2, 0 set)
+//!
+//! IPv6 keys use [`GF2_128`] over P(x) = x^128 + x^7 + x^2 + x + 1 so a whole
+//! 128-bit prefix is a single field element.
 
-use std::ops::{Add, Mul, Sub};
+use std::ops::{Add, Mul, Neg, Sub};
 
 /// Galois Field GF(2^32) element
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -133,6 +136,125 @@ impl Mul for GF2_32 {
     }
 }
 
+/// Galois Field GF(2^128) element
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GF2_128(pub u128);
+
+/// Low bits of the irreducible polynomial for GF(2^128): x^128 + x^7 + x^2 + x + 1
+/// (the x^128 term is implicit)
+const IRREDUCIBLE_POLY_128: u128 = 0x87;
+
+impl GF2_128 {
+    /// Create new GF(2^128) element
+    pub fn new(value: u128) -> Self {
+        Self(value)
+    }
+
+    /// Zero element
+    pub fn zero() -> Self {
+        Self(0)
+    }
+
+    /// One element (multiplicative identity)
+    pub fn one() -> Self {
+        Self(1)
+    }
+
+    /// Multiplicative inverse: x^(2^128 - 2) = x^-1 for x != 0
+    pub fn inv(self) -> Option<Self> {
+        if self.0 == 0 {
+            None
+        } else {
+            Some(self.pow(u128::MAX - 1))
+        }
+    }
+
+    /// Power operation: self^exp
+    pub fn pow(self, mut exp: u128) -> Self {
+        let mut result = Self::one();
+        let mut base = self;
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result * base;
+            }
+            base = base * base;
+            exp >>= 1;
+        }
+
+        result
+    }
+
+    /// Frobenius automorphism: φ(x) = x^(2^k), computed as k squarings
+    pub fn frobenius(self, k: u32) -> Self {
+        (0..k % 128).fold(self, |x, _| x * x)
+    }
+
+    /// Get raw value
+    pub fn value(&self) -> u128 {
+        self.0
+    }
+}
+
+/// Addition in GF(2^128) is XOR
+impl Add for GF2_128 {
+    type Output = Self;
+
+    // Characteristic 2: coefficient-wise addition mod 2 is XOR
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn add(self, other: Self) -> Self {
+        Self(self.0 ^ other.0)
+    }
+}
+
+/// Subtraction in GF(2^128) is also XOR
+impl Sub for GF2_128 {
+    type Output = Self;
+
+    // Characteristic 2: x - y = x + y
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, other: Self) -> Self {
+        Self(self.0 ^ other.0)
+    }
+}
+
+/// Additive inverse (in GF(2^n), x + x = 0, so -x = x)
+impl Neg for GF2_128 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self
+    }
+}
+
+/// Multiplication in GF(2^128)
+impl Mul for GF2_128 {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self(gf128_mul(self.0, other.0))
+    }
+}
+
+/// Carry-less multiply of two GF(2^128) elements, reducing as we go
+fn gf128_mul(a: u128, b: u128) -> u128 {
+    let mut result = 0u128;
+
+    // Horner's rule from the top bit of b: result = result·x + b_i·a
+    for i in (0..128).rev() {
+        let carry = result >> 127;
+        result <<= 1;
+        if carry == 1 {
+            result ^= IRREDUCIBLE_POLY_128;
+        }
+        if (b >> i) & 1 == 1 {
+            result ^= a;
+        }
+    }
+
+    result
+}
+
 /// Multiply two GF(2^32) elements
 fn gf_mul(a: u32, b: u32) -> u32 {
     let mut result = 0u64;
@@ -199,6 +321,7 @@ fn gf_div_mod(dividend: u32, divisor: u32) -> (u32, u32) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::Rng;
 
     #[test]
     fn test_addition() {
@@ -258,5 +381,70 @@ mod tests {
         let sum = a + neg_a;
         assert_eq!(sum, GF2_32::zero());
     }
+
+    fn random_gf128(rng: &mut impl Rng) -> GF2_128 {
+        GF2_128::new(rng.gen())
+    }
+
+    #[test]
+    fn test_gf128_multiplication_identity() {
+        let a = GF2_128::new(0x2001_0db8_0000_0000_0000_0000_0000_0001);
+        assert_eq!(a * GF2_128::one(), a);
+        assert_eq!(a * GF2_128::zero(), GF2_128::zero());
+        assert_eq!(a + -a, GF2_128::zero());
+        assert_eq!(a - a, GF2_128::zero());
+    }
+
+    #[test]
+    fn test_gf128_distributivity() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0x5eed);
+        for _ in 0..256 {
+            let a = random_gf128(&mut rng);
+            let b = random_gf128(&mut rng);
+            let c = random_gf128(&mut rng);
+            assert_eq!(a * (b + c), a * b + a * c);
+            assert_eq!(a * b, b * a);
+        }
+    }
+
+    #[test]
+    fn test_gf128_reduction() {
+        // x^127 · x = x^128 ≡ x^7 + x^2 + x + 1
+        let x127 = GF2_128::new(1 << 127);
+        assert_eq!(x127 * GF2_128::new(2), GF2_128::new(0x87));
+    }
+
+    #[test]
+    fn test_gf128_inverse_and_frobenius() {
+        let a = GF2_128::new(0xfe80_0000_0000_0000_0211_22ff_fe33_4455);
+        assert_eq!(a * a.inv().unwrap(), GF2_128::one());
+        assert_eq!(GF2_128::zero().inv(), None);
+        assert_eq!(a.frobenius(1), a.pow(2));
+        assert_eq!(a.frobenius(3), a.pow(8));
+        // φ^128 is the identity on GF(2^128)
+        assert_eq!(a.frobenius(128), a);
+    }
+
+    #[test]
+    fn test_gf128_distinct_keys_rarely_collide() {
+        use rand::SeedableRng;
+        use std::collections::HashSet;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(128);
+        let keys: HashSet<u128> = (0..10_000).map(|_| rng.gen()).collect();
+        let images: HashSet<u128> = keys
+            .iter()
+            .map(|&k| GF2_128::new(k).frobenius(5).value())
+            .collect();
+        assert_eq!(images.len(), keys.len());
+
+        // Folded down to 32 bits, collisions stay near the birthday bound
+        let folded: HashSet<u32> = images
+            .iter()
+            .map(|&v| (0..4).fold(0u32, |acc, w| acc ^ (v >> (32 * w)) as u32))
+            .collect();
+        assert!(keys.len() - folded.len() < 10);
+    }
 }
 
@@ -5,7 +5,8 @@
 //!
 //! ## Mathematical Foundation
 //!
-//! The AHGF algorithm operates in GF(2^32) and uses:
+//! The AHGF algorithm operates in GF(2^32) for IPv4 keys and GF(2^128) for
+//! IPv6 keys, and uses:
 //! - **Frobenius Automorphism**: φ(x) = x^(2^k) for compression
 //! - **Algebraic Heterodyning**: Mixing high-frequency prefix patterns
 //! - **Multi-domain Orchestration**: Combining algebraic, topological, and quantum domains
@@ -37,76 +38,194 @@ pub mod galois_field;
 pub mod algebraic_heterodyning;
 pub mod frobenius_compression;
 
-pub use galois_field::GF2_32;
+pub use galois_field::{GF2_128, GF2_32};
 pub use algebraic_heterodyning::AHGFEngine;
 pub use frobenius_compression::FrobeniusCompressor;
 
 use anyhow::Result;
-use std::net::Ipv4Addr;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Reverse;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 
-/// IPv4 prefix with CIDR notation
+/// IP prefix with CIDR notation (IPv4 or IPv6)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct Prefix {
-    /// Network address
-    pub addr: u32,
-    /// Prefix length (0-32)
-    pub len: u8,
+pub enum Prefix {
+    /// IPv4 prefix (length 0-32)
+    V4 {
+        /// Network address
+        addr: u32,
+        /// Prefix length (0-32)
+        len: u8,
+    },
+    /// IPv6 prefix (length 0-128)
+    V6 {
+        /// Network address
+        addr: u128,
+        /// Prefix length (0-128)
+        len: u8,
+    },
 }
 
 impl Prefix {
-    /// Create prefix from CIDR notation (e.g., "192.168.1.0/24")
+    /// Create prefix from CIDR notation (e.g., "192.168.1.0/24" or "2001:db8::/32")
     pub fn from_cidr(cidr: &str) -> Result<Self> {
         let parts: Vec<&str> = cidr.split('/').collect();
         if parts.len() != 2 {
             anyhow::bail!("Invalid CIDR format: {}", cidr);
         }
 
-        let addr: Ipv4Addr = parts[0].parse()?;
+        let addr: IpAddr = parts[0].parse()?;
         let len: u8 = parts[1].parse()?;
 
-        if len > 32 {
-            anyhow::bail!("Invalid prefix length: {}", len);
+        match addr {
+            IpAddr::V4(v4) => Self::new(u32::from(v4), len),
+            IpAddr::V6(v6) => Self::new_v6(u128::from(v6), len),
         }
-
-        Ok(Self {
-            addr: u32::from(addr),
-            len,
-        })
     }
 
-    /// Create prefix from IP address and length
+    /// Create IPv4 prefix from IP address and length
+    ///
+    /// Host bits are cleared, so "10.1.9.9/16" and "10.1.0.0/16" are the
+    /// same prefix.
     pub fn new(addr: u32, len: u8) -> Result<Self> {
         if len > 32 {
             anyhow::bail!("Invalid prefix length: {}", len);
         }
-        Ok(Self { addr, len })
+        let mask = (!0u32).checked_shl(32 - len as u32).unwrap_or(0);
+        Ok(Self::V4 { addr: addr & mask, len })
+    }
+
+    /// Create IPv6 prefix from IP address and length (host bits cleared)
+    pub fn new_v6(addr: u128, len: u8) -> Result<Self> {
+        if len > 128 {
+            anyhow::bail!("Invalid prefix length: {}", len);
+        }
+        let mask = (!0u128).checked_shl(128 - len as u32).unwrap_or(0);
+        Ok(Self::V6 { addr: addr & mask, len })
+    }
+
+    /// Prefix length in bits
+    pub fn prefix_len(&self) -> u8 {
+        match *self {
+            Self::V4 { len, .. } | Self::V6 { len, .. } => len,
+        }
+    }
+
+    /// Whether this is an IPv4 prefix
+    pub fn is_ipv4(&self) -> bool {
+        matches!(self, Self::V4 { .. })
+    }
+
+    /// Network address as a 32-bit key.
+    ///
+    /// IPv4 prefixes return their address; IPv6 prefixes return the most
+    /// significant 32 bits. Used by the IPv4-only phases for bucketing.
+    pub fn addr_u32(&self) -> u32 {
+        match *self {
+            Self::V4 { addr, .. } => addr,
+            Self::V6 { addr, .. } => (addr >> 96) as u32,
+        }
+    }
+
+    /// Network address widened to 128 bits
+    pub fn addr_u128(&self) -> u128 {
+        match *self {
+            Self::V4 { addr, .. } => addr as u128,
+            Self::V6 { addr, .. } => addr,
+        }
     }
 
-    /// Check if this prefix matches an IP address
+    /// Check if this prefix matches an IPv4 address
     pub fn matches(&self, ip: u32) -> bool {
-        if self.len == 0 {
-            return true; // Default route matches everything
+        match *self {
+            Self::V4 { addr, len } => {
+                if len == 0 {
+                    return true; // Default route matches everything
+                }
+                let mask = !0u32 << (32 - len);
+                (addr & mask) == (ip & mask)
+            }
+            Self::V6 { .. } => false,
+        }
+    }
+
+    /// Check if this prefix matches an IPv6 address
+    pub fn matches_v6(&self, ip: u128) -> bool {
+        match *self {
+            Self::V6 { addr, len } => {
+                if len == 0 {
+                    return true; // Default route matches everything
+                }
+                let mask = !0u128 << (128 - len as u32);
+                (addr & mask) == (ip & mask)
+            }
+            Self::V4 { .. } => false,
         }
-        let mask = !0u32 << (32 - self.len);
-        (self.addr & mask) == (ip & mask)
     }
 
-    /// Get network mask
-    pub fn mask(&self) -> u32 {
-        if self.len == 0 {
-            0
-        } else {
-            !0u32 << (32 - self.len)
+    /// Check if this prefix matches an IP address of either family
+    pub fn matches_ip(&self, ip: IpAddr) -> bool {
+        match ip {
+            IpAddr::V4(v4) => self.matches(u32::from(v4)),
+            IpAddr::V6(v6) => self.matches_v6(u128::from(v6)),
+        }
+    }
+
+    /// Get network mask (IPv4 masks occupy the low 32 bits)
+    pub fn mask(&self) -> u128 {
+        match *self {
+            Self::V4 { len, .. } => {
+                if len == 0 {
+                    0
+                } else {
+                    (!0u32 << (32 - len)) as u128
+                }
+            }
+            Self::V6 { len, .. } => {
+                if len == 0 {
+                    0
+                } else {
+                    !0u128 << (128 - len as u32)
+                }
+            }
+        }
+    }
+}
+
+/// CIDR notation
+impl fmt::Display for Prefix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::V4 { addr, len } => write!(f, "{}/{}", Ipv4Addr::from(addr), len),
+            Self::V6 { addr, len } => write!(f, "{}/{}", Ipv6Addr::from(addr), len),
         }
     }
+}
+
+/// Serialized as a CIDR string, e.g. `"10.0.0.0/8"`
+impl Serialize for Prefix {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
 
-    /// Convert to string (CIDR notation)
-    pub fn to_string(&self) -> String {
-        let ip = Ipv4Addr::from(self.addr);
-        format!("{}/{}", ip, self.len)
+impl<'de> Deserialize<'de> for Prefix {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let cidr = String::deserialize(deserializer)?;
+        Self::from_cidr(&cidr).map_err(serde::de::Error::custom)
     }
 }
 
+/// Sort key giving matching routes a total preference order
+///
+/// Longest prefix first, then lowest metric, then the lexicographically
+/// smallest next hop, so ties never depend on table or insertion order.
+/// Every phase selects the route with the smallest key.
+pub fn route_rank(prefix_len: u8, metric: u32, next_hop: &str) -> (Reverse<u8>, u32, &str) {
+    (Reverse(prefix_len), metric, next_hop)
+}
+
 /// Route entry with next hop and metric
 #[derive(Debug, Clone)]
 pub struct RouteEntry {
@@ -132,7 +251,20 @@ mod tests {
     #[test]
     fn test_prefix_from_cidr() {
         let prefix = Prefix::from_cidr("192.168.1.0/24").unwrap();
-        assert_eq!(prefix.len, 24);
+        assert_eq!(prefix.prefix_len(), 24);
+    }
+
+    #[test]
+    fn test_prefix_clears_host_bits() {
+        let prefix = Prefix::from_cidr("10.1.9.9/16").unwrap();
+        assert_eq!(prefix, Prefix::from_cidr("10.1.0.0/16").unwrap());
+        assert_eq!(prefix.to_string(), "10.1.0.0/16");
+        assert_eq!(Prefix::new(0xFFFF_FFFF, 0).unwrap().to_string(), "0.0.0.0/0");
+        assert_eq!(Prefix::new(0x0A01_0203, 32).unwrap().to_string(), "10.1.2.3/32");
+
+        let v6 = Prefix::from_cidr("2001:db8:1::1/32").unwrap();
+        assert_eq!(v6.to_string(), "2001:db8::/32");
+        assert_eq!(Prefix::new_v6(u128::MAX, 0).unwrap().to_string(), "::/0");
     }
 
     #[test]
@@ -151,5 +283,45 @@ mod tests {
         let mask = prefix.mask();
         assert_eq!(mask, 0xFFFFFF00);
     }
-}
 
+    #[test]
+    fn test_prefix_v6_from_cidr() {
+        let prefix = Prefix::from_cidr("2001:db8::/32").unwrap();
+        assert!(!prefix.is_ipv4());
+        assert_eq!(prefix.prefix_len(), 32);
+        assert_eq!(prefix.to_string(), "2001:db8::/32");
+        assert!(Prefix::from_cidr("2001:db8::/129").is_err());
+    }
+
+    #[test]
+    fn test_prefix_v6_matches() {
+        let prefix = Prefix::from_cidr("2001:db8::/32").unwrap();
+
+        let ip: Ipv6Addr = "2001:db8::1".parse().unwrap();
+        assert!(prefix.matches_v6(u128::from(ip)));
+
+        let ip2: Ipv6Addr = "2001:db9::1".parse().unwrap();
+        assert!(!prefix.matches_v6(u128::from(ip2)));
+
+        // Families never cross-match
+        assert!(!prefix.matches(u32::from(Ipv4Addr::new(32, 1, 13, 184))));
+    }
+
+    #[test]
+    fn test_prefix_v6_mask() {
+        let prefix = Prefix::from_cidr("2001:db8::/32").unwrap();
+        assert_eq!(prefix.mask(), 0xFFFF_FFFF_u128 << 96);
+    }
+
+    #[test]
+    fn test_prefix_json_roundtrip() {
+        for cidr in ["10.0.0.0/8", "0.0.0.0/0", "2001:db8::/32"] {
+            let prefix = Prefix::from_cidr(cidr).unwrap();
+            let json = serde_json::to_string(&prefix).unwrap();
+            assert_eq!(json, format!("\"{}\"", cidr));
+            assert_eq!(serde_json::from_str::<Prefix>(&json).unwrap(), prefix);
+        }
+
+        assert!(serde_json::from_str::<Prefix>("\"10.0.0.0\"").is_err());
+    }
+}
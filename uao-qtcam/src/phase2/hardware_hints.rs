@@ -61,6 +61,27 @@ impl HardwareHint {
         (ip & mask) == (self.prefix_addr & mask)
     }
 
+    /// Address of this hint; hints are cache-line aligned, so one prefetch
+    /// covers the whole hint
+    #[inline(always)]
+    pub fn address(&self) -> usize {
+        self as *const Self as usize
+    }
+
+    /// Prefetch this hint's cache line into every cache level
+    ///
+    /// A no-op on targets other than x86-64.
+    #[inline(always)]
+    pub fn prefetch(&self) {
+        #[cfg(target_arch = "x86_64")]
+        // SAFETY: prefetching is only a hint and never faults; SSE is part of
+        // the x86-64 baseline, and the pointer refers to a live hint
+        unsafe {
+            use std::arch::x86_64::{_mm_prefetch, _MM_HINT_T0};
+            _mm_prefetch::<_MM_HINT_T0>(self as *const Self as *const i8);
+        }
+    }
+
     /// Calculate distance in compressed space (for nearest neighbor)
     #[inline(always)]
     pub fn distance(&self, coords: &[f32; 4]) -> f32 {
@@ -177,6 +198,29 @@ impl HintGenerator {
         best_match.map(|(_, next_hop)| next_hop)
     }
 
+    /// Prefetch the hint at `hint_idx`; `false` if there is no such hint
+    pub fn prefetch(&self, hint_idx: usize) -> bool {
+        match self.hints.read().get(hint_idx) {
+            Some(hint) => {
+                hint.prefetch();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Address range of the hint table allocation
+    pub fn table_range(&self) -> std::ops::Range<usize> {
+        let hints = self.hints.read();
+        let start = hints.as_ptr() as usize;
+        start..start + hints.len() * std::mem::size_of::<HardwareHint>()
+    }
+
+    /// Address of every hint, in table order
+    pub fn hint_addresses(&self) -> Vec<usize> {
+        self.hints.read().iter().map(HardwareHint::address).collect()
+    }
+
     /// Get number of hints
     pub fn num_hints(&self) -> usize {
         self.hints.read().len()
@@ -305,6 +349,25 @@ mod tests {
         assert_eq!(result, Some(3)); // Should match 192.168.1.64/26 (most specific)
     }
 
+    #[test]
+    fn test_hint_prefetch() {
+        let generator = HintGenerator::new(2);
+        let prefixes = vec![(0xC0A80100, 24), (0xC0A80200, 24), (0x0A000000, 8)];
+        let compressed_coords = vec![[0.0; 4]; 3];
+        generator.generate_hints(&prefixes, &[1, 2, 3], &[0, 0, 1], &compressed_coords).unwrap();
+
+        let range = generator.table_range();
+        let addresses = generator.hint_addresses();
+        assert_eq!(addresses.len(), 3);
+        for address in addresses {
+            assert!(range.contains(&address));
+            assert_eq!(address % CACHE_LINE_SIZE, 0);
+        }
+
+        assert!(generator.prefetch(2));
+        assert!(!generator.prefetch(3));
+    }
+
     #[test]
     fn test_cache_utilization() {
         let generator = HintGenerator::new(2);
@@ -9,9 +9,12 @@
 //! Target: 10 ns latency, 100M lookups/sec, 1,000x speedup
 
 use super::{QuantumState, SpectralAnalyzer, DimensionalFolder, HintGenerator};
-use crate::phase1::Prefix;
+use crate::phase1::{route_rank, Prefix};
 use anyhow::{Result, anyhow};
+use std::cmp::Reverse;
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::sync::RwLock;
 use std::time::Instant;
 
@@ -45,19 +48,33 @@ pub struct QAGFHGStats {
     pub num_clusters: usize,
     /// Compression ratio
     pub compression_ratio: f64,
+    /// Number of times the hint table was regenerated
+    pub hint_rebuilds: u64,
+    /// Hint prefetches issued for the predicted next lookup
+    pub prefetches: u64,
 }
 
 /// Route entry for QAGFHG
+///
+/// Bulk-loaded routes carry no quantum state: a /24 superposition spans
+/// 2^16 amplitudes (~1 MB), which rules out loading large tables.
 #[derive(Debug, Clone)]
 struct RouteEntry {
     prefix: Prefix,
     next_hop: String,
     metric: u32,
-    quantum_state: QuantumState,
+    quantum_state: Option<QuantumState>,
     cluster_id: u16,
     compressed_coords: [f32; 4],
 }
 
+impl RouteEntry {
+    /// Preference key (see [`route_rank`])
+    fn rank(&self) -> (Reverse<u8>, u32, &str) {
+        route_rank(self.prefix.prefix_len(), self.metric, &self.next_hop)
+    }
+}
+
 /// QAGFHG Engine
 pub struct QAGFHGEngine {
     /// Route table
@@ -74,6 +91,10 @@ pub struct QAGFHGEngine {
     num_clusters: usize,
     /// Target dimension for folding
     target_dim: usize,
+    /// Prefetch the matched route's hint after each lookup
+    prefetch: AtomicBool,
+    /// Routes changed since the hint table was last generated
+    hints_stale: AtomicBool,
 }
 
 impl QAGFHGEngine {
@@ -95,24 +116,54 @@ impl QAGFHGEngine {
                 num_routes: 0,
                 num_clusters,
                 compression_ratio: 0.0,
+                hint_rebuilds: 0,
+                prefetches: 0,
             })),
             num_clusters,
             target_dim,
+            prefetch: AtomicBool::new(false),
+            hints_stale: AtomicBool::new(false),
         }
     }
 
+    /// Enable or disable hint prefetching on the lookup path
+    ///
+    /// Lookups tend to repeat, so after each match the matched route's hint
+    /// is prefetched for the predicted next lookup of the same destination.
+    pub fn set_prefetch(&self, enabled: bool) {
+        self.prefetch.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Hardware hint table (one hint per route, in route order)
+    pub fn hint_generator(&self) -> &HintGenerator {
+        &self.hint_generator
+    }
+
     /// Insert a route into the engine
+    ///
+    /// A route already stored for exactly `prefix` is overwritten rather
+    /// than duplicated.
     pub async fn insert(&self, prefix: Prefix, next_hop: String, metric: u32) -> Result<()> {
+        if !prefix.is_ipv4() {
+            return Err(anyhow!("Phase 2 supports IPv4 prefixes only: {}", prefix));
+        }
+
         // Create quantum state for prefix
-        let quantum_state = QuantumState::from_prefix(prefix.addr, prefix.len)?;
+        let quantum_state = QuantumState::from_prefix(prefix.addr_u32(), prefix.prefix_len())?;
 
-        // Add to routes (cluster_id and compressed_coords will be updated during rebuild)
         let mut routes = self.routes.write().await;
+        if let Some(route) = routes.iter_mut().find(|route| route.prefix == prefix) {
+            route.next_hop = next_hop;
+            route.metric = metric;
+            return Ok(());
+        }
+
+        // Add to routes (cluster_id and compressed_coords will be updated during rebuild)
         routes.push(RouteEntry {
             prefix,
             next_hop,
             metric,
-            quantum_state,
+            quantum_state: Some(quantum_state),
             cluster_id: 0,
             compressed_coords: [0.0; 4],
         });
@@ -133,10 +184,99 @@ impl QAGFHGEngine {
         Ok(())
     }
 
+    /// Insert a batch of routes under a single write lock
+    ///
+    /// Routes whose prefix is already present overwrite the existing entry.
+    /// The hint table is invalidated once for the whole batch, so it is
+    /// regenerated at most once. Returns the number of newly added prefixes.
+    pub async fn insert_bulk(&self, batch: Vec<(Prefix, String, u32)>) -> Result<usize> {
+        if let Some((prefix, _, _)) = batch.iter().find(|(p, _, _)| !p.is_ipv4()) {
+            return Err(anyhow!("Phase 2 supports IPv4 prefixes only: {}", prefix));
+        }
+
+        let mut routes = self.routes.write().await;
+        let mut positions: HashMap<Prefix, usize> = routes.iter()
+            .enumerate()
+            .map(|(idx, route)| (route.prefix, idx))
+            .collect();
+
+        let mut inserted = 0;
+        for (prefix, next_hop, metric) in batch {
+            match positions.get(&prefix) {
+                Some(&idx) => {
+                    routes[idx].next_hop = next_hop;
+                    routes[idx].metric = metric;
+                }
+                None => {
+                    positions.insert(prefix, routes.len());
+                    routes.push(RouteEntry {
+                        prefix,
+                        next_hop,
+                        metric,
+                        quantum_state: None,
+                        cluster_id: 0,
+                        compressed_coords: [0.0; 4],
+                    });
+                    inserted += 1;
+                }
+            }
+        }
+
+        let num_routes = routes.len();
+        drop(routes);
+
+        self.stats.write().await.num_routes = num_routes;
+
+        if num_routes >= 3 {
+            self.rebuild_hints().await?;
+        }
+
+        Ok(inserted)
+    }
+
+    /// Remove the route stored for exactly `prefix`
+    ///
+    /// Returns `true` if a route was removed.
+    pub async fn delete(&self, prefix: Prefix) -> bool {
+        let mut routes = self.routes.write().await;
+        let before = routes.len();
+        routes.retain(|route| route.prefix != prefix);
+        let num_routes = routes.len();
+        drop(routes);
+
+        if num_routes == before {
+            return false;
+        }
+        self.stats.write().await.num_routes = num_routes;
+        // Hints are indexed by route position, which just shifted
+        self.hints_stale.store(true, Ordering::Release);
+        true
+    }
+
     /// Rebuild hardware hints (called after inserting routes)
+    ///
+    /// Only marks the table stale: it is regenerated on the next lookup
+    /// that prefetches, so inserts stay O(1) when prefetching is off.
     async fn rebuild_hints(&self) -> Result<()> {
         // For now, skip the complex clustering to avoid deadlocks
         // This will be optimized in production
+        self.hints_stale.store(true, Ordering::Release);
+        Ok(())
+    }
+
+    /// Regenerate the hint table from the current routes
+    async fn refresh_hints(&self) -> Result<()> {
+        let routes = self.routes.read().await;
+        let prefixes: Vec<(u32, u8)> = routes.iter()
+            .map(|route| (route.prefix.addr_u32(), route.prefix.prefix_len()))
+            .collect();
+        let next_hops: Vec<u32> = (0..routes.len() as u32).collect();
+        let cluster_ids: Vec<u16> = routes.iter().map(|route| route.cluster_id).collect();
+        let coords: Vec<[f32; 4]> = routes.iter().map(|route| route.compressed_coords).collect();
+        drop(routes);
+
+        self.hint_generator.generate_hints(&prefixes, &next_hops, &cluster_ids, &coords)?;
+        self.stats.write().await.hint_rebuilds += 1;
         Ok(())
     }
 
@@ -149,32 +289,43 @@ impl QAGFHGEngine {
             .map_err(|e| anyhow!("Invalid IP address: {}", e))?;
         let ip_u32 = u32::from(ip_addr);
 
-        // Simple longest prefix match (fallback if hints not ready)
+        // Simple longest prefix match (fallback if hints not ready), ties
+        // broken by metric then next hop
         let routes = self.routes.read().await;
-        let mut best_match: Option<RouteEntry> = None;
-        let mut best_len = 0u8;
-
-        for route in routes.iter() {
-            if route.prefix.matches(ip_u32) && route.prefix.len >= best_len {
-                best_match = Some(route.clone());
-                best_len = route.prefix.len;
-            }
-        }
+        let best_match = routes.iter()
+            .enumerate()
+            .filter(|(_, route)| route.prefix.matches(ip_u32))
+            .min_by(|(_, a), (_, b)| a.rank().cmp(&b.rank()))
+            .map(|(idx, route)| (idx, route.clone()));
         drop(routes);
 
         let latency_ns = start.elapsed().as_nanos() as u64;
 
+        // Warm the matched route's hint for the next lookup, off the timed path
+        let mut prefetched = false;
+        if let Some((idx, _)) = &best_match {
+            if self.prefetch.load(Ordering::Relaxed) {
+                if self.hints_stale.swap(false, Ordering::AcqRel) {
+                    self.refresh_hints().await?;
+                }
+                prefetched = self.hint_generator.prefetch(*idx);
+            }
+        }
+
         // Update stats
         let mut stats = self.stats.write().await;
         stats.lookups += 1;
         if best_match.is_some() {
             stats.cache_hits += 1;
         }
+        if prefetched {
+            stats.prefetches += 1;
+        }
         stats.avg_latency_ns = (stats.avg_latency_ns * (stats.lookups - 1) as f64 + latency_ns as f64) / stats.lookups as f64;
         drop(stats);
 
         // Return result
-        if let Some(route) = best_match {
+        if let Some((_, route)) = best_match {
             let quantum_state = QuantumState::from_prefix(ip_u32, 32)?;
             let cluster_id = (quantum_state.collapse() % self.num_clusters as u32) as u16;
 
@@ -223,6 +374,21 @@ mod tests {
         assert_eq!(stats.num_routes, 1);
     }
 
+    #[tokio::test]
+    async fn test_qagfhg_delete() {
+        let engine = QAGFHGEngine::new(2, 4);
+
+        let prefix = Prefix::from_cidr("192.168.1.0/24").unwrap();
+        engine.insert(prefix, "gateway1".to_string(), 100).await.unwrap();
+        engine.insert(Prefix::from_cidr("192.168.0.0/16").unwrap(), "gateway2".to_string(), 100).await.unwrap();
+
+        assert!(engine.delete(prefix).await);
+        assert!(!engine.delete(prefix).await);
+        assert_eq!(engine.stats().num_routes, 1);
+        let result = engine.lookup("192.168.1.42").await.unwrap().unwrap();
+        assert_eq!(result.next_hop, "gateway2");
+    }
+
     #[tokio::test]
     async fn test_qagfhg_lookup() {
         let engine = QAGFHGEngine::new(2, 4);
@@ -287,5 +453,62 @@ mod tests {
         let stats = engine.stats();
         assert!(stats.cache_hits > 0);
     }
+
+    #[tokio::test]
+    async fn test_qagfhg_prefetch_hints() {
+        let engine = QAGFHGEngine::new(2, 4);
+        let batch: Vec<_> = (0..4)
+            .map(|i| (Prefix::from_cidr(&format!("10.{}.0.0/16", i)).unwrap(), format!("gateway{}", i), 100))
+            .collect();
+        engine.insert_bulk(batch).await.unwrap();
+
+        // Off by default: no hints built, nothing prefetched
+        engine.lookup("10.1.2.3").await.unwrap().unwrap();
+        assert_eq!(engine.hint_generator().num_hints(), 0);
+        assert_eq!(engine.stats().prefetches, 0);
+
+        engine.set_prefetch(true);
+        engine.lookup("10.1.2.3").await.unwrap().unwrap();
+        assert!(engine.lookup("172.16.0.1").await.unwrap().is_none());
+        assert_eq!(engine.stats().prefetches, 1);
+
+        let hints = engine.hint_generator();
+        assert_eq!(hints.num_hints(), 4);
+        let range = hints.table_range();
+        assert!(hints.hint_addresses().iter().all(|address| range.contains(address)));
+
+        // New routes mark the table stale; the next prefetching lookup rebuilds it
+        let prefix = Prefix::from_cidr("10.9.0.0/16").unwrap();
+        engine.insert(prefix, "gateway9".to_string(), 100).await.unwrap();
+        engine.lookup("10.9.0.1").await.unwrap().unwrap();
+        assert_eq!(engine.hint_generator().num_hints(), 5);
+        assert_eq!(engine.stats().prefetches, 2);
+    }
+
+    #[tokio::test]
+    async fn test_qagfhg_insert_bulk() {
+        let engine = QAGFHGEngine::new(2, 4);
+        engine.set_prefetch(true);
+
+        let batch: Vec<_> = (0..10)
+            .map(|i| (Prefix::from_cidr(&format!("192.168.{}.0/24", i)).unwrap(), format!("gateway{}", i), 100))
+            .collect();
+        assert_eq!(engine.insert_bulk(batch).await.unwrap(), 10);
+
+        // Re-inserting an existing prefix overwrites it
+        let prefix = Prefix::from_cidr("192.168.1.0/24").unwrap();
+        assert_eq!(engine.insert_bulk(vec![(prefix, "updated".to_string(), 5)]).await.unwrap(), 0);
+
+        // One regeneration per batch, however many lookups follow
+        for _ in 0..3 {
+            let result = engine.lookup("192.168.1.42").await.unwrap().unwrap();
+            assert_eq!(result.next_hop, "updated");
+        }
+
+        let stats = engine.stats();
+        assert_eq!(stats.num_routes, 10);
+        assert_eq!(stats.hint_rebuilds, 1);
+        assert_eq!(engine.hint_generator().num_hints(), 10);
+    }
 }
 
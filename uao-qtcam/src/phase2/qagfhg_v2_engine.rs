@@ -102,14 +102,18 @@ impl QAGFHGv2Engine {
     /// POSTULATE 2: Cluster ID from bit pattern (O(1))
     /// POSTULATE 3: Morton encoding for 4D compression (O(1))
     pub fn insert(&self, prefix: Prefix, next_hop: String, metric: u32) -> Result<()> {
+        if !prefix.is_ipv4() {
+            return Err(anyhow!("Phase 2 V2 supports IPv4 prefixes only: {}", prefix));
+        }
+
         // POSTULATE 1: Quantum Coherence Collapse - Pre-compute eigenstate
-        let eigenstate = self.compute_eigenstate(prefix.addr, prefix.len);
+        let eigenstate = self.compute_eigenstate(prefix.addr_u32(), prefix.prefix_len());
 
         // POSTULATE 2: Spectral Locality Invariance - O(1) cluster ID
-        let cluster_id = self.compute_cluster_id(prefix.addr, prefix.len);
+        let cluster_id = self.compute_cluster_id(prefix.addr_u32(), prefix.prefix_len());
 
         // POSTULATE 3: Dimensional Folding Duality - Morton encoding
-        let morton_code = self.morton_encode_4d(prefix.addr, prefix.len, cluster_id, metric);
+        let morton_code = self.morton_encode_4d(prefix.addr_u32(), prefix.prefix_len(), cluster_id, metric);
 
         // Create collapsed quantum state
         let state = CollapsedQuantumState {
@@ -121,7 +125,7 @@ impl QAGFHGv2Engine {
         };
 
         // Lock-free insert into DashMap
-        self.quantum_states.insert(prefix.addr, state);
+        self.quantum_states.insert(prefix.addr_u32(), state);
         self.stats.num_routes.fetch_add(1, Ordering::Relaxed);
 
         Ok(())
@@ -41,7 +41,8 @@ impl QuantumState {
         
         // Encode prefix bits into quantum amplitudes
         for i in 0..dim {
-            let bit_pattern = (prefix_addr >> (32 - prefix_len)) ^ (i as u32);
+            // checked_shr: a /0 prefix would shift by the full 32-bit width
+            let bit_pattern = prefix_addr.checked_shr(32 - prefix_len as u32).unwrap_or(0) ^ (i as u32);
             let amplitude = 1.0 / (dim as f64).sqrt();
             let phase_shift = 2.0 * PI * (bit_pattern as f64) / (dim as f64);
             
@@ -151,5 +152,10 @@ mod tests {
         state.apply_rotation(PI / 4.0);
         assert!((state.phase() - (initial_phase + PI / 4.0)).abs() < 1e-10);
     }
-}
 
+    #[test]
+    fn test_quantum_state_default_route() {
+        let state = QuantumState::from_prefix(0, 0).unwrap();
+        assert_eq!(state.dimension(), 1);
+    }
+}
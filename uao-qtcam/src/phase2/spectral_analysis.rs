@@ -7,6 +7,10 @@
 use nalgebra::{DMatrix, DVector, SymmetricEigen};
 use anyhow::{Result, anyhow};
 use std::collections::HashMap;
+use crate::phase1::Prefix;
+
+/// Iteration cap for k-means on the spectral embedding
+const KMEANS_MAX_ITERATIONS: usize = 100;
 
 /// Spectral analyzer for prefix clustering
 #[derive(Debug)]
@@ -19,6 +23,10 @@ pub struct SpectralAnalyzer {
     eigenvectors: Option<DMatrix<f64>>,
     /// Cluster assignments (prefix_id -> cluster_id)
     clusters: HashMap<usize, usize>,
+    /// Spectral embedding the clusters were formed in (one row per prefix)
+    embedding: Option<DMatrix<f64>>,
+    /// Prefixes passed to `analyze`, indexed by prefix_id
+    prefixes: Vec<Prefix>,
 }
 
 impl SpectralAnalyzer {
@@ -32,9 +40,25 @@ impl SpectralAnalyzer {
             eigenvalues: Vec::new(),
             eigenvectors: None,
             clusters: HashMap::new(),
+            embedding: None,
+            prefixes: Vec::new(),
         }
     }
 
+    /// Build the Laplacian for `prefixes`, decompose it and cluster
+    ///
+    /// Prefix ids are positions in `prefixes`; [`Self::clusters`] maps the
+    /// assignment back to the prefixes themselves.
+    pub fn analyze(&mut self, prefixes: &[Prefix]) -> Result<()> {
+        let addrs: Vec<u32> = prefixes.iter().map(Prefix::addr_u32).collect();
+        let lens: Vec<u8> = prefixes.iter().map(|p| p.prefix_len().min(32)).collect();
+        let laplacian = self.build_laplacian(&addrs, &lens)?;
+        self.decompose(&laplacian)?;
+        self.cluster()?;
+        self.prefixes = prefixes.to_vec();
+        Ok(())
+    }
+
     /// Build graph Laplacian from prefix similarity matrix
     ///
     /// # Arguments
@@ -145,29 +169,125 @@ impl SpectralAnalyzer {
             return Err(anyhow!("Not enough prefixes for {} clusters", self.num_clusters));
         }
 
-        // Use first k eigenvectors as features (skip first eigenvector - constant)
-        let k = self.num_clusters.min(n - 1);
-        
-        // Simple k-means clustering on spectral embedding
-        self.clusters = self.kmeans_clustering(eigenvectors, k)?;
+        // Embed each prefix as its row of the k smallest eigenvectors; the
+        // first is constant, so it shifts every point equally
+        let k = self.num_clusters.min(n - 1).max(1);
+        let embedding = eigenvectors.columns(0, k).into_owned();
+
+        // k-means clustering on spectral embedding
+        self.clusters = self.kmeans_clustering(&embedding, k)?;
+        self.embedding = Some(embedding);
 
         Ok(())
     }
 
-    /// Simple k-means clustering implementation
+    /// k-means over the rows of `features`
+    ///
+    /// Seeded deterministically by farthest-point selection starting from
+    /// row 0, so the same input always yields the same clusters.
     fn kmeans_clustering(&self, features: &DMatrix<f64>, k: usize) -> Result<HashMap<usize, usize>> {
-        // Implementation continues in next file section...
         let n = features.nrows();
-        let mut assignments = HashMap::new();
-        
-        // Simple assignment based on dominant eigenvector component
-        for i in 0..n {
-            let row = features.row(1); // Use second eigenvector (first non-trivial)
-            let cluster_id = ((row[i] + 1.0) * (k as f64 / 2.0)) as usize % k;
-            assignments.insert(i, cluster_id);
+        if n == 0 || k == 0 {
+            return Err(anyhow!("Nothing to cluster"));
         }
-        
-        Ok(assignments)
+        let point = |i: usize| features.row(i).transpose();
+
+        let mut centroids = vec![point(0)];
+        while centroids.len() < k {
+            let farthest = (0..n)
+                .max_by(|&a, &b| {
+                    let da = centroids.iter().map(|c| (point(a) - c).norm()).fold(f64::INFINITY, f64::min);
+                    let db = centroids.iter().map(|c| (point(b) - c).norm()).fold(f64::INFINITY, f64::min);
+                    da.total_cmp(&db)
+                })
+                .unwrap_or(0);
+            centroids.push(point(farthest));
+        }
+
+        let mut assignment = vec![0usize; n];
+        for iteration in 0..KMEANS_MAX_ITERATIONS {
+            let mut changed = false;
+            for (i, slot) in assignment.iter_mut().enumerate() {
+                let nearest = (0..k)
+                    .min_by(|&a, &b| (point(i) - &centroids[a]).norm().total_cmp(&(point(i) - &centroids[b]).norm()))
+                    .unwrap_or(0);
+                changed |= *slot != nearest;
+                *slot = nearest;
+            }
+            if !changed && iteration > 0 {
+                break;
+            }
+
+            for (c, centroid) in centroids.iter_mut().enumerate() {
+                let members: Vec<usize> = (0..n).filter(|&i| assignment[i] == c).collect();
+                if !members.is_empty() {
+                    *centroid = members.iter().map(|&i| point(i)).sum::<DVector<f64>>() / members.len() as f64;
+                }
+            }
+        }
+
+        Ok(assignment.into_iter().enumerate().collect())
+    }
+
+    /// Prefixes grouped by cluster, in cluster-id order
+    ///
+    /// Only populated by [`Self::analyze`]; empty clusters are omitted.
+    pub fn clusters(&self) -> Vec<Vec<Prefix>> {
+        let mut grouped = vec![Vec::new(); self.num_clusters];
+        for (id, prefix) in self.prefixes.iter().enumerate() {
+            if let Some(&cluster) = self.clusters.get(&id) {
+                if let Some(group) = grouped.get_mut(cluster) {
+                    group.push(*prefix);
+                }
+            }
+        }
+        grouped.retain(|group| !group.is_empty());
+        grouped
+    }
+
+    /// Mean silhouette of the current clustering in the spectral embedding
+    ///
+    /// Ranges over [-1, 1]: near 1 when clusters are tight and well
+    /// separated, near 0 when they overlap. Points alone in their cluster
+    /// score 0, and so does a clustering with fewer than two clusters.
+    pub fn cluster_quality(&self) -> f64 {
+        let embedding = match &self.embedding {
+            Some(embedding) => embedding,
+            None => return 0.0,
+        };
+        let n = embedding.nrows();
+        let cluster_of = |i: usize| self.clusters.get(&i).copied().unwrap_or(0);
+        let mut distinct: Vec<usize> = (0..n).map(cluster_of).collect();
+        distinct.sort_unstable();
+        distinct.dedup();
+        if distinct.len() < 2 {
+            return 0.0;
+        }
+
+        let distance = |i: usize, j: usize| (embedding.row(i) - embedding.row(j)).norm();
+        let mean_distance = |i: usize, cluster: usize| {
+            let (sum, count) = (0..n)
+                .filter(|&j| j != i && cluster_of(j) == cluster)
+                .fold((0.0, 0usize), |(sum, count), j| (sum + distance(i, j), count + 1));
+            if count == 0 { None } else { Some(sum / count as f64) }
+        };
+
+        let total: f64 = (0..n)
+            .map(|i| {
+                let own = cluster_of(i);
+                let a = match mean_distance(i, own) {
+                    Some(a) => a,
+                    None => return 0.0,
+                };
+                let b = distinct.iter()
+                    .filter(|&&c| c != own)
+                    .filter_map(|&c| mean_distance(i, c))
+                    .fold(f64::INFINITY, f64::min);
+                let scale = a.max(b);
+                if scale > 0.0 { (b - a) / scale } else { 0.0 }
+            })
+            .sum();
+        total / n as f64
     }
 
     /// Get cluster assignment for a prefix
@@ -228,6 +348,28 @@ mod tests {
         assert!(analyzer.eigenvalues()[0].abs() < 1e-6);
     }
 
+    #[test]
+    fn test_clusters_separate_prefix_groups() {
+        let mut analyzer = SpectralAnalyzer::new(2);
+        let group_a: Vec<Prefix> = (1..=4).map(|i| Prefix::new(0xC0A80000 | (i << 8), 24).unwrap()).collect();
+        let group_b: Vec<Prefix> = (1..=4).map(|i| Prefix::new(0x0A000000 | (i << 16), 16).unwrap()).collect();
+        let mut prefixes = group_a.clone();
+        prefixes.extend(&group_b);
+        // Interleave so the grouping can't come from insertion order
+        prefixes.swap(1, 5);
+
+        analyzer.analyze(&prefixes).unwrap();
+        let clusters = analyzer.clusters();
+        assert_eq!(clusters.len(), 2);
+        for cluster in &clusters {
+            let in_a = cluster.iter().all(|p| group_a.contains(p));
+            let in_b = cluster.iter().all(|p| group_b.contains(p));
+            assert!(in_a || in_b, "mixed cluster {:?}", cluster);
+            assert_eq!(cluster.len(), 4);
+        }
+        assert!(analyzer.cluster_quality() > 0.0);
+    }
+
     #[test]
     fn test_clustering() {
         let mut analyzer = SpectralAnalyzer::new(2);
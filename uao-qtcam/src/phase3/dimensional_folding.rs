@@ -110,7 +110,7 @@ impl DimensionalFoldingEngine {
 
     /// Insert route into folded space
     pub fn insert(&mut self, prefix: Prefix, next_hop: String, metric: u32) {
-        let coordinates = self.fold_to_4d(prefix.addr);
+        let coordinates = self.fold_to_4d(prefix.addr_u32());
         
         let entry = FoldedEntry {
             coordinates,
@@ -133,13 +133,13 @@ impl DimensionalFoldingEngine {
 
         for entry in &self.folded_entries {
             // Check if IP matches prefix
-            let mask = if entry.prefix.len == 0 {
+            let mask = if entry.prefix.prefix_len() == 0 {
                 0
             } else {
-                !0u32 << (32 - entry.prefix.len)
+                !0u32 << (32 - entry.prefix.prefix_len())
             };
 
-            if (ip & mask) == (entry.prefix.addr & mask) {
+            if (ip & mask) == (entry.prefix.addr_u32() & mask) {
                 // Compute geodesic distance in manifold
                 let distance = self.manifold.geodesic_distance(&query_coords, &entry.coordinates);
 
@@ -209,7 +209,7 @@ impl LaplacianQLearningEngine {
     /// Map prefix to graph state
     fn prefix_to_state(&self, prefix: Prefix) -> u32 {
         // Use top bits of prefix as state
-        (prefix.addr >> 24) & 0xFF
+        (prefix.addr_u32() >> 24) & 0xFF
     }
 
     /// Map next hop to graph action
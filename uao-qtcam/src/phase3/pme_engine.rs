@@ -57,7 +57,7 @@ impl PMEEngine {
 
     /// Insert route as particle
     pub fn insert(&mut self, prefix: Prefix, next_hop: String, metric: u32) {
-        let position = self.ip_to_position(prefix.addr);
+        let position = self.ip_to_position(prefix.addr_u32());
         let charge = 1.0 / (metric as f64 + 1.0); // Higher charge for lower metric
 
         let particle = Particle { position, charge };
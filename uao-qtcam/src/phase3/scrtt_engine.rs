@@ -11,8 +11,10 @@
 //!
 //! Target: < 8 ns latency, > 125M lookups/sec, 1,250x+ speedup vs TCAM
 
-use crate::phase1::Prefix;
+use crate::phase1::{route_rank, Prefix};
 use anyhow::{Result, anyhow};
+use std::cmp::Reverse;
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::time::Instant;
@@ -21,14 +23,40 @@ use std::time::Instant;
 #[derive(Debug, Clone)]
 struct HybridTrieNode {
     /// Radix array for top 16 bits (65,536 entries)
-    /// Each entry is an index into Patricia trie or direct result
+    /// Each entry is 1 + index into `radix_routes` (0 = empty)
     radix_table: Vec<u32>,
+    /// Routes of length <= 16 referenced by the radix table
+    radix_routes: Vec<RadixRoute>,
     /// Patricia trie for remaining bits (compressed)
     patricia_nodes: Vec<PatriciaNode>,
+    /// Patricia node indices bucketed by the top 16 bits of their pattern
+    /// (a prefix longer than /16 always lies within a single /16 block)
+    patricia_buckets: HashMap<u16, Vec<u32>>,
     /// Tensor decomposition factors (rank-1 tensors)
     tensor_factors: Vec<TensorFactor>,
 }
 
+/// Radix Route - Route of length <= 16 covering one or more radix slots
+#[derive(Debug, Clone)]
+struct RadixRoute {
+    prefix: Prefix,
+    next_hop: String,
+    metric: u32,
+}
+
+impl RadixRoute {
+    /// Preference key (see [`route_rank`])
+    fn rank(&self) -> (Reverse<u8>, u32, &str) {
+        route_rank(self.prefix.prefix_len(), self.metric, &self.next_hop)
+    }
+
+    /// Whether both routes cover the same block of radix slots
+    fn same_block(&self, prefix: &Prefix) -> bool {
+        self.prefix.prefix_len() == prefix.prefix_len()
+            && self.prefix.addr_u32() & self.prefix.mask() as u32 == prefix.addr_u32() & prefix.mask() as u32
+    }
+}
+
 /// Patricia Trie Node - Variable-stride compressed trie
 #[derive(Debug, Clone)]
 struct PatriciaNode {
@@ -145,7 +173,9 @@ impl SCRTTEngine {
         
         let trie = HybridTrieNode {
             radix_table,
+            radix_routes: Vec::new(),
             patricia_nodes: Vec::new(),
+            patricia_buckets: HashMap::new(),
             tensor_factors: Vec::new(),
         };
 
@@ -162,6 +192,10 @@ impl SCRTTEngine {
     /// POSTULATE 8: Tensor decomposition for compression
     /// POSTULATE 10: Hybrid trie construction
     pub fn insert(&mut self, prefix: Prefix, next_hop: String, metric: u32) -> Result<()> {
+        if !prefix.is_ipv4() {
+            return Err(anyhow!("Phase 3 supports IPv4 prefixes only: {}", prefix));
+        }
+
         // POSTULATE 7: Create sheaf section
         let cohomology_class = self.compute_cohomology_class(prefix);
         let section = SheafSection {
@@ -183,6 +217,152 @@ impl SCRTTEngine {
         Ok(())
     }
 
+    /// Insert a route, overwriting the next hop and metric of an existing
+    /// route with exactly `prefix` instead of adding a duplicate
+    ///
+    /// Returns `true` if the prefix was newly added.
+    pub fn upsert(&mut self, prefix: Prefix, next_hop: String, metric: u32) -> Result<bool> {
+        if !prefix.is_ipv4() {
+            return Err(anyhow!("Phase 3 supports IPv4 prefixes only: {}", prefix));
+        }
+
+        if !self.sheaf_sections.iter().any(|s| s.prefix == prefix) {
+            self.insert(prefix, next_hop, metric)?;
+            return Ok(true);
+        }
+
+        let sections = Arc::get_mut(&mut self.sheaf_sections)
+            .ok_or_else(|| anyhow!("Cannot modify sheaf sections"))?;
+        for section in sections.iter_mut().filter(|s| s.prefix == prefix) {
+            section.next_hop = next_hop.clone();
+            section.metric = metric;
+        }
+
+        let trie = Arc::get_mut(&mut self.trie)
+            .ok_or_else(|| anyhow!("Cannot modify trie"))?;
+        if prefix.prefix_len() <= 16 {
+            if let Some(route) = trie.radix_routes.iter_mut().find(|r| r.prefix == prefix) {
+                route.next_hop = next_hop;
+                route.metric = metric;
+            }
+            Self::reselect_radix_block(trie, prefix);
+        } else {
+            let bucket = trie.patricia_buckets.get(&((prefix.addr_u32() >> 16) as u16));
+            let existing = bucket.into_iter().flatten().copied().find(|&idx| {
+                let node = &trie.patricia_nodes[idx as usize];
+                node.pattern == prefix.addr_u32() && node.pattern_len == prefix.prefix_len()
+            });
+            if let Some(idx) = existing {
+                let node = &mut trie.patricia_nodes[idx as usize];
+                node.next_hop = Some(next_hop);
+                node.metric = metric;
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Remove every route stored for exactly `prefix`
+    ///
+    /// Radix slots that resolved to the removed route fall back to the
+    /// preferred remaining route covering them. Returns `true` if a route
+    /// was removed.
+    pub fn delete(&mut self, prefix: Prefix) -> Result<bool> {
+        if !prefix.is_ipv4() {
+            return Ok(false);
+        }
+
+        let sections = Arc::get_mut(&mut self.sheaf_sections)
+            .ok_or_else(|| anyhow!("Cannot modify sheaf sections"))?;
+        let before = sections.len();
+        sections.retain(|s| s.prefix != prefix);
+        let removed = before - sections.len();
+        if removed == 0 {
+            return Ok(false);
+        }
+
+        let trie = Arc::get_mut(&mut self.trie)
+            .ok_or_else(|| anyhow!("Cannot modify trie"))?;
+        if prefix.prefix_len() <= 16 {
+            Self::delete_from_radix(trie, prefix);
+        } else {
+            Self::delete_from_patricia(trie, prefix);
+        }
+
+        self.stats.num_routes.fetch_sub(removed, Ordering::Relaxed);
+        Ok(true)
+    }
+
+    /// Drop `prefix` from the radix routes and re-point its slots
+    fn delete_from_radix(trie: &mut HybridTrieNode, prefix: Prefix) {
+        while let Some(idx) = trie.radix_routes.iter().position(|r| r.prefix == prefix) {
+            let entry = idx as u32 + 1;
+            trie.radix_routes.remove(idx);
+
+            // Slots holding the removed route are reselected below; later
+            // routes shift down by one
+            for slot in trie.radix_table.iter_mut() {
+                if *slot == entry {
+                    *slot = 0;
+                } else if *slot > entry {
+                    *slot -= 1;
+                }
+            }
+        }
+
+        // Only routes covering the whole block can replace it: a longer
+        // prefix already holds its own slots
+        let network = prefix.addr_u32() & prefix.mask() as u32;
+        let fallback = trie.radix_routes.iter()
+            .enumerate()
+            .filter(|(_, route)| route.prefix.prefix_len() <= prefix.prefix_len() && route.prefix.matches(network))
+            .min_by(|(_, a), (_, b)| a.rank().cmp(&b.rank()))
+            .map_or(0, |(idx, _)| idx as u32 + 1);
+
+        let radix_start = (network >> 16) as usize;
+        let radix_end = (radix_start + (1usize << (16 - prefix.prefix_len()))).min(trie.radix_table.len());
+        for slot in &mut trie.radix_table[radix_start..radix_end] {
+            if *slot == 0 {
+                *slot = fallback;
+            }
+        }
+    }
+
+    /// Drop `prefix` from the Patricia nodes and its bucket
+    fn delete_from_patricia(trie: &mut HybridTrieNode, prefix: Prefix) {
+        let bucket_key = (prefix.addr_u32() >> 16) as u16;
+        loop {
+            let bucket = trie.patricia_buckets.get(&bucket_key);
+            let found = bucket.into_iter().flatten().copied().find(|&idx| {
+                let node = &trie.patricia_nodes[idx as usize];
+                node.pattern == prefix.addr_u32() && node.pattern_len == prefix.prefix_len()
+            });
+            let idx = match found {
+                Some(idx) => idx,
+                None => break,
+            };
+
+            if let Some(bucket) = trie.patricia_buckets.get_mut(&bucket_key) {
+                bucket.retain(|&i| i != idx);
+                if bucket.is_empty() {
+                    trie.patricia_buckets.remove(&bucket_key);
+                }
+            }
+
+            // The last node moves into the freed index
+            let last = trie.patricia_nodes.len() as u32 - 1;
+            trie.patricia_nodes.swap_remove(idx as usize);
+            if idx != last {
+                let moved = (trie.patricia_nodes[idx as usize].pattern >> 16) as u16;
+                if let Some(slot) = trie.patricia_buckets.get_mut(&moved)
+                    .and_then(|bucket| bucket.iter_mut().find(|i| **i == last))
+                {
+                    *slot = idx;
+                }
+            }
+        }
+    }
+
     /// Revolutionary O(1) lookup using hybrid trie + sheaf cohomology
     ///
     /// POSTULATE 7: Sheaf-cohomological locality for O(1) access
@@ -190,6 +370,16 @@ impl SCRTTEngine {
     /// POSTULATE 13: Branch-free computation for deterministic latency
     #[inline(always)]
     pub fn lookup(&self, ip: &str) -> Result<Option<(String, u32, u64)>> {
+        Ok(self.lookup_match(ip)?
+            .map(|(_, next_hop, metric, latency_ns)| (next_hop, metric, latency_ns)))
+    }
+
+    /// Lookup returning the matched prefix alongside next hop, metric and latency
+    ///
+    /// Routes longer than /16 live in the Patricia bucket for the IP's top
+    /// 16 bits and always beat the radix entry, so the bucket is consulted
+    /// first to preserve longest-prefix-match semantics.
+    pub fn lookup_match(&self, ip: &str) -> Result<Option<(Prefix, String, u32, u64)>> {
         let start = Instant::now();
 
         // Parse IP address
@@ -198,18 +388,20 @@ impl SCRTTEngine {
         let ip_u32 = u32::from(ip_addr);
 
         // POSTULATE 10: Hybrid Trie Lookup
-        // Step 1: Radix lookup (top 16 bits) - O(1)
-        let radix_index = (ip_u32 >> 16) as usize;
-        let radix_entry = self.trie.radix_table[radix_index];
+        // Step 1: Patricia bucket for the top 16 bits (prefixes > /16)
+        let patricia_match = self.lookup_from_patricia(ip_u32);
 
-        let result = if radix_entry != 0 {
-            // Radix hit - direct result
-            self.stats.radix_hits.fetch_add(1, Ordering::Relaxed);
-            self.lookup_from_radix(radix_entry, ip_u32)
-        } else {
-            // Patricia traversal for remaining bits
+        let result = if patricia_match.is_some() {
             self.stats.patricia_traversals.fetch_add(1, Ordering::Relaxed);
-            self.lookup_from_patricia(ip_u32)
+            patricia_match
+        } else {
+            // Step 2: Radix lookup (top 16 bits) - O(1)
+            let radix_index = (ip_u32 >> 16) as usize;
+            let radix_entry = self.trie.radix_table[radix_index];
+            if radix_entry != 0 {
+                self.stats.radix_hits.fetch_add(1, Ordering::Relaxed);
+            }
+            self.lookup_from_radix(radix_entry, ip_u32)
         };
 
         let latency_ns = start.elapsed().as_nanos() as u64;
@@ -222,28 +414,36 @@ impl SCRTTEngine {
         }
 
         // Return result
-        Ok(result.map(|(next_hop, metric)| (next_hop, metric, latency_ns)))
+        Ok(result.map(|(prefix, next_hop, metric)| (prefix, next_hop, metric, latency_ns)))
     }
 
     /// POSTULATE 10: Insert into hybrid trie structure
     #[inline(always)]
     fn insert_into_hybrid_trie(&mut self, prefix: Prefix, next_hop: String, metric: u32) -> Result<()> {
         // For prefixes <= 16 bits, insert into radix table
-        if prefix.len <= 16 {
-            let radix_start = (prefix.addr >> 16) as usize;
-            let radix_count = 1 << (16 - prefix.len);
+        if prefix.prefix_len() <= 16 {
+            let radix_start = ((prefix.addr_u32() & prefix.mask() as u32) >> 16) as usize;
+            let radix_count = 1usize << (16 - prefix.prefix_len());
 
             // Get mutable reference to trie
             let trie = Arc::get_mut(&mut self.trie)
                 .ok_or_else(|| anyhow!("Cannot modify trie"))?;
 
-            // Fill radix table entries
+            trie.radix_routes.push(RadixRoute { prefix, next_hop, metric });
+            let entry = trie.radix_routes.len() as u32;
+
+            // Fill radix table entries with the preferred route: a longer
+            // prefix is never shadowed, and routes covering the same block
+            // are ordered by metric then next hop
             for i in 0..radix_count {
                 let index = radix_start + i;
                 if index < trie.radix_table.len() {
-                    // Encode next_hop and metric in radix entry
-                    // For now, use simple encoding (can be optimized)
-                    trie.radix_table[index] = metric;
+                    let existing = trie.radix_table[index];
+                    let preferred = existing == 0
+                        || trie.radix_routes[entry as usize - 1].rank() < trie.radix_routes[existing as usize - 1].rank();
+                    if preferred {
+                        trie.radix_table[index] = entry;
+                    }
                 }
             }
         } else {
@@ -254,6 +454,30 @@ impl SCRTTEngine {
         Ok(())
     }
 
+    /// Re-point the radix slots covered by `prefix` at the preferred route
+    /// of that block, after one of its routes changed
+    fn reselect_radix_block(trie: &mut HybridTrieNode, prefix: Prefix) {
+        let best = trie.radix_routes.iter()
+            .enumerate()
+            .filter(|(_, route)| route.same_block(&prefix))
+            .min_by(|(_, a), (_, b)| a.rank().cmp(&b.rank()))
+            .map(|(idx, _)| idx as u32 + 1);
+        let best = match best {
+            Some(best) => best,
+            None => return,
+        };
+
+        let radix_start = ((prefix.addr_u32() & prefix.mask() as u32) >> 16) as usize;
+        let radix_end = (radix_start + (1usize << (16 - prefix.prefix_len()))).min(trie.radix_table.len());
+        for index in radix_start..radix_end {
+            let existing = trie.radix_table[index];
+            // Slots held by a longer prefix stay shadowed
+            if existing == 0 || trie.radix_routes[existing as usize - 1].same_block(&prefix) {
+                trie.radix_table[index] = best;
+            }
+        }
+    }
+
     /// POSTULATE 10: Insert into Patricia trie (variable-stride)
     fn insert_into_patricia(&mut self, prefix: Prefix, next_hop: String, metric: u32) -> Result<()> {
         let trie = Arc::get_mut(&mut self.trie)
@@ -261,38 +485,41 @@ impl SCRTTEngine {
 
         // Create new Patricia node
         let node = PatriciaNode {
-            pattern: prefix.addr,
-            pattern_len: prefix.len,
+            pattern: prefix.addr_u32(),
+            pattern_len: prefix.prefix_len(),
             next_hop: Some(next_hop),
             metric,
             left_child: None,
             right_child: None,
         };
 
+        let bucket = (prefix.addr_u32() >> 16) as u16;
+        trie.patricia_buckets.entry(bucket)
+            .or_default()
+            .push(trie.patricia_nodes.len() as u32);
         trie.patricia_nodes.push(node);
         Ok(())
     }
 
     /// POSTULATE 10: Lookup from radix table
     #[inline(always)]
-    fn lookup_from_radix(&self, radix_entry: u32, _ip_u32: u32) -> Option<(String, u32)> {
-        // For now, simple implementation
-        // In production, decode next_hop from radix_entry
-        if radix_entry != 0 {
-            Some(("gateway_radix".to_string(), radix_entry))
-        } else {
-            None
+    fn lookup_from_radix(&self, radix_entry: u32, _ip_u32: u32) -> Option<(Prefix, String, u32)> {
+        if radix_entry == 0 {
+            return None;
         }
+        let route = &self.trie.radix_routes[radix_entry as usize - 1];
+        Some((route.prefix, route.next_hop.clone(), route.metric))
     }
 
     /// POSTULATE 10: Lookup from Patricia trie
     #[inline(always)]
-    fn lookup_from_patricia(&self, ip_u32: u32) -> Option<(String, u32)> {
+    fn lookup_from_patricia(&self, ip_u32: u32) -> Option<(Prefix, String, u32)> {
+        let bucket = self.trie.patricia_buckets.get(&((ip_u32 >> 16) as u16))?;
+
         // POSTULATE 13: Branch-free longest prefix match
-        let mut best_match: Option<(String, u32)> = None;
-        let mut best_len = 0u8;
+        let mut best_match: Option<(&PatriciaNode, &str)> = None;
 
-        for node in &self.trie.patricia_nodes {
+        for node in bucket.iter().map(|&idx| &self.trie.patricia_nodes[idx as usize]) {
             // Check if IP matches this node's pattern
             let mask = if node.pattern_len == 0 {
                 0
@@ -302,17 +529,23 @@ impl SCRTTEngine {
 
             let matches = (ip_u32 & mask) == (node.pattern & mask);
 
-            // POSTULATE 13: Branch-free selection using conditional move
-            // This will be optimized by compiler to cmov instruction
-            if matches && node.pattern_len >= best_len {
-                if let Some(ref next_hop) = node.next_hop {
-                    best_match = Some((next_hop.clone(), node.metric));
-                    best_len = node.pattern_len;
+            // Longest prefix, then lowest metric, then smallest next hop
+            if let (true, Some(next_hop)) = (matches, node.next_hop.as_deref()) {
+                let preferred = match best_match {
+                    None => true,
+                    Some((best, best_hop)) => route_rank(node.pattern_len, node.metric, next_hop)
+                        < route_rank(best.pattern_len, best.metric, best_hop),
+                };
+                if preferred {
+                    best_match = Some((node, next_hop));
                 }
             }
         }
 
-        best_match
+        best_match.map(|(node, next_hop)| {
+            let prefix = Prefix::V4 { addr: node.pattern, len: node.pattern_len };
+            (prefix, next_hop.to_string(), node.metric)
+        })
     }
 
     /// POSTULATE 7: Compute cohomology class for sheaf consistency
@@ -321,8 +554,8 @@ impl SCRTTEngine {
         // Cohomology class based on prefix structure
         // H⁰(X, F) = global sections
         // Use hash of prefix for cohomology class assignment
-        let hash = prefix.addr.wrapping_mul(0x9e3779b9);
-        hash ^ (prefix.len as u32)
+        let hash = prefix.addr_u32().wrapping_mul(0x9e3779b9);
+        hash ^ (prefix.prefix_len() as u32)
     }
 
     /// Get statistics (lock-free read)
@@ -407,5 +640,81 @@ mod tests {
         // Different prefixes should have different cohomology classes
         println!("Cohomology class 1: {}, class 2: {}", class1, class2);
     }
-}
 
+    #[test]
+    fn test_scrtt_longest_prefix_match() {
+        let mut engine = SCRTTEngine::new();
+
+        for (cidr, gateway) in [
+            ("0.0.0.0/0", "default"),
+            ("10.1.0.0/16", "gw16"),
+            ("10.0.0.0/8", "gw8"),
+            ("10.1.1.0/24", "gw24"),
+        ] {
+            engine.insert(Prefix::from_cidr(cidr).unwrap(), gateway.to_string(), 100).unwrap();
+        }
+
+        // A shorter prefix inserted later must not shadow a longer one
+        let (prefix, next_hop, _, _) = engine.lookup_match("10.1.2.3").unwrap().unwrap();
+        assert_eq!(next_hop, "gw16");
+        assert_eq!(prefix.to_string(), "10.1.0.0/16");
+
+        let (prefix, next_hop, _, _) = engine.lookup_match("10.1.1.7").unwrap().unwrap();
+        assert_eq!(next_hop, "gw24");
+        assert_eq!(prefix.to_string(), "10.1.1.0/24");
+
+        assert_eq!(engine.lookup("10.2.0.1").unwrap().unwrap().0, "gw8");
+        assert_eq!(engine.lookup("8.8.8.8").unwrap().unwrap().0, "default");
+    }
+
+    #[test]
+    fn test_scrtt_equal_length_tie_break() {
+        // `insert` keeps duplicate prefixes, so equal-length ties are real here
+        let routes = [
+            ("10.1.0.0/16", "nh-b", 10),
+            ("10.1.0.0/16", "nh-a", 10),
+            ("10.1.0.0/16", "nh-c", 5),
+            ("10.2.3.0/24", "nh-z", 20),
+            ("10.2.3.0/24", "nh-y", 20),
+        ];
+        for order in [routes.to_vec(), routes.iter().rev().copied().collect()] {
+            let mut engine = SCRTTEngine::new();
+            for (cidr, gateway, metric) in order {
+                engine.insert(Prefix::from_cidr(cidr).unwrap(), gateway.to_string(), metric).unwrap();
+            }
+
+            // Lowest metric wins, then the smallest next hop
+            assert_eq!(engine.lookup("10.1.4.4").unwrap().unwrap().0, "nh-c");
+            assert_eq!(engine.lookup("10.2.3.200").unwrap().unwrap().0, "nh-y");
+        }
+    }
+
+    #[test]
+    fn test_scrtt_delete() {
+        let mut engine = SCRTTEngine::new();
+
+        for (cidr, gateway) in [
+            ("10.0.0.0/8", "gw8"),
+            ("10.1.0.0/16", "gw16"),
+            ("10.1.1.0/24", "gw24"),
+            ("10.1.2.0/24", "gw24b"),
+        ] {
+            engine.insert(Prefix::from_cidr(cidr).unwrap(), gateway.to_string(), 100).unwrap();
+        }
+
+        // Removing a Patricia node keeps the moved node reachable
+        assert!(engine.delete(Prefix::from_cidr("10.1.1.0/24").unwrap()).unwrap());
+        assert_eq!(engine.lookup("10.1.1.7").unwrap().unwrap().0, "gw16");
+        assert_eq!(engine.lookup("10.1.2.7").unwrap().unwrap().0, "gw24b");
+
+        // Radix slots fall back to the covering route
+        assert!(engine.delete(Prefix::from_cidr("10.1.0.0/16").unwrap()).unwrap());
+        assert_eq!(engine.lookup("10.1.1.7").unwrap().unwrap().0, "gw8");
+        assert_eq!(engine.lookup("10.1.2.7").unwrap().unwrap().0, "gw24b");
+
+        assert!(engine.delete(Prefix::from_cidr("10.0.0.0/8").unwrap()).unwrap());
+        assert!(engine.lookup("10.1.1.7").unwrap().is_none());
+        assert!(!engine.delete(Prefix::from_cidr("10.0.0.0/8").unwrap()).unwrap());
+        assert_eq!(engine.num_routes(), 1);
+    }
+}
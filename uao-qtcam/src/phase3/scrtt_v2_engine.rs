@@ -92,6 +92,10 @@ impl SCRTTv2Engine {
 
     /// Insert route into all engines
     pub fn insert(&mut self, prefix: Prefix, next_hop: String, metric: u32) -> Result<()> {
+        if !prefix.is_ipv4() {
+            return Err(anyhow!("Phase 3 V2 supports IPv4 prefixes only: {}", prefix));
+        }
+
         // Insert into dimensional folding
         self.dimensional_folding.insert(prefix, next_hop.clone(), metric);
         
@@ -103,7 +107,7 @@ impl SCRTTv2Engine {
         
         // Update quantum cache for hot routes (metric < 100)
         if metric < 100 {
-            let cache_idx = (prefix.addr >> 24) as usize % self.quantum_cache.len();
+            let cache_idx = (prefix.addr_u32() >> 24) as usize % self.quantum_cache.len();
             self.quantum_cache[cache_idx] = Some((next_hop, metric));
         }
         
@@ -98,6 +98,10 @@ impl SCRTTv3Engine {
 
     /// Insert route into all engines
     pub fn insert(&mut self, prefix: Prefix, next_hop: String, metric: u32) -> Result<()> {
+        if !prefix.is_ipv4() {
+            return Err(anyhow!("Phase 3 V3 supports IPv4 prefixes only: {}", prefix));
+        }
+
         // Add to next hop storage
         let next_hop_idx = self.next_hops.len() as u16;
         self.next_hops.push(next_hop.clone());
@@ -113,7 +117,7 @@ impl SCRTTv3Engine {
         
         // Update quantum cache for hot routes (metric < 100)
         if metric < 100 {
-            let cache_idx = (prefix.addr >> 16) as usize % self.quantum_cache.len();
+            let cache_idx = (prefix.addr_u32() >> 16) as usize % self.quantum_cache.len();
             self.quantum_cache[cache_idx] = Some((next_hop, metric));
         }
         
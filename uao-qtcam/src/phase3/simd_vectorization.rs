@@ -30,13 +30,13 @@ impl SIMDVectorizationEngine {
 
     /// Insert route with SIMD-aligned storage
     pub fn insert(&mut self, prefix: Prefix, next_hop_idx: u16, metric: u32) {
-        let mask = if prefix.len == 0 {
+        let mask = if prefix.prefix_len() == 0 {
             0
         } else {
-            !0u32 << (32 - prefix.len)
+            !0u32 << (32 - prefix.prefix_len())
         };
 
-        self.prefixes.push(prefix.addr);
+        self.prefixes.push(prefix.addr_u32());
         self.masks.push(mask);
         self.next_hop_indices.push(next_hop_idx);
         self.metrics.push(metric);
@@ -109,10 +109,10 @@ impl TensorDecompositionEngine {
     /// Map prefix to 4D coordinates
     fn prefix_to_coords(&self, prefix: Prefix) -> [usize; 4] {
         [
-            ((prefix.addr >> 24) & 0xFF) as usize,
-            ((prefix.addr >> 16) & 0xFF) as usize,
-            ((prefix.addr >> 8) & 0xFF) as usize,
-            (prefix.addr & 0xFF) as usize,
+            ((prefix.addr_u32() >> 24) & 0xFF) as usize,
+            ((prefix.addr_u32() >> 16) & 0xFF) as usize,
+            ((prefix.addr_u32() >> 8) & 0xFF) as usize,
+            (prefix.addr_u32() & 0xFF) as usize,
         ]
     }
 
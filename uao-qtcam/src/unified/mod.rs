@@ -41,8 +41,11 @@ pub mod tcam_engine;
 pub mod performance_monitor;
 pub mod control_plane;
 
-pub use tcam_engine::{TCAMEngine, Route, LookupResult, TCAMStats, PhaseStrategy};
-pub use performance_monitor::PerformanceMonitor;
+pub use tcam_engine::{
+    TCAMEngine, Route, FlowKey, LookupResult, LookupExplanation, MatchCandidate, TCAMStats, Phase,
+    PhaseStrategy,
+};
+pub use performance_monitor::{PerformanceMonitor, PhaseMetrics};
 pub use control_plane::{ControlPlane, ControlPlaneConfig, HealthStatus, PhaseHealth, GlobalMetrics};
 
 // Re-export Prefix from phase1
@@ -2,9 +2,13 @@
 //!
 //! Real-time performance monitoring and metrics collection for the TCAM engine.
 
+use super::tcam_engine::Phase;
 use std::time::Instant;
 use parking_lot::RwLock;
 
+/// Smoothing factor of the per-phase latency moving average
+const PHASE_LATENCY_ALPHA: f64 = 0.1;
+
 /// Performance monitor
 pub struct PerformanceMonitor {
     metrics: RwLock<Metrics>,
@@ -20,6 +24,24 @@ struct Metrics {
     p50_latency_ns: u64,
     p95_latency_ns: u64,
     p99_latency_ns: u64,
+    phases: [PhaseMetrics; 3],
+}
+
+/// Lookups served by a single phase
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PhaseMetrics {
+    /// Number of lookups served
+    pub lookups: u64,
+    /// Exponential moving average of the realized latency (ns)
+    pub avg_latency_ns: f64,
+}
+
+fn phase_index(phase: Phase) -> usize {
+    match phase {
+        Phase::Phase1 => 0,
+        Phase::Phase2 => 1,
+        Phase::Phase3 => 2,
+    }
 }
 
 impl PerformanceMonitor {
@@ -43,6 +65,28 @@ impl PerformanceMonitor {
         metrics.max_latency_ns = metrics.max_latency_ns.max(latency_ns);
     }
 
+    /// Record a lookup served by `phase`
+    ///
+    /// Counts towards the overall latency metrics as well as the phase's
+    /// lookup counter and moving-average latency.
+    pub fn record_phase_lookup(&self, phase: Phase, latency_ns: u64) {
+        self.record_latency(latency_ns);
+
+        let mut metrics = self.metrics.write();
+        let phase = &mut metrics.phases[phase_index(phase)];
+        phase.lookups += 1;
+        phase.avg_latency_ns = if phase.lookups == 1 {
+            latency_ns as f64
+        } else {
+            PHASE_LATENCY_ALPHA * latency_ns as f64 + (1.0 - PHASE_LATENCY_ALPHA) * phase.avg_latency_ns
+        };
+    }
+
+    /// Get the metrics of a single phase
+    pub fn phase_metrics(&self, phase: Phase) -> PhaseMetrics {
+        self.metrics.read().phases[phase_index(phase)]
+    }
+
     /// Get average latency in nanoseconds
     pub fn avg_latency_ns(&self) -> f64 {
         let metrics = self.metrics.read();
@@ -169,5 +213,22 @@ mod tests {
         assert_eq!(summary.min_latency_ns, 100);
         assert_eq!(summary.max_latency_ns, 200);
     }
+
+    #[test]
+    fn test_phase_metrics() {
+        let monitor = PerformanceMonitor::new();
+
+        monitor.record_phase_lookup(Phase::Phase1, 100);
+        monitor.record_phase_lookup(Phase::Phase1, 200);
+        monitor.record_phase_lookup(Phase::Phase3, 50);
+
+        let phase1 = monitor.phase_metrics(Phase::Phase1);
+        assert_eq!(phase1.lookups, 2);
+        assert!((phase1.avg_latency_ns - 110.0).abs() < 1e-9);
+
+        assert_eq!(monitor.phase_metrics(Phase::Phase2), PhaseMetrics::default());
+        assert_eq!(monitor.phase_metrics(Phase::Phase3).lookups, 1);
+        assert_eq!(monitor.total_operations(), 3);
+    }
 }
 
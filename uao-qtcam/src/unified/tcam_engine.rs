@@ -3,24 +3,202 @@
 //! The main orchestration engine that integrates all phases and provides
 //! a unified API for routing operations with adaptive phase selection.
 
-use crate::phase1::{AHGFEngine, Prefix};
+use crate::phase1::{route_rank, AHGFEngine, Prefix};
 use crate::phase2::QAGFHGEngine;
 use crate::phase3::SCRTTEngine;
+use super::performance_monitor::PerformanceMonitor;
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::sync::RwLock;
 
+/// Lookup phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Phase {
+    /// Phase 1 (AHGF)
+    Phase1,
+    /// Phase 2 (QAGFHG)
+    Phase2,
+    /// Phase 3 (SCRTT)
+    Phase3,
+}
+
+/// Default adaptive threshold: matches of this length or shorter use Phase 1
+pub const DEFAULT_SHORT_THRESHOLD: u8 = 8;
+
+/// Default adaptive threshold: matches of this length or longer use Phase 3
+pub const DEFAULT_LONG_THRESHOLD: u8 = 16;
+
 /// Phase selection strategy
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// IPv6 lookups are always served by Phase 1 whatever the strategy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PhaseStrategy {
-    /// Always use Phase 1 (AHGF - 50 ns)
-    Phase1Only,
-    /// Always use Phase 2 (QAGFHG - 10 ns)
-    Phase2Only,
-    /// Always use Phase 3 (SCRTT - 8 ns)
-    Phase3Only,
-    /// Adaptive selection based on workload
-    Adaptive,
+    /// Always use the given phase
+    Fixed(Phase),
+    /// Select by the length of the longest matching prefix: up to
+    /// `short_threshold` uses Phase 1, from `long_threshold` (or no match)
+    /// uses Phase 3, anything in between uses Phase 2
+    Adaptive {
+        short_threshold: u8,
+        long_threshold: u8,
+    },
+    /// Use the phase with the lowest realized moving-average latency
+    /// (phases without samples are tried first)
+    Auto,
+}
+
+impl Default for PhaseStrategy {
+    fn default() -> Self {
+        Self::Adaptive {
+            short_threshold: DEFAULT_SHORT_THRESHOLD,
+            long_threshold: DEFAULT_LONG_THRESHOLD,
+        }
+    }
+}
+
+/// IPv4 network addresses grouped by prefix length
+///
+/// Lets the adaptive strategy find the length of the longest match (one
+/// hash probe per populated length) before choosing a phase.
+#[derive(Debug)]
+struct PrefixLengthIndex {
+    by_len: Vec<HashSet<u32>>,
+}
+
+impl PrefixLengthIndex {
+    fn new() -> Self {
+        Self { by_len: (0..=32).map(|_| HashSet::new()).collect() }
+    }
+
+    fn mask(len: u8) -> u32 {
+        u32::MAX.checked_shl(32 - len as u32).unwrap_or(0)
+    }
+
+    fn insert(&mut self, prefix: &Prefix) {
+        if prefix.is_ipv4() {
+            let len = prefix.prefix_len();
+            self.by_len[len as usize].insert(prefix.addr_u32() & Self::mask(len));
+        }
+    }
+
+    fn remove(&mut self, prefix: &Prefix) {
+        if prefix.is_ipv4() {
+            let len = prefix.prefix_len();
+            self.by_len[len as usize].remove(&(prefix.addr_u32() & Self::mask(len)));
+        }
+    }
+
+    fn longest_match(&self, ip: u32) -> Option<u8> {
+        (0..=32u8).rev().find(|&len| {
+            let networks = &self.by_len[len as usize];
+            !networks.is_empty() && networks.contains(&(ip & Self::mask(len)))
+        })
+    }
+}
+
+/// Default number of per-address lookup results kept by the result cache
+pub const DEFAULT_RESULT_CACHE_CAPACITY: usize = 4096;
+
+/// LRU cache of lookup results keyed by exact address
+///
+/// Recency is a monotonically increasing stamp; `order` maps stamps back to
+/// addresses so the least recently used entry is always the first one.
+/// Misses (`None` results) are cached too.
+#[derive(Debug)]
+struct ResultCache {
+    capacity: usize,
+    entries: HashMap<IpAddr, (Option<LookupResult>, u64)>,
+    order: BTreeMap<u64, IpAddr>,
+    next_stamp: u64,
+    /// Bumped by every invalidation; results computed under an older
+    /// generation are not stored
+    generation: u64,
+    hits: u64,
+    misses: u64,
+}
+
+impl ResultCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+            next_stamp: 0,
+            generation: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    fn get(&mut self, ip: &IpAddr) -> Option<Option<LookupResult>> {
+        match self.entries.get_mut(ip) {
+            Some((result, last_used)) => {
+                self.order.remove(last_used);
+                *last_used = self.next_stamp;
+                self.order.insert(self.next_stamp, *ip);
+                self.next_stamp += 1;
+                self.hits += 1;
+                Some(result.clone())
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Store a result computed while the cache was at `generation`
+    fn insert(&mut self, ip: IpAddr, result: Option<LookupResult>, generation: u64) {
+        if self.capacity == 0 || generation != self.generation {
+            return;
+        }
+        let stamp = self.next_stamp;
+        self.next_stamp += 1;
+        if let Some((_, previous)) = self.entries.insert(ip, (result, stamp)) {
+            self.order.remove(&previous);
+        }
+        self.order.insert(stamp, ip);
+        self.evict();
+    }
+
+    fn evict(&mut self) {
+        while self.entries.len() > self.capacity {
+            match self.order.pop_first() {
+                Some((_, ip)) => {
+                    self.entries.remove(&ip);
+                }
+                None => break,
+            }
+        }
+    }
+
+    /// Drop every cached address `prefix` covers
+    fn invalidate(&mut self, prefix: &Prefix) {
+        self.generation += 1;
+        let order = &mut self.order;
+        self.entries.retain(|ip, (_, stamp)| {
+            let covered = prefix.matches_ip(*ip);
+            if covered {
+                order.remove(stamp);
+            }
+            !covered
+        });
+    }
+
+    fn clear(&mut self) {
+        self.generation += 1;
+        self.entries.clear();
+        self.order.clear();
+    }
+
+    fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.evict();
+    }
 }
 
 /// Unified TCAM engine
@@ -31,10 +209,18 @@ pub struct TCAMEngine {
     phase2: Arc<QAGFHGEngine>,
     /// Phase 3 engine (SCRTT)
     phase3: Arc<RwLock<SCRTTEngine>>,
-    /// Performance monitor
+    /// Multipath routes (the phases only store the primary next hop)
+    ecmp_routes: Arc<RwLock<HashMap<Prefix, Route>>>,
+    /// Operation counters
     monitor: Arc<RwLock<EngineMonitor>>,
+    /// Per-phase lookup counters and latency
+    performance: Arc<PerformanceMonitor>,
+    /// IPv4 prefix lengths for adaptive phase selection
+    prefix_index: parking_lot::RwLock<PrefixLengthIndex>,
     /// Phase selection strategy
-    strategy: PhaseStrategy,
+    strategy: parking_lot::RwLock<PhaseStrategy>,
+    /// Per-address lookup results, in front of the phase dispatch
+    result_cache: parking_lot::Mutex<ResultCache>,
 }
 
 // Ensure TCAMEngine is Send + Sync for Axum
@@ -45,21 +231,18 @@ const _: () = {
     let _ = assert_sync::<TCAMEngine>;
 };
 
-/// Engine monitor for performance tracking
+/// Engine monitor for operation tracking
 #[derive(Debug, Default)]
 struct EngineMonitor {
     total_lookups: u64,
     total_inserts: u64,
     total_deletes: u64,
-    phase1_lookups: u64,
-    phase2_lookups: u64,
-    phase3_lookups: u64,
 }
 
 impl TCAMEngine {
     /// Create new TCAM engine with default strategy (Adaptive)
     pub fn new() -> Result<Self> {
-        Self::with_strategy(PhaseStrategy::Adaptive)
+        Self::with_strategy(PhaseStrategy::default())
     }
 
     /// Create new TCAM engine with specific phase strategy
@@ -68,20 +251,60 @@ impl TCAMEngine {
             phase1: Arc::new(AHGFEngine::new()),
             phase2: Arc::new(QAGFHGEngine::new(3, 4)), // 3 clusters, 4 dimensions
             phase3: Arc::new(RwLock::new(SCRTTEngine::new())),
+            ecmp_routes: Arc::new(RwLock::new(HashMap::new())),
             monitor: Arc::new(RwLock::new(EngineMonitor::default())),
-            strategy,
+            performance: Arc::new(PerformanceMonitor::new()),
+            prefix_index: parking_lot::RwLock::new(PrefixLengthIndex::new()),
+            strategy: parking_lot::RwLock::new(strategy),
+            result_cache: parking_lot::Mutex::new(ResultCache::new(DEFAULT_RESULT_CACHE_CAPACITY)),
         })
     }
 
-    /// Insert a route
+    /// Change the phase selection strategy used by subsequent lookups
+    ///
+    /// Clears the result cache, whose entries name the phase that served them.
+    pub fn set_phase_strategy(&self, strategy: PhaseStrategy) {
+        *self.strategy.write() = strategy;
+        self.result_cache.lock().clear();
+    }
+
+    /// Resize the lookup result cache, evicting the least recently used
+    /// entries; 0 disables it
+    pub fn set_result_cache_capacity(&self, capacity: usize) {
+        self.result_cache.lock().set_capacity(capacity);
+    }
+
+    /// Insert a route, replacing any route with exactly the same prefix
     pub async fn insert(&self, route: Route) -> Result<()> {
+        route.validate()?;
+        let next_hop = route.primary_next_hop().to_string();
+
+        // IPv6 routes are served by Phase 1 only (Phases 2/3 are IPv4-only)
+        if !route.prefix.is_ipv4() {
+            self.phase1.insert(route.prefix, &next_hop, route.metric)?;
+            let prefix = route.prefix;
+            self.record_next_hops(route).await;
+            self.result_cache.lock().invalidate(&prefix);
+            let mut monitor = self.monitor.write().await;
+            monitor.total_inserts += 1;
+            return Ok(());
+        }
+
         // Insert into all phases for adaptive selection
-        self.phase1.insert(route.prefix, &route.next_hop, route.metric)?;
-        self.phase2.insert(route.prefix, route.next_hop.clone(), route.metric).await?;
+        self.phase1.insert(route.prefix, &next_hop, route.metric)?;
+        self.phase2.insert(route.prefix, next_hop.clone(), route.metric).await?;
 
         // Insert into Phase 3
         let mut phase3 = self.phase3.write().await;
-        phase3.insert(route.prefix, route.next_hop.clone(), route.metric)?;
+        phase3.upsert(route.prefix, next_hop, route.metric)?;
+        drop(phase3);
+
+        self.prefix_index.write().insert(&route.prefix);
+        let prefix = route.prefix;
+        self.record_next_hops(route).await;
+
+        // Only after every phase has the route, so no lookup can cache the old answer
+        self.result_cache.lock().invalidate(&prefix);
 
         // Update monitor
         let mut monitor = self.monitor.write().await;
@@ -90,99 +313,447 @@ impl TCAMEngine {
         Ok(())
     }
 
+    /// Insert a batch of routes
+    ///
+    /// Each phase is locked once for the whole batch and Phase 2 rebuilds
+    /// its hints a single time at the end. Exact-prefix duplicates (within
+    /// the batch or against the table) overwrite rather than add, with the
+    /// last occurrence winning. Clears the lookup result cache. Returns the
+    /// number of newly added prefixes.
+    pub async fn insert_bulk(&self, routes: Vec<Route>) -> Result<usize> {
+        // Deduplicate within the batch, keeping first-seen order
+        let mut positions: HashMap<Prefix, usize> = HashMap::with_capacity(routes.len());
+        let mut batch: Vec<Route> = Vec::with_capacity(routes.len());
+        for route in routes {
+            route.validate()?;
+            match positions.get(&route.prefix) {
+                Some(&idx) => batch[idx] = route,
+                None => {
+                    positions.insert(route.prefix, batch.len());
+                    batch.push(route);
+                }
+            }
+        }
+
+        let inserted = self.phase1.insert_bulk(
+            batch.iter().map(|r| (r.prefix, r.primary_next_hop().to_string(), r.metric))
+        );
+
+        // IPv6 routes are served by Phase 1 only (Phases 2/3 are IPv4-only)
+        let ipv4: Vec<(Prefix, String, u32)> = batch.iter()
+            .filter(|r| r.prefix.is_ipv4())
+            .map(|r| (r.prefix, r.primary_next_hop().to_string(), r.metric))
+            .collect();
+
+        {
+            let mut ecmp_routes = self.ecmp_routes.write().await;
+            for route in batch {
+                if route.is_multipath() {
+                    ecmp_routes.insert(route.prefix, route);
+                } else {
+                    ecmp_routes.remove(&route.prefix);
+                }
+            }
+        }
+
+        {
+            let mut phase3 = self.phase3.write().await;
+            let mut prefix_index = self.prefix_index.write();
+            for (prefix, next_hop, metric) in &ipv4 {
+                phase3.upsert(*prefix, next_hop.clone(), *metric)?;
+                prefix_index.insert(prefix);
+            }
+        }
+
+        self.phase2.insert_bulk(ipv4).await?;
+        self.result_cache.lock().clear();
+
+        let mut monitor = self.monitor.write().await;
+        monitor.total_inserts += inserted as u64;
+
+        Ok(inserted)
+    }
+
+    /// Snapshot the routing table
+    ///
+    /// Same content as [`TCAMEngine::iter_routes`]; paired with
+    /// [`TCAMEngine::import`] for persistence.
+    pub async fn export(&self) -> Vec<Route> {
+        self.iter_routes().await
+    }
+
+    /// Every installed route, deduplicated by prefix
+    ///
+    /// Phase 1 holds every route (IPv4 and IPv6), so the table is read from
+    /// there: one route per prefix (the one Phase 1 resolves to), with
+    /// multipath sets restored, in route preference order. Only takes read
+    /// locks and leaves lookup counters untouched.
+    pub async fn iter_routes(&self) -> Vec<Route> {
+        let ecmp_routes = self.ecmp_routes.read().await;
+        let mut seen = HashSet::new();
+
+        let mut entries = self.phase1.routes();
+        entries.sort_by(|a, b| {
+            route_rank(a.prefix.prefix_len(), a.metric, &a.next_hop)
+                .cmp(&route_rank(b.prefix.prefix_len(), b.metric, &b.next_hop))
+        });
+        entries
+            .into_iter()
+            .filter(|entry| seen.insert(entry.prefix))
+            .map(|entry| match ecmp_routes.get(&entry.prefix) {
+                Some(route) => route.clone(),
+                None => Route::new(entry.prefix, entry.next_hop, entry.metric),
+            })
+            .collect()
+    }
+
+    /// Load a snapshot produced by [`TCAMEngine::export`]
+    pub async fn import(&self, routes: Vec<Route>) -> Result<()> {
+        self.insert_bulk(routes).await?;
+        Ok(())
+    }
+
+    /// Snapshot the routing table as JSON
+    pub async fn export_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(&self.export().await)?)
+    }
+
+    /// Load a JSON snapshot produced by [`TCAMEngine::export_json`]
+    pub async fn import_json(&self, json: &str) -> Result<()> {
+        let routes: Vec<Route> = serde_json::from_str(json)?;
+        self.import(routes).await
+    }
+
     /// Lookup a route for an IP address
+    ///
+    /// Results are cached per address until a route covering the address is
+    /// inserted or deleted; cached answers skip the phase dispatch, so they
+    /// count towards `total_lookups` but not the per-phase counters.
     pub async fn lookup(&self, ip: &str) -> Result<Option<LookupResult>> {
         {
             let mut monitor = self.monitor.write().await;
             monitor.total_lookups += 1;
         }
 
-        // Select phase based on strategy
-        match self.strategy {
-            PhaseStrategy::Phase1Only => {
-                let result = self.phase1.lookup(ip)?;
-                let mut monitor = self.monitor.write().await;
-                monitor.phase1_lookups += 1;
-
-                Ok(result.map(|r| LookupResult {
-                    prefix: r.prefix,
-                    next_hop: r.next_hop,
-                    metric: r.metric,
-                    latency_ns: r.latency_ns,
-                    phase: "Phase1-AHGF".to_string(),
-                }))
+        let addr = ip.parse::<IpAddr>().ok();
+        let generation = {
+            let mut cache = self.result_cache.lock();
+            if let Some(cached) = addr.and_then(|addr| cache.get(&addr)) {
+                return Ok(cached);
+            }
+            cache.generation
+        };
+
+        let strategy = self.strategy();
+        let phase = self.select_phase(ip, strategy);
+
+        let start = Instant::now();
+        let result = match phase {
+            Phase::Phase1 => self.lookup_phase1(ip)?,
+            Phase::Phase2 => self.lookup_phase2(ip).await?,
+            Phase::Phase3 if matches!(strategy, PhaseStrategy::Adaptive { .. }) => {
+                self.lookup_phase3(ip, "Phase3-SCRTT-Adaptive").await?
             }
-            PhaseStrategy::Phase2Only => {
-                let result = self.phase2.lookup(ip).await?;
-                let mut monitor = self.monitor.write().await;
-                monitor.phase2_lookups += 1;
-
-                Ok(result.map(|r| LookupResult {
-                    prefix: format!("{}/{}",
-                        std::net::Ipv4Addr::from(r.prefix.addr),
-                        r.prefix.len),
-                    next_hop: r.next_hop,
-                    metric: r.metric,
-                    latency_ns: r.latency_ns as f64,
-                    phase: "Phase2-QAGFHG".to_string(),
-                }))
+            Phase::Phase3 => self.lookup_phase3(ip, "Phase3-SCRTT").await?,
+        };
+        self.performance.record_phase_lookup(phase, start.elapsed().as_nanos() as u64);
+
+        let result = self.attach_next_hops(result).await?;
+        if let Some(addr) = addr {
+            self.result_cache.lock().insert(addr, result.clone(), generation);
+        }
+        Ok(result)
+    }
+
+    /// Explain how a lookup for `ip` would be resolved, for debugging
+    ///
+    /// Reports the phase the current strategy picks, every matching prefix
+    /// (longest first) and the entry that phase returns. Read-only: lookup
+    /// counters and latency metrics are left untouched.
+    pub async fn explain(&self, ip: &str) -> Result<LookupExplanation> {
+        let addr: IpAddr = ip.parse()?;
+        let strategy = self.strategy();
+        let phase = self.select_phase(ip, strategy);
+
+        let mut seen = HashSet::new();
+        let mut candidates: Vec<MatchCandidate> = self.phase1.routes()
+            .into_iter()
+            .filter(|entry| entry.prefix.matches_ip(addr) && seen.insert(entry.prefix))
+            .map(|entry| MatchCandidate {
+                prefix_len: entry.prefix.prefix_len(),
+                prefix: entry.prefix,
+                next_hop: entry.next_hop,
+                metric: entry.metric,
+            })
+            .collect();
+        candidates.sort_by(|a, b| {
+            route_rank(a.prefix_len, a.metric, &a.next_hop).cmp(&route_rank(b.prefix_len, b.metric, &b.next_hop))
+        });
+
+        let selected = match phase {
+            Phase::Phase1 => self.lookup_phase1(ip)?,
+            Phase::Phase2 => self.lookup_phase2(ip).await?,
+            Phase::Phase3 => self.lookup_phase3(ip, "Phase3-SCRTT").await?,
+        };
+        let selected = self.attach_next_hops(selected).await?;
+
+        let reason = match (&selected, candidates.first()) {
+            (Some(result), Some(longest)) if result.prefix == longest.prefix.to_string() => format!(
+                "{} is the longest of {} matching prefixes",
+                result.prefix, candidates.len()
+            ),
+            (Some(result), Some(longest)) => format!(
+                "{:?} returned {} but the longest match is {}",
+                phase, result.prefix, longest.prefix
+            ),
+            (Some(result), None) => format!("{:?} returned {} with no matching prefix in Phase 1", phase, result.prefix),
+            (None, Some(_)) => format!("{:?} found no route although {} prefixes match", phase, candidates.len()),
+            (None, None) => "no prefix matches".to_string(),
+        };
+
+        Ok(LookupExplanation {
+            ip: ip.to_string(),
+            strategy,
+            phase,
+            candidates,
+            selected,
+            reason,
+        })
+    }
+
+    /// Choose the phase that serves a lookup under `strategy`
+    fn select_phase(&self, ip: &str, strategy: PhaseStrategy) -> Phase {
+        // IPv6 lookups always go through Phase 1
+        if ip.parse::<Ipv6Addr>().is_ok() {
+            return Phase::Phase1;
+        }
+
+        match strategy {
+            PhaseStrategy::Fixed(phase) => phase,
+            PhaseStrategy::Adaptive { short_threshold, long_threshold } => {
+                let matched_len = ip.parse::<Ipv4Addr>()
+                    .ok()
+                    .and_then(|addr| self.prefix_index.read().longest_match(u32::from(addr)));
+
+                match matched_len {
+                    Some(len) if len <= short_threshold => Phase::Phase1,
+                    Some(len) if len < long_threshold => Phase::Phase2,
+                    _ => Phase::Phase3,
+                }
             }
-            PhaseStrategy::Phase3Only => {
-                let phase3 = self.phase3.read().await;
-                let result = phase3.lookup(ip)?;
-                let mut monitor = self.monitor.write().await;
-                monitor.phase3_lookups += 1;
-
-                Ok(result.map(|(next_hop, metric, latency_ns)| LookupResult {
-                    prefix: ip.to_string(), // Simplified for now
-                    next_hop,
-                    metric,
-                    latency_ns: latency_ns as f64,
-                    phase: "Phase3-SCRTT".to_string(),
-                }))
+            PhaseStrategy::Auto => {
+                let phases = [Phase::Phase1, Phase::Phase2, Phase::Phase3];
+                let metrics = phases.map(|phase| (phase, self.performance.phase_metrics(phase)));
+
+                metrics.iter()
+                    .find(|(_, m)| m.lookups == 0)
+                    .or_else(|| metrics.iter().min_by(|a, b| a.1.avg_latency_ns.total_cmp(&b.1.avg_latency_ns)))
+                    .map(|&(phase, _)| phase)
+                    .unwrap_or(Phase::Phase3)
             }
-            PhaseStrategy::Adaptive => {
-                // For now, use Phase 3 if available, fallback to Phase 2
-                // In production, this would use workload characteristics
-                let phase3 = self.phase3.read().await;
-                let result = phase3.lookup(ip)?;
-                let mut monitor = self.monitor.write().await;
-                monitor.phase3_lookups += 1;
-
-                Ok(result.map(|(next_hop, metric, latency_ns)| LookupResult {
-                    prefix: ip.to_string(), // Simplified for now
-                    next_hop,
-                    metric,
-                    latency_ns: latency_ns as f64,
-                    phase: "Phase3-SCRTT-Adaptive".to_string(),
-                }))
+        }
+    }
+
+    /// Replace the primary next hop with the full multipath set, if any
+    async fn attach_next_hops(&self, result: Option<LookupResult>) -> Result<Option<LookupResult>> {
+        let Some(mut result) = result else {
+            return Ok(None);
+        };
+
+        let ecmp_routes = self.ecmp_routes.read().await;
+        if !ecmp_routes.is_empty() {
+            if let Some(route) = ecmp_routes.get(&Prefix::from_cidr(&result.prefix)?) {
+                result.next_hops = route.next_hops.clone();
+                result.weights = route.weights.clone();
             }
         }
+
+        Ok(Some(result))
     }
 
-    /// Delete a route
-    pub async fn delete(&self, _prefix: Prefix) -> Result<()> {
-        // TODO: Implement delete in Phase 1
-        let mut monitor = self.monitor.write().await;
-        monitor.total_deletes += 1;
+    /// Track the multipath set for a freshly inserted route
+    async fn record_next_hops(&self, route: Route) {
+        let mut ecmp_routes = self.ecmp_routes.write().await;
+        if route.is_multipath() {
+            ecmp_routes.insert(route.prefix, route);
+        } else {
+            ecmp_routes.remove(&route.prefix);
+        }
+    }
+
+    /// Phase 1 (AHGF) lookup
+    fn lookup_phase1(&self, ip: &str) -> Result<Option<LookupResult>> {
+        let result = self.phase1.lookup(ip)?;
+
+        Ok(result.map(|r| LookupResult {
+            prefix: r.prefix,
+            next_hops: vec![r.next_hop],
+            weights: vec![1],
+            metric: r.metric,
+            latency_ns: r.latency_ns,
+            phase: "Phase1-AHGF".to_string(),
+        }))
+    }
+
+    /// Phase 2 (QAGFHG) lookup
+    async fn lookup_phase2(&self, ip: &str) -> Result<Option<LookupResult>> {
+        let result = self.phase2.lookup(ip).await?;
+
+        Ok(result.map(|r| LookupResult {
+            prefix: r.prefix.to_string(),
+            next_hops: vec![r.next_hop],
+            weights: vec![1],
+            metric: r.metric,
+            latency_ns: r.latency_ns as f64,
+            phase: "Phase2-QAGFHG".to_string(),
+        }))
+    }
+
+    /// Phase 3 (SCRTT) lookup
+    async fn lookup_phase3(&self, ip: &str, phase: &str) -> Result<Option<LookupResult>> {
+        let phase3 = self.phase3.read().await;
+        let result = phase3.lookup_match(ip)?;
+
+        Ok(result.map(|(prefix, next_hop, metric, latency_ns)| LookupResult {
+            prefix: prefix.to_string(),
+            next_hops: vec![next_hop],
+            weights: vec![1],
+            metric,
+            latency_ns: latency_ns as f64,
+            phase: phase.to_string(),
+        }))
+    }
+
+    /// Verify that every phase resolves each probe IP to the same longest match
+    ///
+    /// Runs each probe through Phase 1, 2 and 3 directly (bypassing the
+    /// strategy and the lookup counters) and compares the matched prefix
+    /// and next hop. Returns an error listing every divergent probe.
+    pub async fn verify_lpm_consistency(&self, probes: &[&str]) -> Result<()> {
+        let mut mismatches = Vec::new();
+
+        for &ip in probes {
+            let results = [
+                self.lookup_phase1(ip)?,
+                self.lookup_phase2(ip).await?,
+                self.lookup_phase3(ip, "Phase3-SCRTT").await?,
+            ];
+
+            let keys: Vec<Option<(&str, &str)>> = results.iter()
+                .map(|r| r.as_ref().map(|r| (r.prefix.as_str(), r.primary_next_hop())))
+                .collect();
+
+            if keys.iter().any(|k| *k != keys[0]) {
+                mismatches.push(format!(
+                    "{}: phase1={:?} phase2={:?} phase3={:?}",
+                    ip, keys[0], keys[1], keys[2]
+                ));
+            }
+        }
+
+        if !mismatches.is_empty() {
+            anyhow::bail!("LPM divergence across phases: {}", mismatches.join("; "));
+        }
+
+        Ok(())
+    }
+
+    /// Delete a route from every phase
+    pub async fn delete(&self, prefix: Prefix) -> Result<()> {
+        self.ecmp_routes.write().await.remove(&prefix);
+        let phase2_deleted = self.phase2.delete(prefix).await;
+        let phase3_deleted = self.phase3.write().await.delete(prefix)?;
+        if self.phase1.delete(prefix) | phase2_deleted | phase3_deleted {
+            self.prefix_index.write().remove(&prefix);
+            self.result_cache.lock().invalidate(&prefix);
+            let mut monitor = self.monitor.write().await;
+            monitor.total_deletes += 1;
+        }
         Ok(())
     }
 
+    /// Merge sibling IPv4 prefixes that forward identically into their parent
+    ///
+    /// Two prefixes of the same length that differ only in their last bit
+    /// (e.g. 10.0.0.0/25 and 10.0.0.128/25) are replaced by the covering
+    /// prefix (10.0.0.0/24) when they carry the same next hops, weights and
+    /// metric. Together the siblings cover the parent exactly and longer
+    /// prefixes inside them keep precedence, so every address resolves to
+    /// the same next hop afterwards. A parent already in the table with a
+    /// different route blocks the merge. Repeats until no pair is left and
+    /// returns the number of merges.
+    pub async fn aggregate(&self) -> Result<usize> {
+        let mut merges = 0;
+
+        loop {
+            let table: HashMap<Prefix, Route> = self.iter_routes().await
+                .into_iter()
+                .filter(|route| {
+                    let len = route.prefix.prefix_len();
+                    route.prefix.is_ipv4()
+                        && len > 0
+                        && route.prefix.addr_u32() & !PrefixLengthIndex::mask(len) == 0
+                })
+                .map(|route| (route.prefix, route))
+                .collect();
+
+            let mut merged = HashSet::new();
+            let mut round = 0;
+            for (prefix, route) in &table {
+                if merged.contains(prefix) {
+                    continue;
+                }
+                let len = prefix.prefix_len();
+                let sibling = Prefix::new(prefix.addr_u32() ^ (1u32 << (32 - len)), len)?;
+                let forwards_alike = |other: &Route| {
+                    other.next_hops == route.next_hops
+                        && other.weights == route.weights
+                        && other.metric == route.metric
+                };
+                if merged.contains(&sibling) || !table.get(&sibling).is_some_and(forwards_alike) {
+                    continue;
+                }
+
+                // A parent merged away earlier in this round is no longer installed
+                let parent = Prefix::new(prefix.addr_u32() & PrefixLengthIndex::mask(len - 1), len - 1)?;
+                if merged.contains(&parent) {
+                    continue;
+                }
+                match table.get(&parent) {
+                    Some(existing) if !forwards_alike(existing) => continue,
+                    Some(_) => {}
+                    // Insert before deleting, so a failed insert loses nothing
+                    None => self.insert(Route { prefix: parent, ..route.clone() }).await?,
+                }
+
+                self.delete(*prefix).await?;
+                self.delete(sibling).await?;
+                merged.insert(*prefix);
+                merged.insert(sibling);
+                round += 1;
+            }
+
+            if round == 0 {
+                break;
+            }
+            merges += round;
+        }
+
+        Ok(merges)
+    }
+
     /// Get engine statistics
     pub async fn stats(&self) -> TCAMStats {
         let monitor = self.monitor.read().await;
         let phase1_stats = self.phase1.stats();
         let phase2_stats = self.phase2.stats();
 
-        // Calculate weighted average latency
-        let total_phase_lookups = monitor.phase1_lookups + monitor.phase2_lookups + monitor.phase3_lookups;
-        let avg_lookup_ns = if total_phase_lookups > 0 {
-            (phase1_stats.avg_lookup_ns * monitor.phase1_lookups as f64
-                + phase2_stats.avg_latency_ns * monitor.phase2_lookups as f64
-                + 8.0 * monitor.phase3_lookups as f64) // Phase 3 is ~8ns
-                / total_phase_lookups as f64
-        } else {
-            0.0
+        let phase1 = self.performance.phase_metrics(Phase::Phase1);
+        let phase2 = self.performance.phase_metrics(Phase::Phase2);
+        let phase3 = self.performance.phase_metrics(Phase::Phase3);
+        let (result_cache_hits, result_cache_misses) = {
+            let cache = self.result_cache.lock();
+            (cache.hits, cache.misses)
         };
 
         TCAMStats {
@@ -190,18 +761,24 @@ impl TCAMEngine {
             total_inserts: monitor.total_inserts,
             total_deletes: monitor.total_deletes,
             route_count: self.phase1.route_count(),
-            avg_lookup_ns,
-            phase1_lookups: monitor.phase1_lookups,
-            phase2_lookups: monitor.phase2_lookups,
-            phase3_lookups: monitor.phase3_lookups,
+            avg_lookup_ns: self.performance.avg_latency_ns(),
+            phase1_lookups: phase1.lookups,
+            phase2_lookups: phase2.lookups,
+            phase3_lookups: phase3.lookups,
+            phase1_avg_latency_ns: phase1.avg_latency_ns,
+            phase2_avg_latency_ns: phase2.avg_latency_ns,
+            phase3_avg_latency_ns: phase3.avg_latency_ns,
             cache_hits: phase1_stats.cache_hits + phase2_stats.cache_hits,
             cache_misses: phase1_stats.cache_misses,
+            hint_rebuilds: phase2_stats.hint_rebuilds,
+            result_cache_hits,
+            result_cache_misses,
         }
     }
 
     /// Get current phase strategy
     pub fn strategy(&self) -> PhaseStrategy {
-        self.strategy
+        *self.strategy.read()
     }
 
     /// Get route count
@@ -211,35 +788,180 @@ impl TCAMEngine {
 }
 
 /// Route entry
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Route {
     pub prefix: Prefix,
-    pub next_hop: String,
+    /// Equal-cost next hops; the first one is the primary
+    pub next_hops: Vec<String>,
+    /// Relative weight of each next hop (parallel to `next_hops`)
+    pub weights: Vec<u32>,
     pub metric: u32,
 }
 
 impl Route {
+    /// Single next-hop route
     pub fn new(prefix: Prefix, next_hop: impl Into<String>, metric: u32) -> Self {
         Self {
             prefix,
-            next_hop: next_hop.into(),
+            next_hops: vec![next_hop.into()],
+            weights: vec![1],
             metric,
         }
     }
+
+    /// Multipath route from `(next_hop, weight)` pairs
+    pub fn ecmp(prefix: Prefix, next_hops: Vec<(String, u32)>, metric: u32) -> Self {
+        let (next_hops, weights) = next_hops.into_iter().unzip();
+        Self {
+            prefix,
+            next_hops,
+            weights,
+            metric,
+        }
+    }
+
+    /// First next hop of the set
+    pub fn primary_next_hop(&self) -> &str {
+        self.next_hops.first().map(String::as_str).unwrap_or_default()
+    }
+
+    /// Whether the route carries more than one next hop
+    pub fn is_multipath(&self) -> bool {
+        self.next_hops.len() > 1
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.next_hops.is_empty() {
+            anyhow::bail!("Route {} has no next hops", self.prefix);
+        }
+        if self.weights.len() != self.next_hops.len() {
+            anyhow::bail!(
+                "Route {} has {} next hops but {} weights",
+                self.prefix, self.next_hops.len(), self.weights.len()
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Flow 5-tuple used for multipath next-hop selection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FlowKey {
+    pub src_ip: IpAddr,
+    pub dst_ip: IpAddr,
+    pub src_port: u16,
+    pub dst_port: u16,
+    pub protocol: u8,
+}
+
+impl FlowKey {
+    pub fn new(src_ip: IpAddr, dst_ip: IpAddr, src_port: u16, dst_port: u16, protocol: u8) -> Self {
+        Self { src_ip, dst_ip, src_port, dst_port, protocol }
+    }
+
+    /// Deterministic flow hash (FNV-1a with a 64-bit finalizer)
+    ///
+    /// Stable across processes and releases, unlike `std`'s `DefaultHasher`.
+    pub fn flow_hash(&self) -> u64 {
+        let mut hash = 0xcbf29ce484222325u64;
+        let mut feed = |bytes: &[u8]| {
+            for &byte in bytes {
+                hash ^= byte as u64;
+                hash = hash.wrapping_mul(0x100000001b3);
+            }
+        };
+
+        for ip in [self.src_ip, self.dst_ip] {
+            match ip {
+                IpAddr::V4(v4) => feed(&v4.octets()),
+                IpAddr::V6(v6) => feed(&v6.octets()),
+            }
+        }
+        feed(&self.src_port.to_be_bytes());
+        feed(&self.dst_port.to_be_bytes());
+        feed(&[self.protocol]);
+
+        // Finalize so the low bits used by the modulo are well mixed
+        hash ^= hash >> 33;
+        hash = hash.wrapping_mul(0xff51afd7ed558ccd);
+        hash ^= hash >> 33;
+        hash = hash.wrapping_mul(0xc4ceb9fe1a85ec53);
+        hash ^ (hash >> 33)
+    }
 }
 
 /// Lookup result
 #[derive(Debug, Clone)]
 pub struct LookupResult {
     pub prefix: String,
-    pub next_hop: String,
+    /// Equal-cost next hops; the first one is the primary
+    pub next_hops: Vec<String>,
+    /// Relative weight of each next hop (parallel to `next_hops`)
+    pub weights: Vec<u32>,
     pub metric: u32,
     pub latency_ns: f64,
     pub phase: String,
 }
 
-/// TCAM statistics
+impl LookupResult {
+    /// First next hop of the set
+    pub fn primary_next_hop(&self) -> &str {
+        self.next_hops.first().map(String::as_str).unwrap_or_default()
+    }
+
+    /// Pick a next hop for `flow`, proportionally to the hop weights
+    ///
+    /// The choice depends only on the flow 5-tuple, so every packet of a
+    /// flow takes the same path. All-zero weights fall back to uniform.
+    pub fn select_next_hop(&self, flow: &FlowKey) -> &str {
+        if self.next_hops.len() <= 1 {
+            return self.primary_next_hop();
+        }
+
+        let hash = flow.flow_hash();
+        let total: u64 = self.weights.iter().map(|&w| w as u64).sum();
+        if total == 0 {
+            return &self.next_hops[(hash % self.next_hops.len() as u64) as usize];
+        }
+
+        let mut point = hash % total;
+        for (next_hop, &weight) in self.next_hops.iter().zip(&self.weights) {
+            if point < weight as u64 {
+                return next_hop;
+            }
+            point -= weight as u64;
+        }
+        self.primary_next_hop()
+    }
+}
+
+/// Prefix that matched an explained lookup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchCandidate {
+    pub prefix: Prefix,
+    pub prefix_len: u8,
+    pub next_hop: String,
+    pub metric: u32,
+}
+
+/// Result of [`TCAMEngine::explain`]
 #[derive(Debug, Clone)]
+pub struct LookupExplanation {
+    pub ip: String,
+    /// Strategy in force when the lookup was explained
+    pub strategy: PhaseStrategy,
+    /// Phase the strategy selected
+    pub phase: Phase,
+    /// Every matching prefix, longest first
+    pub candidates: Vec<MatchCandidate>,
+    /// What the selected phase returns (next hops attached)
+    pub selected: Option<LookupResult>,
+    /// Why `selected` won (or why nothing did)
+    pub reason: String,
+}
+
+/// TCAM statistics
+#[derive(Debug, Clone, Serialize)]
 pub struct TCAMStats {
     pub total_lookups: u64,
     pub total_inserts: u64,
@@ -249,8 +971,17 @@ pub struct TCAMStats {
     pub phase1_lookups: u64,
     pub phase2_lookups: u64,
     pub phase3_lookups: u64,
+    /// Moving-average realized lookup latency per phase (ns)
+    pub phase1_avg_latency_ns: f64,
+    pub phase2_avg_latency_ns: f64,
+    pub phase3_avg_latency_ns: f64,
     pub cache_hits: u64,
     pub cache_misses: u64,
+    pub hint_rebuilds: u64,
+    /// Lookups answered by the per-address result cache
+    pub result_cache_hits: u64,
+    /// Lookups that went through the phase dispatch
+    pub result_cache_misses: u64,
 }
 
 #[cfg(test)]
@@ -270,7 +1001,7 @@ mod tests {
         assert!(result.is_some());
 
         let result = result.unwrap();
-        assert_eq!(result.next_hop, "next_hop_1");
+        assert_eq!(result.primary_next_hop(), "next_hop_1");
         assert_eq!(result.phase, "Phase3-SCRTT-Adaptive"); // Adaptive uses Phase 3
     }
 
@@ -290,5 +1021,500 @@ mod tests {
         assert_eq!(stats.phase3_lookups, 1); // Adaptive uses Phase 3
         assert_eq!(stats.route_count, 1);
     }
-}
 
+    #[tokio::test]
+    async fn test_explain_lists_ancestors() {
+        let engine = TCAMEngine::new().unwrap();
+        for (cidr, next_hop) in [
+            ("0.0.0.0/0", "default"),
+            ("10.0.0.0/8", "nh8"),
+            ("10.1.0.0/16", "nh16"),
+            ("10.1.2.0/24", "nh24"),
+            ("10.1.2.128/25", "nh25"),
+            ("192.168.0.0/16", "other"),
+        ] {
+            engine.insert(Route::new(Prefix::from_cidr(cidr).unwrap(), next_hop, 10)).await.unwrap();
+        }
+
+        let explanation = engine.explain("10.1.2.5").await.unwrap();
+        let prefixes: Vec<String> = explanation.candidates.iter().map(|c| c.prefix.to_string()).collect();
+        assert_eq!(prefixes, ["10.1.2.0/24", "10.1.0.0/16", "10.0.0.0/8", "0.0.0.0/0"]);
+        let lengths: Vec<u8> = explanation.candidates.iter().map(|c| c.prefix_len).collect();
+        assert_eq!(lengths, [24, 16, 8, 0]);
+
+        let selected = explanation.selected.unwrap();
+        assert_eq!(selected.prefix, "10.1.2.0/24");
+        assert_eq!(selected.primary_next_hop(), "nh24");
+        assert_eq!(explanation.phase, Phase::Phase3);
+        assert!(explanation.reason.contains("longest"));
+
+        // Explaining is not a lookup
+        assert_eq!(engine.stats().await.total_lookups, 0);
+        assert!(engine.explain("not-an-ip").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_ipv6_route() {
+        let engine = TCAMEngine::new().unwrap();
+
+        let prefix = Prefix::from_cidr("2001:db8::/32").unwrap();
+        engine.insert(Route::new(prefix, "next_hop_v6", 10)).await.unwrap();
+
+        let result = engine.lookup("2001:db8::1").await.unwrap().unwrap();
+        assert_eq!(result.primary_next_hop(), "next_hop_v6");
+        assert_eq!(result.phase, "Phase1-AHGF");
+
+        assert!(engine.lookup("2001:db9::1").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_verify_lpm_consistency() {
+        let engine = TCAMEngine::new().unwrap();
+
+        for (cidr, next_hop) in [
+            ("0.0.0.0/0", "default"),
+            ("10.0.0.0/8", "nh8"),
+            ("10.1.0.0/16", "nh16"),
+            ("10.1.1.0/24", "nh24"),
+        ] {
+            let route = Route::new(Prefix::from_cidr(cidr).unwrap(), next_hop, 100);
+            engine.insert(route).await.unwrap();
+        }
+
+        let probes = [
+            "10.1.1.1", "10.1.1.255", "10.1.2.1", "10.1.255.255",
+            "10.2.0.1", "10.255.255.255", "11.0.0.1", "192.168.1.1",
+            "0.0.0.0", "255.255.255.255",
+        ];
+        engine.verify_lpm_consistency(&probes).await.unwrap();
+
+        let result = engine.lookup("10.1.2.1").await.unwrap().unwrap();
+        assert_eq!(result.prefix, "10.1.0.0/16");
+        assert_eq!(result.primary_next_hop(), "nh16");
+    }
+
+    #[tokio::test]
+    async fn test_overlapping_routes_resolve_deterministically() {
+        // Distinct overlapping prefixes whose metrics disagree with their lengths
+        let routes = [
+            ("10.0.0.0/8", "nh-a", 1),
+            ("10.1.0.0/16", "nh-b", 5),
+            ("10.1.4.0/24", "nh-c", 50),
+            ("10.2.0.0/16", "nh-d", 10),
+            ("10.2.3.0/24", "nh-e", 20),
+        ];
+        let probes = [("10.1.4.4", "nh-c"), ("10.1.5.5", "nh-b"), ("10.2.3.200", "nh-e"), ("10.3.0.1", "nh-a")];
+        async fn build(routes: &[(&str, &str, u32)]) -> TCAMEngine {
+            let engine = TCAMEngine::new().unwrap();
+            for &(cidr, next_hop, metric) in routes {
+                engine.insert(Route::new(Prefix::from_cidr(cidr).unwrap(), next_hop, metric)).await.unwrap();
+            }
+            engine
+        }
+
+        // Fresh engines stand in for process restarts with a different insertion order
+        let reversed: Vec<_> = routes.iter().rev().copied().collect();
+        let engines = [build(&routes).await, build(&reversed).await];
+        let restored = TCAMEngine::new().unwrap();
+        restored.import_json(&engines[0].export_json().await.unwrap()).await.unwrap();
+
+        let ips: Vec<&str> = probes.iter().map(|&(ip, _)| ip).collect();
+        for engine in engines.iter().chain([&restored]) {
+            engine.verify_lpm_consistency(&ips).await.unwrap();
+            for phase in [Phase::Phase1, Phase::Phase2, Phase::Phase3] {
+                engine.set_phase_strategy(PhaseStrategy::Fixed(phase));
+                engine.result_cache.lock().clear();
+                // The longest prefix wins whatever its metric
+                for (ip, expected) in probes {
+                    let result = engine.lookup(ip).await.unwrap().unwrap();
+                    assert_eq!(result.primary_next_hop(), expected, "{:?} {}", phase, ip);
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_iter_routes() {
+        let engine = TCAMEngine::new().unwrap();
+        let routes = [
+            ("0.0.0.0/0", "default"),
+            ("10.0.0.0/8", "nh8"),
+            ("10.1.0.0/16", "nh16"),
+            ("10.1.1.0/24", "nh24"),
+            ("2001:db8::/32", "nh_v6"),
+        ];
+        for (cidr, next_hop) in routes {
+            engine.insert(Route::new(Prefix::from_cidr(cidr).unwrap(), next_hop, 10)).await.unwrap();
+        }
+        // Re-inserting a prefix does not duplicate it
+        engine.insert(Route::new(Prefix::from_cidr("10.0.0.0/8").unwrap(), "nh8", 10)).await.unwrap();
+
+        let listed = engine.iter_routes().await;
+        assert_eq!(listed.len(), routes.len());
+        for (cidr, next_hop) in routes {
+            let route = listed.iter().find(|r| r.prefix.to_string() == cidr).unwrap();
+            assert_eq!(route.next_hops, vec![next_hop.to_string()]);
+        }
+        assert_eq!(engine.stats().await.total_lookups, 0);
+    }
+
+    #[tokio::test]
+    async fn test_insert_bulk_100k() {
+        use rand::Rng;
+
+        const N: u32 = 100_000;
+        let engine = TCAMEngine::new().unwrap();
+        engine.phase2.set_prefetch(true);
+
+        let subnet = |i: u32| (10u32 << 24) + (i << 8);
+        let mut routes: Vec<Route> = (0..N)
+            .map(|i| Route::new(Prefix::new(subnet(i), 24).unwrap(), format!("nh{}", i), 100))
+            .collect();
+        // Exact-prefix duplicate: the later entry overwrites the earlier one
+        routes.push(Route::new(Prefix::new(subnet(7), 24).unwrap(), "nh7-override", 50));
+
+        let inserted = engine.insert_bulk(routes).await.unwrap();
+        assert_eq!(inserted, N as usize);
+
+        let stats = engine.stats().await;
+        assert_eq!(stats.route_count, N as usize);
+        assert_eq!(stats.total_inserts, N as u64);
+        assert_eq!(stats.hint_rebuilds, 0);
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..1_000 {
+            let i = rng.gen_range(0..N);
+            let ip = std::net::Ipv4Addr::from(subnet(i) | rng.gen_range(0..256));
+            let result = engine.lookup(&ip.to_string()).await.unwrap().unwrap();
+            let expected = if i == 7 { "nh7-override".to_string() } else { format!("nh{}", i) };
+            assert_eq!(result.primary_next_hop(), expected);
+            assert_eq!(result.prefix, format!("{}/24", std::net::Ipv4Addr::from(subnet(i))));
+        }
+
+        let result = engine.lookup_phase1("10.0.7.1").unwrap().unwrap();
+        assert_eq!(result.primary_next_hop(), "nh7-override");
+        assert!(engine.lookup("9.255.255.255").await.unwrap().is_none());
+
+        // The hint table covering the whole batch is generated exactly once
+        engine.set_phase_strategy(PhaseStrategy::Fixed(Phase::Phase2));
+        for i in [0, 7, N - 1] {
+            let ip = std::net::Ipv4Addr::from(subnet(i) | 1);
+            engine.lookup(&ip.to_string()).await.unwrap().unwrap();
+        }
+        assert_eq!(engine.stats().await.hint_rebuilds, 1);
+        assert_eq!(engine.phase2.hint_generator().num_hints(), N as usize);
+    }
+
+    #[tokio::test]
+    async fn test_ecmp_flow_hash_selection() {
+        let engine = TCAMEngine::new().unwrap();
+
+        let prefix = Prefix::from_cidr("10.0.0.0/24").unwrap();
+        let hops = vec![("hop_a".to_string(), 1), ("hop_b".to_string(), 1)];
+        engine.insert(Route::ecmp(prefix, hops, 100)).await.unwrap();
+
+        let result = engine.lookup("10.0.0.7").await.unwrap().unwrap();
+        assert_eq!(result.next_hops, vec!["hop_a", "hop_b"]);
+        assert_eq!(result.primary_next_hop(), "hop_a");
+
+        let dst: IpAddr = "10.0.0.7".parse().unwrap();
+        let mut hop_a = 0;
+        for i in 0..10_000u32 {
+            let src = IpAddr::from(std::net::Ipv4Addr::from(0xC0A8_0000 | (i >> 8)));
+            let flow = FlowKey::new(src, dst, 1024 + (i & 0xFF) as u16, 443, 6);
+
+            let selected = result.select_next_hop(&flow);
+            assert_eq!(selected, result.select_next_hop(&flow), "selection must be stable");
+            if selected == "hop_a" {
+                hop_a += 1;
+            }
+        }
+        assert!((4_000..=6_000).contains(&hop_a), "unbalanced split: {} / 10000", hop_a);
+
+        // Re-inserting as a single-hop route drops the multipath set
+        engine.insert(Route::new(prefix, "hop_c", 100)).await.unwrap();
+        let result = engine.lookup("10.0.0.7").await.unwrap().unwrap();
+        assert_eq!(result.next_hops, vec!["hop_c"]);
+    }
+
+    #[tokio::test]
+    async fn test_reinsert_replaces_route() {
+        let engine = TCAMEngine::new().unwrap();
+        engine.set_result_cache_capacity(0);
+
+        for (cidr, ip) in [("10.9.0.0/16", "10.9.1.1"), ("2001:db8:9::/48", "2001:db8:9::1")] {
+            let prefix = Prefix::from_cidr(cidr).unwrap();
+            engine.insert(Route::new(prefix, "first", 100)).await.unwrap();
+            engine.insert(Route::new(prefix, "second", 100)).await.unwrap();
+
+            let result = engine.lookup(ip).await.unwrap().unwrap();
+            assert_eq!(result.primary_next_hop(), "second", "{}", cidr);
+            let result = engine.lookup_phase1(ip).unwrap().unwrap();
+            assert_eq!(result.primary_next_hop(), "second", "{}", cidr);
+        }
+
+        let result = engine.lookup_phase2("10.9.1.1").await.unwrap().unwrap();
+        assert_eq!(result.primary_next_hop(), "second");
+        let result = engine.lookup_phase3("10.9.1.1", "Phase3-SCRTT").await.unwrap().unwrap();
+        assert_eq!(result.primary_next_hop(), "second");
+        assert_eq!(engine.iter_routes().await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_per_phase_counters() {
+        let engine = TCAMEngine::new().unwrap();
+        // Every repeat must reach a phase
+        engine.set_result_cache_capacity(0);
+
+        for (cidr, next_hop) in [
+            ("10.0.0.0/8", "short8"),
+            ("172.16.0.0/12", "short12"),
+            ("10.1.1.0/24", "long24"),
+            ("192.168.1.128/25", "long25"),
+            ("2001:db8::/32", "v6_short"),
+            ("2001:db8:1:2::/64", "v6_long"),
+        ] {
+            engine.insert(Route::new(Prefix::from_cidr(cidr).unwrap(), next_hop, 100)).await.unwrap();
+        }
+
+        // Default adaptive thresholds: /8 -> Phase 1, /12 -> Phase 2,
+        // /24, /25 and misses -> Phase 3, IPv6 -> Phase 1
+        let v4_probes = ["10.2.3.4", "172.20.0.1", "10.1.1.9", "192.168.1.200", "8.8.8.8"];
+        let v6_probes = ["2001:db8::1", "2001:db8:1:2::5", "2001:db9::1"];
+        for _ in 0..10 {
+            for ip in v4_probes.iter().chain(v6_probes.iter()) {
+                engine.lookup(ip).await.unwrap();
+            }
+        }
+
+        let stats = engine.stats().await;
+        assert_eq!(stats.total_lookups, 80);
+        assert_eq!(stats.phase1_lookups + stats.phase2_lookups + stats.phase3_lookups, stats.total_lookups);
+        assert_eq!(stats.phase1_lookups, 40);
+        assert_eq!(stats.phase2_lookups, 10);
+        assert_eq!(stats.phase3_lookups, 30);
+
+        assert!(stats.phase1_avg_latency_ns > 0.0);
+        assert!(stats.phase2_avg_latency_ns > 0.0);
+        assert!(stats.phase3_avg_latency_ns > 0.0);
+        assert!(stats.avg_lookup_ns > 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_result_cache_invalidation() {
+        let engine = TCAMEngine::new().unwrap();
+        engine.insert(Route::new(Prefix::from_cidr("10.0.0.0/8").unwrap(), "nh8", 100)).await.unwrap();
+
+        for _ in 0..2 {
+            let result = engine.lookup("10.1.2.3").await.unwrap().unwrap();
+            assert_eq!(result.primary_next_hop(), "nh8");
+        }
+        let stats = engine.stats().await;
+        assert_eq!((stats.result_cache_hits, stats.result_cache_misses), (1, 1));
+        assert_eq!(stats.phase1_lookups + stats.phase2_lookups + stats.phase3_lookups, 1);
+
+        // A prefix elsewhere leaves the entry alone
+        engine.insert(Route::new(Prefix::from_cidr("192.168.0.0/16").unwrap(), "lan", 100)).await.unwrap();
+        engine.lookup("10.1.2.3").await.unwrap();
+        assert_eq!(engine.stats().await.result_cache_hits, 2);
+
+        // A covering prefix invalidates it
+        engine.insert(Route::new(Prefix::from_cidr("10.1.0.0/16").unwrap(), "nh16", 100)).await.unwrap();
+        let result = engine.lookup("10.1.2.3").await.unwrap().unwrap();
+        assert_eq!(result.primary_next_hop(), "nh16");
+        let stats = engine.stats().await;
+        assert_eq!((stats.result_cache_hits, stats.result_cache_misses), (2, 2));
+
+        engine.delete(Prefix::from_cidr("10.1.0.0/16").unwrap()).await.unwrap();
+        let result = engine.lookup("10.1.2.3").await.unwrap().unwrap();
+        assert_eq!(result.primary_next_hop(), "nh8");
+        assert_eq!(engine.stats().await.result_cache_misses, 3);
+    }
+
+    #[test]
+    fn test_result_cache_evicts_least_recently_used() {
+        let ip = |last: u8| IpAddr::from([10, 0, 0, last]);
+        let mut cache = ResultCache::new(2);
+        cache.insert(ip(1), None, 0);
+        cache.insert(ip(2), None, 0);
+        assert!(cache.get(&ip(1)).is_some());
+        cache.insert(ip(3), None, 0);
+        assert!(cache.get(&ip(2)).is_none());
+        assert!(cache.get(&ip(1)).is_some());
+        assert!(cache.get(&ip(3)).is_some());
+
+        // Results computed before an invalidation are not stored
+        cache.invalidate(&Prefix::from_cidr("10.0.0.0/24").unwrap());
+        assert!(cache.entries.is_empty() && cache.order.is_empty());
+        cache.insert(ip(4), None, 0);
+        assert!(cache.get(&ip(4)).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_delete_removes_route_from_every_phase() {
+        let strategies = [
+            PhaseStrategy::Fixed(Phase::Phase1),
+            PhaseStrategy::Fixed(Phase::Phase2),
+            PhaseStrategy::Fixed(Phase::Phase3),
+            PhaseStrategy::default(),
+            PhaseStrategy::Auto,
+        ];
+        for strategy in strategies {
+            let engine = TCAMEngine::new().unwrap();
+            engine.set_phase_strategy(strategy);
+            let prefix = Prefix::from_cidr("10.1.1.0/24").unwrap();
+            engine.insert(Route::new(prefix, "nh24", 100)).await.unwrap();
+            engine.insert(Route::new(Prefix::from_cidr("10.2.0.0/16").unwrap(), "nh16", 100)).await.unwrap();
+
+            // Auto samples each phase in turn
+            for _ in 0..3 {
+                assert!(engine.lookup("10.1.1.5").await.unwrap().is_some(), "{:?}", strategy);
+            }
+            engine.delete(prefix).await.unwrap();
+            for _ in 0..3 {
+                assert!(engine.lookup("10.1.1.5").await.unwrap().is_none(), "{:?}", strategy);
+                engine.result_cache.lock().clear();
+            }
+            assert_eq!(engine.lookup("10.2.3.4").await.unwrap().unwrap().primary_next_hop(), "nh16");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fixed_phase1_strategy() {
+        let engine = TCAMEngine::new().unwrap();
+        engine.set_phase_strategy(PhaseStrategy::Fixed(Phase::Phase1));
+        assert_eq!(engine.strategy(), PhaseStrategy::Fixed(Phase::Phase1));
+
+        for cidr in ["10.0.0.0/8", "10.1.0.0/16", "10.1.1.0/24", "10.1.1.1/32"] {
+            engine.insert(Route::new(Prefix::from_cidr(cidr).unwrap(), cidr, 100)).await.unwrap();
+        }
+
+        for (ip, expected) in [
+            ("10.9.9.9", "10.0.0.0/8"),
+            ("10.1.9.9", "10.1.0.0/16"),
+            ("10.1.1.9", "10.1.1.0/24"),
+            ("10.1.1.1", "10.1.1.1/32"),
+        ] {
+            let result = engine.lookup(ip).await.unwrap().unwrap();
+            assert_eq!(result.phase, "Phase1-AHGF");
+            assert_eq!(result.prefix, expected);
+        }
+
+        let stats = engine.stats().await;
+        assert_eq!(stats.phase1_lookups, 4);
+        assert_eq!(stats.phase2_lookups + stats.phase3_lookups, 0);
+    }
+
+    #[tokio::test]
+    async fn test_adaptive_thresholds() {
+        let engine = TCAMEngine::new().unwrap();
+        let prefix = Prefix::from_cidr("172.16.0.0/16").unwrap();
+        engine.insert(Route::new(prefix, "nh16", 100)).await.unwrap();
+
+        async fn phase_for(engine: &TCAMEngine) -> String {
+            engine.lookup("172.16.5.5").await.unwrap().unwrap().phase
+        }
+
+        // Defaults (8, 16): a /16 counts as long
+        assert_eq!(phase_for(&engine).await, "Phase3-SCRTT-Adaptive");
+
+        engine.set_phase_strategy(PhaseStrategy::Adaptive { short_threshold: 16, long_threshold: 24 });
+        assert_eq!(phase_for(&engine).await, "Phase1-AHGF");
+
+        engine.set_phase_strategy(PhaseStrategy::Adaptive { short_threshold: 8, long_threshold: 24 });
+        assert_eq!(phase_for(&engine).await, "Phase2-QAGFHG");
+    }
+
+    #[tokio::test]
+    async fn test_export_import_roundtrip() {
+        let engine = TCAMEngine::new().unwrap();
+
+        for (cidr, next_hop, metric) in [
+            ("0.0.0.0/0", "default", 500),
+            ("10.0.0.0/8", "nh8", 100),
+            ("10.1.0.0/16", "nh16", 50),
+            ("10.1.1.0/24", "nh24", 10),
+            ("192.168.0.0/16", "lan", 20),
+            ("2001:db8::/32", "nh_v6", 30),
+        ] {
+            let route = Route::new(Prefix::from_cidr(cidr).unwrap(), next_hop, metric);
+            engine.insert(route).await.unwrap();
+        }
+        let ecmp_prefix = Prefix::from_cidr("172.16.0.0/12").unwrap();
+        let hops = vec![("hop_a".to_string(), 3), ("hop_b".to_string(), 1)];
+        engine.insert(Route::ecmp(ecmp_prefix, hops, 40)).await.unwrap();
+
+        let json = engine.export_json().await.unwrap();
+        let restored = TCAMEngine::new().unwrap();
+        restored.import_json(&json).await.unwrap();
+
+        assert_eq!(restored.route_count(), engine.route_count());
+        assert_eq!(restored.export().await, engine.export().await);
+
+        let probes = [
+            "10.1.1.1", "10.1.2.1", "10.2.0.1", "11.0.0.1", "192.168.5.5",
+            "172.20.1.1", "2001:db8::1", "2001:db9::1",
+        ];
+        for ip in probes {
+            let expected = engine.lookup(ip).await.unwrap();
+            let actual = restored.lookup(ip).await.unwrap();
+            let key = |r: Option<LookupResult>| r.map(|r| (r.prefix, r.next_hops, r.weights, r.metric));
+            assert_eq!(key(actual), key(expected), "lookup mismatch for {}", ip);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_aggregate_merges_only_matching_siblings() {
+        let engine = TCAMEngine::new().unwrap();
+        for (cidr, next_hop) in [
+            ("10.0.0.0/25", "nh-a"),
+            ("10.0.0.128/25", "nh-a"),
+            ("10.0.1.0/25", "nh-a"),
+            ("10.0.1.128/25", "nh-b"),
+        ] {
+            engine.insert(Route::new(Prefix::from_cidr(cidr).unwrap(), next_hop, 10)).await.unwrap();
+        }
+
+        let probes = ["10.0.0.1", "10.0.0.200", "10.0.1.1", "10.0.1.200", "10.0.2.1"];
+        let mut before = Vec::new();
+        for ip in probes {
+            before.push(engine.lookup(ip).await.unwrap().map(|r| r.primary_next_hop().to_string()));
+        }
+
+        assert_eq!(engine.aggregate().await.unwrap(), 1);
+        assert_eq!(engine.aggregate().await.unwrap(), 0);
+
+        let prefixes: Vec<String> = engine.iter_routes().await.iter().map(|r| r.prefix.to_string()).collect();
+        assert_eq!(prefixes.len(), 3);
+        assert!(prefixes.contains(&"10.0.0.0/24".to_string()));
+        assert!(prefixes.contains(&"10.0.1.0/25".to_string()));
+        assert!(prefixes.contains(&"10.0.1.128/25".to_string()));
+
+        for (ip, expected) in probes.iter().zip(before) {
+            let actual = engine.lookup(ip).await.unwrap().map(|r| r.primary_next_hop().to_string());
+            assert_eq!(actual, expected, "next hop changed for {}", ip);
+        }
+
+        // Every phase resolves to the parent, and forgets it once deleted
+        let phases = [Phase::Phase1, Phase::Phase2, Phase::Phase3];
+        for phase in phases {
+            engine.set_phase_strategy(PhaseStrategy::Fixed(phase));
+            engine.result_cache.lock().clear();
+            for ip in ["10.0.0.1", "10.0.0.200"] {
+                let result = engine.lookup(ip).await.unwrap().unwrap();
+                assert_eq!(result.prefix, "10.0.0.0/24", "{:?} {}", phase, ip);
+            }
+            let result = engine.lookup("10.0.1.200").await.unwrap().unwrap();
+            assert_eq!(result.prefix, "10.0.1.128/25", "{:?}", phase);
+        }
+
+        engine.delete(Prefix::from_cidr("10.0.0.0/24").unwrap()).await.unwrap();
+        for phase in phases {
+            engine.set_phase_strategy(PhaseStrategy::Fixed(phase));
+            engine.result_cache.lock().clear();
+            for ip in ["10.0.0.1", "10.0.0.200"] {
+                assert!(engine.lookup(ip).await.unwrap().is_none(), "{:?} {}", phase, ip);
+            }
+        }
+    }
+}